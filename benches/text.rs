@@ -0,0 +1,49 @@
+//! Per-row text operations on space-padded 1 KB fixed-width fields.
+//!
+//! `cargo bench --bench text`
+
+use criterion::{Criterion, Throughput, criterion_group, criterion_main};
+use std::hint::black_box;
+use yurki::text::strip_in_string;
+
+const FIELD_BYTES: usize = 1024;
+const FIELDS: usize = 1000;
+
+fn strip(c: &mut Criterion) {
+    // Short values centred in wide padding, as fixed-width exports leave them
+    let fields: Vec<String> = (0..FIELDS)
+        .map(|i| {
+            let value = format!("value {i} é");
+            let pad = (FIELD_BYTES - value.len()) / 2;
+            format!(
+                "{:pad$}{value}{:rest$}",
+                "",
+                "",
+                rest = FIELD_BYTES - pad - value.len()
+            )
+        })
+        .collect();
+
+    let mut group = c.benchmark_group("strip_1kb_fields");
+    group.throughput(Throughput::Bytes((FIELD_BYTES * FIELDS) as u64));
+    group.bench_function("strip_single_ascii", |b| {
+        b.iter(|| {
+            fields
+                .iter()
+                .map(|f| strip_in_string(black_box(f), Some(" "), true, true).len())
+                .sum::<usize>()
+        })
+    });
+    group.bench_function("std_trim_matches", |b| {
+        b.iter(|| {
+            fields
+                .iter()
+                .map(|f| black_box(f.as_str()).trim_matches(' ').len())
+                .sum::<usize>()
+        })
+    });
+    group.finish();
+}
+
+criterion_group!(benches, strip);
+criterion_main!(benches);
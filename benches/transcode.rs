@@ -0,0 +1,123 @@
+//! UCS -> UTF-8 transcoding throughput on 10 MB strings.
+//!
+//! `cargo bench --bench transcode`
+
+use criterion::{Criterion, Throughput, criterion_group, criterion_main};
+use std::hint::black_box;
+use yurki::simd::{
+    ucs1_to_utf8, ucs1_to_utf8_bump, ucs2_to_utf8, ucs2_to_utf8_bump, ucs4_to_utf8,
+    ucs4_to_utf8_bump, validate_utf8_simd,
+};
+
+const SIZE_BYTES: usize = 10 * 1024 * 1024;
+
+fn repeat_to<T: Copy>(pattern: &[T], bytes: usize) -> Vec<T> {
+    let len = bytes / size_of::<T>();
+    pattern.iter().copied().cycle().take(len).collect()
+}
+
+fn transcode(c: &mut Criterion) {
+    let ucs1 = repeat_to(
+        &"latin é text, "
+            .chars()
+            .map(|c| c as u8)
+            .collect::<Vec<_>>(),
+        SIZE_BYTES,
+    );
+    let ucs2 = repeat_to(
+        &"日本語のテキストと中文文本"
+            .encode_utf16()
+            .collect::<Vec<_>>(),
+        SIZE_BYTES,
+    );
+    // Western-European prose: mostly ASCII with scattered accents
+    let ucs1_prose = repeat_to(
+        &"Le café où Zoë a lu « Ça déjà » über Straße, señor. "
+            .chars()
+            .map(|c| c as u8)
+            .collect::<Vec<_>>(),
+        SIZE_BYTES,
+    );
+    // Cyrillic words and spaces: 1, 2 and 3-byte characters in every chunk
+    let ucs2_mixed = repeat_to(
+        &"Привет, мир! Это текст — 中文 "
+            .encode_utf16()
+            .collect::<Vec<_>>(),
+        SIZE_BYTES,
+    );
+    let ucs4 = repeat_to(
+        &"emoji 😀 and 漢字 "
+            .chars()
+            .map(|c| c as u32)
+            .collect::<Vec<_>>(),
+        SIZE_BYTES,
+    );
+
+    let mut group = c.benchmark_group("transcode_10mb");
+    group.sample_size(20);
+    group.throughput(Throughput::Bytes(SIZE_BYTES as u64));
+
+    group.bench_function("ucs1_to_utf8", |b| {
+        b.iter(|| ucs1_to_utf8(black_box(&ucs1)).len())
+    });
+    group.bench_function("ucs1_to_utf8_bump", |b| {
+        let mut bump = bumpalo::Bump::new();
+        b.iter(|| {
+            let len = ucs1_to_utf8_bump(black_box(&ucs1), &bump).len();
+            bump.reset();
+            len
+        })
+    });
+    group.bench_function("ucs1_prose_to_utf8_bump", |b| {
+        let mut bump = bumpalo::Bump::new();
+        b.iter(|| {
+            let len = ucs1_to_utf8_bump(black_box(&ucs1_prose), &bump).len();
+            bump.reset();
+            len
+        })
+    });
+    group.bench_function("ucs2_to_utf8", |b| {
+        b.iter(|| ucs2_to_utf8(black_box(&ucs2)).len())
+    });
+    group.bench_function("ucs2_to_utf8_bump", |b| {
+        let mut bump = bumpalo::Bump::new();
+        b.iter(|| {
+            let len = ucs2_to_utf8_bump(black_box(&ucs2), &bump).len();
+            bump.reset();
+            len
+        })
+    });
+    group.bench_function("ucs2_mixed_to_utf8_bump", |b| {
+        let mut bump = bumpalo::Bump::new();
+        b.iter(|| {
+            let len = ucs2_to_utf8_bump(black_box(&ucs2_mixed), &bump).len();
+            bump.reset();
+            len
+        })
+    });
+    group.bench_function("ucs4_to_utf8", |b| {
+        b.iter(|| ucs4_to_utf8(black_box(&ucs4)).len())
+    });
+    group.bench_function("ucs4_to_utf8_bump", |b| {
+        let mut bump = bumpalo::Bump::new();
+        b.iter(|| {
+            let len = ucs4_to_utf8_bump(black_box(&ucs4), &bump).len();
+            bump.reset();
+            len
+        })
+    });
+    // Validation of the UTF-8 files read_text loads, against the standard library
+    let utf8 = repeat_to("Привет, мир! Это текст — 中文 😀 ".as_bytes(), SIZE_BYTES);
+    let valid = std::str::from_utf8(&utf8).map_or_else(|e| e.valid_up_to(), str::len);
+    let utf8 = &utf8[..valid];
+    group.bench_function("validate_utf8", |b| {
+        b.iter(|| validate_utf8_simd(black_box(utf8)).is_ok())
+    });
+    group.bench_function("std_from_utf8", |b| {
+        b.iter(|| std::str::from_utf8(black_box(utf8)).is_ok())
+    });
+    group.finish();
+}
+
+criterion_group!(benches, transcode);
+criterion_main!(benches);
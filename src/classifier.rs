@@ -0,0 +1,129 @@
+use std::borrow::Cow;
+use std::sync::Arc;
+
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+use pyo3::types::{PyDict, PyList};
+use regex::{RegexSet, RegexSetBuilder};
+
+use crate::converter::ToPyObject;
+use crate::core;
+
+/// Labels each string with the first matching pattern of a `RegexSet`.
+///
+/// Patterns are tried in priority order: dict insertion order by default, or
+/// the order given by `priority`. Labels not listed in `priority` follow the
+/// listed ones in insertion order.
+#[pyclass(frozen, module = "yurki.internal")]
+pub struct Classifier {
+    set: Arc<RegexSet>,
+    labels: Arc<Vec<String>>,
+    #[pyo3(get)]
+    default: String,
+}
+
+#[pymethods]
+impl Classifier {
+    #[new]
+    #[pyo3(signature = (patterns, default = String::new(), priority = None, case = false))]
+    fn new(
+        patterns: &Bound<PyDict>,
+        default: String,
+        priority: Option<Vec<String>>,
+        case: bool,
+    ) -> PyResult<Self> {
+        let mut entries = patterns
+            .iter()
+            .map(|(k, v)| Ok((k.extract::<String>()?, v.extract::<String>()?)))
+            .collect::<PyResult<Vec<_>>>()?;
+
+        if let Some(priority) = priority {
+            for label in &priority {
+                if !entries.iter().any(|(l, _)| l == label) {
+                    return Err(PyValueError::new_err(format!(
+                        "unknown label in priority: {label:?}"
+                    )));
+                }
+            }
+            // Stable sort keeps insertion order for labels missing from `priority`
+            entries.sort_by_key(|(label, _)| {
+                priority
+                    .iter()
+                    .position(|p| p == label)
+                    .unwrap_or(priority.len())
+            });
+        }
+
+        let set = RegexSetBuilder::new(entries.iter().map(|(_, pattern)| pattern))
+            .case_insensitive(case)
+            .build()
+            .map_err(|e| PyValueError::new_err(e.to_string()))?;
+
+        Ok(Self {
+            set: Arc::new(set),
+            labels: Arc::new(entries.into_iter().map(|(label, _)| label).collect()),
+            default,
+        })
+    }
+
+    /// Labels in priority order.
+    #[getter]
+    fn labels(&self) -> Vec<String> {
+        self.labels.as_ref().clone()
+    }
+
+    #[pyo3(signature = (list, multi = false, jobs = 1, inplace = false, progress = None, progress_interval = 10_000))]
+    #[allow(clippy::too_many_arguments)]
+    fn classify(
+        &self,
+        py: Python,
+        list: &Bound<PyList>,
+        multi: bool,
+        jobs: usize,
+        inplace: bool,
+        progress: Option<Bound<PyAny>>,
+        progress_interval: usize,
+    ) -> PyResult<PyObject> {
+        let progress = core::Progress::optional(progress, progress_interval)?;
+        let set = self.set.clone();
+        let labels = self.labels.clone();
+
+        if multi {
+            let make_func = move || unsafe {
+                let set = set.clone();
+                let labels = labels.clone();
+                move |s: &str| {
+                    set.matches(s)
+                        .iter()
+                        .map(|idx| Cow::Borrowed(labels[idx].as_str()))
+                        .collect::<Vec<_>>()
+                        .to_py_object()
+                }
+            };
+            core::map_pylist(py, list, jobs, inplace, progress, make_func)
+        } else {
+            let default = self.default.clone();
+            let make_func = move || unsafe {
+                let set = set.clone();
+                let labels = labels.clone();
+                let default = default.clone();
+                move |s: &str| {
+                    set.matches(s)
+                        .iter()
+                        .next()
+                        .map_or(default.as_str(), |idx| labels[idx].as_str())
+                        .to_py_object()
+                }
+            };
+            core::map_pylist(py, list, jobs, inplace, progress, make_func)
+        }
+    }
+
+    fn __len__(&self) -> usize {
+        self.labels.len()
+    }
+
+    fn __repr__(&self) -> String {
+        format!("Classifier({:?})", self.labels)
+    }
+}
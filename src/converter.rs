@@ -1,8 +1,7 @@
 #![allow(dead_code)]
 
-use crate::core::PyObjectPtr;
-use crate::object::create_fast_string;
-use crate::object::{create_list_empty, list_set_item_transfer};
+use crate::core::{PyObjectPtr, create_result_list, create_result_string};
+use crate::object::list_set_item_transfer;
 use parking_lot::Mutex;
 use pyo3::ffi as pyo3_ffi;
 use std::borrow::Cow;
@@ -20,16 +19,15 @@ pub trait ToPyObject: ConversionStrategy {
     unsafe fn to_py_object(self) -> PyObjectPtr;
 }
 
-// String implementations
-#[cfg(not(feature = "disable-fast-string"))]
+// String implementations - yurki.String, or plain str created under the GIL
+// when fast strings are off (see `core::set_fast_types`)
 impl ConversionStrategy for String {
     const THREAD_SAFE: bool = true;
 }
 
 impl ToPyObject for String {
-    #[cfg(not(feature = "disable-fast-string"))]
     unsafe fn to_py_object(self) -> PyObjectPtr {
-        PyObjectPtr(create_fast_string(&self))
+        create_result_string(&self)
     }
 }
 
@@ -49,6 +47,149 @@ impl ToPyObject for bool {
     }
 }
 
+// Number implementations - CPython caches small ints, and allocating
+// int/float objects needs the GIL, so these are never built in workers
+impl ConversionStrategy for i64 {
+    const THREAD_SAFE: bool = false;
+}
+
+impl ToPyObject for i64 {
+    unsafe fn to_py_object(self) -> PyObjectPtr {
+        PyObjectPtr(pyo3_ffi::PyLong_FromLongLong(self))
+    }
+}
+
+impl ConversionStrategy for usize {
+    const THREAD_SAFE: bool = false;
+}
+
+impl ToPyObject for usize {
+    unsafe fn to_py_object(self) -> PyObjectPtr {
+        PyObjectPtr(pyo3_ffi::PyLong_FromSize_t(self))
+    }
+}
+
+impl ConversionStrategy for f64 {
+    const THREAD_SAFE: bool = false;
+}
+
+impl ToPyObject for f64 {
+    unsafe fn to_py_object(self) -> PyObjectPtr {
+        PyObjectPtr(pyo3_ffi::PyFloat_FromDouble(self))
+    }
+}
+
+// Bytes implementations - PyBytes_FromStringAndSize allocates through
+// CPython's allocator, which needs the GIL
+impl ConversionStrategy for &[u8] {
+    const THREAD_SAFE: bool = false;
+}
+
+impl ToPyObject for &[u8] {
+    unsafe fn to_py_object(self) -> PyObjectPtr {
+        PyObjectPtr(pyo3_ffi::PyBytes_FromStringAndSize(
+            self.as_ptr() as *const _,
+            self.len() as pyo3_ffi::Py_ssize_t,
+        ))
+    }
+}
+
+impl ConversionStrategy for Vec<u8> {
+    const THREAD_SAFE: bool = false;
+}
+
+impl ToPyObject for Vec<u8> {
+    unsafe fn to_py_object(self) -> PyObjectPtr {
+        self.as_slice().to_py_object()
+    }
+}
+
+/// Raw value returned by a worker function. Workers send it to the main
+/// thread as `WorkerResult::Deferred`, which creates the Python object while
+/// holding the GIL.
+#[derive(Clone, Debug, PartialEq)]
+pub enum DeferredValue {
+    Int(i64),
+    UInt(usize),
+    Float(f64),
+    Bytes(Vec<u8>),
+    UInts(Vec<usize>),
+}
+
+impl From<i64> for DeferredValue {
+    fn from(value: i64) -> Self {
+        DeferredValue::Int(value)
+    }
+}
+
+impl From<usize> for DeferredValue {
+    fn from(value: usize) -> Self {
+        DeferredValue::UInt(value)
+    }
+}
+
+impl From<f64> for DeferredValue {
+    fn from(value: f64) -> Self {
+        DeferredValue::Float(value)
+    }
+}
+
+impl From<Vec<u8>> for DeferredValue {
+    fn from(value: Vec<u8>) -> Self {
+        DeferredValue::Bytes(value)
+    }
+}
+
+impl From<Vec<usize>> for DeferredValue {
+    fn from(value: Vec<usize>) -> Self {
+        DeferredValue::UInts(value)
+    }
+}
+
+impl ConversionStrategy for DeferredValue {
+    const THREAD_SAFE: bool = false;
+}
+
+impl ToPyObject for DeferredValue {
+    unsafe fn to_py_object(self) -> PyObjectPtr {
+        match self {
+            DeferredValue::Int(value) => value.to_py_object(),
+            DeferredValue::UInt(value) => value.to_py_object(),
+            DeferredValue::Float(value) => value.to_py_object(),
+            DeferredValue::Bytes(value) => value.to_py_object(),
+            DeferredValue::UInts(values) => {
+                let list = create_result_list(values.len() as isize);
+                if list.is_null() {
+                    return PyObjectPtr(std::ptr::null_mut());
+                }
+                for (index, value) in values.into_iter().enumerate() {
+                    list_set_item_transfer(list, index as isize, value.to_py_object().0);
+                }
+                PyObjectPtr(list)
+            }
+        }
+    }
+}
+
+// Option implementations - None maps to Python's None singleton
+impl<T: ToPyObject> ConversionStrategy for Option<T> {
+    const THREAD_SAFE: bool = T::THREAD_SAFE;
+}
+
+impl<T: ToPyObject> ToPyObject for Option<T> {
+    unsafe fn to_py_object(self) -> PyObjectPtr {
+        match self {
+            Some(value) => value.to_py_object(),
+            None => {
+                // The list takes ownership of the reference, so hand out a new one
+                let none = pyo3_ffi::Py_None();
+                pyo3_ffi::Py_INCREF(none);
+                PyObjectPtr(none)
+            }
+        }
+    }
+}
+
 // Vec implementations - use streaming approach with FastList
 // default impl<T> ConversionStrategy for Vec<T>
 // where
@@ -81,15 +222,13 @@ impl ToPyObject for bool {
 // }
 
 // &str implementations
-#[cfg(not(feature = "disable-fast-string"))]
 impl ConversionStrategy for &str {
     const THREAD_SAFE: bool = true;
 }
 
 impl ToPyObject for &str {
-    #[cfg(not(feature = "disable-fast-string"))]
     unsafe fn to_py_object(self) -> PyObjectPtr {
-        PyObjectPtr(create_fast_string(self)) // No mutex - FastString path
+        create_result_string(self)
     }
 }
 
@@ -116,13 +255,14 @@ impl ToPyObject for Vec<Cow<'_, str>> {
     unsafe fn to_py_object(self) -> PyObjectPtr {
         let len = self.len();
 
-        // Pre-allocate FastList with exact size (thread-safe)
-        let list = create_list_empty(len as isize);
+        // Pre-allocate the result list with exact size (see `create_result_list`)
+        let list = create_result_list(len as isize);
         if list.is_null() {
             return PyObjectPtr(std::ptr::null_mut());
         }
 
-        // Convert each Cow<str> and set (all thread-safe operations)
+        // Convert each Cow<str> and set (all thread-safe operations). A failed
+        // item leaves a null slot, see `list_is_complete`
         for (index, cow_str) in self.into_iter().enumerate() {
             let py_str = cow_str.to_py_object(); // Thread-safe
             list_set_item_transfer(list, index as isize, py_str.0);
@@ -1,11 +1,27 @@
+use crossbeam_channel::RecvTimeoutError;
+use parking_lot::Mutex;
 use pyo3::Python;
+use pyo3::exceptions::{PyMemoryError, PyRuntimeError, PyTypeError, PyValueError};
 use pyo3::ffi as pyo3_ffi;
 use pyo3::prelude::*;
-use pyo3::types::PyList;
+use pyo3::types::{PyDict, PyList, PyString};
+use rayon::slice::ParallelSliceMut;
+use std::cell::{Cell, RefCell};
+use std::collections::{HashMap, HashSet};
+use std::hash::{DefaultHasher, Hash, Hasher};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::time::Duration;
 
 // Import the unified debug system
+use crate::converter::{DeferredValue, ToPyObject};
 use crate::debug_println;
-use crate::object::{convert_pystring, create_list_empty, list_set_item_transfer};
+use crate::object::{
+    ListBuilder, borrow_ascii_pystring, convert_pystring, create_fast_string, create_list_empty,
+    create_plain_string, has_cached_hash, hash_string, is_exact_string, is_list, is_result_list,
+    list_from_refs, list_is_complete, list_release_items, list_set_item_transfer,
+    list_set_item_transfer_replacing, list_track, private_ascii_buffer,
+};
 
 // hack object to pass raw pointer for PyObject
 #[derive(Clone, Debug)]
@@ -18,35 +34,596 @@ impl Copy for PyObjectPtr {}
 #[derive(Debug)]
 pub enum WorkerResult {
     PyObject((usize, PyObjectPtr)),
+    // Converted by the main thread, which holds the GIL
+    Deferred((usize, DeferredValue)),
 }
 
 unsafe impl Send for WorkerResult {}
 
+impl WorkerResult {
+    /// Index of the result and its Python object, creating deferred values.
+    ///
+    /// # Safety
+    ///
+    /// The GIL must be held.
+    unsafe fn into_py_object(self) -> (usize, PyObjectPtr) {
+        match self {
+            WorkerResult::PyObject(result) => result,
+            WorkerResult::Deferred((index, value)) => (index, value.to_py_object()),
+        }
+    }
+}
+
+/// Value a worker function returns for one item.
+pub trait IntoWorkerResult: Send + 'static {
+    fn into_worker_result(self, index: usize) -> WorkerResult;
+}
+
+impl IntoWorkerResult for PyObjectPtr {
+    fn into_worker_result(self, index: usize) -> WorkerResult {
+        WorkerResult::PyObject((index, self))
+    }
+}
+
+impl IntoWorkerResult for DeferredValue {
+    fn into_worker_result(self, index: usize) -> WorkerResult {
+        WorkerResult::Deferred((index, self))
+    }
+}
+
 // Helper function to safely set list items with PyObjectPtr
 #[inline(always)]
-unsafe fn set_list_item(list_ptr: &PyObjectPtr, index: usize, item_ptr: PyObjectPtr) {
+pub(crate) unsafe fn set_list_item(list_ptr: &PyObjectPtr, index: usize, item_ptr: PyObjectPtr) {
     list_set_item_transfer(list_ptr.0, index as isize, item_ptr.0);
 }
 
+// Same as `set_list_item`, but releases the item being overwritten. Inplace
+// writes must use this, otherwise every replaced element leaks. The caller's
+// list may have been resized by Python code meanwhile, so the index is checked
+// against its current size. An item that failed to allocate, or a nested
+// result with unfilled slots, must not reach the caller's list. Requires the GIL.
+#[inline(always)]
+pub(crate) unsafe fn replace_list_item(
+    list_ptr: &PyObjectPtr,
+    index: usize,
+    item_ptr: PyObjectPtr,
+) -> PyResult<()> {
+    if item_ptr.0.is_null() {
+        return Err(PyMemoryError::new_err("failed to allocate result item"));
+    }
+    if index >= pyo3_ffi::PyList_GET_SIZE(list_ptr.0) as usize {
+        pyo3_ffi::Py_DECREF(item_ptr.0);
+        return Err(PyRuntimeError::new_err(
+            "list changed size during inplace processing",
+        ));
+    }
+    if is_result_list(item_ptr.0) && !list_is_complete(item_ptr.0) {
+        pyo3_ffi::Py_DECREF(item_ptr.0);
+        return Err(PyMemoryError::new_err("failed to allocate result item"));
+    }
+    // Nested results built by workers join a list Python can already reach
+    if is_result_list(item_ptr.0) {
+        list_track(item_ptr.0);
+    }
+    list_set_item_transfer_replacing(list_ptr.0, index as isize, item_ptr.0);
+    Ok(())
+}
+
+/// Hand a list built with `create_result_list` to Python.
+///
+/// A result list must never escape with unfilled (null) slots: if a conversion
+/// failed to allocate, the filled items are released and `MemoryError` raised.
+///
+/// # Safety
+///
+/// The GIL must be held and `list_ptr` must be an owned reference to a result
+/// list from `create_result_list`, which this takes over.
+pub(crate) unsafe fn finish_result_list(py: Python, list_ptr: PyObjectPtr) -> PyResult<PyObject> {
+    if !list_is_complete(list_ptr.0) {
+        discard_result_list(list_ptr);
+        return Err(PyMemoryError::new_err("failed to allocate result items"));
+    }
+    list_track(list_ptr.0);
+    Ok(Py::from_owned_ptr(py, list_ptr.0))
+}
+
+/// Drop a partially built result list after an early error, releasing only
+/// the slots that were filled.
+///
+/// # Safety
+///
+/// The GIL must be held and `list_ptr` must be an owned reference to a result
+/// list from `create_result_list`, which this releases.
+pub(crate) unsafe fn discard_result_list(list_ptr: PyObjectPtr) {
+    list_release_items(list_ptr.0);
+    pyo3_ffi::Py_DECREF(list_ptr.0);
+}
+
+// How often the main thread checks for pending signals (Ctrl-C): every
+// SIGNAL_CHECK_INTERVAL items, or after SIGNAL_CHECK_TIMEOUT without results
+const SIGNAL_CHECK_INTERVAL: usize = 1024;
+const SIGNAL_CHECK_TIMEOUT: Duration = Duration::from_millis(50);
+
+/// Python progress callback, called as `callback(processed, total)` from the
+/// calling thread every `interval` items and once more at completion.
+pub struct Progress<'py> {
+    callback: Bound<'py, PyAny>,
+    interval: usize,
+    total: usize,
+    reported: usize,
+}
+
+impl<'py> Progress<'py> {
+    pub fn new(callback: Bound<'py, PyAny>, interval: usize) -> PyResult<Self> {
+        if interval == 0 {
+            return Err(PyValueError::new_err("progress interval must be > 0"));
+        }
+        if !callback.is_callable() {
+            return Err(PyValueError::new_err("progress must be callable"));
+        }
+        Ok(Self {
+            callback,
+            interval,
+            total: 0,
+            reported: 0,
+        })
+    }
+
+    /// Build from an optional Python callable, as passed to the bindings.
+    pub fn optional(
+        callback: Option<Bound<'py, PyAny>>,
+        interval: usize,
+    ) -> PyResult<Option<Self>> {
+        callback
+            .map(|callback| Self::new(callback, interval))
+            .transpose()
+    }
+
+    fn start(&mut self, total: usize) {
+        self.total = total;
+        self.reported = 0;
+    }
+
+    // Report once at least `interval` items completed since the last call
+    fn update(&mut self, processed: usize) -> PyResult<()> {
+        if processed >= self.reported + self.interval {
+            self.report(processed)?;
+        }
+        Ok(())
+    }
+
+    fn finish(&mut self) -> PyResult<()> {
+        if self.reported != self.total || self.total == 0 {
+            self.report(self.total)?;
+        }
+        Ok(())
+    }
+
+    fn report(&mut self, processed: usize) -> PyResult<()> {
+        self.reported = processed;
+        self.callback.call1((processed, self.total))?;
+        Ok(())
+    }
+}
+
+/// Per-call cache of result strings, for outputs with many repeated values.
+///
+/// While a thread has entered an `InternScope`, every string result it
+/// converts (see `create_result_string`) is looked up by its UTF-8 text and
+/// the first object created for that text is reused. Workers can't INCREF
+/// without the GIL, so reuses are only counted; `settle` adds the references
+/// on the main thread once every worker has finished, before the result list
+/// is released or reaches Python code.
+pub(crate) struct ResultInterner {
+    shards: Vec<Mutex<HashMap<Box<str>, InternedString>>>,
+}
+
+struct InternedString {
+    ptr: PyObjectPtr,
+    // References handed out beyond the one returned on creation
+    reuses: usize,
+}
+
+// Spread workers over several locks so they rarely contend
+const INTERN_SHARDS: usize = 64;
+
+impl ResultInterner {
+    pub(crate) fn new() -> Self {
+        Self {
+            shards: (0..INTERN_SHARDS)
+                .map(|_| Mutex::new(HashMap::new()))
+                .collect(),
+        }
+    }
+
+    fn get_or_create(&self, text: &str) -> PyObjectPtr {
+        let mut hasher = DefaultHasher::new();
+        text.hash(&mut hasher);
+        let shard = &self.shards[hasher.finish() as usize % INTERN_SHARDS];
+
+        let mut entries = shard.lock();
+        if let Some(entry) = entries.get_mut(text) {
+            entry.reuses += 1;
+            return entry.ptr;
+        }
+        let ptr = PyObjectPtr(unsafe { create_string(text) });
+        if !ptr.0.is_null() {
+            entries.insert(text.into(), InternedString { ptr, reuses: 0 });
+        }
+        ptr
+    }
+
+    /// Add the references counted for reused strings. Idempotent.
+    ///
+    /// # Safety
+    ///
+    /// The GIL must be held and no other thread may still be in an
+    /// `InternScope` of this interner.
+    unsafe fn settle(&self) {
+        for shard in &self.shards {
+            for entry in shard.lock().values_mut() {
+                for _ in 0..entry.reuses {
+                    pyo3_ffi::Py_INCREF(entry.ptr.0);
+                }
+                entry.reuses = 0;
+            }
+        }
+    }
+}
+
+thread_local! {
+    static INTERNER: RefCell<Option<Arc<ResultInterner>>> = const { RefCell::new(None) };
+}
+
+/// Routes the string results of the current thread through `interner` (or
+/// none) until dropped, then restores the previous one. Entered for every
+/// map, so a map started from a progress callback never uses the interner
+/// of the map that called it.
+struct InternScope {
+    previous: Option<Arc<ResultInterner>>,
+}
+
+impl InternScope {
+    fn enter(interner: Option<&Arc<ResultInterner>>) -> Self {
+        let previous = INTERNER.with(|current| current.replace(interner.cloned()));
+        Self { previous }
+    }
+}
+
+impl Drop for InternScope {
+    fn drop(&mut self) {
+        let previous = self.previous.take();
+        INTERNER.with(|current| *current.borrow_mut() = previous);
+    }
+}
+
+/// Input strings of a map returned unchanged as results, in place of a new
+/// identical object (see `create_result_string`).
+///
+/// Workers can't INCREF without the GIL, so each reuse is only recorded;
+/// `settle` adds the references on the main thread once every worker has
+/// finished, before the result list is released or reaches Python code.
+/// The snapshot of the input keeps the originals alive meanwhile.
+#[derive(Default)]
+pub(crate) struct ReusedInputs {
+    items: Mutex<Vec<PyObjectPtr>>,
+}
+
+impl ReusedInputs {
+    /// Add the references recorded so far. Idempotent.
+    ///
+    /// # Safety
+    ///
+    /// The GIL must be held and no other thread may still be in a
+    /// `ReuseScope` of this map.
+    unsafe fn settle(&self) {
+        for item in self.items.lock().drain(..) {
+            pyo3_ffi::Py_INCREF(item.0);
+        }
+    }
+}
+
+thread_local! {
+    // UTF-8 text and object of the input the current thread is mapping,
+    // set by `ListSnapshot::apply` for inputs that may be reused
+    static CURRENT_INPUT: Cell<Option<(*const u8, usize, PyObjectPtr)>> = const { Cell::new(None) };
+    // Reuses of the current thread, flushed by its `ReuseScope`
+    static REUSED: RefCell<Option<Vec<PyObjectPtr>>> = const { RefCell::new(None) };
+}
+
+/// Lets results of the current thread reuse unchanged inputs, recorded into
+/// `target` when dropped (or disables reuse with `None`). Restores the
+/// previous state, like `InternScope`.
+struct ReuseScope {
+    target: Option<Arc<ReusedInputs>>,
+    previous: Option<Vec<PyObjectPtr>>,
+}
+
+impl ReuseScope {
+    fn enter(target: Option<&Arc<ReusedInputs>>) -> Self {
+        let previous = REUSED.with(|current| current.replace(target.map(|_| Vec::new())));
+        Self {
+            target: target.cloned(),
+            previous,
+        }
+    }
+}
+
+impl Drop for ReuseScope {
+    fn drop(&mut self) {
+        let reused = REUSED.with(|current| current.replace(self.previous.take()));
+        if let (Some(target), Some(reused)) = (&self.target, reused) {
+            target.items.lock().extend(reused);
+        }
+    }
+}
+
+/// The input being mapped, if `text` is all of it and reuse is enabled.
+fn reuse_input(text: &str) -> Option<PyObjectPtr> {
+    let (ptr, len, obj) = CURRENT_INPUT.get()?;
+    if ptr != text.as_ptr() || len != text.len() {
+        return None;
+    }
+    REUSED.with(|current| {
+        current.borrow_mut().as_mut()?.push(obj);
+        Some(obj)
+    })
+}
+
+/// Create the Python string for a result, reusing an identical one if the
+/// running map interns its results, or the input string itself when the
+/// result is the whole of it unchanged.
+///
+/// # Safety
+///
+/// Same as `create_fast_string`.
+pub(crate) unsafe fn create_result_string(text: &str) -> PyObjectPtr {
+    INTERNER.with(|current| match current.borrow().as_ref() {
+        Some(interner) => interner.get_or_create(text),
+        None => reuse_input(text).unwrap_or_else(|| PyObjectPtr(create_string(text))),
+    })
+}
+
+// Output types, switched at runtime by `set_fast_types`. A map reads them
+// once when it starts (see `TypesScope`), so a change applies from the next
+// call on
+static FAST_STRINGS: AtomicBool = AtomicBool::new(true);
+static FAST_LISTS: AtomicBool = AtomicBool::new(true);
+
+/// Choose whether results are yurki.String and yurki.List objects, or plain
+/// `str` and `list` for code that rejects subclasses. Plain results are
+/// created under the GIL. Strings stay plain with `disable-fast-string`.
+pub fn set_fast_types(strings: bool, lists: bool) {
+    FAST_STRINGS.store(strings, Ordering::Relaxed);
+    FAST_LISTS.store(lists, Ordering::Relaxed);
+}
+
+/// Turn yurki.String results on or off alone, leaving lists as they are,
+/// e.g. to bisect whether the custom string type is behind a crash.
+pub fn set_fast_strings(enabled: bool) {
+    FAST_STRINGS.store(enabled, Ordering::Relaxed);
+}
+
+pub fn get_fast_types() -> (bool, bool) {
+    (fast_strings_enabled(), FAST_LISTS.load(Ordering::Relaxed))
+}
+
+/// True if result strings are created as yurki.String.
+pub fn fast_strings_enabled() -> bool {
+    cfg!(not(feature = "disable-fast-string")) && FAST_STRINGS.load(Ordering::Relaxed)
+}
+
+/// Result types selected by `set_fast_types`, as one map reads them.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) struct FastTypes {
+    strings: bool,
+    lists: bool,
+}
+
+impl FastTypes {
+    /// The current setting. Read under the GIL, which `set_fast_types`
+    /// also needs, so a map can't start halfway through a change.
+    pub(crate) fn current() -> Self {
+        let (strings, lists) = get_fast_types();
+        Self { strings, lists }
+    }
+
+    /// Types of results created on the current thread: those of the map it
+    /// runs for, else the current setting.
+    fn in_effect() -> Self {
+        TYPES.get().unwrap_or_else(Self::current)
+    }
+}
+
+thread_local! {
+    static TYPES: Cell<Option<FastTypes>> = const { Cell::new(None) };
+}
+
+/// Makes the current thread create results of `types` until dropped, then
+/// restores the previous ones. Every map enters one on its calling thread
+/// and in each worker with the types read when it started, so a
+/// `set_fast_types` call from a progress callback, or from another thread
+/// while the GIL is released, can't mix types within one result.
+pub(crate) struct TypesScope {
+    previous: Option<FastTypes>,
+}
+
+impl TypesScope {
+    pub(crate) fn enter(types: FastTypes) -> Self {
+        Self {
+            previous: TYPES.replace(Some(types)),
+        }
+    }
+}
+
+impl Drop for TypesScope {
+    fn drop(&mut self) {
+        TYPES.set(self.previous);
+    }
+}
+
+/// Create a result string: a yurki.String, or a plain `str` when fast
+/// strings are off (see `TypesScope`).
+///
+/// # Safety
+///
+/// Same as `create_fast_string`.
+unsafe fn create_string(text: &str) -> *mut pyo3_ffi::PyObject {
+    if FastTypes::in_effect().strings {
+        create_fast_string(text)
+    } else {
+        create_plain_string(text)
+    }
+}
+
+/// Create an empty result list of `len` slots to fill with
+/// `list_set_item_transfer`: a yurki.List, or a plain `list` when fast lists
+/// are off (see `TypesScope`). Either starts untracked, and
+/// `finish_result_list` or `set_list_item` registers it with the collector.
+///
+/// # Safety
+///
+/// Same as `create_list_empty`. A plain list takes the GIL to be created.
+pub(crate) unsafe fn create_result_list(len: isize) -> *mut pyo3_ffi::PyObject {
+    if FastTypes::in_effect().lists {
+        return create_list_empty(len);
+    }
+    Python::with_gil(|_| {
+        let list = pyo3_ffi::PyList_New(len);
+        if !list.is_null() {
+            pyo3_ffi::PyObject_GC_UnTrack(list as _);
+        }
+        list
+    })
+}
+
+/// Run `f`, the part of a map that waits for its workers.
+///
+/// While the map creates plain results (see `TypesScope`, entered by the
+/// caller), workers create them through the C API and need the GIL, so it is
+/// released for the duration. Otherwise workers never touch the interpreter
+/// and the GIL stays held.
+pub(crate) fn while_workers_run<T, F>(py: Python, f: F) -> T
+where
+    F: FnOnce() -> T + Send,
+    T: Send,
+{
+    let types = FastTypes::in_effect();
+    if types.strings && types.lists {
+        f()
+    } else {
+        py.allow_threads(f)
+    }
+}
+
 // Bump allocator manager to prevent code duplication
 pub struct BumpAllocatorManager {
     pub name: String,
     pub bump: bumpalo::Bump,
+    initial_capacity: usize,
+    reset_threshold: usize,
+    free_threshold: usize,
 }
 
-const MANAGEMENT_BATCH_SIZE: usize = 100;
+// Arena tuning, adjustable from Python via `set_arena_config`
+static ARENA_INITIAL_CAPACITY: AtomicUsize = AtomicUsize::new(256 * 1024); // 256KB
+static ARENA_RESET_THRESHOLD: AtomicUsize = AtomicUsize::new(16 * 1024 * 1024); // 16MB
+static ARENA_FREE_THRESHOLD: AtomicUsize = AtomicUsize::new(32 * 1024 * 1024); // 32MB
+static MANAGEMENT_BATCH_SIZE: AtomicUsize = AtomicUsize::new(100);
 
-impl BumpAllocatorManager {
-    // Memory management constants
-    const INITIAL_CAPACITY: usize = 256 * 1024; // 256KB
-    const RESET_THRESHOLD: usize = 16 * 1024 * 1024; // 16MB 
-    const FREE_THRESHOLD: usize = Self::RESET_THRESHOLD * 2; // 32MB
+// Arenas of finished managers, reset and kept for the next call so small
+// calls don't allocate a fresh arena per worker every time
+static ARENA_POOL: ArenaPool = ArenaPool::new();
+const ARENA_POOL_CAPACITY: usize = 64;
+
+/// Reset arenas waiting for reuse, up to `ARENA_POOL_CAPACITY` of them.
+struct ArenaPool(Mutex<Vec<bumpalo::Bump>>);
+
+impl ArenaPool {
+    const fn new() -> Self {
+        Self(Mutex::new(Vec::new()))
+    }
+
+    /// A pooled arena, or a new one of `capacity` bytes if none is left.
+    fn take(&self, capacity: usize) -> bumpalo::Bump {
+        self.0
+            .lock()
+            .pop()
+            .unwrap_or_else(|| bumpalo::Bump::with_capacity(capacity))
+    }
+
+    /// Reset `bump` and keep it for a later `take`, unless the pool is full.
+    fn put(&self, mut bump: bumpalo::Bump) {
+        bump.reset();
+        let mut arenas = self.0.lock();
+        if arenas.len() < ARENA_POOL_CAPACITY {
+            arenas.push(bump);
+        }
+    }
+
+    fn clear(&self) {
+        self.0.lock().clear();
+    }
+}
+
+/// Update arena thresholds used by newly created `BumpAllocatorManager`s.
+///
+/// Sizes are in bytes. `batch_size` is how many items a worker processes
+/// between `manage_memory` calls.
+pub fn set_arena_config(
+    initial_capacity: usize,
+    reset_threshold: usize,
+    free_threshold: usize,
+    batch_size: usize,
+) -> PyResult<()> {
+    if reset_threshold == 0 {
+        return Err(PyValueError::new_err("reset threshold must be > 0"));
+    }
+    if free_threshold < reset_threshold {
+        return Err(PyValueError::new_err(
+            "free threshold must be >= reset threshold",
+        ));
+    }
+    if initial_capacity > reset_threshold {
+        return Err(PyValueError::new_err(
+            "initial capacity must be <= reset threshold",
+        ));
+    }
+    if batch_size == 0 {
+        return Err(PyValueError::new_err("batch size must be > 0"));
+    }
+
+    ARENA_INITIAL_CAPACITY.store(initial_capacity, Ordering::Relaxed);
+    ARENA_RESET_THRESHOLD.store(reset_threshold, Ordering::Relaxed);
+    ARENA_FREE_THRESHOLD.store(free_threshold, Ordering::Relaxed);
+    MANAGEMENT_BATCH_SIZE.store(batch_size, Ordering::Relaxed);
+    // Pooled arenas were sized for the previous config
+    ARENA_POOL.clear();
+    Ok(())
+}
 
-    // Constructor with custom name for threading/context
+/// Current arena config as `(initial_capacity, reset_threshold, free_threshold, batch_size)`.
+pub fn get_arena_config() -> (usize, usize, usize, usize) {
+    (
+        ARENA_INITIAL_CAPACITY.load(Ordering::Relaxed),
+        ARENA_RESET_THRESHOLD.load(Ordering::Relaxed),
+        ARENA_FREE_THRESHOLD.load(Ordering::Relaxed),
+        MANAGEMENT_BATCH_SIZE.load(Ordering::Relaxed),
+    )
+}
+
+impl BumpAllocatorManager {
+    // Constructor with custom name for threading/context, reusing a pooled
+    // arena when one is available
     pub fn new(name: String) -> Self {
+        let initial_capacity = ARENA_INITIAL_CAPACITY.load(Ordering::Relaxed);
+        let bump = ARENA_POOL.take(initial_capacity);
         Self {
             name,
-            bump: bumpalo::Bump::with_capacity(Self::INITIAL_CAPACITY),
+            bump,
+            initial_capacity,
+            reset_threshold: ARENA_RESET_THRESHOLD.load(Ordering::Relaxed),
+            free_threshold: ARENA_FREE_THRESHOLD.load(Ordering::Relaxed),
         }
     }
 
@@ -54,14 +631,14 @@ impl BumpAllocatorManager {
     pub fn manage_memory(&mut self) {
         let current_size = self.bump.allocated_bytes();
 
-        if current_size > Self::FREE_THRESHOLD {
-            self.bump = bumpalo::Bump::with_capacity(Self::INITIAL_CAPACITY);
+        if current_size > self.free_threshold {
+            self.bump = bumpalo::Bump::with_capacity(self.initial_capacity);
             debug_println!(
                 "{}: freed arena at {}MB",
                 self.name,
                 current_size / 1024 / 1024
             );
-        } else if current_size > Self::RESET_THRESHOLD {
+        } else if current_size > self.reset_threshold {
             self.bump.reset();
             debug_println!(
                 "{}: reset arena at {}MB",
@@ -77,15 +654,261 @@ impl BumpAllocatorManager {
     }
 }
 
-fn get_string_at_idx<'a>(list_ptr: &PyObjectPtr, idx: usize, bump: &'a bumpalo::Bump) -> &'a str {
-    unsafe {
-        let str_ptr = pyo3_ffi::PyList_GET_ITEM(list_ptr.0, idx as isize);
-        assert!(!str_ptr.is_null());
-        convert_pystring(str_ptr, bump)
+impl Drop for BumpAllocatorManager {
+    // Return the arena to the pool, reset; grown ones are freed instead
+    fn drop(&mut self) {
+        let bump = std::mem::take(&mut self.bump);
+        if bump.allocated_bytes() <= self.reset_threshold {
+            ARENA_POOL.put(bump);
+        }
+    }
+}
+
+/// Python-style `start:stop:step` selection of the list items to process,
+/// the whole list by default.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct SliceArgs {
+    pub start: Option<isize>,
+    pub stop: Option<isize>,
+    pub step: Option<isize>,
+}
+
+/// Indices selected by `SliceArgs` in a list of known length.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Selection {
+    start: isize,
+    step: isize,
+    len: usize,
+}
+
+impl SliceArgs {
+    /// Resolve against a list of `len` items, clamping and wrapping negative
+    /// bounds exactly like `list[start:stop:step]`. `None` if step is zero.
+    pub fn resolve(&self, len: usize) -> Option<Selection> {
+        let step = self.step.unwrap_or(1);
+        if step == 0 {
+            return None;
+        }
+
+        let len = len as isize;
+        let (lower, upper) = if step < 0 { (-1, len - 1) } else { (0, len) };
+        let clamp = |bound: isize| {
+            if bound < 0 {
+                (bound + len).max(lower)
+            } else {
+                bound.min(upper)
+            }
+        };
+        let start = self
+            .start
+            .map_or(if step < 0 { upper } else { lower }, clamp);
+        let stop = self
+            .stop
+            .map_or(if step < 0 { lower } else { upper }, clamp);
+
+        let len = if step < 0 && stop < start {
+            (start - stop - 1) / -step + 1
+        } else if step > 0 && start < stop {
+            (stop - start - 1) / step + 1
+        } else {
+            0
+        };
+        Some(Selection {
+            start,
+            step,
+            len: len as usize,
+        })
+    }
+}
+
+impl Selection {
+    /// The whole list of `len` items.
+    pub fn all(len: usize) -> Self {
+        Self {
+            start: 0,
+            step: 1,
+            len,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// List index of the `i`-th selected item.
+    pub fn index(&self, i: usize) -> usize {
+        (self.start + i as isize * self.step) as usize
+    }
+}
+
+/// Strong references to every element of the input list, taken under the GIL
+/// before any worker starts.
+///
+/// Workers read strings only from the snapshot, never from the list itself,
+/// so inplace writes or Python code mutating the list meanwhile (a progress
+/// callback, another thread on free-threaded builds) can't free or move a
+/// string a worker is still converting.
+///
+/// Only the items picked by `selection` are taken; result `i` belongs at
+/// `selection.index(i)` of the list.
+///
+/// Whether each string is pure ASCII is read from its header while taking
+/// the snapshot, so workers borrow those buffers directly instead of
+/// transcoding them. Items of the type the map creates are marked
+/// `reusable`: returned unchanged, they become the result themselves (see
+/// `ReusedInputs`). That is exact `str` or yurki.String with fast strings
+/// on, only exact `str` with them off, never other str subclasses.
+struct ListSnapshot {
+    items: Vec<Py<PyAny>>,
+    ascii: Vec<bool>,
+    reusable: Vec<bool>,
+    selection: Selection,
+}
+
+impl ListSnapshot {
+    fn new(list: &Bound<PyList>, slice: SliceArgs) -> PyResult<Self> {
+        let selection = slice
+            .resolve(list.len())
+            .ok_or_else(|| PyValueError::new_err("slice step cannot be zero"))?;
+        let items = (0..selection.len())
+            .map(|i| {
+                let idx = selection.index(i);
+                let item = list.get_item(idx)?;
+                if !item.is_instance_of::<PyString>() {
+                    return Err(PyTypeError::new_err(format!(
+                        "expected str at index {idx}, got {}",
+                        item.get_type().name()?
+                    )));
+                }
+                Ok(item.unbind())
+            })
+            .collect::<PyResult<Vec<_>>>()?;
+        let ascii = items
+            .iter()
+            .map(|item| unsafe { pyo3::ffi::PyUnicode_IS_ASCII(item.as_ptr()) != 0 })
+            .collect();
+        let fast_strings = FastTypes::in_effect().strings;
+        let reusable = items
+            .iter()
+            .map(|item| unsafe {
+                if fast_strings {
+                    is_exact_string(item.as_ptr())
+                } else {
+                    pyo3::ffi::PyUnicode_CheckExact(item.as_ptr()) != 0
+                }
+            })
+            .collect();
+        Ok(Self {
+            items,
+            ascii,
+            reusable,
+            selection,
+        })
+    }
+
+    fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    /// Total length in characters of the items, extrapolated from up to
+    /// `WORK_SAMPLE_SIZE` of them spread evenly over the list.
+    fn estimated_chars(&self) -> usize {
+        let len = self.items.len();
+        let samples = len.min(WORK_SAMPLE_SIZE);
+        if samples == 0 {
+            return 0;
+        }
+        let sampled: usize = (0..samples)
+            .map(|i| {
+                let item = &self.items[i * len / samples];
+                unsafe { pyo3::ffi::PyUnicode_GET_LENGTH(item.as_ptr()) as usize }
+            })
+            .sum();
+        sampled.saturating_mul(len) / samples
+    }
+
+    // Safe without the GIL: the snapshot keeps the string alive and str
+    // contents are immutable
+    fn get_string<'a>(&'a self, idx: usize, bump: &'a bumpalo::Bump) -> &'a str {
+        let item = &self.items[idx];
+        if self.ascii[idx] {
+            return unsafe { borrow_ascii_pystring(item).unwrap_unchecked() };
+        }
+        convert_pystring(item, bump)
+    }
+}
+
+/// Elements a map reads at each index and how they are handed to the worker
+/// function `F`.
+///
+/// Implemented for a single list (`Fn(&str)`) and a pair of lists
+/// (`Fn(&str, &str)`), so both arities share the chunking, channel and
+/// cancellation logic of `map_parallel` and `map_sequential`. `R` is what
+/// `F` returns for each item.
+trait MapInput<F, R>: Send + Sync + 'static {
+    fn len(&self) -> usize;
+
+    /// Index in the input list that result `idx` is written back to with `inplace`.
+    fn position(&self, idx: usize) -> usize;
+
+    /// Transcode the element(s) at `idx` into `bump` and run `func` on them.
+    fn apply(&self, func: &F, idx: usize, bump: &bumpalo::Bump) -> R;
+}
+
+impl<F, R> MapInput<F, R> for ListSnapshot
+where
+    F: for<'a> Fn(&'a str) -> R,
+{
+    fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    fn position(&self, idx: usize) -> usize {
+        self.selection.index(idx)
+    }
+
+    fn apply(&self, func: &F, idx: usize, bump: &bumpalo::Bump) -> R {
+        let text = self.get_string(idx, bump);
+        if !self.reusable[idx] {
+            return func(text);
+        }
+        let input = (
+            text.as_ptr(),
+            text.len(),
+            PyObjectPtr(self.items[idx].as_ptr()),
+        );
+        let previous = CURRENT_INPUT.replace(Some(input));
+        let result = func(text);
+        CURRENT_INPUT.set(previous);
+        result
+    }
+}
+
+/// Two snapshots of equal length, read element-wise.
+struct PairSnapshot(ListSnapshot, ListSnapshot);
+
+impl<F, R> MapInput<F, R> for PairSnapshot
+where
+    F: for<'a> Fn(&'a str, &'a str) -> R,
+{
+    fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    fn position(&self, idx: usize) -> usize {
+        self.0.selection.index(idx)
+    }
+
+    fn apply(&self, func: &F, idx: usize, bump: &bumpalo::Bump) -> R {
+        func(self.0.get_string(idx, bump), self.1.get_string(idx, bump))
     }
 }
 
-fn make_range(len: usize, jobs: usize, i: usize) -> (usize, usize) {
+pub(crate) fn make_range(len: usize, jobs: usize, i: usize) -> (usize, usize) {
     assert!(jobs > 0, "jobs must be > 0");
     assert!(
         i < jobs,
@@ -104,19 +927,30 @@ fn make_range(len: usize, jobs: usize, i: usize) -> (usize, usize) {
     (start, end)
 }
 
-fn map_pylist_parallel<'py, F1, F2>(
+// `list` is the list results are written back to with `inplace`
+#[allow(clippy::too_many_arguments)]
+fn map_parallel<'py, I, F1, F2, R>(
     py: Python<'py>,
     list: &Bound<'py, PyList>,
+    input: I,
     jobs: usize,
     inplace: bool,
+    interner: Option<Arc<ResultInterner>>,
+    mut progress: Option<Progress<'py>>,
     make_func: F1,
 ) -> PyResult<PyObject>
 where
+    I: MapInput<F2, R>,
     F1: Fn() -> F2 + Send + Sync,
-    F2: for<'a> Fn(&'a str) -> PyObjectPtr + Send + 'static,
+    F2: Send + 'static,
+    R: IntoWorkerResult,
 {
-    let list_len = list.len();
+    let types = FastTypes::current();
+    let _types_scope = TypesScope::enter(types);
+    let input = Arc::new(input);
+    let list_len = input.len();
     let input_list_ptr = PyObjectPtr(list.as_ptr());
+    let batch_size = MANAGEMENT_BATCH_SIZE.load(Ordering::Relaxed);
 
     let real_jobs = jobs.min(list_len);
     debug_println!("parallel processing: jobs {}", real_jobs);
@@ -126,7 +960,7 @@ where
         input_list_ptr.clone()
     } else {
         unsafe {
-            let result_list = create_list_empty(list_len as isize);
+            let result_list = create_result_list(list_len as isize);
             assert!(!result_list.is_null());
             PyObjectPtr(result_list)
         }
@@ -145,16 +979,41 @@ where
         .build()
         .unwrap();
 
-    // Create channel for streaming results from workers to main thread
+    // Create channel for streaming inplace and deferred results from workers to
+    // the main thread. Other results never travel through it: it only signals
+    // completion once every worker has dropped its sender.
     let (sender, receiver) = crossbeam_channel::unbounded::<WorkerResult>();
 
+    // Inplace results reach the caller's list right away, so they can't
+    // borrow the input's reference until settled
+    let reused = (!inplace).then(|| Arc::new(ReusedInputs::default()));
+
+    // Raised by the main thread when a signal is pending or the progress
+    // callback fails; workers stop early
+    let cancelled = Arc::new(AtomicBool::new(false));
+
+    // Items finished by all workers, flushed once per batch for progress reporting
+    let processed = Arc::new(AtomicUsize::new(0));
+    let track_progress = progress.is_some();
+    if let Some(progress) = progress.as_mut() {
+        progress.start(list_len);
+    }
+
     for job_idx in 0..real_jobs {
         let (range_start, range_stop) = make_range(list_len, real_jobs, job_idx);
-        let input_list_ptr = input_list_ptr.clone();
+        let input = input.clone();
         let sender = sender.clone();
+        let cancelled = cancelled.clone();
+        let processed = processed.clone();
+        let interner = interner.clone();
+        let reused = reused.clone();
 
         let func = make_func();
         pool.spawn(move || {
+            let _types_scope = TypesScope::enter(types);
+            let _intern_scope = InternScope::enter(interner.as_ref());
+            // Flushed when the closure returns, before its sender disconnects
+            let _reuse_scope = ReuseScope::enter(reused.as_ref());
             debug_println!(
                 "thread {} started, range {}, {}",
                 job_idx,
@@ -164,22 +1023,45 @@ where
 
             // Pre-allocate bump arena for this thread
             let mut bump_manager = BumpAllocatorManager::new(format!("Thread {}", job_idx));
+            let mut pending = 0usize;
 
             for i in range_start..range_stop {
-                // Extract string from input list
-                let bump_string = get_string_at_idx(&input_list_ptr, i, bump_manager.bump());
+                if cancelled.load(Ordering::Relaxed) {
+                    debug_println!("thread {} cancelled at {}", job_idx, i);
+                    break;
+                }
 
-                let py_obj = func(bump_string);
-                if inplace {
-                    sender.send(WorkerResult::PyObject((i, py_obj))).unwrap();
-                } else {
-                    unsafe { set_list_item(&target_list_ptr, i, py_obj) };
+                // Read from the snapshot of the input list(s)
+                let result = input
+                    .apply(&func, i, bump_manager.bump())
+                    .into_worker_result(i);
+                match result {
+                    // Ranges from `make_range` are contiguous and disjoint, so
+                    // each worker fills its own slice of the result in input
+                    // order with no reordering step
+                    WorkerResult::PyObject((i, py_obj)) if !inplace => unsafe {
+                        set_list_item(&target_list_ptr, i, py_obj)
+                    },
+                    result => sender.send(result).unwrap(),
                 }
 
-                if (i - range_start) % MANAGEMENT_BATCH_SIZE == 0 {
+                if (i - range_start) % batch_size == 0 {
                     bump_manager.manage_memory();
                 }
+
+                if track_progress {
+                    pending += 1;
+                    if pending == batch_size {
+                        processed.fetch_add(pending, Ordering::Relaxed);
+                        pending = 0;
+                    }
+                }
             }
+            processed.fetch_add(pending, Ordering::Relaxed);
+
+            // Drop our handle before the sender disconnects, so the last one
+            // (and with it the element DECREFs) is released by the main thread
+            drop(input);
 
             debug_println!(
                 "Thread {} finished, final arena size: {}MB",
@@ -192,40 +1074,104 @@ where
     // Close sender side to signal when all workers are done
     drop(sender);
 
-    // Main thread: apply results as they arrive (streaming updates)
-    for result in receiver {
-        match result {
-            WorkerResult::PyObject((index, py_obj)) => {
-                // Pre-converted in worker thread - just set
-                unsafe {
-                    set_list_item(&target_list_ptr, index, py_obj);
+    // Main thread: apply results as they arrive (streaming updates), polling
+    // for signals so a long run can be interrupted with Ctrl-C
+    let mut interrupted = None;
+    let mut received = 0usize;
+    loop {
+        let check_signals =
+            match while_workers_run(py, || receiver.recv_timeout(SIGNAL_CHECK_TIMEOUT)) {
+                Ok(result) => {
+                    // Deferred values are converted here, under the GIL
+                    let (index, py_obj) = unsafe { result.into_py_object() };
+                    let status = unsafe {
+                        if inplace {
+                            replace_list_item(&target_list_ptr, input.position(index), py_obj)
+                        } else {
+                            set_list_item(&target_list_ptr, index, py_obj);
+                            Ok(())
+                        }
+                    };
+                    if let (Err(err), None) = (status, &interrupted) {
+                        cancelled.store(true, Ordering::Relaxed);
+                        interrupted = Some(err);
+                    }
+                    received += 1;
+                    received.is_multiple_of(SIGNAL_CHECK_INTERVAL)
                 }
-            }
+                Err(RecvTimeoutError::Timeout) => true,
+                // All workers have finished and dropped their senders
+                Err(RecvTimeoutError::Disconnected) => break,
+            };
+
+        if interrupted.is_some() {
+            continue;
+        }
+
+        let mut status = if check_signals {
+            py.check_signals()
+        } else {
+            Ok(())
+        };
+        if let (Ok(()), Some(progress)) = (&status, progress.as_mut()) {
+            status = progress.update(processed.load(Ordering::Relaxed));
+        }
+        if let Err(err) = status {
+            debug_println!("aborting, cancelling workers");
+            cancelled.store(true, Ordering::Relaxed);
+            interrupted = Some(err);
         }
     }
 
     debug_println!("Passed the barrier");
 
+    // Workers are done: give reused results their references before the
+    // list is finished or discarded
+    if let Some(interner) = &interner {
+        unsafe { interner.settle() };
+    }
+    if let Some(reused) = &reused {
+        unsafe { reused.settle() };
+    }
+
+    if let (None, Some(progress)) = (&interrupted, progress.as_mut()) {
+        interrupted = progress.finish().err();
+    }
+
+    if let Some(err) = interrupted {
+        // Inplace results already applied stay in the caller's list
+        if !inplace {
+            unsafe { discard_result_list(target_list_ptr) };
+        }
+        return Err(err);
+    }
+
     if inplace {
         Ok(list.clone().into())
     } else {
-        unsafe { Ok(Py::from_owned_ptr(py, target_list_ptr.0)) }
+        unsafe { finish_result_list(py, target_list_ptr) }
     }
 }
 
 // Sequential processing for jobs=1 or fallback
-fn map_pylist_sequential<'py, F1, F2>(
+fn map_sequential<'py, I, F1, F2, R>(
     py: Python<'py>,
     list: &Bound<'py, PyList>,
+    input: I,
     inplace: bool,
+    interner: Option<Arc<ResultInterner>>,
+    mut progress: Option<Progress<'py>>,
     make_func: F1,
 ) -> PyResult<PyObject>
 where
+    I: MapInput<F2, R>,
     F1: Fn() -> F2,
-    F2: for<'a> Fn(&'a str) -> PyObjectPtr,
+    R: IntoWorkerResult,
 {
-    let list_len = list.len();
+    let _types_scope = TypesScope::enter(FastTypes::current());
+    let list_len = input.len();
     let input_list_ptr = PyObjectPtr(list.as_ptr());
+    let batch_size = MANAGEMENT_BATCH_SIZE.load(Ordering::Relaxed);
     let func = make_func();
 
     debug_println!("sequential processing, list length {}", list_len);
@@ -233,58 +1179,1004 @@ where
     // Use bump allocator manager for sequential processing too
     let mut bump_manager = BumpAllocatorManager::new("Sequential".to_string());
 
-    if inplace {
-        // Modify existing list in place
-        for i in 0..list_len {
-            let bump_string = get_string_at_idx(&input_list_ptr, i, bump_manager.bump());
-            let py_obj = func(bump_string);
-
-            unsafe {
-                set_list_item(&input_list_ptr, i, py_obj);
-            }
-
-            if i % MANAGEMENT_BATCH_SIZE == 0 {
-                bump_manager.manage_memory();
-            }
-        }
-        Ok(list.clone().into())
+    // Modify existing list in place or create new list with exact size
+    let target_list_ptr = if inplace {
+        input_list_ptr
     } else {
         unsafe {
-            // Create new list with exact size
-            let result_list = create_list_empty(list_len as isize);
+            let result_list = create_result_list(list_len as isize);
             assert!(!result_list.is_null());
-            let result_list_ptr = PyObjectPtr(result_list);
+            PyObjectPtr(result_list)
+        }
+    };
 
-            for i in 0..list_len {
-                let bump_string = get_string_at_idx(&input_list_ptr, i, bump_manager.bump());
-                let py_obj = func(bump_string);
-                set_list_item(&result_list_ptr, i, py_obj);
+    if let Some(progress) = progress.as_mut() {
+        progress.start(list_len);
+    }
 
-                if i % MANAGEMENT_BATCH_SIZE == 0 {
-                    bump_manager.manage_memory();
-                }
+    // Settled before the result list is discarded or returned
+    let _intern_scope = InternScope::enter(interner.as_ref());
+    let reused = (!inplace).then(|| Arc::new(ReusedInputs::default()));
+    let reuse_scope = ReuseScope::enter(reused.as_ref());
+    let mut reuse_scope = Some(reuse_scope);
+    let mut settle = || {
+        if let Some(interner) = &interner {
+            unsafe { interner.settle() };
+        }
+        // Flush this thread's reuses first
+        drop(reuse_scope.take());
+        if let Some(reused) = &reused {
+            unsafe { reused.settle() };
+        }
+    };
+
+    for i in 0..list_len {
+        let result = input
+            .apply(&func, i, bump_manager.bump())
+            .into_worker_result(i);
+        let (_, py_obj) = unsafe { result.into_py_object() };
+
+        let mut status = unsafe {
+            if inplace {
+                replace_list_item(&target_list_ptr, input.position(i), py_obj)
+            } else {
+                set_list_item(&target_list_ptr, i, py_obj);
+                Ok(())
             }
+        };
 
-            Ok(Py::from_owned_ptr(py, result_list))
+        if i % batch_size == 0 {
+            bump_manager.manage_memory();
         }
+
+        if status.is_ok() && (i + 1).is_multiple_of(SIGNAL_CHECK_INTERVAL) {
+            status = py.check_signals();
+        }
+        if let (Ok(()), Some(progress)) = (&status, progress.as_mut()) {
+            status = progress.update(i + 1);
+        }
+        if let Err(err) = status {
+            debug_println!("aborting at {}", i);
+            settle();
+            if !inplace {
+                unsafe { discard_result_list(target_list_ptr) };
+            }
+            return Err(err);
+        }
+    }
+
+    settle();
+
+    if let Some(Err(err)) = progress.as_mut().map(Progress::finish) {
+        if !inplace {
+            unsafe { discard_result_list(target_list_ptr) };
+        }
+        return Err(err);
+    }
+
+    if inplace {
+        Ok(list.clone().into())
+    } else {
+        unsafe { finish_result_list(py, target_list_ptr) }
+    }
+}
+
+// Estimated size of a map, in characters, below which it runs sequentially
+// whatever `jobs` asks for (see `runs_parallel`); 0 always honors `jobs`
+static PARALLEL_THRESHOLD: AtomicUsize = AtomicUsize::new(DEFAULT_PARALLEL_THRESHOLD);
+const DEFAULT_PARALLEL_THRESHOLD: usize = 512 * 1024;
+// Each item costs about as much as this many characters of text on its own
+// (snapshot, result object, bookkeeping)
+const ITEM_OVERHEAD_CHARS: usize = 256;
+// Items read to estimate the average item length
+const WORK_SAMPLE_SIZE: usize = 64;
+
+/// Set the estimated input size, in characters, below which maps run
+/// sequentially even with `jobs > 1`. 0 disables the switch.
+pub fn set_parallel_threshold(chars: usize) {
+    PARALLEL_THRESHOLD.store(chars, Ordering::Relaxed);
+}
+
+pub fn get_parallel_threshold() -> usize {
+    PARALLEL_THRESHOLD.load(Ordering::Relaxed)
+}
+
+/// Whether a map over `items` items of `chars` characters in total (see
+/// `ListSnapshot::estimated_chars`) is worth `jobs` workers: building the
+/// pool, channel and arenas costs about a millisecond, which smaller inputs
+/// don't win back.
+fn runs_parallel(jobs: usize, items: usize, chars: usize) -> bool {
+    if jobs == 1 {
+        return false;
+    }
+    let work = chars.saturating_add(items.saturating_mul(ITEM_OVERHEAD_CHARS));
+    work >= PARALLEL_THRESHOLD.load(Ordering::Relaxed)
+}
+
+/// Result of a map over no items: the caller's list with `inplace`, else a
+/// new empty result list. Progress still reports completion.
+fn map_empty(
+    py: Python,
+    list: &Bound<PyList>,
+    inplace: bool,
+    progress: Option<Progress>,
+) -> PyResult<PyObject> {
+    if let Some(mut progress) = progress {
+        progress.start(0);
+        progress.finish()?;
+    }
+    if inplace {
+        Ok(list.clone().into())
+    } else {
+        unsafe { finish_result_list(py, PyObjectPtr(create_result_list(0))) }
     }
 }
 
 // Main entry point - simplified to just sequential vs parallel
-pub fn map_pylist<'py, F1, F2>(
+pub fn map_pylist<'py, F1, F2, R>(
     py: Python<'py>,
     list: &Bound<'py, PyList>,
     jobs: usize,
     inplace: bool,
+    progress: Option<Progress<'py>>,
     make_func: F1,
 ) -> PyResult<PyObject>
 where
     F1: Fn() -> F2 + Send + Sync,
-    F2: for<'a> Fn(&'a str) -> PyObjectPtr + Send + 'static,
+    F2: for<'a> Fn(&'a str) -> R + Send + 'static,
+    R: IntoWorkerResult,
 {
-    if jobs == 1 {
-        map_pylist_sequential(py, list, inplace, make_func)
+    let slice = SliceArgs::default();
+    map_pylist_slice(py, list, slice, jobs, inplace, false, progress, make_func)
+}
+
+/// `map_pylist` over the items selected by `slice` only. The result holds one
+/// item per selected index; with `inplace`, only those positions of `list`
+/// are overwritten.
+///
+/// With `intern`, equal string results share one object (see
+/// `ResultInterner`). Not supported together with `inplace`, as results
+/// reach the caller's list before their references are settled.
+#[allow(clippy::too_many_arguments)]
+pub fn map_pylist_slice<'py, F1, F2, R>(
+    py: Python<'py>,
+    list: &Bound<'py, PyList>,
+    slice: SliceArgs,
+    jobs: usize,
+    inplace: bool,
+    intern: bool,
+    progress: Option<Progress<'py>>,
+    make_func: F1,
+) -> PyResult<PyObject>
+where
+    F1: Fn() -> F2 + Send + Sync,
+    F2: for<'a> Fn(&'a str) -> R + Send + 'static,
+    R: IntoWorkerResult,
+{
+    check_inplace_target(list, inplace)?;
+    if intern && inplace {
+        return Err(PyValueError::new_err(
+            "intern_results is not supported with inplace=True",
+        ));
+    }
+    let interner = intern.then(|| Arc::new(ResultInterner::new()));
+
+    let input = ListSnapshot::new(list, slice)?;
+    if input.len() == 0 {
+        return map_empty(py, list, inplace, progress);
+    }
+    if runs_parallel(jobs, input.len(), input.estimated_chars()) {
+        map_parallel(
+            py, list, input, jobs, inplace, interner, progress, make_func,
+        )
+    } else {
+        map_sequential(py, list, input, inplace, interner, progress, make_func)
+    }
+}
+
+/// Element-wise map over two lists of equal length: `func` receives the pair
+/// of strings at each index. With `inplace`, results replace the items of
+/// `list_a`.
+pub fn map_pylist2<'py, F1, F2, R>(
+    py: Python<'py>,
+    list_a: &Bound<'py, PyList>,
+    list_b: &Bound<'py, PyList>,
+    jobs: usize,
+    inplace: bool,
+    progress: Option<Progress<'py>>,
+    make_func: F1,
+) -> PyResult<PyObject>
+where
+    F1: Fn() -> F2 + Send + Sync,
+    F2: for<'a> Fn(&'a str, &'a str) -> R + Send + 'static,
+    R: IntoWorkerResult,
+{
+    check_inplace_target(list_a, inplace)?;
+
+    let input = PairSnapshot(
+        ListSnapshot::new(list_a, SliceArgs::default())?,
+        ListSnapshot::new(list_b, SliceArgs::default())?,
+    );
+    if input.0.len() != input.1.len() {
+        return Err(PyValueError::new_err(format!(
+            "lists must have equal length, got {} and {}",
+            input.0.len(),
+            input.1.len()
+        )));
+    }
+
+    if input.0.len() == 0 {
+        return map_empty(py, list_a, inplace, progress);
+    }
+    let chars = input.0.estimated_chars() + input.1.estimated_chars();
+    if runs_parallel(jobs, input.0.len(), chars) {
+        map_parallel(py, list_a, input, jobs, inplace, None, progress, make_func)
+    } else {
+        map_sequential(py, list_a, input, inplace, None, progress, make_func)
+    }
+}
+
+/// Apply a length-preserving byte transform to every string of `list`, inplace.
+///
+/// `func` receives UTF-8 bytes and must leave bytes >= 0x80 untouched and map
+/// ASCII to ASCII, so the string keeps its length and kind. Strings owned
+/// only by `list` are rewritten in their own buffer, without allocating (see
+/// `private_ascii_buffer`), by up to `jobs` workers. Every other string
+/// (shared, interned or non-ASCII) is copied, transformed and replaced in
+/// the list, so other references to it never see a change.
+pub fn map_pylist_bytes_inplace<F>(list: &Bound<PyList>, jobs: usize, func: F) -> PyResult<PyObject>
+where
+    F: Fn(&mut [u8]) + Sync,
+{
+    check_inplace_target(list, true)?;
+
+    // Sort the items under the GIL; nothing below calls into Python, so the
+    // private buffers can't be reached by other code while they are rewritten
+    let mut private = Vec::new();
+    let mut shared = Vec::new();
+    for idx in 0..list.len() {
+        // Borrowed, so a string held only by the list has a refcount of 1
+        let item_ptr = unsafe { pyo3_ffi::PyList_GET_ITEM(list.as_ptr(), idx as isize) };
+        if unsafe { pyo3_ffi::PyUnicode_Check(item_ptr) } == 0 {
+            return Err(PyTypeError::new_err(format!(
+                "expected str at index {idx}, got {}",
+                list.get_item(idx)?.get_type().name()?
+            )));
+        }
+        match unsafe { private_ascii_buffer(item_ptr) } {
+            Some(buffer) => private.push(buffer),
+            None => shared.push(idx),
+        }
+    }
+    debug_println!(
+        "bytes inplace: {} private, {} shared",
+        private.len(),
+        shared.len()
+    );
+
+    // A string can't be in the list twice with a refcount of 1, so the
+    // buffers never alias
+    let real_jobs = jobs.min(private.len()).max(1);
+    if real_jobs == 1 {
+        private.iter_mut().for_each(|buffer| func(buffer));
+    } else {
+        let pool = rayon::ThreadPoolBuilder::new()
+            .num_threads(real_jobs)
+            .thread_name(|t| format!("worker_{}", t))
+            .build()
+            .unwrap();
+
+        let chunk_len = private.len().div_ceil(real_jobs);
+        let func = &func;
+        pool.scope(|scope| {
+            for chunk in private.chunks_mut(chunk_len) {
+                scope.spawn(move |_| chunk.iter_mut().for_each(|buffer| func(buffer)));
+            }
+        });
+    }
+
+    let list_ptr = PyObjectPtr(list.as_ptr());
+    let bump = bumpalo::Bump::new();
+    for idx in shared {
+        let item = list.get_item(idx)?.unbind();
+        let mut bytes = convert_pystring(&item, &bump).as_bytes().to_vec();
+        func(&mut bytes);
+        unsafe {
+            let text = std::str::from_utf8_unchecked(&bytes);
+            let py_obj = PyObjectPtr(create_string(text));
+            if py_obj.0.is_null() {
+                return Err(PyMemoryError::new_err("failed to allocate result item"));
+            }
+            replace_list_item(&list_ptr, idx, py_obj)?;
+        }
+    }
+
+    Ok(list.clone().into())
+}
+
+/// Compute the hash of every string of `list` ahead of use as dict or set
+/// keys, where it is then found cached, by up to `jobs` workers.
+///
+/// Exact `str` and yurki.String items not hashed yet go to the workers (see
+/// `hash_string`). Other `str` subclasses may override `__hash__`, so they
+/// are hashed on the calling thread.
+pub fn prehash_pylist(list: &Bound<PyList>, jobs: usize) -> PyResult<PyObject> {
+    let py = list.py();
+    let mut items = Vec::with_capacity(list.len());
+    for (idx, item) in list.iter().enumerate() {
+        if !item.is_instance_of::<PyString>() {
+            return Err(PyTypeError::new_err(format!(
+                "expected str at index {idx}, got {}",
+                item.get_type().name()?
+            )));
+        }
+        items.push(item.unbind());
+    }
+
+    // The `__hash__` of a subclass may change the list, so the strings are
+    // read from the snapshot, which keeps them alive until the workers finish
+    let mut pending = Vec::new();
+    let mut seen = HashSet::new();
+    for item in &items {
+        let item_ptr = item.as_ptr();
+        if unsafe { !is_exact_string(item_ptr) } {
+            item.bind(py).hash()?;
+        } else if unsafe { !has_cached_hash(item_ptr) } && seen.insert(item_ptr) {
+            pending.push(PyObjectPtr(item_ptr));
+        }
+    }
+    debug_println!("prehash: {} strings to hash", pending.len());
+
+    // Workers run while the GIL is held here, so no Python code runs and
+    // the strings of the snapshot stay unchanged. `pending` holds each string once, so no two
+    // workers write the same cached hash.
+    let real_jobs = jobs.min(pending.len()).max(1);
+    if real_jobs == 1 {
+        pending.iter().for_each(|obj| unsafe {
+            hash_string(obj.0);
+        });
     } else {
-        map_pylist_parallel(py, list, jobs, inplace, make_func)
+        let pool = rayon::ThreadPoolBuilder::new()
+            .num_threads(real_jobs)
+            .thread_name(|t| format!("worker_{}", t))
+            .build()
+            .unwrap();
+
+        let chunk_len = pending.len().div_ceil(real_jobs);
+        pool.scope(|scope| {
+            for chunk in pending.chunks(chunk_len) {
+                scope.spawn(move |_| {
+                    chunk.iter().for_each(|obj| unsafe {
+                        hash_string(obj.0);
+                    })
+                });
+            }
+        });
+    }
+
+    Ok(list.clone().into())
+}
+
+/// New result list of the strings of `list` in ascending order, as
+/// `sorted(list)` gives, or descending with `reverse`. Equal strings keep
+/// their order either way.
+///
+/// Strings compare by code point, which is the order of their UTF-8 bytes,
+/// so `str` subclasses overriding the comparisons are not consulted. The
+/// keys are converted on the calling thread and sorted by up to `jobs`
+/// workers without the GIL; the result holds the same objects.
+pub fn sort_pylist(list: &Bound<PyList>, reverse: bool, jobs: usize) -> PyResult<PyObject> {
+    let py = list.py();
+    let mut items = Vec::with_capacity(list.len());
+    for idx in 0..list.len() {
+        let item = list.get_item(idx)?;
+        if !item.is_instance_of::<PyString>() {
+            return Err(PyTypeError::new_err(format!(
+                "expected str at index {idx}, got {}",
+                item.get_type().name()?
+            )));
+        }
+        items.push(item.unbind());
+    }
+
+    let bump = bumpalo::Bump::new();
+    let keys: Vec<&str> = items
+        .iter()
+        .map(|item| borrow_ascii_pystring(item).unwrap_or_else(|| convert_pystring(item, &bump)))
+        .collect();
+    let compare = |&a: &usize, &b: &usize| {
+        if reverse {
+            keys[b].cmp(keys[a])
+        } else {
+            keys[a].cmp(keys[b])
+        }
+    };
+
+    // Stable sorts, so ties stay in list order in both directions
+    let mut order: Vec<usize> = (0..items.len()).collect();
+    let real_jobs = jobs.min(items.len()).max(1);
+    if real_jobs == 1 {
+        order.sort_by(compare);
+    } else {
+        let pool = rayon::ThreadPoolBuilder::new()
+            .num_threads(real_jobs)
+            .thread_name(|t| format!("worker_{}", t))
+            .build()
+            .unwrap();
+
+        // Workers only compare the keys, which `items` keeps alive
+        py.allow_threads(|| pool.install(|| order.par_sort_by(compare)));
+    }
+
+    let sorted: Vec<_> = order.into_iter().map(|i| items[i].as_ptr()).collect();
+    unsafe {
+        let result = list_from_refs(sorted.into_iter(), create_result_list);
+        Ok(Bound::from_owned_ptr_or_err(py, result)?.unbind())
+    }
+}
+
+/// Map every string of `list` to `columns` values, scattering value `k` of
+/// row `i` into item `i` of the `k`-th result list.
+///
+/// `func` fills one slot per column with a new reference, or rejects the row
+/// with a message, without creating any object. A rejected row stops the
+/// work past it; the first one by index is raised as `ValueError`, with
+/// every result list released.
+pub fn map_pylist_columns<F1, F2>(
+    py: Python,
+    list: &Bound<PyList>,
+    jobs: usize,
+    columns: usize,
+    make_func: F1,
+) -> PyResult<Vec<PyObject>>
+where
+    F1: Fn() -> F2 + Send + Sync,
+    F2: for<'a> Fn(&'a str, &mut [PyObjectPtr]) -> Result<(), String>,
+{
+    let types = FastTypes::current();
+    let _types_scope = TypesScope::enter(types);
+    let input = ListSnapshot::new(list, SliceArgs::default())?;
+    let list_len = input.len();
+
+    let targets = (0..columns)
+        .map(|_| unsafe {
+            let result_list = create_result_list(list_len as isize);
+            assert!(!result_list.is_null());
+            PyObjectPtr(result_list)
+        })
+        .collect::<Vec<_>>();
+
+    // Lowest rejected row so far; workers stop once they pass it, so rows
+    // before it are still checked and the reported one doesn't depend on timing
+    let first_rejected = AtomicUsize::new(usize::MAX);
+    let first_error: Mutex<Option<(usize, String)>> = Mutex::new(None);
+
+    let run_range = |range_start: usize, range_stop: usize| {
+        let _types_scope = TypesScope::enter(types);
+        let _intern_scope = InternScope::enter(None);
+        let func = make_func();
+        let mut bump_manager = BumpAllocatorManager::new("Columns".to_string());
+        let batch_size = MANAGEMENT_BATCH_SIZE.load(Ordering::Relaxed);
+        let mut row = vec![PyObjectPtr(std::ptr::null_mut()); columns];
+
+        for i in range_start..range_stop {
+            if i > first_rejected.load(Ordering::Relaxed) {
+                break;
+            }
+            match func(input.get_string(i, bump_manager.bump()), &mut row) {
+                Ok(()) => {
+                    for (target, item) in targets.iter().zip(&row) {
+                        unsafe { set_list_item(target, i, *item) };
+                    }
+                }
+                Err(message) => {
+                    first_rejected.fetch_min(i, Ordering::Relaxed);
+                    let mut first = first_error.lock();
+                    if first.as_ref().is_none_or(|(idx, _)| i < *idx) {
+                        *first = Some((i, message));
+                    }
+                    break;
+                }
+            }
+            if (i - range_start).is_multiple_of(batch_size) {
+                bump_manager.manage_memory();
+            }
+        }
+    };
+
+    let real_jobs = jobs.min(list_len).max(1);
+    debug_println!(
+        "columns: {} rows, {} columns, jobs {}",
+        list_len,
+        columns,
+        real_jobs
+    );
+    if real_jobs == 1 {
+        run_range(0, list_len);
+    } else {
+        let pool = rayon::ThreadPoolBuilder::new()
+            .num_threads(real_jobs)
+            .thread_name(|t| format!("worker_{}", t))
+            .build()
+            .unwrap();
+
+        // Each worker fills its own contiguous range of every result list
+        let run_range = &run_range;
+        while_workers_run(py, || {
+            pool.scope(|scope| {
+                for job_idx in 0..real_jobs {
+                    let (range_start, range_stop) = make_range(list_len, real_jobs, job_idx);
+                    scope.spawn(move |_| run_range(range_start, range_stop));
+                }
+            })
+        });
+    }
+
+    if let Some((idx, message)) = first_error.into_inner() {
+        for target in targets {
+            unsafe { discard_result_list(target) };
+        }
+        return Err(PyValueError::new_err(format!("row {idx}: {message}")));
+    }
+
+    let mut results = Vec::with_capacity(columns);
+    let mut targets = targets.into_iter();
+    while let Some(target) = targets.next() {
+        match unsafe { finish_result_list(py, target) } {
+            Ok(result) => results.push(result),
+            Err(err) => {
+                targets.for_each(|target| unsafe { discard_result_list(target) });
+                return Err(err);
+            }
+        }
+    }
+    Ok(results)
+}
+
+/// Map every string of `list` in two passes: workers run the function from
+/// `make_func` over their ranges in parallel, then `finish` turns each
+/// row's output into its result on the calling thread, in row order and
+/// holding the GIL, so it can call back into Python.
+///
+/// `finish` receives the row's text and worker output, and returns the new
+/// text, or `None` to keep the input string itself. Its first error is
+/// returned, and the rows after it are not finished.
+pub fn map_pylist_then<F1, F2, T, G>(
+    py: Python,
+    list: &Bound<PyList>,
+    jobs: usize,
+    make_func: F1,
+    mut finish: G,
+) -> PyResult<PyObject>
+where
+    F1: Fn() -> F2 + Send + Sync,
+    F2: for<'a> Fn(&'a str) -> T,
+    T: Send,
+    G: FnMut(&str, T) -> PyResult<Option<String>>,
+{
+    let _types_scope = TypesScope::enter(FastTypes::current());
+    let input = ListSnapshot::new(list, SliceArgs::default())?;
+    let list_len = input.len();
+    let real_jobs = jobs.min(list_len).max(1);
+
+    // Worker outputs of each range, finished in order below
+    let parts = (0..real_jobs)
+        .map(|_| Mutex::new(Vec::new()))
+        .collect::<Vec<_>>();
+
+    let run_range = |job_idx: usize, range_start: usize, range_stop: usize| {
+        let _intern_scope = InternScope::enter(None);
+        let func = make_func();
+        let mut bump_manager = BumpAllocatorManager::new("Then".to_string());
+        let batch_size = MANAGEMENT_BATCH_SIZE.load(Ordering::Relaxed);
+        let mut outputs = parts[job_idx].lock();
+        outputs.reserve(range_stop - range_start);
+
+        for i in range_start..range_stop {
+            outputs.push(func(input.get_string(i, bump_manager.bump())));
+            if (i - range_start).is_multiple_of(batch_size) {
+                bump_manager.manage_memory();
+            }
+        }
+    };
+
+    debug_println!("then: {} rows, jobs {}", list_len, real_jobs);
+    if real_jobs == 1 {
+        run_range(0, 0, list_len);
+    } else {
+        let pool = rayon::ThreadPoolBuilder::new()
+            .num_threads(real_jobs)
+            .thread_name(|t| format!("worker_{}", t))
+            .build()
+            .unwrap();
+
+        let run_range = &run_range;
+        while_workers_run(py, || {
+            pool.scope(|scope| {
+                for job_idx in 0..real_jobs {
+                    let (range_start, range_stop) = make_range(list_len, real_jobs, job_idx);
+                    scope.spawn(move |_| run_range(job_idx, range_start, range_stop));
+                }
+            })
+        });
+    }
+
+    let result = unsafe { create_result_list(list_len as isize) };
+    if result.is_null() {
+        return Err(PyMemoryError::new_err("failed to allocate result list"));
+    }
+    let result = PyObjectPtr(result);
+    let outputs = parts.into_iter().flat_map(Mutex::into_inner);
+    let mut bump_manager = BumpAllocatorManager::new("Then".to_string());
+    let batch_size = MANAGEMENT_BATCH_SIZE.load(Ordering::Relaxed);
+    for (i, output) in outputs.enumerate() {
+        let text = input.get_string(i, bump_manager.bump());
+        let item = match finish(text, output) {
+            Ok(Some(text)) => unsafe { create_result_string(&text) },
+            Ok(None) => {
+                let item = input.items[i].as_ptr();
+                unsafe { pyo3_ffi::Py_INCREF(item) };
+                PyObjectPtr(item)
+            }
+            Err(err) => {
+                unsafe { discard_result_list(result) };
+                return Err(err);
+            }
+        };
+        unsafe { set_list_item(&result, i, item) };
+        if i.is_multiple_of(batch_size) {
+            bump_manager.manage_memory();
+        }
+    }
+    unsafe { finish_result_list(py, result) }
+}
+
+/// Row indices of one range of a list, bucketed by key, the buckets in
+/// order of first appearance.
+#[derive(Default)]
+struct Groups {
+    slots: HashMap<String, usize>,
+    none_slot: Option<usize>,
+    buckets: Vec<(Option<String>, Vec<usize>)>,
+}
+
+impl Groups {
+    fn slot(&mut self, key: Option<&str>) -> usize {
+        let found = match key {
+            Some(key) => self.slots.get(key).copied(),
+            None => self.none_slot,
+        };
+        found.unwrap_or_else(|| {
+            let slot = self.buckets.len();
+            match key {
+                Some(key) => {
+                    self.slots.insert(key.to_owned(), slot);
+                }
+                None => self.none_slot = Some(slot),
+            }
+            self.buckets.push((key.map(str::to_owned), Vec::new()));
+            slot
+        })
+    }
+
+    fn push(&mut self, key: Option<&str>, row: usize) {
+        let slot = self.slot(key);
+        self.buckets[slot].1.push(row);
+    }
+
+    /// Append the rows of `other`, a range after all of this one's rows.
+    fn merge(&mut self, other: Groups) {
+        for (key, mut rows) in other.buckets {
+            let slot = self.slot(key.as_deref());
+            self.buckets[slot].1.append(&mut rows);
+        }
+    }
+}
+
+/// Bucket the strings of `list` by the key the function from `make_func`
+/// finds in each, as a byte range of the row, `None` for rows without one. Returns a dict of each
+/// key to a result list of its rows, holding the same string objects; keys
+/// are in order of first appearance and rows in list order.
+///
+/// Each worker groups the row indices of its contiguous range under its own
+/// keys, and the ranges are merged in order on the calling thread.
+pub fn group_pylist<F1, F2>(
+    py: Python,
+    list: &Bound<PyList>,
+    jobs: usize,
+    make_func: F1,
+) -> PyResult<Py<PyDict>>
+where
+    F1: Fn() -> F2 + Send + Sync,
+    F2: Fn(&str) -> Option<std::ops::Range<usize>>,
+{
+    let input = ListSnapshot::new(list, SliceArgs::default())?;
+    let list_len = input.len();
+    let real_jobs = jobs.min(list_len).max(1);
+    let parts = (0..real_jobs)
+        .map(|_| Mutex::new(Groups::default()))
+        .collect::<Vec<_>>();
+
+    let run_range = |job_idx: usize, range_start: usize, range_stop: usize| {
+        let func = make_func();
+        let mut bump_manager = BumpAllocatorManager::new("Group".to_string());
+        let batch_size = MANAGEMENT_BATCH_SIZE.load(Ordering::Relaxed);
+        let mut groups = parts[job_idx].lock();
+
+        for i in range_start..range_stop {
+            let text = input.get_string(i, bump_manager.bump());
+            groups.push(func(text).map(|span| &text[span]), i);
+            if (i - range_start).is_multiple_of(batch_size) {
+                bump_manager.manage_memory();
+            }
+        }
+    };
+
+    debug_println!("group: {} rows, jobs {}", list_len, real_jobs);
+    if real_jobs == 1 {
+        run_range(0, 0, list_len);
+    } else {
+        let pool = rayon::ThreadPoolBuilder::new()
+            .num_threads(real_jobs)
+            .thread_name(|t| format!("worker_{}", t))
+            .build()
+            .unwrap();
+
+        // Workers create no objects, so the GIL is never needed
+        let run_range = &run_range;
+        py.allow_threads(|| {
+            pool.scope(|scope| {
+                for job_idx in 0..real_jobs {
+                    let (range_start, range_stop) = make_range(list_len, real_jobs, job_idx);
+                    scope.spawn(move |_| run_range(job_idx, range_start, range_stop));
+                }
+            })
+        });
+    }
+
+    let mut parts = parts.into_iter().map(Mutex::into_inner);
+    let mut groups = parts.next().unwrap_or_default();
+    parts.for_each(|part| groups.merge(part));
+
+    let result = PyDict::new(py);
+    for (key, rows) in groups.buckets {
+        let items: Vec<_> = rows.iter().map(|&i| input.items[i].as_ptr()).collect();
+        let bucket = unsafe {
+            Bound::from_owned_ptr_or_err(py, list_from_refs(items.into_iter(), create_result_list))?
+        };
+        result.set_item(key, bucket)?;
+    }
+    Ok(result.unbind())
+}
+
+/// Map every string of `list` to any number of items and concatenate them
+/// into one flat result list, in row order.
+///
+/// Each worker pushes the items of its contiguous range into a local
+/// `ListBuilder`; the builders are joined in range order once all are done.
+/// With `rows`, also returns a parallel list holding the source row index
+/// of every item.
+pub fn flat_map_pylist<F1, F2>(
+    py: Python,
+    list: &Bound<PyList>,
+    jobs: usize,
+    rows: bool,
+    make_func: F1,
+) -> PyResult<(PyObject, Option<PyObject>)>
+where
+    F1: Fn() -> F2 + Send + Sync,
+    F2: for<'a> Fn(&'a str, &mut ListBuilder),
+{
+    let types = FastTypes::current();
+    let _types_scope = TypesScope::enter(types);
+    let input = ListSnapshot::new(list, SliceArgs::default())?;
+    let list_len = input.len();
+    let real_jobs = jobs.min(list_len).max(1);
+
+    // One builder and row index vector per range, joined in order below
+    let parts = (0..real_jobs)
+        .map(|_| Mutex::new((ListBuilder::new(), Vec::new())))
+        .collect::<Vec<_>>();
+
+    let run_range = |job_idx: usize, range_start: usize, range_stop: usize| {
+        let _types_scope = TypesScope::enter(types);
+        let _intern_scope = InternScope::enter(None);
+        let func = make_func();
+        let mut bump_manager = BumpAllocatorManager::new("Flat".to_string());
+        let batch_size = MANAGEMENT_BATCH_SIZE.load(Ordering::Relaxed);
+        let mut part = parts[job_idx].lock();
+        let (builder, row_indices) = &mut *part;
+
+        for i in range_start..range_stop {
+            func(input.get_string(i, bump_manager.bump()), builder);
+            if rows {
+                row_indices.resize(builder.len(), i);
+            }
+            if (i - range_start).is_multiple_of(batch_size) {
+                bump_manager.manage_memory();
+            }
+        }
+    };
+
+    debug_println!("flat: {} rows, jobs {}", list_len, real_jobs);
+    if real_jobs == 1 {
+        run_range(0, 0, list_len);
+    } else {
+        let pool = rayon::ThreadPoolBuilder::new()
+            .num_threads(real_jobs)
+            .thread_name(|t| format!("worker_{}", t))
+            .build()
+            .unwrap();
+
+        let run_range = &run_range;
+        while_workers_run(py, || {
+            pool.scope(|scope| {
+                for job_idx in 0..real_jobs {
+                    let (range_start, range_stop) = make_range(list_len, real_jobs, job_idx);
+                    scope.spawn(move |_| run_range(job_idx, range_start, range_stop));
+                }
+            })
+        });
+    }
+
+    let mut items = ListBuilder::new();
+    let mut row_indices = Vec::new();
+    let parts = parts.into_iter().map(Mutex::into_inner).collect::<Vec<_>>();
+    let total = parts
+        .iter()
+        .map(|(builder, _)| builder.len())
+        .sum::<usize>();
+    if !items.reserve(total) || (rows && row_indices.try_reserve(total).is_err()) {
+        return Err(PyMemoryError::new_err("failed to allocate result items"));
+    }
+    for (mut builder, part_rows) in parts {
+        items.append(&mut builder);
+        row_indices.extend(part_rows);
+    }
+
+    let result = unsafe { items.finish_with(create_result_list) };
+    if result.is_null() {
+        return Err(PyMemoryError::new_err("failed to allocate result list"));
+    }
+    let result = unsafe { finish_result_list(py, PyObjectPtr(result))? };
+    if !rows {
+        return Ok((result, None));
+    }
+
+    let row_list = unsafe { create_result_list(row_indices.len() as isize) };
+    if row_list.is_null() {
+        return Err(PyMemoryError::new_err("failed to allocate result list"));
+    }
+    let row_list = PyObjectPtr(row_list);
+    for (k, &row) in row_indices.iter().enumerate() {
+        unsafe { set_list_item(&row_list, k, PyObjectPtr(pyo3_ffi::PyLong_FromSize_t(row))) };
+    }
+    let row_list = unsafe { finish_result_list(py, row_list)? };
+    Ok((result, Some(row_list)))
+}
+
+// yurki.List results are fixed-size: refuse to rewrite them behind the caller's back
+fn check_inplace_target(list: &Bound<PyList>, inplace: bool) -> PyResult<()> {
+    if inplace && unsafe { is_list(list.as_ptr()) } {
+        return Err(PyTypeError::new_err(
+            "inplace=True requires a builtin list, yurki.List is fixed-size",
+        ));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn make_range_covers_list_in_order() {
+        for len in [0, 1, 2, 7, 100, 1001] {
+            for jobs in 1..=9 {
+                let mut expected_start = 0;
+                for i in 0..jobs {
+                    let (start, end) = make_range(len, jobs, i);
+                    assert_eq!(start, expected_start, "len={len} jobs={jobs} i={i}");
+                    assert!(start <= end);
+                    // Engines clamp jobs to the length, so no worker idles
+                    if jobs <= len {
+                        assert!(start < end, "len={len} jobs={jobs} i={i}");
+                    }
+                    expected_start = end;
+                }
+                assert_eq!(expected_start, len, "len={len} jobs={jobs}");
+            }
+        }
+    }
+
+    #[test]
+    fn arena_pool_reuses_reset_arenas() {
+        // A pool of its own: other tests share the global one
+        let pool = ArenaPool::new();
+        let bump = pool.take(1024);
+        let first = bump.alloc(0u8) as *const u8;
+        pool.put(bump);
+
+        // The pooled arena hands out the same memory again, from the start
+        let bump = pool.take(1024);
+        assert_eq!(bump.alloc(0u8) as *const u8, first);
+        pool.put(bump);
+
+        for _ in 0..ARENA_POOL_CAPACITY + 10 {
+            pool.put(bumpalo::Bump::with_capacity(1024));
+        }
+        assert_eq!(pool.0.lock().len(), ARENA_POOL_CAPACITY);
+    }
+
+    #[test]
+    fn make_range_balanced() {
+        let sizes: Vec<usize> = (0..4)
+            .map(|i| {
+                let (start, end) = make_range(10, 4, i);
+                end - start
+            })
+            .collect();
+        assert_eq!(sizes, vec![3, 3, 2, 2]);
+    }
+
+    fn slice(start: Option<isize>, stop: Option<isize>, step: Option<isize>) -> SliceArgs {
+        SliceArgs { start, stop, step }
+    }
+
+    fn selected(args: SliceArgs, len: usize) -> Vec<usize> {
+        let selection = args.resolve(len).unwrap();
+        (0..selection.len()).map(|i| selection.index(i)).collect()
+    }
+
+    #[test]
+    fn slice_matches_python_semantics() {
+        let all = |len: usize| (0..len).collect::<Vec<_>>();
+        assert_eq!(selected(SliceArgs::default(), 5), all(5));
+        assert_eq!(selected(slice(None, None, Some(2)), 5), vec![0, 2, 4]);
+        assert_eq!(selected(slice(Some(1), None, Some(2)), 5), vec![1, 3]);
+        assert_eq!(selected(slice(Some(-2), None, None), 5), vec![3, 4]);
+        assert_eq!(selected(slice(None, Some(-1), None), 5), vec![0, 1, 2, 3]);
+        assert_eq!(
+            selected(slice(None, None, Some(-1)), 5),
+            vec![4, 3, 2, 1, 0]
+        );
+        assert_eq!(selected(slice(Some(3), Some(0), Some(-2)), 5), vec![3, 1]);
+        assert_eq!(selected(slice(Some(-100), Some(100), None), 3), all(3));
+        assert_eq!(selected(slice(Some(100), None, Some(-1)), 3), vec![2, 1, 0]);
+    }
+
+    #[test]
+    fn slice_empty_selections() {
+        assert!(slice(Some(3), Some(1), None).resolve(5).unwrap().is_empty());
+        assert!(
+            slice(Some(1), Some(3), Some(-1))
+                .resolve(5)
+                .unwrap()
+                .is_empty()
+        );
+        assert!(slice(Some(10), None, None).resolve(5).unwrap().is_empty());
+        assert!(SliceArgs::default().resolve(0).unwrap().is_empty());
+        assert!(slice(None, None, Some(-1)).resolve(0).unwrap().is_empty());
+    }
+
+    #[test]
+    fn slice_zero_step() {
+        assert!(slice(None, None, Some(0)).resolve(5).is_none());
+    }
+
+    #[test]
+    fn runs_parallel_by_estimated_size() {
+        assert!(!runs_parallel(1, 1_000_000, 100_000_000));
+        assert!(!runs_parallel(8, 10, 1_000));
+        assert!(!runs_parallel(8, 1_000, 8_000));
+        assert!(runs_parallel(8, 2_100, 0));
+        assert!(runs_parallel(8, 10, DEFAULT_PARALLEL_THRESHOLD));
+        assert!(runs_parallel(2, usize::MAX, usize::MAX));
+    }
+
+    #[test]
+    #[should_panic(expected = "jobs must be > 0")]
+    fn make_range_zero_jobs() {
+        make_range(10, 0, 0);
     }
 }
@@ -0,0 +1,204 @@
+//! Line-oriented processing of files read directly in Rust, skipping the
+//! creation of Python `str` objects for the input.
+
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::Path;
+
+use pyo3::exceptions::{PyUnicodeDecodeError, PyValueError};
+use pyo3::prelude::*;
+
+use crate::core::{
+    FastTypes, PyObjectPtr, TypesScope, create_result_list, finish_result_list, make_range,
+    set_list_item, while_workers_run,
+};
+use crate::pipeline::Pipeline;
+use crate::simd::validate_utf8_simd;
+
+/// How invalid UTF-8 in the input is handled.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum DecodeErrors {
+    Strict,
+    Replace,
+}
+
+impl DecodeErrors {
+    pub fn parse(encoding: &str, errors: &str) -> PyResult<Self> {
+        if !matches!(encoding.to_ascii_lowercase().as_str(), "utf-8" | "utf8") {
+            return Err(PyValueError::new_err(format!(
+                "unsupported encoding {encoding:?}, only utf-8 is supported"
+            )));
+        }
+        match errors {
+            "strict" => Ok(Self::Strict),
+            "replace" => Ok(Self::Replace),
+            _ => Err(PyValueError::new_err(format!(
+                "errors must be 'strict' or 'replace', got {errors:?}"
+            ))),
+        }
+    }
+}
+
+/// Read a whole file and decode it as UTF-8.
+pub fn read_text(path: &Path, errors: DecodeErrors) -> PyResult<String> {
+    let data = std::fs::read(path)?;
+    debug_println!("read_text: {} bytes from {:?}", data.len(), path);
+
+    match (validate_utf8_simd(&data), errors) {
+        // SAFETY: validated just above
+        (Ok(()), _) => Ok(unsafe { String::from_utf8_unchecked(data) }),
+        (Err(_), DecodeErrors::Replace) => Ok(String::from_utf8_lossy(&data).into_owned()),
+        (Err(err), DecodeErrors::Strict) => {
+            // Report the offending line only, the file may be huge
+            let position = err.valid_up_to;
+            let error_len = err.error_len.unwrap_or(data.len() - position);
+            let line_start = data[..position]
+                .iter()
+                .rposition(|&b| b == b'\n')
+                .map_or(0, |p| p + 1);
+            let line_end = data[position..]
+                .iter()
+                .position(|&b| b == b'\n')
+                .map_or(data.len(), |p| position + p);
+            let line_number = data[..line_start].iter().filter(|&&b| b == b'\n').count() + 1;
+
+            Err(PyUnicodeDecodeError::new_err((
+                "utf-8",
+                data[line_start..line_end].to_vec(),
+                position - line_start,
+                position - line_start + error_len,
+                format!("invalid utf-8 on line {line_number}"),
+            )))
+        }
+    }
+}
+
+/// Split text into lines, accepting `\n` and `\r\n` endings and a missing
+/// trailing newline.
+pub fn split_lines(text: &str) -> Vec<&str> {
+    text.lines().collect()
+}
+
+/// Run `pipeline` over every line of the file at `path` in parallel and
+/// return the results as a `yurki.List`.
+pub fn process_file(
+    py: Python,
+    path: &Path,
+    pipeline: &Pipeline,
+    jobs: usize,
+    errors: DecodeErrors,
+) -> PyResult<PyObject> {
+    if jobs == 0 {
+        return Err(PyValueError::new_err("jobs must be > 0"));
+    }
+
+    let types = FastTypes::current();
+    let _types_scope = TypesScope::enter(types);
+    let text = read_text(path, errors)?;
+    let lines = split_lines(&text);
+    let real_jobs = jobs.min(lines.len()).max(1);
+    debug_println!("process_file: {} lines, jobs {}", lines.len(), real_jobs);
+
+    let result_list_ptr = unsafe {
+        let result_list = create_result_list(lines.len() as isize);
+        assert!(!result_list.is_null());
+        PyObjectPtr(result_list)
+    };
+
+    let pool = rayon::ThreadPoolBuilder::new()
+        .num_threads(real_jobs)
+        .thread_name(|t| format!("file_worker_{}", t))
+        .build()
+        .unwrap();
+
+    // Each worker fills its own contiguous range of the result list
+    while_workers_run(py, || {
+        pool.scope(|scope| {
+            for job_idx in 0..real_jobs {
+                let (range_start, range_stop) = make_range(lines.len(), real_jobs, job_idx);
+                let lines = &lines;
+                scope.spawn(move |_| {
+                    let _types_scope = TypesScope::enter(types);
+                    for (i, line) in lines[range_start..range_stop].iter().enumerate() {
+                        unsafe {
+                            let py_obj = pipeline.apply(line);
+                            set_list_item(&result_list_ptr, range_start + i, py_obj);
+                        }
+                    }
+                });
+            }
+        })
+    });
+
+    unsafe { finish_result_list(py, result_list_ptr) }
+}
+
+/// Run `pipeline` over every line of the file at `path` in parallel and write
+/// the results newline-delimited to `output_path`, in input order.
+///
+/// For a pipeline ending with `is_match`, matching input lines are written
+/// unchanged (grep-like filtering). Returns the number of lines written.
+pub fn process_file_to_path(
+    py: Python,
+    path: &Path,
+    output_path: &Path,
+    pipeline: &Pipeline,
+    jobs: usize,
+    errors: DecodeErrors,
+) -> PyResult<usize> {
+    if jobs == 0 {
+        return Err(PyValueError::new_err("jobs must be > 0"));
+    }
+    if !pipeline.produces_text() {
+        return Err(PyValueError::new_err(
+            "output_path requires string ops, optionally ending with is_match",
+        ));
+    }
+
+    let text = read_text(path, errors)?;
+
+    // No Python objects are touched from here on
+    py.allow_threads(|| {
+        let lines = split_lines(&text);
+        let real_jobs = jobs.min(lines.len()).max(1);
+        debug_println!(
+            "process_file_to_path: {} lines, jobs {}",
+            lines.len(),
+            real_jobs
+        );
+
+        let pool = rayon::ThreadPoolBuilder::new()
+            .num_threads(real_jobs)
+            .thread_name(|t| format!("file_worker_{}", t))
+            .build()
+            .unwrap();
+
+        // Each worker renders its contiguous range into its own buffer
+        let mut buffers = vec![(Vec::new(), 0usize); real_jobs];
+        pool.scope(|scope| {
+            for (job_idx, (buffer, written)) in buffers.iter_mut().enumerate() {
+                let (range_start, range_stop) = make_range(lines.len(), real_jobs, job_idx);
+                let lines = &lines;
+                scope.spawn(move |_| {
+                    for line in &lines[range_start..range_stop] {
+                        if let Some(result) = pipeline.apply_text(line) {
+                            buffer.extend_from_slice(result.as_bytes());
+                            buffer.push(b'\n');
+                            *written += 1;
+                        }
+                    }
+                });
+            }
+        });
+
+        // Stitch buffers in range order
+        let mut output = BufWriter::new(File::create(output_path)?);
+        let mut total = 0;
+        for (buffer, written) in &buffers {
+            output.write_all(buffer)?;
+            total += written;
+        }
+        output.flush()?;
+        Ok(total)
+    })
+}
@@ -4,9 +4,14 @@
 
 use crate::converter::ToPyObject;
 use mimalloc::MiMalloc;
+use pyo3::exceptions::{PyIndexError, PyKeyError, PyTypeError, PyValueError};
 use pyo3::prelude::*;
-use pyo3::types::{PyList, PyString};
+use pyo3::types::{PyDict, PyList, PyString, PyTuple};
 use regex::RegexBuilder;
+use std::borrow::Cow;
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::Arc;
 
 // Let's globaly use mimmaloc as allocator
 #[global_allocator]
@@ -26,11 +31,17 @@ macro_rules! debug_println {
 // Export the macro so it can be used in other modules
 pub(crate) use debug_println;
 
+pub mod classifier;
 pub mod converter;
 pub mod core;
+pub mod file;
 pub mod object;
+pub mod pattern;
+pub mod pipeline;
 pub mod simd;
+pub mod stream;
 pub mod text;
+pub mod translate;
 
 #[pymodule(gil_used = false)]
 mod yurki {
@@ -40,7 +51,18 @@ mod yurki {
     mod internal {
         use super::*;
 
+        #[pymodule_export]
+        use crate::classifier::Classifier;
+
+        #[pymodule_export]
+        use crate::pattern::CompiledPattern;
+
+        #[pymodule_export]
+        use crate::stream::MapIter;
+
         #[pyfunction]
+        #[pyo3(signature = (list, pattern, case, jobs, inplace, progress = None, progress_interval = 10_000, start = None, stop = None, step = None, no_match = "empty", intern_results = false))]
+        #[allow(clippy::too_many_arguments)]
         fn find_regex_in_string(
             py: Python,
             list: &Bound<PyList>,
@@ -48,25 +70,67 @@ mod yurki {
             case: bool,
             jobs: usize,
             inplace: bool,
+            progress: Option<Bound<PyAny>>,
+            progress_interval: usize,
+            start: Option<isize>,
+            stop: Option<isize>,
+            step: Option<isize>,
+            no_match: &str,
+            intern_results: bool,
         ) -> PyResult<PyObject> {
+            let progress = core::Progress::optional(progress, progress_interval)?;
+            let slice = core::SliceArgs { start, stop, step };
+
             let pattern = RegexBuilder::new(&pattern.to_string())
                 .case_insensitive(case)
                 .build()
                 .unwrap();
 
-            let make_func = {
-                let pattern = pattern.clone();
-                move || unsafe {
-                    let pattern = pattern.clone();
-                    move |s: &str| text::find_in_string(s, &pattern).to_py_object()
+            let list = match no_match {
+                "empty" => {
+                    let make_func = move || unsafe {
+                        let pattern = pattern.clone();
+                        move |s: &str| text::find_in_string(s, &pattern).to_py_object()
+                    };
+                    core::map_pylist_slice(
+                        py,
+                        list,
+                        slice,
+                        jobs,
+                        inplace,
+                        intern_results,
+                        progress,
+                        make_func,
+                    )?
+                }
+                "none" => {
+                    let make_func = move || unsafe {
+                        let pattern = pattern.clone();
+                        move |s: &str| text::find_in_string_opt(s, &pattern).to_py_object()
+                    };
+                    core::map_pylist_slice(
+                        py,
+                        list,
+                        slice,
+                        jobs,
+                        inplace,
+                        intern_results,
+                        progress,
+                        make_func,
+                    )?
+                }
+                _ => {
+                    return Err(PyValueError::new_err(format!(
+                        "no_match must be 'empty' or 'none', got {no_match:?}"
+                    )));
                 }
             };
-
-            let list = core::map_pylist(py, list, jobs, inplace, make_func)?;
             Ok(list)
         }
 
         #[pyfunction]
+        #[pyo3(signature = (list, pattern, case, jobs, inplace, progress = None, progress_interval = 10_000, start = None, stop = None, step = None))]
+        #[allow(clippy::too_many_arguments)]
         fn is_match_regex_in_string(
             py: Python,
             list: &Bound<PyList>,
@@ -74,7 +138,15 @@ mod yurki {
             case: bool,
             jobs: usize,
             inplace: bool,
+            progress: Option<Bound<PyAny>>,
+            progress_interval: usize,
+            start: Option<isize>,
+            stop: Option<isize>,
+            step: Option<isize>,
         ) -> PyResult<PyObject> {
+            let progress = core::Progress::optional(progress, progress_interval)?;
+            let slice = core::SliceArgs { start, stop, step };
+
             let pattern = RegexBuilder::new(&pattern.to_string())
                 .case_insensitive(case)
                 .build()
@@ -85,11 +157,86 @@ mod yurki {
                 move |s: &str| text::is_match_in_string(s, &pattern).to_py_object()
             };
 
-            let list = core::map_pylist(py, list, jobs, inplace, make_func)?;
+            let list =
+                core::map_pylist_slice(py, list, slice, jobs, inplace, false, progress, make_func)?;
+            Ok(list)
+        }
+
+        #[pyfunction]
+        #[pyo3(signature = (list, pattern, case, jobs, inplace, progress = None, progress_interval = 10_000, start = None, stop = None, step = None))]
+        #[allow(clippy::too_many_arguments)]
+        fn is_match_at_start_regex_in_string(
+            py: Python,
+            list: &Bound<PyList>,
+            pattern: &Bound<PyString>,
+            case: bool,
+            jobs: usize,
+            inplace: bool,
+            progress: Option<Bound<PyAny>>,
+            progress_interval: usize,
+            start: Option<isize>,
+            stop: Option<isize>,
+            step: Option<isize>,
+        ) -> PyResult<PyObject> {
+            let progress = core::Progress::optional(progress, progress_interval)?;
+            let slice = core::SliceArgs { start, stop, step };
+
+            let anchored = text::anchored_pattern(&pattern.to_string(), false)
+                .map_err(|e| PyValueError::new_err(e.to_string()))?;
+            let pattern = RegexBuilder::new(&anchored)
+                .case_insensitive(case)
+                .build()
+                .map_err(|e| PyValueError::new_err(e.to_string()))?;
+
+            let make_func = move || unsafe {
+                let pattern = pattern.clone();
+                move |s: &str| text::is_match_at_start(s, &pattern).to_py_object()
+            };
+
+            let list =
+                core::map_pylist_slice(py, list, slice, jobs, inplace, false, progress, make_func)?;
+            Ok(list)
+        }
+
+        #[pyfunction]
+        #[pyo3(signature = (list, pattern, case, jobs, inplace, progress = None, progress_interval = 10_000, start = None, stop = None, step = None))]
+        #[allow(clippy::too_many_arguments)]
+        fn is_fullmatch_regex_in_string(
+            py: Python,
+            list: &Bound<PyList>,
+            pattern: &Bound<PyString>,
+            case: bool,
+            jobs: usize,
+            inplace: bool,
+            progress: Option<Bound<PyAny>>,
+            progress_interval: usize,
+            start: Option<isize>,
+            stop: Option<isize>,
+            step: Option<isize>,
+        ) -> PyResult<PyObject> {
+            let progress = core::Progress::optional(progress, progress_interval)?;
+            let slice = core::SliceArgs { start, stop, step };
+
+            let anchored = text::anchored_pattern(&pattern.to_string(), true)
+                .map_err(|e| PyValueError::new_err(e.to_string()))?;
+            let pattern = RegexBuilder::new(&anchored)
+                .case_insensitive(case)
+                .build()
+                .map_err(|e| PyValueError::new_err(e.to_string()))?;
+
+            let make_func = move || unsafe {
+                let pattern = pattern.clone();
+                move |s: &str| text::is_fullmatch_in_string(s, &pattern).to_py_object()
+            };
+
+            let list =
+                core::map_pylist_slice(py, list, slice, jobs, inplace, false, progress, make_func)?;
             Ok(list)
         }
 
         #[pyfunction]
+        #[pyo3(signature = (list, pattern, case, jobs, inplace, progress = None, progress_interval = 10_000, start = None, stop = None, step = None, intern_results = false))]
+        #[allow(clippy::too_many_arguments)]
         fn capture_regex_in_string(
             py: Python,
             list: &Bound<PyList>,
@@ -97,7 +244,16 @@ mod yurki {
             case: bool,
             jobs: usize,
             inplace: bool,
+            progress: Option<Bound<PyAny>>,
+            progress_interval: usize,
+            start: Option<isize>,
+            stop: Option<isize>,
+            step: Option<isize>,
+            intern_results: bool,
         ) -> PyResult<PyObject> {
+            let progress = core::Progress::optional(progress, progress_interval)?;
+            let slice = core::SliceArgs { start, stop, step };
+
             let pattern = RegexBuilder::new(&pattern.to_string())
                 .case_insensitive(case)
                 .build()
@@ -108,11 +264,22 @@ mod yurki {
                 move |s: &str| text::capture_regex_in_string(s, &pattern).to_py_object()
             };
 
-            let list = core::map_pylist(py, list, jobs, inplace, make_func)?;
+            let list = core::map_pylist_slice(
+                py,
+                list,
+                slice,
+                jobs,
+                inplace,
+                intern_results,
+                progress,
+                make_func,
+            )?;
             Ok(list)
         }
 
         #[pyfunction]
+        #[pyo3(signature = (list, pattern, case, jobs, inplace, progress = None, progress_interval = 10_000, start = None, stop = None, step = None, intern_results = false))]
+        #[allow(clippy::too_many_arguments)]
         fn split_by_regexp_string(
             py: Python,
             list: &Bound<PyList>,
@@ -120,7 +287,16 @@ mod yurki {
             case: bool,
             jobs: usize,
             inplace: bool,
+            progress: Option<Bound<PyAny>>,
+            progress_interval: usize,
+            start: Option<isize>,
+            stop: Option<isize>,
+            step: Option<isize>,
+            intern_results: bool,
         ) -> PyResult<PyObject> {
+            let progress = core::Progress::optional(progress, progress_interval)?;
+            let slice = core::SliceArgs { start, stop, step };
+
             let pattern = RegexBuilder::new(&pattern.to_string())
                 .case_insensitive(case)
                 .build()
@@ -131,11 +307,279 @@ mod yurki {
                 move |s: &str| text::split_by_regexp_string(s, &pattern).to_py_object()
             };
 
-            let list = core::map_pylist(py, list, jobs, inplace, make_func)?;
+            let list = core::map_pylist_slice(
+                py,
+                list,
+                slice,
+                jobs,
+                inplace,
+                intern_results,
+                progress,
+                make_func,
+            )?;
+            Ok(list)
+        }
+
+        #[pyfunction]
+        #[pyo3(signature = (list, unicode = false, jobs = 1, inplace = false, progress = None, progress_interval = 10_000, start = None, stop = None, step = None, intern_results = false))]
+        #[allow(clippy::too_many_arguments)]
+        fn split_whitespace_string(
+            py: Python,
+            list: &Bound<PyList>,
+            unicode: bool,
+            jobs: usize,
+            inplace: bool,
+            progress: Option<Bound<PyAny>>,
+            progress_interval: usize,
+            start: Option<isize>,
+            stop: Option<isize>,
+            step: Option<isize>,
+            intern_results: bool,
+        ) -> PyResult<PyObject> {
+            let progress = core::Progress::optional(progress, progress_interval)?;
+            let slice = core::SliceArgs { start, stop, step };
+
+            let make_func = move || {
+                move |s: &str| unsafe {
+                    text::split_whitespace_in_string(s, unicode).to_py_object()
+                }
+            };
+
+            let list = core::map_pylist_slice(
+                py,
+                list,
+                slice,
+                jobs,
+                inplace,
+                intern_results,
+                progress,
+                make_func,
+            )?;
             Ok(list)
         }
 
         #[pyfunction]
+        #[pyo3(signature = (list, pattern, n, case = false, jobs = 1, missing = "empty", extra = "truncate"))]
+        #[allow(clippy::too_many_arguments)]
+        fn split_columns(
+            py: Python,
+            list: &Bound<PyList>,
+            pattern: &Bound<PyString>,
+            n: usize,
+            case: bool,
+            jobs: usize,
+            missing: &str,
+            extra: &str,
+        ) -> PyResult<Py<PyTuple>> {
+            if n == 0 {
+                return Err(PyValueError::new_err("n must be > 0"));
+            }
+            let fill_none = match missing {
+                "empty" => false,
+                "none" => true,
+                _ => {
+                    return Err(PyValueError::new_err(format!(
+                        "missing must be 'empty' or 'none', got {missing:?}"
+                    )));
+                }
+            };
+            let strict = match extra {
+                "truncate" => false,
+                "error" => true,
+                _ => {
+                    return Err(PyValueError::new_err(format!(
+                        "extra must be 'truncate' or 'error', got {extra:?}"
+                    )));
+                }
+            };
+
+            let pattern = RegexBuilder::new(&pattern.to_string())
+                .case_insensitive(case)
+                .build()
+                .map_err(|e| PyValueError::new_err(e.to_string()))?;
+
+            let make_func = move || {
+                let pattern = pattern.clone();
+                move |s: &str, row: &mut [core::PyObjectPtr]| {
+                    let (fields, overflow) = text::split_fields(s, &pattern, n);
+                    if overflow && strict {
+                        return Err(format!("more than {n} fields"));
+                    }
+                    for (k, slot) in row.iter_mut().enumerate() {
+                        *slot = unsafe {
+                            match fields.get(k) {
+                                Some(field) => field.to_py_object(),
+                                None if fill_none => None::<&str>.to_py_object(),
+                                None => "".to_py_object(),
+                            }
+                        };
+                    }
+                    Ok(())
+                }
+            };
+
+            let columns = core::map_pylist_columns(py, list, jobs, n, make_func)?;
+            Ok(PyTuple::new(py, columns)?.unbind())
+        }
+
+        #[pyfunction]
+        #[pyo3(signature = (list, pattern, func, count = 1, case = false, jobs = 1))]
+        fn replace_regex_callback(
+            py: Python,
+            list: &Bound<PyList>,
+            pattern: &Bound<PyString>,
+            func: &Bound<PyAny>,
+            count: usize,
+            case: bool,
+            jobs: usize,
+        ) -> PyResult<PyObject> {
+            if !func.is_callable() {
+                return Err(PyTypeError::new_err(format!(
+                    "func must be callable, got {}",
+                    func.get_type().name()?
+                )));
+            }
+            let pattern = RegexBuilder::new(&pattern.to_string())
+                .case_insensitive(case)
+                .build()
+                .map_err(|e| PyValueError::new_err(e.to_string()))?;
+
+            // Workers only find the matches; the callback runs on this thread
+            let make_func = move || {
+                let pattern = pattern.clone();
+                move |s: &str| text::match_spans(s, &pattern, count)
+            };
+            let finish = |s: &str, spans: Vec<(usize, usize)>| {
+                if spans.is_empty() {
+                    return Ok(None);
+                }
+                let mut out = String::with_capacity(s.len());
+                let mut last = 0;
+                for (start, end) in spans {
+                    let replacement = func.call1((&s[start..end],))?;
+                    let replacement = replacement.downcast::<PyString>().map_err(|_| {
+                        PyTypeError::new_err(format!(
+                            "callback must return str, not {}",
+                            replacement
+                                .get_type()
+                                .name()
+                                .map_or_else(|_| "?".to_string(), |n| n.to_string())
+                        ))
+                    })?;
+                    out.push_str(&s[last..start]);
+                    out.push_str(&replacement.to_cow()?);
+                    last = end;
+                }
+                out.push_str(&s[last..]);
+                Ok(Some(out))
+            };
+
+            core::map_pylist_then(py, list, jobs, make_func, finish)
+        }
+
+        #[pyfunction]
+        #[pyo3(signature = (list, pattern, case = false, jobs = 1, rows = false))]
+        fn flat_split(
+            py: Python,
+            list: &Bound<PyList>,
+            pattern: &Bound<PyString>,
+            case: bool,
+            jobs: usize,
+            rows: bool,
+        ) -> PyResult<PyObject> {
+            let pattern = RegexBuilder::new(&pattern.to_string())
+                .case_insensitive(case)
+                .build()
+                .map_err(|e| PyValueError::new_err(e.to_string()))?;
+
+            let make_func = move || {
+                let pattern = pattern.clone();
+                move |s: &str, builder: &mut object::ListBuilder| {
+                    for piece in pattern.split(s) {
+                        builder.push(unsafe { piece.to_py_object().0 });
+                    }
+                }
+            };
+
+            let (tokens, row_list) = core::flat_map_pylist(py, list, jobs, rows, make_func)?;
+            match row_list {
+                Some(row_list) => Ok(PyTuple::new(py, [tokens, row_list])?.into_any().unbind()),
+                None => Ok(tokens),
+            }
+        }
+
+        #[pyfunction]
+        #[pyo3(signature = (list, pattern, case = false, jobs = 1))]
+        fn capture_columns(
+            py: Python,
+            list: &Bound<PyList>,
+            pattern: &Bound<PyString>,
+            case: bool,
+            jobs: usize,
+        ) -> PyResult<Py<PyTuple>> {
+            let pattern = RegexBuilder::new(&pattern.to_string())
+                .case_insensitive(case)
+                .build()
+                .map_err(|e| PyValueError::new_err(e.to_string()))?;
+            let n = pattern.captures_len() - 1;
+            if n == 0 {
+                return Err(PyValueError::new_err("pattern has no capture groups"));
+            }
+
+            let make_func = move || {
+                let pattern = pattern.clone();
+                move |s: &str, row: &mut [core::PyObjectPtr]| {
+                    let groups = text::capture_groups(s, &pattern);
+                    for (slot, group) in row.iter_mut().zip(groups) {
+                        *slot = unsafe { group.to_py_object() };
+                    }
+                    Ok(())
+                }
+            };
+
+            let columns = core::map_pylist_columns(py, list, jobs, n, make_func)?;
+            Ok(PyTuple::new(py, columns)?.unbind())
+        }
+
+        #[pyfunction]
+        #[pyo3(signature = (list, pattern, group = None, case = false, jobs = 1))]
+        fn group_by_regex(
+            py: Python,
+            list: &Bound<PyList>,
+            pattern: &Bound<PyString>,
+            group: Option<&Bound<PyAny>>,
+            case: bool,
+            jobs: usize,
+        ) -> PyResult<Py<PyDict>> {
+            let pattern = RegexBuilder::new(&pattern.to_string())
+                .case_insensitive(case)
+                .build()
+                .map_err(|e| PyValueError::new_err(e.to_string()))?;
+            // By number or name, as `re.Match.group` takes it, 1 by default
+            let index = match group {
+                None => Some(1).filter(|&i| i < pattern.captures_len()),
+                Some(group) => match group.downcast::<PyString>() {
+                    Ok(name) => {
+                        let name = name.to_str()?;
+                        pattern.capture_names().position(|n| n == Some(name))
+                    }
+                    Err(_) => usize::try_from(group.extract::<isize>()?)
+                        .ok()
+                        .filter(|&i| i < pattern.captures_len()),
+                },
+            };
+            let group = index.ok_or_else(|| PyIndexError::new_err("no such group"))?;
+
+            let make_func = move || {
+                let pattern = pattern.clone();
+                move |s: &str| text::capture_group_span(s, &pattern, group)
+            };
+            core::group_pylist(py, list, jobs, make_func)
+        }
+
+        #[pyfunction]
+        #[pyo3(signature = (list, pattern, replacement, count, case, jobs, inplace, progress = None, progress_interval = 10_000, start = None, stop = None, step = None, intern_results = false))]
+        #[allow(clippy::too_many_arguments)]
         fn replace_regexp_in_string(
             py: Python,
             list: &Bound<PyList>,
@@ -145,7 +589,16 @@ mod yurki {
             case: bool,
             jobs: usize,
             inplace: bool,
+            progress: Option<Bound<PyAny>>,
+            progress_interval: usize,
+            start: Option<isize>,
+            stop: Option<isize>,
+            step: Option<isize>,
+            intern_results: bool,
         ) -> PyResult<PyObject> {
+            let progress = core::Progress::optional(progress, progress_interval)?;
+            let slice = core::SliceArgs { start, stop, step };
+
             let pattern = RegexBuilder::new(&pattern.to_string())
                 .case_insensitive(case)
                 .build()
@@ -161,21 +614,896 @@ mod yurki {
                 }
             };
 
-            let list = core::map_pylist(py, list, jobs, inplace, make_func)?;
+            let list = core::map_pylist_slice(
+                py,
+                list,
+                slice,
+                jobs,
+                inplace,
+                intern_results,
+                progress,
+                make_func,
+            )?;
             Ok(list)
         }
 
-        /// Hack: workaround for https://github.com/PyO3/pyo3/issues/759
-        #[pymodule_init]
-        fn init(m: &Bound<'_, PyModule>) -> PyResult<()> {
-            let _ = Python::with_gil(|py| {
-                Python::import(py, "sys")?
-                    .getattr("modules")?
-                    .set_item("yurki.internal", m)
-            });
+        #[pyfunction]
+        #[pyo3(signature = (list, ops, jobs = 1, inplace = false, progress = None, progress_interval = 10_000, start = None, stop = None, step = None, intern_results = false))]
+        #[allow(clippy::too_many_arguments)]
+        fn pipeline(
+            py: Python,
+            list: &Bound<PyList>,
+            ops: &Bound<PyList>,
+            jobs: usize,
+            inplace: bool,
+            progress: Option<Bound<PyAny>>,
+            progress_interval: usize,
+            start: Option<isize>,
+            stop: Option<isize>,
+            step: Option<isize>,
+            intern_results: bool,
+        ) -> PyResult<PyObject> {
+            let progress = core::Progress::optional(progress, progress_interval)?;
+            let slice = core::SliceArgs { start, stop, step };
+            let pipeline = Arc::new(crate::pipeline::Pipeline::compile(ops)?);
+
+            let make_func = move || unsafe {
+                let pipeline = pipeline.clone();
+                move |s: &str| pipeline.apply(s)
+            };
+
+            let list = core::map_pylist_slice(
+                py,
+                list,
+                slice,
+                jobs,
+                inplace,
+                intern_results,
+                progress,
+                make_func,
+            )?;
+            Ok(list)
+        }
+
+        #[pyfunction]
+        #[pyo3(signature = (list_a, list_b, jobs = 1, inplace = false, progress = None, progress_interval = 10_000))]
+        fn equals(
+            py: Python,
+            list_a: &Bound<PyList>,
+            list_b: &Bound<PyList>,
+            jobs: usize,
+            inplace: bool,
+            progress: Option<Bound<PyAny>>,
+            progress_interval: usize,
+        ) -> PyResult<PyObject> {
+            let progress = core::Progress::optional(progress, progress_interval)?;
+
+            let make_func = move || unsafe { move |a: &str, b: &str| (a == b).to_py_object() };
+
+            let list = core::map_pylist2(py, list_a, list_b, jobs, inplace, progress, make_func)?;
+            Ok(list)
+        }
+
+        #[pyfunction]
+        #[pyo3(signature = (list_a, list_b, sep = "", jobs = 1, inplace = false, progress = None, progress_interval = 10_000))]
+        #[allow(clippy::too_many_arguments)]
+        fn concat_pairwise(
+            py: Python,
+            list_a: &Bound<PyList>,
+            list_b: &Bound<PyList>,
+            sep: &str,
+            jobs: usize,
+            inplace: bool,
+            progress: Option<Bound<PyAny>>,
+            progress_interval: usize,
+        ) -> PyResult<PyObject> {
+            let progress = core::Progress::optional(progress, progress_interval)?;
+            let sep = sep.to_string();
+
+            let make_func = move || {
+                let sep = sep.clone();
+                move |a: &str, b: &str| unsafe { text::concat_with_sep(a, &sep, b).to_py_object() }
+            };
+
+            let list = core::map_pylist2(py, list_a, list_b, jobs, inplace, progress, make_func)?;
+            Ok(list)
+        }
+
+        #[pyfunction]
+        #[pyo3(signature = (list, jobs = 1, inplace = false, progress = None, progress_interval = 10_000, start = None, stop = None, step = None))]
+        #[allow(clippy::too_many_arguments)]
+        fn ascii_upper_string(
+            py: Python,
+            list: &Bound<PyList>,
+            jobs: usize,
+            inplace: bool,
+            progress: Option<Bound<PyAny>>,
+            progress_interval: usize,
+            start: Option<isize>,
+            stop: Option<isize>,
+            step: Option<isize>,
+        ) -> PyResult<PyObject> {
+            let progress = core::Progress::optional(progress, progress_interval)?;
+            let slice = core::SliceArgs { start, stop, step };
+
+            let make_func =
+                move || move |s: &str| unsafe { text::ascii_upper_in_string(s).to_py_object() };
+
+            let list =
+                core::map_pylist_slice(py, list, slice, jobs, inplace, false, progress, make_func)?;
+            Ok(list)
+        }
+
+        #[pyfunction]
+        #[pyo3(signature = (list, jobs = 1, inplace = false, progress = None, progress_interval = 10_000, start = None, stop = None, step = None))]
+        #[allow(clippy::too_many_arguments)]
+        fn ascii_lower_string(
+            py: Python,
+            list: &Bound<PyList>,
+            jobs: usize,
+            inplace: bool,
+            progress: Option<Bound<PyAny>>,
+            progress_interval: usize,
+            start: Option<isize>,
+            stop: Option<isize>,
+            step: Option<isize>,
+        ) -> PyResult<PyObject> {
+            let progress = core::Progress::optional(progress, progress_interval)?;
+            let slice = core::SliceArgs { start, stop, step };
+
+            let make_func =
+                move || move |s: &str| unsafe { text::ascii_lower_in_string(s).to_py_object() };
+
+            let list =
+                core::map_pylist_slice(py, list, slice, jobs, inplace, false, progress, make_func)?;
+            Ok(list)
+        }
+
+        #[pyfunction]
+        #[pyo3(signature = (list, jobs = 1, inplace = false, progress = None, progress_interval = 10_000, start = None, stop = None, step = None))]
+        #[allow(clippy::too_many_arguments)]
+        fn swapcase_string(
+            py: Python,
+            list: &Bound<PyList>,
+            jobs: usize,
+            inplace: bool,
+            progress: Option<Bound<PyAny>>,
+            progress_interval: usize,
+            start: Option<isize>,
+            stop: Option<isize>,
+            step: Option<isize>,
+        ) -> PyResult<PyObject> {
+            let progress = core::Progress::optional(progress, progress_interval)?;
+            let slice = core::SliceArgs { start, stop, step };
+
+            let make_func =
+                move || move |s: &str| unsafe { text::swapcase_in_string(s).to_py_object() };
+
+            let list =
+                core::map_pylist_slice(py, list, slice, jobs, inplace, false, progress, make_func)?;
+            Ok(list)
+        }
+
+        #[pyfunction]
+        #[pyo3(signature = (list, unicode = false, jobs = 1, inplace = false, progress = None, progress_interval = 10_000, start = None, stop = None, step = None))]
+        #[allow(clippy::too_many_arguments)]
+        fn isdigit_string(
+            py: Python,
+            list: &Bound<PyList>,
+            unicode: bool,
+            jobs: usize,
+            inplace: bool,
+            progress: Option<Bound<PyAny>>,
+            progress_interval: usize,
+            start: Option<isize>,
+            stop: Option<isize>,
+            step: Option<isize>,
+        ) -> PyResult<PyObject> {
+            let progress = core::Progress::optional(progress, progress_interval)?;
+            let slice = core::SliceArgs { start, stop, step };
+
+            let class = text::CharClass::Digit;
+            let pattern = unicode.then(|| class.unicode_pattern());
+            let make_func = move || unsafe {
+                let pattern = pattern.clone();
+                move |s: &str| text::is_class_in_string(s, class, pattern.as_ref()).to_py_object()
+            };
+
+            let list =
+                core::map_pylist_slice(py, list, slice, jobs, inplace, false, progress, make_func)?;
+            Ok(list)
+        }
+
+        #[pyfunction]
+        #[pyo3(signature = (list, unicode = false, jobs = 1, inplace = false, progress = None, progress_interval = 10_000, start = None, stop = None, step = None))]
+        #[allow(clippy::too_many_arguments)]
+        fn isalpha_string(
+            py: Python,
+            list: &Bound<PyList>,
+            unicode: bool,
+            jobs: usize,
+            inplace: bool,
+            progress: Option<Bound<PyAny>>,
+            progress_interval: usize,
+            start: Option<isize>,
+            stop: Option<isize>,
+            step: Option<isize>,
+        ) -> PyResult<PyObject> {
+            let progress = core::Progress::optional(progress, progress_interval)?;
+            let slice = core::SliceArgs { start, stop, step };
+
+            let class = text::CharClass::Alpha;
+            let pattern = unicode.then(|| class.unicode_pattern());
+            let make_func = move || unsafe {
+                let pattern = pattern.clone();
+                move |s: &str| text::is_class_in_string(s, class, pattern.as_ref()).to_py_object()
+            };
+
+            let list =
+                core::map_pylist_slice(py, list, slice, jobs, inplace, false, progress, make_func)?;
+            Ok(list)
+        }
+
+        #[pyfunction]
+        #[pyo3(signature = (list, unicode = false, jobs = 1, inplace = false, progress = None, progress_interval = 10_000, start = None, stop = None, step = None))]
+        #[allow(clippy::too_many_arguments)]
+        fn isalnum_string(
+            py: Python,
+            list: &Bound<PyList>,
+            unicode: bool,
+            jobs: usize,
+            inplace: bool,
+            progress: Option<Bound<PyAny>>,
+            progress_interval: usize,
+            start: Option<isize>,
+            stop: Option<isize>,
+            step: Option<isize>,
+        ) -> PyResult<PyObject> {
+            let progress = core::Progress::optional(progress, progress_interval)?;
+            let slice = core::SliceArgs { start, stop, step };
+
+            let class = text::CharClass::Alnum;
+            let pattern = unicode.then(|| class.unicode_pattern());
+            let make_func = move || unsafe {
+                let pattern = pattern.clone();
+                move |s: &str| text::is_class_in_string(s, class, pattern.as_ref()).to_py_object()
+            };
+
+            let list =
+                core::map_pylist_slice(py, list, slice, jobs, inplace, false, progress, make_func)?;
+            Ok(list)
+        }
+
+        #[pyfunction]
+        #[pyo3(signature = (list, unicode = false, jobs = 1, inplace = false, progress = None, progress_interval = 10_000, start = None, stop = None, step = None))]
+        #[allow(clippy::too_many_arguments)]
+        fn isspace_string(
+            py: Python,
+            list: &Bound<PyList>,
+            unicode: bool,
+            jobs: usize,
+            inplace: bool,
+            progress: Option<Bound<PyAny>>,
+            progress_interval: usize,
+            start: Option<isize>,
+            stop: Option<isize>,
+            step: Option<isize>,
+        ) -> PyResult<PyObject> {
+            let progress = core::Progress::optional(progress, progress_interval)?;
+            let slice = core::SliceArgs { start, stop, step };
+
+            let class = text::CharClass::Space;
+            let pattern = unicode.then(|| class.unicode_pattern());
+            let make_func = move || unsafe {
+                let pattern = pattern.clone();
+                move |s: &str| text::is_class_in_string(s, class, pattern.as_ref()).to_py_object()
+            };
+
+            let list =
+                core::map_pylist_slice(py, list, slice, jobs, inplace, false, progress, make_func)?;
+            Ok(list)
+        }
+
+        #[pyfunction]
+        #[pyo3(signature = (list, tabsize = 8, jobs = 1, inplace = false, progress = None, progress_interval = 10_000, start = None, stop = None, step = None))]
+        #[allow(clippy::too_many_arguments)]
+        fn expandtabs_string(
+            py: Python,
+            list: &Bound<PyList>,
+            tabsize: isize,
+            jobs: usize,
+            inplace: bool,
+            progress: Option<Bound<PyAny>>,
+            progress_interval: usize,
+            start: Option<isize>,
+            stop: Option<isize>,
+            step: Option<isize>,
+        ) -> PyResult<PyObject> {
+            let progress = core::Progress::optional(progress, progress_interval)?;
+            let slice = core::SliceArgs { start, stop, step };
+
+            let make_func = move || {
+                move |s: &str| unsafe { text::expandtabs_in_string(s, tabsize).to_py_object() }
+            };
+
+            let list =
+                core::map_pylist_slice(py, list, slice, jobs, inplace, false, progress, make_func)?;
+            Ok(list)
+        }
+
+        #[pyfunction]
+        #[pyo3(signature = (list, table, jobs = 1, inplace = false, progress = None, progress_interval = 10_000, start = None, stop = None, step = None))]
+        #[allow(clippy::too_many_arguments)]
+        fn translate(
+            py: Python,
+            list: &Bound<PyList>,
+            table: &Bound<PyAny>,
+            jobs: usize,
+            inplace: bool,
+            progress: Option<Bound<PyAny>>,
+            progress_interval: usize,
+            start: Option<isize>,
+            stop: Option<isize>,
+            step: Option<isize>,
+        ) -> PyResult<PyObject> {
+            let progress = core::Progress::optional(progress, progress_interval)?;
+            let slice = core::SliceArgs { start, stop, step };
+
+            let table = Arc::new(crate::translate::TranslateTable::from_py(table)?);
+
+            let make_func = move || {
+                let table = table.clone();
+                move |s: &str| unsafe { table.apply(s).to_py_object() }
+            };
+
+            let list =
+                core::map_pylist_slice(py, list, slice, jobs, inplace, false, progress, make_func)?;
+            Ok(list)
+        }
+
+        #[pyfunction]
+        #[pyo3(signature = (list, mapping, jobs = 1))]
+        fn fill_template(
+            py: Python,
+            list: &Bound<PyList>,
+            mapping: &Bound<PyDict>,
+            jobs: usize,
+        ) -> PyResult<PyObject> {
+            // Values are formatted once, as str() would, before the workers start
+            let mut values = HashMap::with_capacity(mapping.len());
+            for (key, value) in mapping.iter() {
+                let key = key.downcast::<PyString>().map_err(|_| {
+                    PyTypeError::new_err(format!(
+                        "mapping keys must be str, not {}",
+                        key.get_type()
+                            .name()
+                            .map_or_else(|_| "?".to_string(), |n| n.to_string())
+                    ))
+                })?;
+                values.insert(key.to_string(), value.str()?.to_string());
+            }
+            let values = Arc::new(values);
+
+            let make_func = move || {
+                let values = values.clone();
+                move |s: &str| {
+                    text::fill_template(s, &values).map(|filled| match filled {
+                        Cow::Borrowed(_) => None,
+                        Cow::Owned(filled) => Some(filled),
+                    })
+                }
+            };
+            // Rows are checked in order, so the first bad row is the one raised
+            let finish = |_: &str, filled| match filled {
+                Ok(filled) => Ok(filled),
+                Err(text::TemplateError::MissingKey(key)) => Err(PyKeyError::new_err(key)),
+                Err(text::TemplateError::Malformed(message)) => Err(PyValueError::new_err(message)),
+            };
+
+            core::map_pylist_then(py, list, jobs, make_func, finish)
+        }
+
+        #[pyfunction]
+        #[pyo3(signature = (list, jobs = 1, inplace = false, progress = None, progress_interval = 10_000, start = None, stop = None, step = None))]
+        #[allow(clippy::too_many_arguments)]
+        fn char_len(
+            py: Python,
+            list: &Bound<PyList>,
+            jobs: usize,
+            inplace: bool,
+            progress: Option<Bound<PyAny>>,
+            progress_interval: usize,
+            start: Option<isize>,
+            stop: Option<isize>,
+            step: Option<isize>,
+        ) -> PyResult<PyObject> {
+            let progress = core::Progress::optional(progress, progress_interval)?;
+            let slice = core::SliceArgs { start, stop, step };
+
+            // ints are created by the main thread, see `DeferredValue`
+            let make_func =
+                move || move |s: &str| converter::DeferredValue::from(text::char_len(s));
+
+            let list =
+                core::map_pylist_slice(py, list, slice, jobs, inplace, false, progress, make_func)?;
+            Ok(list)
+        }
+
+        #[pyfunction]
+        #[pyo3(signature = (list, chars = None, left = true, right = true, jobs = 1, inplace = false, progress = None, progress_interval = 10_000, start = None, stop = None, step = None))]
+        #[allow(clippy::too_many_arguments)]
+        fn strip_string(
+            py: Python,
+            list: &Bound<PyList>,
+            chars: Option<String>,
+            left: bool,
+            right: bool,
+            jobs: usize,
+            inplace: bool,
+            progress: Option<Bound<PyAny>>,
+            progress_interval: usize,
+            start: Option<isize>,
+            stop: Option<isize>,
+            step: Option<isize>,
+        ) -> PyResult<PyObject> {
+            let progress = core::Progress::optional(progress, progress_interval)?;
+            let slice = core::SliceArgs { start, stop, step };
+
+            let make_func = move || unsafe {
+                let chars = chars.clone();
+                move |s: &str| {
+                    text::strip_in_string(s, chars.as_deref(), left, right).to_py_object()
+                }
+            };
+
+            core::map_pylist_slice(py, list, slice, jobs, inplace, false, progress, make_func)
+        }
+
+        #[pyfunction]
+        #[pyo3(signature = (list, prefix, start = None, end = None, jobs = 1, inplace = false, progress = None, progress_interval = 10_000))]
+        #[allow(clippy::too_many_arguments)]
+        fn startswith_string(
+            py: Python,
+            list: &Bound<PyList>,
+            prefix: &Bound<PyAny>,
+            start: Option<isize>,
+            end: Option<isize>,
+            jobs: usize,
+            inplace: bool,
+            progress: Option<Bound<PyAny>>,
+            progress_interval: usize,
+        ) -> PyResult<PyObject> {
+            let progress = core::Progress::optional(progress, progress_interval)?;
+            let affixes = Arc::new(affixes_arg(prefix, "startswith")?);
+
+            let make_func = move || unsafe {
+                let affixes = affixes.clone();
+                move |s: &str| text::has_affix(s, &affixes, start, end, false).to_py_object()
+            };
+
+            core::map_pylist(py, list, jobs, inplace, progress, make_func)
+        }
+
+        #[pyfunction]
+        #[pyo3(signature = (list, suffix, start = None, end = None, jobs = 1, inplace = false, progress = None, progress_interval = 10_000))]
+        #[allow(clippy::too_many_arguments)]
+        fn endswith_string(
+            py: Python,
+            list: &Bound<PyList>,
+            suffix: &Bound<PyAny>,
+            start: Option<isize>,
+            end: Option<isize>,
+            jobs: usize,
+            inplace: bool,
+            progress: Option<Bound<PyAny>>,
+            progress_interval: usize,
+        ) -> PyResult<PyObject> {
+            let progress = core::Progress::optional(progress, progress_interval)?;
+            let affixes = Arc::new(affixes_arg(suffix, "endswith")?);
+
+            let make_func = move || unsafe {
+                let affixes = affixes.clone();
+                move |s: &str| text::has_affix(s, &affixes, start, end, true).to_py_object()
+            };
+
+            core::map_pylist(py, list, jobs, inplace, progress, make_func)
+        }
+
+        /// The `prefix` or `suffix` argument of `str.startswith` and
+        /// `str.endswith`: a str or a tuple of them, with CPython's errors.
+        fn affixes_arg(value: &Bound<PyAny>, method: &str) -> PyResult<Vec<String>> {
+            let type_name = |value: &Bound<PyAny>| {
+                value
+                    .get_type()
+                    .name()
+                    .map_or_else(|_| "?".to_string(), |n| n.to_string())
+            };
+            if let Ok(affix) = value.downcast::<PyString>() {
+                return Ok(vec![affix.to_string()]);
+            }
+            let Ok(affixes) = value.downcast::<PyTuple>() else {
+                return Err(PyTypeError::new_err(format!(
+                    "{method} first arg must be str or a tuple of str, not {}",
+                    type_name(value)
+                )));
+            };
+            affixes
+                .iter()
+                .map(|affix| match affix.downcast::<PyString>() {
+                    Ok(affix) => Ok(affix.to_string()),
+                    Err(_) => Err(PyTypeError::new_err(format!(
+                        "tuple for {method} must only contain str, not {}",
+                        type_name(&affix)
+                    ))),
+                })
+                .collect()
+        }
+
+        #[pyfunction]
+        #[pyo3(signature = (list, ch, jobs = 1, inplace = false, progress = None, progress_interval = 10_000, start = None, stop = None, step = None))]
+        #[allow(clippy::too_many_arguments)]
+        fn count_char_byte(
+            py: Python,
+            list: &Bound<PyList>,
+            ch: &str,
+            jobs: usize,
+            inplace: bool,
+            progress: Option<Bound<PyAny>>,
+            progress_interval: usize,
+            start: Option<isize>,
+            stop: Option<isize>,
+            step: Option<isize>,
+        ) -> PyResult<PyObject> {
+            let &[needle] = ch.as_bytes() else {
+                return Err(PyValueError::new_err(format!(
+                    "ch must be a single ASCII character, got {ch:?}"
+                )));
+            };
+            let progress = core::Progress::optional(progress, progress_interval)?;
+            let slice = core::SliceArgs { start, stop, step };
+
+            // ints are created by the main thread, see `DeferredValue`
+            let make_func = move || {
+                move |s: &str| {
+                    converter::DeferredValue::from(simd::count_byte(s.as_bytes(), needle))
+                }
+            };
+
+            let list =
+                core::map_pylist_slice(py, list, slice, jobs, inplace, false, progress, make_func)?;
+            Ok(list)
+        }
+
+        #[pyfunction]
+        #[pyo3(signature = (list, needle, overlapping = false, chars = false, jobs = 1, inplace = false, progress = None, progress_interval = 10_000, start = None, stop = None, step = None))]
+        #[allow(clippy::too_many_arguments)]
+        fn match_indices_string(
+            py: Python,
+            list: &Bound<PyList>,
+            needle: &str,
+            overlapping: bool,
+            chars: bool,
+            jobs: usize,
+            inplace: bool,
+            progress: Option<Bound<PyAny>>,
+            progress_interval: usize,
+            start: Option<isize>,
+            stop: Option<isize>,
+            step: Option<isize>,
+        ) -> PyResult<PyObject> {
+            let progress = core::Progress::optional(progress, progress_interval)?;
+            let slice = core::SliceArgs { start, stop, step };
+            let needle = needle.to_string();
+
+            // ints are created by the main thread, see `DeferredValue`
+            let make_func = move || {
+                let needle = needle.clone();
+                move |s: &str| {
+                    let mut offsets = text::match_indices_in_string(s, &needle, overlapping);
+                    if chars {
+                        text::byte_to_char_offsets(s, &mut offsets);
+                    }
+                    converter::DeferredValue::from(offsets)
+                }
+            };
+
+            let list =
+                core::map_pylist_slice(py, list, slice, jobs, inplace, false, progress, make_func)?;
+            Ok(list)
+        }
+
+        #[pyfunction]
+        #[pyo3(signature = (list, jobs = 1, inplace = false, progress = None, progress_interval = 10_000, start = None, stop = None, step = None))]
+        #[allow(clippy::too_many_arguments)]
+        fn encode_utf8(
+            py: Python,
+            list: &Bound<PyList>,
+            jobs: usize,
+            inplace: bool,
+            progress: Option<Bound<PyAny>>,
+            progress_interval: usize,
+            start: Option<isize>,
+            stop: Option<isize>,
+            step: Option<isize>,
+        ) -> PyResult<PyObject> {
+            let progress = core::Progress::optional(progress, progress_interval)?;
+            let slice = core::SliceArgs { start, stop, step };
+
+            // bytes are created by the main thread, see `DeferredValue`
+            let make_func =
+                move || move |s: &str| converter::DeferredValue::from(s.as_bytes().to_vec());
+
+            let list =
+                core::map_pylist_slice(py, list, slice, jobs, inplace, false, progress, make_func)?;
+            Ok(list)
+        }
+
+        #[pyfunction]
+        #[pyo3(signature = (list, jobs = 1))]
+        fn ascii_upper_inplace(list: &Bound<PyList>, jobs: usize) -> PyResult<PyObject> {
+            core::map_pylist_bytes_inplace(list, jobs, text::ascii_upper_bytes)
+        }
+
+        #[pyfunction]
+        #[pyo3(signature = (list, jobs = 1))]
+        fn prehash(list: &Bound<PyList>, jobs: usize) -> PyResult<PyObject> {
+            core::prehash_pylist(list, jobs)
+        }
+
+        #[pyfunction]
+        #[pyo3(signature = (list, n, jobs = 1, inplace = false))]
+        fn caesar_shift(
+            py: Python,
+            list: &Bound<PyList>,
+            n: i64,
+            jobs: usize,
+            inplace: bool,
+        ) -> PyResult<PyObject> {
+            let n = n.rem_euclid(26) as u8;
+            if inplace {
+                return core::map_pylist_bytes_inplace(list, jobs, move |bytes: &mut [u8]| {
+                    text::caesar_shift_bytes(bytes, n)
+                });
+            }
+
+            let make_func =
+                move || move |s: &str| unsafe { text::caesar_shift_in_string(s, n).to_py_object() };
+            core::map_pylist(py, list, jobs, false, None, make_func)
+        }
+
+        #[pyfunction]
+        #[pyo3(signature = (list, jobs = 1, inplace = false))]
+        fn rot13(
+            py: Python,
+            list: &Bound<PyList>,
+            jobs: usize,
+            inplace: bool,
+        ) -> PyResult<PyObject> {
+            caesar_shift(py, list, 13, jobs, inplace)
+        }
+
+        #[pyfunction]
+        #[pyo3(signature = (iterable, ops, jobs = 1, batch_size = 10_000, inplace = false))]
+        fn map_iter(
+            iterable: &Bound<PyAny>,
+            ops: &Bound<PyList>,
+            jobs: usize,
+            batch_size: usize,
+            inplace: bool,
+        ) -> PyResult<stream::MapIter> {
+            if inplace {
+                return Err(PyValueError::new_err(
+                    "map_iter does not support inplace: results are yielded lazily",
+                ));
+            }
+            let pipeline = crate::pipeline::Pipeline::compile(ops)?;
+            stream::MapIter::new(iterable, pipeline, jobs, batch_size)
+        }
+
+        #[pyfunction]
+        #[pyo3(signature = (path, ops, jobs = 1, encoding = "utf-8", errors = "strict", output_path = None))]
+        fn process_file(
+            py: Python,
+            path: PathBuf,
+            ops: &Bound<PyList>,
+            jobs: usize,
+            encoding: &str,
+            errors: &str,
+            output_path: Option<PathBuf>,
+        ) -> PyResult<PyObject> {
+            let errors = file::DecodeErrors::parse(encoding, errors)?;
+            let pipeline = crate::pipeline::Pipeline::compile(ops)?;
+            match output_path {
+                Some(output_path) => {
+                    let written = file::process_file_to_path(
+                        py,
+                        &path,
+                        &output_path,
+                        &pipeline,
+                        jobs,
+                        errors,
+                    )?;
+                    Ok(written.into_pyobject(py)?.into_any().unbind())
+                }
+                None => file::process_file(py, &path, &pipeline, jobs, errors),
+            }
+        }
+
+        #[pyfunction]
+        #[pyo3(signature = (initial_kb, reset_mb, free_mb, batch_size = 100))]
+        fn set_arena_config(
+            initial_kb: usize,
+            reset_mb: usize,
+            free_mb: usize,
+            batch_size: usize,
+        ) -> PyResult<()> {
+            core::set_arena_config(
+                initial_kb.saturating_mul(1024),
+                reset_mb.saturating_mul(1024 * 1024),
+                free_mb.saturating_mul(1024 * 1024),
+                batch_size,
+            )
+        }
+
+        #[pyfunction]
+        fn get_arena_config() -> (usize, usize, usize, usize) {
+            let (initial, reset, free, batch_size) = core::get_arena_config();
+            (
+                initial / 1024,
+                reset / 1024 / 1024,
+                free / 1024 / 1024,
+                batch_size,
+            )
+        }
+
+        #[pyfunction]
+        fn set_parallel_threshold(chars: usize) {
+            core::set_parallel_threshold(chars)
+        }
+
+        #[pyfunction]
+        fn get_parallel_threshold() -> usize {
+            core::get_parallel_threshold()
+        }
+
+        #[pyfunction]
+        #[pyo3(signature = (bytes = None, ucs1 = None, ucs2 = None, ucs4 = None))]
+        fn set_simd_thresholds(
+            bytes: Option<usize>,
+            ucs1: Option<usize>,
+            ucs2: Option<usize>,
+            ucs4: Option<usize>,
+        ) {
+            let current = simd::simd_thresholds();
+            simd::set_simd_thresholds(simd::SimdThresholds {
+                bytes: bytes.unwrap_or(current.bytes),
+                ucs1: ucs1.unwrap_or(current.ucs1),
+                ucs2: ucs2.unwrap_or(current.ucs2),
+                ucs4: ucs4.unwrap_or(current.ucs4),
+            })
+        }
+
+        /// The thresholds as keyword arguments of `set_simd_thresholds`.
+        fn thresholds_dict(thresholds: simd::SimdThresholds) -> HashMap<&'static str, usize> {
+            HashMap::from([
+                ("bytes", thresholds.bytes),
+                ("ucs1", thresholds.ucs1),
+                ("ucs2", thresholds.ucs2),
+                ("ucs4", thresholds.ucs4),
+            ])
+        }
+
+        #[pyfunction]
+        fn get_simd_thresholds() -> HashMap<&'static str, usize> {
+            thresholds_dict(simd::simd_thresholds())
+        }
+
+        #[pyfunction]
+        fn autotune_simd_thresholds(py: Python) -> HashMap<&'static str, usize> {
+            thresholds_dict(py.allow_threads(simd::autotune_simd_thresholds))
+        }
+
+        #[pyfunction]
+        fn set_utf8_cache(enabled: bool) {
+            object::set_utf8_cache(enabled)
+        }
+
+        #[pyfunction]
+        fn get_utf8_cache() -> bool {
+            object::get_utf8_cache()
+        }
+
+        /// Materialize any iterable into a `yurki.List` of the same objects.
+        #[pyfunction]
+        fn list_from(iterable: &Bound<PyAny>) -> PyResult<PyObject> {
+            unsafe {
+                let list = object::list_from_iterable(iterable.as_ptr());
+                Ok(Bound::from_owned_ptr_or_err(iterable.py(), list)?.unbind())
+            }
+        }
+
+        /// Split a list into result lists of `size` consecutive items, the last
+        /// one shorter, holding the same objects.
+        #[pyfunction]
+        fn chunk_list(list: &Bound<PyList>, size: isize) -> PyResult<PyObject> {
+            if size <= 0 {
+                return Err(PyValueError::new_err(format!(
+                    "size must be positive, got {size}"
+                )));
+            }
+            unsafe {
+                let chunks =
+                    object::list_chunks(list.as_ptr(), size as usize, core::create_result_list);
+                Ok(Bound::from_owned_ptr_or_err(list.py(), chunks)?.unbind())
+            }
+        }
+
+        /// Parallel shallow copy of a list of strings through the map engine,
+        /// rebuilding the list with new references to the same `str` objects.
+        #[pyfunction]
+        #[pyo3(signature = (list, jobs = 1))]
+        fn copy_list(py: Python, list: &Bound<PyList>, jobs: usize) -> PyResult<PyObject> {
+            let make_func = || |s: &str| unsafe { s.to_py_object() };
+            core::map_pylist(py, list, jobs, false, None, make_func)
+        }
+
+        /// A new result list of the same items in reverse order.
+        #[pyfunction]
+        fn reversed_list(list: &Bound<PyList>) -> PyResult<PyObject> {
+            unsafe {
+                let reversed = object::list_reversed(list.as_ptr(), core::create_result_list);
+                Ok(Bound::from_owned_ptr_or_err(list.py(), reversed)?.unbind())
+            }
+        }
+
+        #[pyfunction]
+        #[pyo3(signature = (list, reverse = false, jobs = 1))]
+        fn sort_strings(list: &Bound<PyList>, reverse: bool, jobs: usize) -> PyResult<PyObject> {
+            core::sort_pylist(list, reverse, jobs)
+        }
+
+        #[pyfunction]
+        fn simd_implementation() -> &'static str {
+            simd::simd_implementation()
+        }
+
+        #[pyfunction]
+        fn fast_string_enabled() -> bool {
+            core::fast_strings_enabled()
+        }
+
+        #[pyfunction]
+        #[pyo3(signature = (strings=true, lists=true))]
+        fn set_fast_types(strings: bool, lists: bool) {
+            core::set_fast_types(strings, lists)
+        }
+
+        #[pyfunction]
+        fn set_fast_string(enabled: bool) {
+            core::set_fast_strings(enabled)
+        }
+
+        #[pyfunction]
+        fn get_fast_types() -> (bool, bool) {
+            core::get_fast_types()
+        }
+
+        /// Hack: workaround for https://github.com/PyO3/pyo3/issues/759
+        #[pymodule_init]
+        fn init(m: &Bound<'_, PyModule>) -> PyResult<()> {
+            let _ = Python::with_gil(|py| {
+                Python::import(py, "sys")?
+                    .getattr("modules")?
+                    .set_item("yurki.internal", m)
+            });
+
+            if std::env::var_os("YURKI_AUTOTUNE").is_some_and(|value| value == "1") {
+                simd::autotune_simd_thresholds();
+            }
 
             unsafe {
                 object::init_string_type(m.as_ptr())?;
+                object::init_small_strings();
                 object::init_list_type(m.as_ptr())?;
                 Ok(())
             }
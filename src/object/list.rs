@@ -1,9 +1,10 @@
 //! yurki::object::list  —  immutable list with custom allocator
 #[allow(static_mut_refs)]
 use pyo3::{ffi, prelude::*};
-use std::{alloc, mem, os::raw::c_int, ptr};
+use std::{alloc, ffi::CStr, mem, os::raw::c_int, ptr};
 
 use crate::debug_println;
+use crate::object::init_object_header;
 
 #[inline(always)]
 unsafe fn internal_alloc_bytes(size: usize) -> *mut u8 {
@@ -19,6 +20,13 @@ unsafe fn internal_free_bytes(ptr: *mut std::ffi::c_void, size: usize) {
     alloc::dealloc(ptr as *mut u8, layout)
 }
 
+/// Room for the collector's `PyGC_Head`, which precedes every object of a
+/// GC type (none in free-threaded builds, where it lives in the header).
+#[cfg(not(Py_GIL_DISABLED))]
+const GC_HEADER_SIZE: usize = 2 * mem::size_of::<usize>();
+#[cfg(Py_GIL_DISABLED)]
+const GC_HEADER_SIZE: usize = 0;
+
 //  List C-level layout
 /// Exact copy of `PyListObject`
 #[repr(C)]
@@ -33,7 +41,10 @@ struct PyList {
 // ───────────────────────────────────────────
 static mut LIST_TYPE: *mut ffi::PyTypeObject = ptr::null_mut();
 
-/// Custom tp_alloc — one shot for header + elements.
+/// Custom tp_alloc — one shot for GC header + header + elements.
+///
+/// The object starts untracked, so workers may build lists without the GIL;
+/// `list_track` registers it with the collector before Python can see it.
 unsafe extern "C" fn list_alloc(
     subtype: *mut ffi::PyTypeObject,
     item_count: ffi::Py_ssize_t,
@@ -48,23 +59,22 @@ unsafe extern "C" fn list_alloc(
     };
     let total_size = header + elements * mem::size_of::<*mut ffi::PyObject>();
 
-    let raw = internal_alloc_bytes(total_size) as *mut PyList;
-    if raw.is_null() {
+    let block = internal_alloc_bytes(GC_HEADER_SIZE + total_size);
+    if block.is_null() {
         ffi::PyErr_NoMemory();
         return ptr::null_mut();
     }
-    ptr::write_bytes(raw as *mut u8, 0, total_size);
+    // A zeroed GC header reads as untracked
+    ptr::write_bytes(block, 0, GC_HEADER_SIZE + total_size);
+    let raw = block.add(GC_HEADER_SIZE) as *mut PyList;
 
-    // Initialise ob_refcnt / ob_type / ob_size
+    // Initialise the object header and ob_size
     let var = &mut (*raw).ob_base;
-    std::ptr::write(
-        &mut (*var).ob_base.ob_refcnt as *mut _ as *mut ffi::Py_ssize_t,
-        1,
-    );
-    var.ob_base.ob_type = subtype;
+    init_object_header(&mut var.ob_base, subtype);
     var.ob_size = item_count;
 
-    // Data area immediately after the struct
+    // Data area immediately after the struct. Free-threaded CPython reads the
+    // capacity from the word before `ob_item`, which is `allocated` here
     if elements > 0 {
         (*raw).ob_item = (raw as *mut u8).add(header) as *mut *mut ffi::PyObject;
         (*raw).allocated = item_count;
@@ -83,8 +93,15 @@ unsafe extern "C" fn list_alloc(
 /// tp_dealloc – decref each element, then call tp_free.
 unsafe extern "C" fn list_dealloc(obj: *mut ffi::PyObject) {
     debug_println!("list_dealloc ▶ obj={:p}", obj);
+    // No-op for lists that never reached Python
+    ffi::PyObject_GC_UnTrack(obj as _);
     let fl = obj as *mut PyList;
-    let n = (*fl).ob_base.ob_size;
+    // Empty lists have no item storage at all, whatever ob_size says
+    let n = if (*fl).ob_item.is_null() {
+        0
+    } else {
+        (*fl).ob_base.ob_size
+    };
     for i in 0..n {
         let it_ptr = *(*fl).ob_item.add(i as usize);
         if !it_ptr.is_null() {
@@ -98,31 +115,74 @@ unsafe extern "C" fn list_dealloc(obj: *mut ffi::PyObject) {
 
 /// tp_free – actual memory release through mimalloc.
 unsafe extern "C" fn list_free(ptr_: *mut std::ffi::c_void) {
-    // Reconstruct size to free
+    // Reconstruct size to free from the allocated capacity, ob_size may
+    // have been zeroed by `list_release_items`
     let fl = ptr_ as *mut PyList;
     let header = (*(*fl).ob_base.ob_base.ob_type).tp_basicsize as usize;
-    let items = (*fl).ob_base.ob_size as usize;
+    let items = (*fl).allocated as usize;
     let total = header + items * mem::size_of::<*mut ffi::PyObject>();
 
     debug_println!(
         "list_free ▶ ptr={:p} header={header} items={items} total={total}",
         ptr_
     );
-    internal_free_bytes(ptr_, total);
+    internal_free_bytes(
+        (ptr_ as *mut u8).sub(GC_HEADER_SIZE) as _,
+        GC_HEADER_SIZE + total,
+    );
     debug_println!("list_free ◀");
 }
 
+/// tp_traverse – visit every filled slot.
+unsafe extern "C" fn list_traverse(
+    obj: *mut ffi::PyObject,
+    visit: ffi::visitproc,
+    arg: *mut std::ffi::c_void,
+) -> c_int {
+    for &item in list_items(obj) {
+        if !item.is_null() {
+            let result = visit(item, arg);
+            if result != 0 {
+                return result;
+            }
+        }
+    }
+    0
+}
+
+/// tp_clear – break reference cycles by releasing every item. The storage
+/// is part of the object and is kept; the list just becomes empty.
+unsafe extern "C" fn list_clear(obj: *mut ffi::PyObject) -> c_int {
+    list_release_items(obj);
+    0
+}
+
+/// Raise the TypeError for deleting items, which would resize the list
+unsafe fn immutable_delete() -> c_int {
+    ffi::PyErr_SetString(
+        ffi::PyExc_TypeError,
+        c"'yurki.List' object is immutable: cannot resize (item deletion not allowed)".as_ptr(),
+    );
+    -1
+}
+
 /// sq_ass_item – Allow item assignment but no resizing
 unsafe extern "C" fn list_ass_item(
     obj: *mut ffi::PyObject,
-    index: ffi::Py_ssize_t,
+    mut index: ffi::Py_ssize_t,
     value: *mut ffi::PyObject,
 ) -> c_int {
+    if value.is_null() {
+        return immutable_delete();
+    }
     let fl = obj as *mut PyList;
     let size = (*fl).ob_base.ob_size;
+    if index < 0 {
+        index += size;
+    }
 
     // Check bounds
-    if index < 0 || index >= size {
+    if index < 0 || index >= size || (*fl).ob_item.is_null() {
         ffi::PyErr_SetString(
             ffi::PyExc_IndexError,
             b"list index out of range\0".as_ptr() as *const _,
@@ -130,18 +190,184 @@ unsafe extern "C" fn list_ass_item(
         return -1;
     }
 
-    // Replace item (DECREF old, assign new)
+    // Replace item (assign new, then DECREF old: its finalizer may read the list)
     let old_item = *(*fl).ob_item.add(index as usize);
+    ffi::Py_INCREF(value);
+    *(*fl).ob_item.add(index as usize) = value;
     if !old_item.is_null() {
         ffi::Py_DECREF(old_item);
     }
 
-    if !value.is_null() {
-        ffi::Py_INCREF(value);
+    0
+}
+
+/// mp_ass_subscript – `lst[i] = x` only. Replaces the inherited builtin one,
+/// which resizes the item storage allocated with the object on slice
+/// assignment and deletion
+unsafe extern "C" fn list_ass_subscript(
+    obj: *mut ffi::PyObject,
+    key: *mut ffi::PyObject,
+    value: *mut ffi::PyObject,
+) -> c_int {
+    if ffi::PyIndex_Check(key) != 0 {
+        let index = ffi::PyNumber_AsSsize_t(key, ffi::PyExc_IndexError);
+        if index == -1 && !ffi::PyErr_Occurred().is_null() {
+            return -1;
+        }
+        return list_ass_item(obj, index, value);
     }
-    *(*fl).ob_item.add(index as usize) = value;
 
-    0
+    if ffi::PySlice_Check(key) != 0 {
+        let message: &CStr = if value.is_null() {
+            c"'yurki.List' object is immutable: cannot resize (slice deletion not allowed)"
+        } else {
+            c"'yurki.List' object is immutable: cannot resize (slice assignment not allowed)"
+        };
+        ffi::PyErr_SetString(ffi::PyExc_TypeError, message.as_ptr());
+        return -1;
+    }
+
+    ffi::PyErr_Format(
+        ffi::PyExc_TypeError,
+        c"list indices must be integers or slices, not %.200s".as_ptr(),
+        (*ffi::Py_TYPE(key)).tp_name,
+    );
+    -1
+}
+
+/// mp_subscript – `lst[i]` and `lst[a:b:c]`, slices returning a new yurki.List
+unsafe extern "C" fn list_subscript(
+    obj: *mut ffi::PyObject,
+    key: *mut ffi::PyObject,
+) -> *mut ffi::PyObject {
+    let fl = obj as *mut PyList;
+    // Empty lists have no item storage at all, whatever ob_size says
+    let size = if (*fl).ob_item.is_null() {
+        0
+    } else {
+        (*fl).ob_base.ob_size
+    };
+
+    if ffi::PyIndex_Check(key) != 0 {
+        let mut index = ffi::PyNumber_AsSsize_t(key, ffi::PyExc_IndexError);
+        if index == -1 && !ffi::PyErr_Occurred().is_null() {
+            return ptr::null_mut();
+        }
+        if index < 0 {
+            index += size;
+        }
+        if index < 0 || index >= size {
+            ffi::PyErr_SetString(ffi::PyExc_IndexError, c"list index out of range".as_ptr());
+            return ptr::null_mut();
+        }
+        let item = *(*fl).ob_item.add(index as usize);
+        ffi::Py_INCREF(item);
+        return item;
+    }
+
+    if ffi::PySlice_Check(key) != 0 {
+        let (mut start, mut stop, mut step) = (0, 0, 0);
+        if ffi::PySlice_Unpack(key, &mut start, &mut stop, &mut step) < 0 {
+            return ptr::null_mut();
+        }
+        let length = ffi::PySlice_AdjustIndices(size, &mut start, &mut stop, step);
+
+        let result = list_alloc(LIST_TYPE, length);
+        if result.is_null() {
+            return ptr::null_mut();
+        }
+        let items = (*(result as *mut PyList)).ob_item;
+        for i in 0..length {
+            let item = *(*fl).ob_item.offset(start + i * step);
+            ffi::Py_INCREF(item);
+            *items.add(i as usize) = item;
+        }
+        list_track(result);
+        return result;
+    }
+
+    ffi::PyErr_Format(
+        ffi::PyExc_TypeError,
+        c"list indices must be integers or slices, not %.200s".as_ptr(),
+        (*ffi::Py_TYPE(key)).tp_name,
+    );
+    ptr::null_mut()
+}
+
+/// Items of a `list` or `yurki.List`, which share the `PyListObject` layout.
+unsafe fn list_items<'a>(obj: *mut ffi::PyObject) -> &'a [*mut ffi::PyObject] {
+    let fl = obj as *mut PyList;
+    if (*fl).ob_item.is_null() {
+        return &[];
+    }
+    std::slice::from_raw_parts((*fl).ob_item, (*fl).ob_base.ob_size as usize)
+}
+
+/// New yurki.List of `len` items, `fill` writing each one with a new reference.
+unsafe fn list_build(len: usize, fill: impl Fn(&mut [*mut ffi::PyObject])) -> *mut ffi::PyObject {
+    if len > (isize::MAX as usize) / mem::size_of::<*mut ffi::PyObject>() {
+        return ffi::PyErr_NoMemory();
+    }
+    let result = list_alloc(LIST_TYPE, len as ffi::Py_ssize_t);
+    if result.is_null() {
+        return result;
+    }
+    if len > 0 {
+        fill(std::slice::from_raw_parts_mut(
+            (*(result as *mut PyList)).ob_item,
+            len,
+        ));
+    }
+    list_track(result);
+    result
+}
+
+/// Concatenation of two lists into a new yurki.List.
+unsafe fn list_concat_items(a: *mut ffi::PyObject, b: *mut ffi::PyObject) -> *mut ffi::PyObject {
+    let (a, b) = (list_items(a), list_items(b));
+    list_build(a.len() + b.len(), |out| {
+        for (slot, &item) in out.iter_mut().zip(a.iter().chain(b)) {
+            ffi::Py_INCREF(item);
+            *slot = item;
+        }
+    })
+}
+
+/// sq_concat – `lst + other` for any list `other`. With a builtin list on
+/// the left, the builtin concatenation runs and gives a builtin list
+unsafe extern "C" fn list_concat(
+    obj: *mut ffi::PyObject,
+    other: *mut ffi::PyObject,
+) -> *mut ffi::PyObject {
+    if ffi::PyList_Check(other) == 0 {
+        ffi::PyErr_Format(
+            ffi::PyExc_TypeError,
+            c"can only concatenate list (not \"%.200s\") to list".as_ptr(),
+            (*ffi::Py_TYPE(other)).tp_name,
+        );
+        return ptr::null_mut();
+    }
+    list_concat_items(obj, other)
+}
+
+/// sq_repeat – `lst * n` and `n * lst`; `n <= 0` gives an empty yurki.List
+unsafe extern "C" fn list_repeat(
+    obj: *mut ffi::PyObject,
+    count: ffi::Py_ssize_t,
+) -> *mut ffi::PyObject {
+    let items = list_items(obj);
+    let count = count.max(0) as usize;
+    let Some(len) = items.len().checked_mul(count) else {
+        return ffi::PyErr_NoMemory();
+    };
+    list_build(len, |out| {
+        for chunk in out.chunks_exact_mut(items.len()) {
+            for (slot, &item) in chunk.iter_mut().zip(items) {
+                ffi::Py_INCREF(item);
+                *slot = item;
+            }
+        }
+    })
 }
 
 /// Block in-place concatenation that would resize the list
@@ -170,6 +396,20 @@ unsafe extern "C" fn list_inplace_repeat(
     ptr::null_mut()
 }
 
+/// Block `list.__init__`, which clears the list and refills it from an
+/// iterable, reallocating the inline item array
+unsafe extern "C" fn immutable_init(
+    _self: *mut ffi::PyObject,
+    _args: *mut ffi::PyObject,
+    _kwds: *mut ffi::PyObject,
+) -> c_int {
+    ffi::PyErr_SetString(
+        ffi::PyExc_TypeError,
+        c"'yurki.List' object is immutable: cannot resize (__init__ not allowed)".as_ptr(),
+    );
+    -1
+}
+
 /// Block dangerous list methods that would resize the list
 unsafe extern "C" fn immutable_append(
     _self: *mut ffi::PyObject,
@@ -242,8 +482,246 @@ unsafe extern "C" fn immutable_clear(
     ptr::null_mut()
 }
 
+// ───────────────────────────────────────────
+//  Pickle and copy support
+// ───────────────────────────────────────────
+/// `yurki.internal._rebuild_list`, the constructor `__reduce_ex__` records.
+static mut LIST_FACTORY: *mut ffi::PyObject = ptr::null_mut();
+
+/// `_rebuild_list(items)` – new yurki.List of the items of any iterable
+unsafe extern "C" fn list_from_items(
+    _module: *mut ffi::PyObject,
+    items: *mut ffi::PyObject,
+) -> *mut ffi::PyObject {
+    list_from_iterable(items)
+}
+
+/// New yurki.List holding new references to the items of any iterable,
+/// generators included. Null with the error set if iterating fails.
+///
+/// # Safety
+///
+/// The GIL must be held and `items` must be a live object.
+pub unsafe fn list_from_iterable(items: *mut ffi::PyObject) -> *mut ffi::PyObject {
+    let iter = ffi::PyObject_GetIter(items);
+    if iter.is_null() {
+        return ptr::null_mut();
+    }
+    let hint = ffi::PyObject_LengthHint(items, 0);
+    if hint < 0 {
+        ffi::Py_DECREF(iter);
+        return ptr::null_mut();
+    }
+    let mut builder = ListBuilder::new();
+    // Only a hint: the builder grows past it, or fills less of it
+    builder.reserve(hint as usize);
+    loop {
+        let item = ffi::PyIter_Next(iter);
+        if item.is_null() {
+            break;
+        }
+        builder.push(item);
+    }
+    ffi::Py_DECREF(iter);
+    if !ffi::PyErr_Occurred().is_null() {
+        return ptr::null_mut();
+    }
+    let list = builder.finish();
+    if !list.is_null() {
+        list_track(list);
+    }
+    list
+}
+
+/// New list from `alloc` holding new references to `items`, registered with
+/// the collector. Null with the error set if it can't be allocated.
+///
+/// # Safety
+///
+/// The GIL must be held, `items` must be live objects and `alloc` must
+/// return an empty, untracked result list (see `create_list_empty`).
+pub unsafe fn list_from_refs(
+    items: impl ExactSizeIterator<Item = *mut ffi::PyObject>,
+    alloc: unsafe fn(ffi::Py_ssize_t) -> *mut ffi::PyObject,
+) -> *mut ffi::PyObject {
+    let list = alloc(items.len() as ffi::Py_ssize_t);
+    if list.is_null() {
+        return if ffi::PyErr_Occurred().is_null() {
+            ffi::PyErr_NoMemory()
+        } else {
+            list
+        };
+    }
+    for (i, item) in items.enumerate() {
+        ffi::Py_INCREF(item);
+        list_set_item_transfer(list, i as isize, item);
+    }
+    list_track(list);
+    list
+}
+
+/// New list of lists of `size` consecutive items of `list` each, the last
+/// one shorter if they don't divide evenly, all created by `alloc`. The
+/// chunks hold new references to the same objects. Null with the error set
+/// if a list can't be allocated.
+///
+/// # Safety
+///
+/// `list` must be a live `list` or yurki.List and `size` positive. Otherwise
+/// the same as `list_from_refs`.
+pub unsafe fn list_chunks(
+    list: *mut ffi::PyObject,
+    size: usize,
+    alloc: unsafe fn(ffi::Py_ssize_t) -> *mut ffi::PyObject,
+) -> *mut ffi::PyObject {
+    let items = list_items(list);
+    let mut builder = ListBuilder::new();
+    builder.reserve(items.len().div_ceil(size));
+    for chunk in items.chunks(size) {
+        let sub = list_from_refs(chunk.iter().copied(), alloc);
+        if sub.is_null() {
+            return sub;
+        }
+        builder.push(sub);
+    }
+    let result = builder.finish_with(alloc);
+    if result.is_null() {
+        return ffi::PyErr_NoMemory();
+    }
+    list_track(result);
+    result
+}
+
+/// New list from `alloc` of the items of `list` in reverse order, holding
+/// new references to the same objects. Null with the error set if it can't
+/// be allocated.
+///
+/// # Safety
+///
+/// `list` must be a live `list` or yurki.List. Otherwise the same as
+/// `list_from_refs`.
+pub unsafe fn list_reversed(
+    list: *mut ffi::PyObject,
+    alloc: unsafe fn(ffi::Py_ssize_t) -> *mut ffi::PyObject,
+) -> *mut ffi::PyObject {
+    list_from_refs(list_items(list).iter().rev().copied(), alloc)
+}
+
+const LIST_FACTORY_DEF: ffi::PyMethodDef = ffi::PyMethodDef {
+    ml_name: c"_rebuild_list".as_ptr(),
+    ml_meth: ffi::PyMethodDefPointer {
+        PyCFunction: list_from_items,
+    },
+    ml_flags: ffi::METH_O,
+    ml_doc: c"_rebuild_list(items) -- yurki.List of the given items, used by pickle".as_ptr(),
+};
+
+/// `__reduce_ex__(protocol)` – rebuilt through `_rebuild_list`, so the type
+/// survives pickling (the type itself can't be called)
+unsafe extern "C" fn list_reduce_ex(
+    obj: *mut ffi::PyObject,
+    _protocol: *mut ffi::PyObject,
+) -> *mut ffi::PyObject {
+    let items = ffi::PySequence_Tuple(obj);
+    if items.is_null() {
+        return ptr::null_mut();
+    }
+    let args = ffi::PyTuple_Pack(1, items);
+    ffi::Py_DECREF(items);
+    if args.is_null() {
+        return ptr::null_mut();
+    }
+    let result = ffi::PyTuple_Pack(2, LIST_FACTORY, args);
+    ffi::Py_DECREF(args);
+    result
+}
+
+/// `__copy__()` – shallow copy as a new yurki.List
+unsafe extern "C" fn list_copy(
+    obj: *mut ffi::PyObject,
+    _args: *mut ffi::PyObject,
+) -> *mut ffi::PyObject {
+    let items = list_items(obj);
+    list_build(items.len(), |out| {
+        for (slot, &item) in out.iter_mut().zip(items) {
+            ffi::Py_INCREF(item);
+            *slot = item;
+        }
+    })
+}
+
+/// `__deepcopy__(memo)` – new yurki.List of deep copies of the items
+unsafe extern "C" fn list_deepcopy(
+    obj: *mut ffi::PyObject,
+    memo: *mut ffi::PyObject,
+) -> *mut ffi::PyObject {
+    let len = list_items(obj).len();
+    // Filled with None until copied: element copies run Python code, which
+    // may reach the result through `memo`
+    let result = list_build(len, |out| {
+        for slot in out {
+            ffi::Py_INCREF(ffi::Py_None());
+            *slot = ffi::Py_None();
+        }
+    });
+    if result.is_null() {
+        return ptr::null_mut();
+    }
+
+    // Registered first, so cycles through this list resolve to the copy
+    let key = ffi::PyLong_FromVoidPtr(obj as *mut _);
+    if key.is_null() || ffi::PyObject_SetItem(memo, key, result) < 0 {
+        ffi::Py_XDECREF(key);
+        ffi::Py_DECREF(result);
+        return ptr::null_mut();
+    }
+    ffi::Py_DECREF(key);
+
+    let copy_module = ffi::PyImport_ImportModule(c"copy".as_ptr());
+    if copy_module.is_null() {
+        ffi::Py_DECREF(result);
+        return ptr::null_mut();
+    }
+    let deepcopy = ffi::PyObject_GetAttrString(copy_module, c"deepcopy".as_ptr());
+    ffi::Py_DECREF(copy_module);
+    if deepcopy.is_null() {
+        ffi::Py_DECREF(result);
+        return ptr::null_mut();
+    }
+
+    for i in 0..len {
+        // Items may be reassigned meanwhile, so each is read when its turn comes
+        let Some(&item) = list_items(obj).get(i) else {
+            break;
+        };
+        ffi::Py_INCREF(item);
+        let copied = ffi::PyObject_CallFunctionObjArgs(
+            deepcopy,
+            item,
+            memo,
+            ptr::null_mut::<ffi::PyObject>(),
+        );
+        ffi::Py_DECREF(item);
+        if copied.is_null() {
+            ffi::Py_DECREF(deepcopy);
+            ffi::Py_DECREF(result);
+            return ptr::null_mut();
+        }
+        let slot = (*(result as *mut PyList)).ob_item.add(i);
+        let placeholder = *slot;
+        *slot = copied;
+        ffi::Py_DECREF(placeholder);
+    }
+    ffi::Py_DECREF(deepcopy);
+    result
+}
+
 /// Method table that overrides dangerous list methods
-const IMMUTABLE_LIST_METHODS: [ffi::PyMethodDef; 7] = [
+///
+/// Only methods that resize the list are blocked. `sort()` and `reverse()`
+/// reorder the items inside the existing array and are inherited as is; a
+/// sort that fails midway leaves a permutation of the items.
+const IMMUTABLE_LIST_METHODS: [ffi::PyMethodDef; 10] = [
     ffi::PyMethodDef {
         ml_name: b"append\0".as_ptr() as *const _,
         ml_meth: ffi::PyMethodDefPointer {
@@ -293,6 +771,30 @@ const IMMUTABLE_LIST_METHODS: [ffi::PyMethodDef; 7] = [
         ml_flags: ffi::METH_NOARGS,
         ml_doc: b"clear() -- Unsupported: yurki.List is immutable\0".as_ptr() as *const _,
     },
+    ffi::PyMethodDef {
+        ml_name: c"__reduce_ex__".as_ptr(),
+        ml_meth: ffi::PyMethodDefPointer {
+            PyCFunction: list_reduce_ex,
+        },
+        ml_flags: ffi::METH_O,
+        ml_doc: c"__reduce_ex__(protocol) -- Pickle as a yurki.List".as_ptr(),
+    },
+    ffi::PyMethodDef {
+        ml_name: c"__copy__".as_ptr(),
+        ml_meth: ffi::PyMethodDefPointer {
+            PyCFunction: list_copy,
+        },
+        ml_flags: ffi::METH_NOARGS,
+        ml_doc: c"__copy__() -- Shallow copy as a yurki.List".as_ptr(),
+    },
+    ffi::PyMethodDef {
+        ml_name: c"__deepcopy__".as_ptr(),
+        ml_meth: ffi::PyMethodDefPointer {
+            PyCFunction: list_deepcopy,
+        },
+        ml_flags: ffi::METH_O,
+        ml_doc: c"__deepcopy__(memo) -- Deep copy as a yurki.List".as_ptr(),
+    },
     ffi::PyMethodDef {
         ml_name: ptr::null(),
         ml_meth: ffi::PyMethodDefPointer {
@@ -317,6 +819,10 @@ pub unsafe fn init_list_type(m: *mut ffi::PyObject) -> PyResult<()> {
             slot: ffi::Py_tp_new as c_int,
             pfunc: ptr::null_mut(), // block Python-side instantiation
         },
+        ffi::PyType_Slot {
+            slot: ffi::Py_tp_init as c_int,
+            pfunc: immutable_init as *mut _,
+        },
         ffi::PyType_Slot {
             slot: ffi::Py_tp_alloc as c_int,
             pfunc: list_alloc as *mut _,
@@ -329,10 +835,34 @@ pub unsafe fn init_list_type(m: *mut ffi::PyObject) -> PyResult<()> {
             slot: ffi::Py_tp_free as c_int,
             pfunc: list_free as *mut _,
         },
+        ffi::PyType_Slot {
+            slot: ffi::Py_tp_traverse as c_int,
+            pfunc: list_traverse as *mut _,
+        },
+        ffi::PyType_Slot {
+            slot: ffi::Py_tp_clear as c_int,
+            pfunc: list_clear as *mut _,
+        },
         ffi::PyType_Slot {
             slot: ffi::Py_sq_ass_item as c_int,
             pfunc: list_ass_item as *mut _,
         },
+        ffi::PyType_Slot {
+            slot: ffi::Py_mp_subscript as c_int,
+            pfunc: list_subscript as *mut _,
+        },
+        ffi::PyType_Slot {
+            slot: ffi::Py_mp_ass_subscript as c_int,
+            pfunc: list_ass_subscript as *mut _,
+        },
+        ffi::PyType_Slot {
+            slot: ffi::Py_sq_concat as c_int,
+            pfunc: list_concat as *mut _,
+        },
+        ffi::PyType_Slot {
+            slot: ffi::Py_sq_repeat as c_int,
+            pfunc: list_repeat as *mut _,
+        },
         ffi::PyType_Slot {
             slot: ffi::Py_sq_inplace_concat as c_int,
             pfunc: list_inplace_concat as *mut _,
@@ -356,8 +886,10 @@ pub unsafe fn init_list_type(m: *mut ffi::PyObject) -> PyResult<()> {
         name: b"yurki.List\0".as_ptr() as *const _,
         basicsize: mem::size_of::<PyList>() as c_int,
         itemsize: mem::size_of::<*mut ffi::PyObject>() as c_int,
-        flags: (ffi::Py_TPFLAGS_DEFAULT | ffi::Py_TPFLAGS_LIST_SUBCLASS | ffi::Py_TPFLAGS_BASETYPE)
-            as u32,
+        flags: (ffi::Py_TPFLAGS_DEFAULT
+            | ffi::Py_TPFLAGS_LIST_SUBCLASS
+            | ffi::Py_TPFLAGS_BASETYPE
+            | ffi::Py_TPFLAGS_HAVE_GC) as u32,
         slots: slots.as_mut_ptr(),
     };
 
@@ -367,6 +899,21 @@ pub unsafe fn init_list_type(m: *mut ffi::PyObject) -> PyResult<()> {
     }
     LIST_TYPE = typ;
     ffi::PyModule_AddObject(m, b"List\0".as_ptr() as *const _ as *mut _, typ as _);
+
+    // Pickle finds the factory by its `__module__`, which must name the
+    // importable path of this module
+    let module_name = ffi::PyUnicode_FromString(c"yurki.internal".as_ptr());
+    let factory = ffi::PyCFunction_NewEx(
+        &LIST_FACTORY_DEF as *const _ as *mut _,
+        ptr::null_mut(),
+        module_name,
+    );
+    ffi::Py_XDECREF(module_name);
+    if factory.is_null() {
+        return Err(PyErr::fetch(Python::assume_gil_acquired()));
+    }
+    LIST_FACTORY = factory;
+    ffi::PyModule_AddObject(m, c"_rebuild_list".as_ptr(), factory);
     Ok(())
 }
 
@@ -395,6 +942,7 @@ pub unsafe fn create_list(items: &[*mut ffi::PyObject]) -> *mut ffi::PyObject {
             ffi::Py_INCREF(it);
             *(*fl).ob_item.add(i) = it;
         }
+        list_track(obj);
     } else {
         // GIL not held; just copy raw pointers – caller must keep them alive.
         ptr::copy_nonoverlapping(items.as_ptr(), (*fl).ob_item, items.len());
@@ -421,6 +969,107 @@ pub unsafe fn create_list_empty(size: isize) -> *mut ffi::PyObject {
     obj
 }
 
+/// True if `obj` is a `yurki.List` (or a subclass of it).
+///
+/// # Safety
+///
+/// `obj` must be a live object.
+pub unsafe fn is_list(obj: *mut ffi::PyObject) -> bool {
+    !LIST_TYPE.is_null() && ffi::PyObject_TypeCheck(obj, LIST_TYPE) != 0
+}
+
+// ───────────────────────────────────────────
+//  Growable builder (GIL **not** required)
+// ───────────────────────────────────────────
+/// Builds a result list whose length isn't known up front, as filters and
+/// flat maps produce. A yurki.List keeps its items in the block of its
+/// header and can't grow in place, so items gather in a vector with
+/// amortized growth and `finish` moves them into an exactly sized list.
+///
+/// Pushed items are owned by the builder. Dropping it unfinished releases
+/// them if the GIL is held and leaks them otherwise, as a worker can't.
+#[derive(Default)]
+pub struct ListBuilder {
+    items: Vec<*mut ffi::PyObject>,
+}
+
+// Workers build lists of objects no other thread can reach yet
+unsafe impl Send for ListBuilder {}
+
+impl ListBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Make room for `additional` more items ahead of pushing them. False
+    /// if the memory isn't available now; `push` still grows as needed.
+    pub fn reserve(&mut self, additional: usize) -> bool {
+        self.items.try_reserve(additional).is_ok()
+    }
+
+    /// Append `item`, taking over its reference.
+    pub fn push(&mut self, item: *mut ffi::PyObject) {
+        self.items.push(item);
+    }
+
+    /// Move all of `other`'s items to the end of this builder, in order.
+    pub fn append(&mut self, other: &mut ListBuilder) {
+        self.items.append(&mut other.items);
+    }
+
+    pub fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+
+    /// Move the items into a new yurki.List of exactly their number,
+    /// untracked like `create_list_empty`. Null if it can't be allocated,
+    /// the items then dropped with the builder.
+    ///
+    /// # Safety
+    ///
+    /// The list must be registered with the collector (see `list_track`)
+    /// under the GIL before Python code can reach it.
+    pub unsafe fn finish(self) -> *mut ffi::PyObject {
+        self.finish_with(create_list_empty)
+    }
+
+    /// `finish` with `alloc` creating the empty list, e.g.
+    /// `core::create_result_list` to follow `set_fast_types`.
+    ///
+    /// # Safety
+    ///
+    /// `alloc` must return an empty, untracked result list of the given
+    /// length, or null. Otherwise the same as `finish`.
+    pub unsafe fn finish_with(
+        mut self,
+        alloc: unsafe fn(ffi::Py_ssize_t) -> *mut ffi::PyObject,
+    ) -> *mut ffi::PyObject {
+        let list = alloc(self.items.len() as ffi::Py_ssize_t);
+        if list.is_null() {
+            return list;
+        }
+        for (i, item) in self.items.drain(..).enumerate() {
+            list_set_item_transfer(list, i as isize, item);
+        }
+        list
+    }
+}
+
+impl Drop for ListBuilder {
+    fn drop(&mut self) {
+        if self.items.is_empty() || unsafe { ffi::PyGILState_Check() } == 0 {
+            return;
+        }
+        for &item in &self.items {
+            unsafe { ffi::Py_DECREF(item) };
+        }
+    }
+}
+
 // Set item at index with ownership transfer (no INCREF)
 pub unsafe fn list_set_item_transfer(
     list: *mut ffi::PyObject,
@@ -437,3 +1086,101 @@ pub unsafe fn list_set_item_transfer(
     *(*fl).ob_item.add(index as usize) = item;
     debug_println!("list_set_item_transfer ◀");
 }
+
+/// Set item at index with ownership transfer, releasing the previous occupant.
+/// Used when overwriting a populated list (inplace mode).
+///
+/// # Safety
+///
+/// The GIL must be held, `index` must be in bounds of `list`, and the
+/// caller's reference to `item` moves into the list.
+pub unsafe fn list_set_item_transfer_replacing(
+    list: *mut ffi::PyObject,
+    index: isize,
+    item: *mut ffi::PyObject,
+) {
+    debug_println!(
+        "list_set_item_transfer_replacing ▶ list={:p} index={} item={:p}",
+        list,
+        index,
+        item
+    );
+    let fl = list as *mut PyList;
+    let slot = (*fl).ob_item.add(index as usize);
+    let old_item = *slot;
+    *slot = item;
+    if !old_item.is_null() {
+        ffi::Py_DECREF(old_item);
+    }
+    debug_println!("list_set_item_transfer_replacing ◀");
+}
+
+/// True if `obj` is exactly a yurki.List or a `list`, the types of the
+/// result lists workers build (see `core::create_result_list`).
+///
+/// # Safety
+///
+/// `obj` must be a live object.
+pub unsafe fn is_result_list(obj: *mut ffi::PyObject) -> bool {
+    ffi::Py_TYPE(obj) == LIST_TYPE || ffi::PyList_CheckExact(obj) != 0
+}
+
+/// True if every slot of a result list has been filled, and so have the
+/// slots of the result lists nested in it: a worker that failed to allocate
+/// one item of a nested result leaves a hole there.
+///
+/// # Safety
+///
+/// `list` must be a live result list whose filled slots hold live objects.
+pub unsafe fn list_is_complete(list: *mut ffi::PyObject) -> bool {
+    list_items(list)
+        .iter()
+        .all(|&item| !item.is_null() && (!is_result_list(item) || list_is_complete(item)))
+}
+
+/// Register `list` with the cycle collector, along with the result lists
+/// nested in it that were built by workers. Every result list must be
+/// tracked before Python code can reach it: an untracked one would hide
+/// the references of a cycle running through it.
+///
+/// # Safety
+///
+/// The GIL must be held and `list` must be a live result list.
+pub unsafe fn list_track(list: *mut ffi::PyObject) {
+    if ffi::PyObject_GC_IsTracked(list) != 0 {
+        return;
+    }
+    ffi::PyObject_GC_Track(list as _);
+    for &item in list_items(list) {
+        if !item.is_null() && is_result_list(item) {
+            list_track(item);
+        }
+    }
+}
+
+/// DECREF the filled slots of a partially built list and set its size to 0,
+/// so it can never be observed with holes.
+///
+/// # Safety
+///
+/// The GIL must be held and `list` must be a live list that owns the
+/// references in its filled slots.
+pub unsafe fn list_release_items(list: *mut ffi::PyObject) {
+    debug_println!("list_release_items ▶ list={:p}", list);
+    let fl = list as *mut PyList;
+    let n = if (*fl).ob_item.is_null() {
+        0
+    } else {
+        (*fl).ob_base.ob_size as usize
+    };
+    (*fl).ob_base.ob_size = 0;
+    for i in 0..n {
+        let slot = (*fl).ob_item.add(i);
+        let item = *slot;
+        *slot = ptr::null_mut();
+        if !item.is_null() {
+            ffi::Py_DECREF(item);
+        }
+    }
+    debug_println!("list_release_items ◀");
+}
@@ -1,6 +1,44 @@
+use pyo3::ffi;
+
 pub mod list;
 pub mod string;
 
-pub use crate::simd::convert_pystring;
-pub use list::{create_list, create_list_empty, init_list_type, list_set_item_transfer};
-pub use string::{create_fast_string, init_string_type};
+pub use crate::simd::{borrow_ascii_pystring, convert_pystring};
+pub use list::{
+    ListBuilder, create_list, create_list_empty, init_list_type, is_list, is_result_list,
+    list_chunks, list_from_iterable, list_from_refs, list_is_complete, list_release_items,
+    list_reversed, list_set_item_transfer, list_set_item_transfer_replacing, list_track,
+};
+pub use string::{
+    create_fast_string, create_plain_string, get_utf8_cache, has_cached_hash, hash_string,
+    init_small_strings, init_string_type, is_exact_string, private_ascii_buffer, set_utf8_cache,
+};
+
+// Free-threaded refcount layout, private in CPython's headers: the low bits of
+// `ob_ref_shared` are flags, `MERGED` meaning no thread owns the object.
+#[cfg(Py_GIL_DISABLED)]
+const REF_SHARED_SHIFT: isize = 2;
+#[cfg(Py_GIL_DISABLED)]
+const REF_MERGED: isize = 3;
+
+/// Write the `PyObject` header of a freshly allocated yurki object: one
+/// reference, type `typ`. CPython's own initialisation isn't used since
+/// workers create objects without a thread state.
+///
+/// Free-threaded builds keep a reference count per owning thread plus a
+/// shared one. The object is left unowned with its reference in the shared
+/// count, as `_Py_ExplicitMergeRefcount` does, so every thread takes the
+/// atomic path and the last DECREF, from any thread, deallocates it.
+#[inline(always)]
+pub(crate) unsafe fn init_object_header(obj: *mut ffi::PyObject, typ: *mut ffi::PyTypeObject) {
+    #[allow(unused_mut)]
+    let mut header = ffi::PyObject_HEAD_INIT;
+    #[cfg(Py_GIL_DISABLED)]
+    {
+        use std::sync::atomic::{AtomicIsize, AtomicU32};
+        header.ob_ref_local = AtomicU32::new(0);
+        header.ob_ref_shared = AtomicIsize::new((1 << REF_SHARED_SHIFT) | REF_MERGED);
+    }
+    header.ob_type = typ;
+    std::ptr::write(obj, header);
+}
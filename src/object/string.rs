@@ -1,7 +1,9 @@
 use pyo3::{ffi, prelude::*};
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::{alloc, mem, ptr};
 
 use crate::debug_println;
+use crate::object::init_object_header;
 use crate::simd;
 
 /// Allocate bytes with usize alignment.
@@ -39,7 +41,12 @@ unsafe extern "C" fn string_alloc(
 /// tp_dealloc runs before tp_free
 unsafe extern "C" fn string_dealloc(obj: *mut ffi::PyObject) {
     debug_println!("string_dealloc ▶ {:?}", obj);
-    // Nothing special to clean for a plain str
+    // A UTF-8 form CPython cached itself (`PyUnicode_AsUTF8`) is its own
+    // block; one attached at creation goes with the string's
+    let utf8 = string_utf8(obj);
+    if !utf8.is_null() && utf8 != inline_utf8(obj) {
+        ffi::PyObject_Free(utf8 as _);
+    }
     ffi::Py_TYPE(obj).as_ref().unwrap().tp_free.unwrap()(obj as _);
     debug_println!("string_dealloc ◀");
 }
@@ -65,34 +72,14 @@ unsafe extern "C" fn string_free(obj: *mut std::ffi::c_void) {
         return;
     }
 
-    let _refcnt = ptr::read(&(*py_object).ob_refcnt as *const _ as *const ffi::Py_ssize_t);
+    let _refcnt = ffi::Py_REFCNT(py_object);
     debug_println!("  refcnt  = {}", _refcnt);
 
-    // Decode string layout
-    let ascii = obj as *mut ffi::PyASCIIObject;
-    let character_count = (*ascii).length as usize;
-    let flags = ptr::read(&(*ascii).state as *const _ as *const u32);
-    let element_size = match (flags >> 2) & 0b111 {
-        1 => 1,
-        2 => 2,
-        _ => 4,
-    };
-    let _is_ascii = ((flags >> 5) & 1) == 1;
-
-    debug_println!(
-        "  character_count = {character_count}, element_size = {element_size}, flags = 0x{flags:x}, is_ascii={_is_ascii}"
-    );
-
     // Compute total allocation size
-    let header_size = (*(*ascii).ob_base.ob_type).tp_basicsize as usize;
-    let total_size = header_size + (character_count + 1) * element_size;
+    let total_size = string_block_size(py_object);
+    debug_println!("  total_size to free = {total_size}");
 
-    debug_println!("  header_size (tp_basicsize) = {header_size}");
-    debug_println!("  total_size to free         = {total_size}");
-
-    if total_size == 0 || total_size > 10_000_000 {
-        panic!("string_free: suspicious total_size = {total_size}");
-    }
+    debug_assert!(total_size > mem::size_of::<ffi::PyASCIIObject>());
 
     // Free memory
     debug_println!("  calling internal_free_bytes …");
@@ -100,6 +87,95 @@ unsafe extern "C" fn string_free(obj: *mut std::ffi::c_void) {
     debug_println!("string_free ◀ finished (freed {:p})", obj);
 }
 
+// ───────────────────────────────────────────
+//  Pickle, copy and exact str support
+// ───────────────────────────────────────────
+
+/// `to_str()` – the same text as an exact `str`, for code that checks
+/// `type(x) is str`. A copy: a `str` owns its characters.
+unsafe extern "C" fn string_to_str(
+    obj: *mut ffi::PyObject,
+    _args: *mut ffi::PyObject,
+) -> *mut ffi::PyObject {
+    ffi::PyUnicode_FromKindAndData(
+        ffi::PyUnicode_KIND(obj) as _,
+        ffi::PyUnicode_DATA(obj),
+        ffi::PyUnicode_GET_LENGTH(obj),
+    )
+}
+
+/// `__reduce__()` – pickled as a plain `str`: yurki.String has no
+/// constructor, and is only a faster-built `str`
+unsafe extern "C" fn string_reduce(
+    obj: *mut ffi::PyObject,
+    _args: *mut ffi::PyObject,
+) -> *mut ffi::PyObject {
+    let text = string_to_str(obj, ptr::null_mut());
+    if text.is_null() {
+        return ptr::null_mut();
+    }
+    let args = ffi::PyTuple_Pack(1, text);
+    ffi::Py_DECREF(text);
+    if args.is_null() {
+        return ptr::null_mut();
+    }
+    let result = ffi::PyTuple_Pack(2, ptr::addr_of_mut!(ffi::PyUnicode_Type), args);
+    ffi::Py_DECREF(args);
+    result
+}
+
+/// `__copy__()` and `__deepcopy__(memo)` – the string itself, as for `str`
+unsafe extern "C" fn string_copy(
+    obj: *mut ffi::PyObject,
+    _args: *mut ffi::PyObject,
+) -> *mut ffi::PyObject {
+    ffi::Py_INCREF(obj);
+    obj
+}
+
+const STRING_METHODS: [ffi::PyMethodDef; 5] = [
+    ffi::PyMethodDef {
+        ml_name: c"to_str".as_ptr(),
+        ml_meth: ffi::PyMethodDefPointer {
+            PyCFunction: string_to_str,
+        },
+        ml_flags: ffi::METH_NOARGS,
+        ml_doc: c"to_str() -- Copy as an exact str".as_ptr(),
+    },
+    ffi::PyMethodDef {
+        ml_name: c"__reduce__".as_ptr(),
+        ml_meth: ffi::PyMethodDefPointer {
+            PyCFunction: string_reduce,
+        },
+        ml_flags: ffi::METH_NOARGS,
+        ml_doc: c"__reduce__() -- Pickle as a plain str".as_ptr(),
+    },
+    ffi::PyMethodDef {
+        ml_name: c"__copy__".as_ptr(),
+        ml_meth: ffi::PyMethodDefPointer {
+            PyCFunction: string_copy,
+        },
+        ml_flags: ffi::METH_NOARGS,
+        ml_doc: c"__copy__() -- The string itself, it is immutable".as_ptr(),
+    },
+    ffi::PyMethodDef {
+        ml_name: c"__deepcopy__".as_ptr(),
+        ml_meth: ffi::PyMethodDefPointer {
+            PyCFunction: string_copy,
+        },
+        ml_flags: ffi::METH_O,
+        ml_doc: c"__deepcopy__(memo) -- The string itself, it is immutable".as_ptr(),
+    },
+    ffi::PyMethodDef {
+        ml_name: ptr::null(),
+        ml_meth: ffi::PyMethodDefPointer {
+            PyCFunction: string_copy, // Never called since ml_name is null
+        },
+        ml_flags: 0,
+        ml_doc: ptr::null(),
+    },
+];
+
 /// Initialize String type for module.
 pub unsafe fn init_string_type(m: *mut ffi::PyObject) -> PyResult<()> {
     let mut slots = [
@@ -108,9 +184,9 @@ pub unsafe fn init_string_type(m: *mut ffi::PyObject) -> PyResult<()> {
             pfunc: &raw mut ffi::PyUnicode_Type as *mut _ as *mut _,
         },
         ffi::PyType_Slot {
-            slot: ffi::Py_tp_new as i32,
-            pfunc: std::ptr::null_mut(),
-        }, // Prevent external instantiation
+            slot: ffi::Py_tp_methods,
+            pfunc: STRING_METHODS.as_ptr() as *mut _,
+        },
         ffi::PyType_Slot {
             slot: ffi::Py_tp_alloc as i32,
             pfunc: string_alloc as *mut _,
@@ -135,9 +211,12 @@ pub unsafe fn init_string_type(m: *mut ffi::PyObject) -> PyResult<()> {
         name: b"yurki.String\0".as_ptr() as *const _,
         basicsize: base_size as i32,
         itemsize: 0,
+        // Instances only come from `create_fast_string`: `str.__new__`
+        // would build a non-compact string `string_free` can't size
         flags: (ffi::Py_TPFLAGS_DEFAULT
             | ffi::Py_TPFLAGS_UNICODE_SUBCLASS
-            | ffi::Py_TPFLAGS_BASETYPE) as u32,
+            | ffi::Py_TPFLAGS_BASETYPE
+            | ffi::Py_TPFLAGS_DISALLOW_INSTANTIATION) as u32,
         slots: slots.as_mut_ptr(),
     };
 
@@ -151,17 +230,184 @@ pub unsafe fn init_string_type(m: *mut ffi::PyObject) -> PyResult<()> {
     Ok(())
 }
 
+// Small string cache
+
+/// Interpreter-owned `""` (last slot) and latin-1 single-character strings,
+/// indexed by code point. Filled once at module init; null slots fall back
+/// to allocation.
+static mut SMALL_STRINGS: [*mut ffi::PyObject; 257] = [std::ptr::null_mut(); 257];
+
+/// Cache the interpreter's empty and single-character latin-1 strings.
+///
+/// Only immortal objects are kept: `Py_INCREF`/`Py_DECREF` leave their
+/// refcount untouched, so workers can hand them out without the GIL.
+///
+/// # Safety
+///
+/// The caller must hold the GIL.
+pub unsafe fn init_small_strings() {
+    let cache = &mut *ptr::addr_of_mut!(SMALL_STRINGS);
+    for (idx, slot) in cache.iter_mut().enumerate() {
+        let obj = if idx == 256 {
+            ffi::PyUnicode_New(0, 0)
+        } else {
+            ffi::PyUnicode_FromOrdinal(idx as i32)
+        };
+        if obj.is_null() {
+            ffi::PyErr_Clear();
+            continue;
+        }
+        // Same lower bound pyo3-ffi uses to detect immortal objects
+        if ffi::Py_REFCNT(obj) >= 1 << 30 {
+            *slot = obj;
+        } else {
+            ffi::Py_DECREF(obj);
+        }
+    }
+}
+
+/// Cached string for `""` or a single latin-1 character, with a new
+/// reference.
+#[inline(always)]
+unsafe fn small_string(text: &str) -> Option<*mut ffi::PyObject> {
+    let idx = match *text.as_bytes() {
+        [] => 256,
+        [b] if b < 0x80 => b as usize,
+        // Two-byte UTF-8 sequences starting 0xC2/0xC3 encode U+0080..U+00FF
+        [b0 @ (0xC2 | 0xC3), b1] => (((b0 & 0x1F) as usize) << 6) | (b1 & 0x3F) as usize,
+        _ => return None,
+    };
+    let obj = (*ptr::addr_of!(SMALL_STRINGS))[idx];
+    if obj.is_null() {
+        return None;
+    }
+    ffi::Py_INCREF(obj);
+    Some(obj)
+}
+
 // String creation
 
-/// Create a yurki.String from UTF-8 text.
+/// First bit of `kind` in `PyASCIIObject.state`, after `interned`: two bits,
+/// or a whole byte on free-threaded builds.
+#[cfg(not(Py_GIL_DISABLED))]
+const STATE_KIND_SHIFT: u32 = 2;
+#[cfg(Py_GIL_DISABLED)]
+const STATE_KIND_SHIFT: u32 = 8;
+
+/// Element size (the unicode kind) and ascii flag of a compact string's
+/// `PyASCIIObject.state`.
+#[inline(always)]
+fn state_layout(state: u32) -> (usize, bool) {
+    let kind = ((state >> STATE_KIND_SHIFT) & 0b111) as usize;
+    let ascii = (state >> (STATE_KIND_SHIFT + 4)) & 1 == 1;
+    (kind, ascii)
+}
+
+/// Where a non-ASCII yurki.String keeps the UTF-8 form attached at
+/// creation: right after its payload. The block has spare room there (the
+/// header is sized as `tp_basicsize`, larger than a compact header), so the
+/// address is inside it even with nothing attached, and can't be that of a
+/// buffer CPython allocated.
+/// Safety: `obj` must be a live yurki.String.
+unsafe fn inline_utf8(obj: *mut ffi::PyObject) -> *mut u8 {
+    let ascii = obj as *mut ffi::PyASCIIObject;
+    let (element_size, _) = state_layout((*ascii).state);
+    let header = mem::size_of::<ffi::PyCompactUnicodeObject>();
+    (obj as *mut u8).add(header + ((*ascii).length as usize + 1) * element_size)
+}
+
+/// Size of the block `create_fast_string` allocated for `obj`.
+/// Safety: `obj` must be a live yurki.String.
+unsafe fn string_block_size(obj: *mut ffi::PyObject) -> usize {
+    let ascii = obj as *mut ffi::PyASCIIObject;
+    let (element_size, _) = state_layout((*ascii).state);
+    let header_padded = (*STRING_TYPE).tp_basicsize as usize;
+    let mut size = header_padded + ((*ascii).length as usize + 1) * element_size;
+    if string_utf8(obj) == inline_utf8(obj) {
+        size += (*(obj as *mut ffi::PyCompactUnicodeObject)).utf8_length as usize + 1;
+    }
+    size
+}
+
+/// The cached UTF-8 form of a yurki.String, null if it has none. ASCII
+/// strings never do: their payload is their UTF-8 form.
+/// Safety: `obj` must be a live yurki.String.
+unsafe fn string_utf8(obj: *mut ffi::PyObject) -> *mut u8 {
+    let ascii = obj as *mut ffi::PyASCIIObject;
+    match state_layout((*ascii).state) {
+        (_, true) => ptr::null_mut(),
+        (_, false) => (*(obj as *mut ffi::PyCompactUnicodeObject)).utf8 as *mut u8,
+    }
+}
+
+// UTF-8 cache
+
+static UTF8_CACHE: AtomicBool = AtomicBool::new(false);
+
+/// Attach the UTF-8 form to every non-ASCII yurki.String created from now
+/// on, so `PyUnicode_AsUTF8` returns it without encoding. Costs the UTF-8
+/// length again per string, in the same allocation.
+pub fn set_utf8_cache(enabled: bool) {
+    UTF8_CACHE.store(enabled, Ordering::Relaxed);
+}
+
+pub fn get_utf8_cache() -> bool {
+    UTF8_CACHE.load(Ordering::Relaxed)
+}
+
+/// `PyASCIIObject.state` of a compact, not interned string.
+///
+/// Layout: interned | kind(3) | compact(1) | ascii(1) | statically_allocated(1).
+/// The last bit was `ready` before 3.12 and must now stay clear, since the
+/// string is heap allocated.
+#[inline(always)]
+fn compact_state(kind: u32, ascii: bool) -> u32 {
+    (kind << STATE_KIND_SHIFT)
+        | (1 << (STATE_KIND_SHIFT + 3))
+        | ((ascii as u32) << (STATE_KIND_SHIFT + 4))
+}
+
+/// Create a yurki.String from UTF-8 text. Empty and single latin-1
+/// character texts return the interpreter's shared `str` instead.
 /// Safety: caller must hold the GIL and `text` must be valid UTF-8.
 pub unsafe fn create_fast_string(text: &str) -> *mut ffi::PyObject {
     debug_println!("create_fast_string: input {:?}", text);
 
+    // "" and single latin-1 characters are shared immortal strings
+    if let Some(obj) = small_string(text) {
+        return obj;
+    }
+
     // SIMD-accelerated analysis: get max codepoint and length in one pass
     let (character_count, max_codepoint) = simd::analyze_utf8_simd(text.as_bytes());
+    match build_fast_string(text, character_count, max_codepoint, write_simd) {
+        Ok(obj) => obj,
+        Err(_written) => {
+            // The SIMD analysis and conversion disagree, which would leave
+            // characters uninitialized. Count and convert again one character
+            // at a time: workers may run without the GIL, ruling out the C API
+            debug_println!("  wrote {_written} of {character_count} characters, rebuilding");
+            let (count, max) = text
+                .chars()
+                .fold((0, 0), |(n, max), c| (n + 1, max.max(c as u32)));
+            build_fast_string(text, count, max, write_chars).unwrap_or(ptr::null_mut())
+        }
+    }
+}
 
-    // Choose internal kind / element size
+/// Allocate and fill a yurki.String of `character_count` characters up to
+/// `max_codepoint`, `write` converting `text` into its payload. Null if the
+/// allocation fails; `Err` with the characters written, the block freed, if
+/// `write` disagrees with the count.
+unsafe fn build_fast_string(
+    text: &str,
+    character_count: usize,
+    max_codepoint: u32,
+    write: unsafe fn(&str, *mut u8, usize, usize) -> usize,
+) -> Result<*mut ffi::PyObject, usize> {
+    // Choose internal kind / element size. Characters past U+FFFF select the
+    // 4-byte kind, so a 2-byte payload never holds surrogate pairs and its
+    // length in UTF-16 code units is `character_count`
     let (unicode_kind, element_size) = match max_codepoint {
         0x0000..=0x00FF => (ffi::PyUnicode_1BYTE_KIND as u32, 1),
         0x0100..=0xFFFF => (ffi::PyUnicode_2BYTE_KIND as u32, 2),
@@ -175,69 +421,247 @@ pub unsafe fn create_fast_string(text: &str) -> *mut ffi::PyObject {
         std::mem::size_of::<ffi::PyCompactUnicodeObject>()
     };
     let header_padded = (*STRING_TYPE).tp_basicsize as usize;
-    let total_bytes = header_padded + (character_count + 1) * element_size;
+    let payload_end = header_padded + (character_count + 1) * element_size;
+    let attach_utf8 = max_codepoint >= 0x80 && get_utf8_cache();
+    let total_bytes = if attach_utf8 {
+        payload_end + text.len() + 1
+    } else {
+        payload_end
+    };
 
     // Allocate memory
     let raw = internal_alloc_bytes(total_bytes) as *mut u8;
     if raw.is_null() {
         ffi::PyErr_NoMemory();
-        return std::ptr::null_mut();
+        return Ok(std::ptr::null_mut());
     }
     debug_println!("  alloc {:p}, total_bytes={total_bytes}", raw);
 
     // PyObject header
-    let py_object = raw as *mut ffi::PyVarObject;
-    std::ptr::write(
-        &mut (*py_object).ob_base.ob_refcnt as *mut _ as *mut ffi::Py_ssize_t,
-        1,
-    );
-    (*py_object).ob_base.ob_type = STRING_TYPE;
+    init_object_header(raw as *mut ffi::PyObject, STRING_TYPE);
 
     // PyASCII fields
     let ascii_header = &mut *(raw as *mut ffi::PyASCIIObject);
     ascii_header.length = character_count as ffi::Py_ssize_t;
     ascii_header.hash = -1;
 
-    // Bit layout: interned(2) | kind(3) | compact(1) | ascii(1) | ready(1)
     let is_ascii = if max_codepoint < 0x80 { 1 } else { 0 };
-    let flags: u32 = (unicode_kind << 2)        // bits 2-4  (1/2/4-BYTE)
-                   | (1 << 5)                   // compact = 1 (always)
-                   | ((is_ascii as u32) << 6)   // ascii = 0 or 1
-                   | (1 << 7); // ready  = 1
-
+    let flags = compact_state(unicode_kind, is_ascii == 1);
     std::ptr::write(&mut ascii_header.state as *mut _ as *mut u32, flags);
     debug_println!("  flags = 0x{flags:x} (is_ascii={is_ascii})");
 
-    // Compact-unicode extras
+    // Compact-unicode extras, with the UTF-8 form NUL-terminated after the
+    // payload (see `inline_utf8`)
     if is_ascii == 0 {
         let compact_unicode = &mut *(raw as *mut ffi::PyCompactUnicodeObject);
-        compact_unicode.utf8_length = 0;
-        compact_unicode.utf8 = std::ptr::null_mut();
+        if attach_utf8 {
+            let utf8 = raw.add(header_actual + (character_count + 1) * element_size);
+            ptr::copy_nonoverlapping(text.as_ptr(), utf8, text.len());
+            *utf8.add(text.len()) = 0;
+            compact_unicode.utf8_length = text.len() as ffi::Py_ssize_t;
+            compact_unicode.utf8 = utf8 as *mut _;
+        } else {
+            compact_unicode.utf8_length = 0;
+            compact_unicode.utf8 = std::ptr::null_mut();
+        }
     }
 
-    // Copy canonical data just after real header using SIMD
+    // Copy canonical data just after real header. Kind and length come from
+    // the analysis, so a conversion disagreeing with it is refused
     let payload = raw.add(header_actual);
+    let written = write(text, payload, character_count, element_size);
+    if written != character_count {
+        internal_free_bytes(raw as *mut std::ffi::c_void, total_bytes);
+        return Err(written);
+    }
+    // CPython expects a NUL character after the last one
+    ptr::write_bytes(payload.add(character_count * element_size), 0, element_size);
+    debug_println!("  payload copied @ {:p}", payload);
+
+    Ok(raw as *mut ffi::PyObject)
+}
+
+/// Convert `text` into `count` characters of `element_size` bytes at
+/// `payload` using SIMD, returning the number written.
+unsafe fn write_simd(text: &str, payload: *mut u8, count: usize, element_size: usize) -> usize {
     match element_size {
         1 => {
-            let dst_slice = std::slice::from_raw_parts_mut(payload, character_count);
-            let actual_len = simd::utf8_to_ucs1_simd(text.as_bytes(), dst_slice);
-            debug_assert_eq!(actual_len, character_count);
+            let dst_slice = std::slice::from_raw_parts_mut(payload, count);
+            simd::utf8_to_ucs1_simd(text.as_bytes(), dst_slice)
         }
         2 => {
-            let dst = payload as *mut u16;
-            let dst_slice = std::slice::from_raw_parts_mut(dst, character_count);
-            let actual_len = simd::utf8_to_ucs2_simd(text.as_bytes(), dst_slice);
-            debug_assert_eq!(actual_len, character_count);
+            let dst_slice = std::slice::from_raw_parts_mut(payload as *mut u16, count);
+            simd::utf8_to_ucs2_simd(text.as_bytes(), dst_slice)
         }
         4 => {
-            let dst = payload as *mut u32;
-            let dst_slice = std::slice::from_raw_parts_mut(dst, character_count);
-            let actual_len = simd::utf8_to_ucs4_simd(text.as_bytes(), dst_slice);
-            debug_assert_eq!(actual_len, character_count);
+            let dst_slice = std::slice::from_raw_parts_mut(payload as *mut u32, count);
+            simd::utf8_to_ucs4_simd(text.as_bytes(), dst_slice)
         }
         _ => unreachable!(),
     }
-    debug_println!("  payload copied @ {:p}", payload);
+}
+
+/// Same as `write_simd`, one `char` at a time. Exact whenever `count` and
+/// the kind come from `text.chars()`.
+unsafe fn write_chars(text: &str, payload: *mut u8, count: usize, element_size: usize) -> usize {
+    let mut written = 0;
+    for c in text.chars().take(count) {
+        match element_size {
+            1 => *payload.add(written) = c as u8,
+            2 => *(payload as *mut u16).add(written) = c as u16,
+            _ => *(payload as *mut u32).add(written) = c as u32,
+        }
+        written += 1;
+    }
+    written
+}
+
+/// Create a plain `str` through the C API, for results while fast strings
+/// are off (see `core::set_fast_types`) or disabled by the feature.
+///
+/// Unlike `create_fast_string` this needs the GIL, which it takes itself:
+/// workers call it while the main thread waits with the GIL released (see
+/// `core::while_workers_run`). Reentrant on a thread already holding it.
+///
+/// # Safety
+///
+/// The interpreter must be running. The caller may or may not hold the GIL.
+pub unsafe fn create_plain_string(text: &str) -> *mut ffi::PyObject {
+    Python::with_gil(|_| {
+        ffi::PyUnicode_FromStringAndSize(text.as_ptr() as *const _, text.len() as ffi::Py_ssize_t)
+    })
+}
+
+/// True if `obj` is exactly a `str` or a yurki.String, not another subclass.
+///
+/// # Safety
+///
+/// `obj` must point to a live object.
+pub unsafe fn is_exact_string(obj: *mut ffi::PyObject) -> bool {
+    let string_type = ptr::read(ptr::addr_of!(STRING_TYPE));
+    ffi::PyUnicode_CheckExact(obj) != 0
+        || (!string_type.is_null() && ffi::Py_TYPE(obj) == string_type)
+}
+
+// Hashing
+
+/// True if the hash of string `obj` is already cached in it.
+///
+/// # Safety
+///
+/// `obj` must point to a live `str`.
+pub unsafe fn has_cached_hash(obj: *mut ffi::PyObject) -> bool {
+    (*(obj as *mut ffi::PyASCIIObject)).hash != -1
+}
 
-    raw as *mut ffi::PyObject
+/// Hash `obj` with `str.__hash__`, which caches the result in the string.
+///
+/// Workers call this without a thread state while the calling thread holds
+/// the GIL. That is sound on the supported CPython versions (3.12 and up):
+/// `unicode_hash` only reads the string's data and the process-wide hash
+/// secret, then stores the cached hash. It never allocates, raises, or
+/// looks up the thread state, so it has no use for one.
+///
+/// # Safety
+///
+/// `obj` must be a live exact `str` or yurki.String (see `is_exact_string`)
+/// that no other thread mutates or hashes meanwhile, as both would write its
+/// cached hash.
+pub unsafe fn hash_string(obj: *mut ffi::PyObject) -> ffi::Py_hash_t {
+    let hash = (*ptr::addr_of!(ffi::PyUnicode_Type)).tp_hash.unwrap();
+    hash(obj)
+}
+
+// In-place mutation
+
+/// Mutable payload of a string that no other code can observe, for
+/// length-preserving byte transforms.
+///
+/// Only compact ASCII strings qualify, and only when the caller's reference
+/// is the sole one (`Py_REFCNT == 1`, which also rules out immortal strings)
+/// and the string is not interned. The cached hash is reset, since the
+/// contents are about to change. Returns `None` for any other object.
+///
+/// # Safety
+///
+/// The caller must hold the GIL, and must neither hand out `obj` nor call into
+/// Python code while the returned slice is alive. Writes must keep every byte
+/// ASCII.
+pub unsafe fn private_ascii_buffer<'a>(obj: *mut ffi::PyObject) -> Option<&'a mut [u8]> {
+    if ffi::PyUnicode_Check(obj) == 0
+        || ffi::Py_REFCNT(obj) != 1
+        || ffi::PyUnicode_IS_COMPACT_ASCII(obj) == 0
+    {
+        return None;
+    }
+
+    let ascii_header = &mut *(obj as *mut ffi::PyASCIIObject);
+    if ascii_header.interned() != ffi::SSTATE_NOT_INTERNED {
+        return None;
+    }
+    ascii_header.hash = -1;
+
+    let len = ascii_header.length as usize;
+    let data = ffi::PyUnicode_DATA(obj) as *mut u8;
+    Some(std::slice::from_raw_parts_mut(data, len))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn write_chars_matches_simd() {
+        let texts = [
+            ("plain ascii ".repeat(20), 1),
+            ("latin é ÿ ".repeat(20), 1),
+            ("wide 中 Ω ".repeat(20), 2),
+            ("astral 😀 中 é ".repeat(20), 4),
+        ];
+        for (text, size) in &texts {
+            let count = text.chars().count();
+            // u32 storage keeps the payload aligned for every kind
+            let mut scalar = vec![0u32; count];
+            let mut vector = vec![0u32; count];
+            unsafe {
+                let written = write_chars(text, scalar.as_mut_ptr().cast(), count, *size);
+                assert_eq!(written, count);
+                let written = write_simd(text, vector.as_mut_ptr().cast(), count, *size);
+                assert_eq!(written, count);
+            }
+            assert_eq!(scalar, vector, "{text:?}");
+            let bytes: &[u8] =
+                unsafe { std::slice::from_raw_parts(scalar.as_ptr().cast(), count * size) };
+            let units: Vec<u32> = bytes
+                .chunks(*size)
+                .map(|b| match b {
+                    [x] => *x as u32,
+                    [x, y] => u16::from_ne_bytes([*x, *y]) as u32,
+                    _ => u32::from_ne_bytes(b.try_into().unwrap()),
+                })
+                .collect();
+            assert_eq!(units, text.chars().map(|c| c as u32).collect::<Vec<_>>());
+        }
+    }
+    #[test]
+    fn analysis_sizes_emoji_heavy_text() {
+        // Astral characters around every vector boundary, among BMP text
+        for filler in ["a", "é", "中"] {
+            for n in 0..130 {
+                let text = filler.repeat(n) + "😀🎉" + &filler.repeat(130 - n) + "🚀";
+                let (count, max) = simd::analyze_utf8_simd(text.as_bytes());
+                assert_eq!(count, text.chars().count(), "{filler} {n}");
+                assert!(max > 0xFFFF);
+                let mut payload = vec![0u32; count];
+                let written = unsafe { write_simd(&text, payload.as_mut_ptr().cast(), count, 4) };
+                assert_eq!(written, count);
+
+                // Without them, the UTF-16 length is the character count
+                let bmp = text.replace(['😀', '🎉', '🚀'], "");
+                let (count, max) = simd::analyze_utf8_simd(bmp.as_bytes());
+                assert!(max <= 0xFFFF);
+                assert_eq!(count, bmp.encode_utf16().count());
+            }
+        }
+    }
 }
@@ -0,0 +1,129 @@
+use std::sync::Arc;
+
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+use pyo3::types::PyList;
+use regex::{Regex, RegexBuilder};
+
+use crate::converter::ToPyObject;
+use crate::{core, text};
+
+/// Regex compiled once and shared by every call made through it.
+#[pyclass(frozen, module = "yurki.internal")]
+pub struct CompiledPattern {
+    regex: Arc<Regex>,
+    #[pyo3(get)]
+    case: bool,
+    #[pyo3(get)]
+    multiline: bool,
+    #[pyo3(get)]
+    dotall: bool,
+}
+
+#[pymethods]
+impl CompiledPattern {
+    #[new]
+    #[pyo3(signature = (pattern, case = false, multiline = false, dotall = false))]
+    fn new(pattern: &str, case: bool, multiline: bool, dotall: bool) -> PyResult<Self> {
+        let regex = RegexBuilder::new(pattern)
+            .case_insensitive(case)
+            .multi_line(multiline)
+            .dot_matches_new_line(dotall)
+            .build()
+            .map_err(|e| PyValueError::new_err(e.to_string()))?;
+
+        Ok(Self {
+            regex: Arc::new(regex),
+            case,
+            multiline,
+            dotall,
+        })
+    }
+
+    #[getter]
+    fn pattern(&self) -> &str {
+        self.regex.as_str()
+    }
+
+    #[pyo3(signature = (list, replacement, count = 1, jobs = 1, inplace = false, progress = None, progress_interval = 10_000))]
+    #[allow(clippy::too_many_arguments)]
+    fn replace(
+        &self,
+        py: Python,
+        list: &Bound<PyList>,
+        replacement: &str,
+        count: usize,
+        jobs: usize,
+        inplace: bool,
+        progress: Option<Bound<PyAny>>,
+        progress_interval: usize,
+    ) -> PyResult<PyObject> {
+        let progress = core::Progress::optional(progress, progress_interval)?;
+        let pattern = self.regex.clone();
+        let replacement = replacement.to_string();
+
+        let make_func = move || unsafe {
+            let pattern = pattern.clone();
+            let replacement = replacement.clone();
+            move |s: &str| {
+                text::replace_regexp_in_string(s, &pattern, &replacement, count).to_py_object()
+            }
+        };
+
+        core::map_pylist(py, list, jobs, inplace, progress, make_func)
+    }
+
+    #[pyo3(signature = (list, jobs = 1, inplace = false, progress = None, progress_interval = 10_000, overlapping = false))]
+    #[allow(clippy::too_many_arguments)]
+    fn findall(
+        &self,
+        py: Python,
+        list: &Bound<PyList>,
+        jobs: usize,
+        inplace: bool,
+        progress: Option<Bound<PyAny>>,
+        progress_interval: usize,
+        overlapping: bool,
+    ) -> PyResult<PyObject> {
+        let progress = core::Progress::optional(progress, progress_interval)?;
+        let pattern = self.regex.clone();
+
+        let make_func = move || unsafe {
+            let pattern = pattern.clone();
+            move |s: &str| {
+                if overlapping {
+                    text::find_all_overlapping(s, &pattern).to_py_object()
+                } else {
+                    text::find_all_in_string(s, &pattern).to_py_object()
+                }
+            }
+        };
+
+        core::map_pylist(py, list, jobs, inplace, progress, make_func)
+    }
+
+    #[pyo3(name = "match", signature = (list, jobs = 1, inplace = false, progress = None, progress_interval = 10_000))]
+    fn is_match(
+        &self,
+        py: Python,
+        list: &Bound<PyList>,
+        jobs: usize,
+        inplace: bool,
+        progress: Option<Bound<PyAny>>,
+        progress_interval: usize,
+    ) -> PyResult<PyObject> {
+        let progress = core::Progress::optional(progress, progress_interval)?;
+        let pattern = self.regex.clone();
+
+        let make_func = move || unsafe {
+            let pattern = pattern.clone();
+            move |s: &str| text::is_match_in_string(s, &pattern).to_py_object()
+        };
+
+        core::map_pylist(py, list, jobs, inplace, progress, make_func)
+    }
+
+    fn __repr__(&self) -> String {
+        format!("CompiledPattern({:?})", self.regex.as_str())
+    }
+}
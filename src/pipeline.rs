@@ -0,0 +1,240 @@
+//! Fused operation pipelines: several string operations applied per element
+//! inside a single `map_pylist` pass, transcoding each string only once.
+
+use std::borrow::Cow;
+
+use pyo3::exceptions::{PyTypeError, PyValueError};
+use pyo3::prelude::*;
+use pyo3::types::{PyList, PyTuple};
+use regex::{Regex, RegexBuilder};
+
+use crate::converter::ToPyObject;
+use crate::core::PyObjectPtr;
+use crate::text;
+
+/// String-to-string step; any number of them can be chained.
+enum Step {
+    Lower,
+    Upper,
+    Strip,
+    LStrip,
+    RStrip,
+    Replace {
+        from: String,
+        to: String,
+        count: usize,
+    },
+    ReplaceRegexp {
+        pattern: Regex,
+        replacement: String,
+        count: usize,
+    },
+    Find(Regex),
+}
+
+/// Step that changes the output type; only allowed last.
+enum Terminal {
+    IsMatch(Regex),
+    Capture(Regex),
+    Split(Regex),
+}
+
+impl Step {
+    fn apply<'a>(&self, string: &'a str) -> Cow<'a, str> {
+        match self {
+            Step::Lower => text::to_lower_in_string(string),
+            Step::Upper => text::to_upper_in_string(string),
+            Step::Strip => Cow::Borrowed(string.trim()),
+            Step::LStrip => Cow::Borrowed(string.trim_start()),
+            Step::RStrip => Cow::Borrowed(string.trim_end()),
+            Step::Replace { from, to, count } => text::replace_in_string(string, from, to, *count),
+            Step::ReplaceRegexp {
+                pattern,
+                replacement,
+                count,
+            } => text::replace_regexp_in_string(string, pattern, replacement, *count),
+            Step::Find(pattern) => text::find_in_string(string, pattern),
+        }
+    }
+
+    // Borrowed results of an owned input must be copied out before it drops
+    fn apply_cow<'a>(&self, string: Cow<'a, str>) -> Cow<'a, str> {
+        match string {
+            Cow::Borrowed(s) => self.apply(s),
+            Cow::Owned(s) => Cow::Owned(self.apply(&s).into_owned()),
+        }
+    }
+}
+
+/// Operations compiled from Python descriptors, shared across workers.
+pub struct Pipeline {
+    steps: Vec<Step>,
+    terminal: Option<Terminal>,
+}
+
+impl Pipeline {
+    /// Compile a list of `(name, *args)` tuples.
+    ///
+    /// Supported ops: `lower`, `upper`, `strip`, `lstrip`, `rstrip`,
+    /// `replace(from, to, count=0)`, `replace_regexp(pattern, replacement, count=1, case=False)`,
+    /// `find(pattern, case=False)`, and as the last op only `is_match`, `capture`
+    /// or `split` with `(pattern, case=False)`.
+    pub fn compile(ops: &Bound<PyList>) -> PyResult<Self> {
+        let mut steps = Vec::new();
+        let mut terminal = None;
+
+        for op in ops.iter() {
+            let op = op
+                .downcast::<PyTuple>()
+                .map_err(|_| PyTypeError::new_err("pipeline op must be a tuple (name, *args)"))?;
+            if op.is_empty() {
+                return Err(PyValueError::new_err("pipeline op must not be empty"));
+            }
+            if terminal.is_some() {
+                return Err(PyValueError::new_err(
+                    "is_match, capture and split must be the last pipeline op",
+                ));
+            }
+
+            let name: String = op.get_item(0)?.extract()?;
+            let args = op.get_slice(1, op.len());
+            let arity = |min: usize, max: usize| -> PyResult<()> {
+                if args.len() < min || args.len() > max {
+                    return Err(PyValueError::new_err(format!(
+                        "pipeline op {name:?} takes {min} to {max} arguments, got {}",
+                        args.len()
+                    )));
+                }
+                Ok(())
+            };
+            let arg = |idx: usize| args.get_item(idx);
+            let regex_arg = |idx: usize| -> PyResult<Regex> {
+                let case = if args.len() > idx + 1 {
+                    arg(idx + 1)?.extract::<bool>()?
+                } else {
+                    false
+                };
+                compile_regex(&arg(idx)?.extract::<String>()?, case)
+            };
+
+            match name.as_str() {
+                "lower" | "upper" | "strip" | "lstrip" | "rstrip" => {
+                    arity(0, 0)?;
+                    steps.push(match name.as_str() {
+                        "lower" => Step::Lower,
+                        "upper" => Step::Upper,
+                        "strip" => Step::Strip,
+                        "lstrip" => Step::LStrip,
+                        _ => Step::RStrip,
+                    });
+                }
+                "replace" => {
+                    arity(2, 3)?;
+                    steps.push(Step::Replace {
+                        from: arg(0)?.extract()?,
+                        to: arg(1)?.extract()?,
+                        count: match args.len() {
+                            3 => arg(2)?.extract()?,
+                            _ => 0,
+                        },
+                    });
+                }
+                "replace_regexp" => {
+                    arity(2, 4)?;
+                    let case = match args.len() {
+                        4 => arg(3)?.extract::<bool>()?,
+                        _ => false,
+                    };
+                    steps.push(Step::ReplaceRegexp {
+                        pattern: compile_regex(&arg(0)?.extract::<String>()?, case)?,
+                        replacement: arg(1)?.extract()?,
+                        count: match args.len() {
+                            3 | 4 => arg(2)?.extract()?,
+                            _ => 1,
+                        },
+                    });
+                }
+                "find" => {
+                    arity(1, 2)?;
+                    steps.push(Step::Find(regex_arg(0)?));
+                }
+                "is_match" => {
+                    arity(1, 2)?;
+                    terminal = Some(Terminal::IsMatch(regex_arg(0)?));
+                }
+                "capture" => {
+                    arity(1, 2)?;
+                    terminal = Some(Terminal::Capture(regex_arg(0)?));
+                }
+                "split" => {
+                    arity(1, 2)?;
+                    terminal = Some(Terminal::Split(regex_arg(0)?));
+                }
+                _ => {
+                    return Err(PyValueError::new_err(format!(
+                        "unknown pipeline op: {name:?}"
+                    )));
+                }
+            }
+        }
+
+        Ok(Self { steps, terminal })
+    }
+
+    fn run_steps<'a>(&self, string: &'a str) -> Cow<'a, str> {
+        self.steps
+            .iter()
+            .fold(Cow::Borrowed(string), |acc, step| step.apply_cow(acc))
+    }
+
+    /// True when the result of `apply_text` is defined: string ops only,
+    /// optionally ending with an `is_match` filter.
+    pub fn produces_text(&self) -> bool {
+        matches!(self.terminal, None | Some(Terminal::IsMatch(_)))
+    }
+
+    /// Run every step on one element without creating Python objects.
+    ///
+    /// Returns the transformed string, or for an `is_match` pipeline the
+    /// original string when it matches and `None` otherwise.
+    pub fn apply_text<'a>(&self, string: &'a str) -> Option<Cow<'a, str>> {
+        let result = self.run_steps(string);
+        match &self.terminal {
+            None => Some(result),
+            Some(Terminal::IsMatch(pattern)) => {
+                text::is_match_in_string(&result, pattern).then_some(Cow::Borrowed(string))
+            }
+            Some(_) => panic!("apply_text called on a pipeline with list output"),
+        }
+    }
+
+    /// Run every step on one element and convert the result.
+    ///
+    /// # Safety
+    ///
+    /// Same requirements as `ToPyObject::to_py_object`: the yurki types must
+    /// be initialised and the result handed to Python with the GIL held.
+    pub unsafe fn apply(&self, string: &str) -> PyObjectPtr {
+        let result = self.run_steps(string);
+
+        match &self.terminal {
+            None => result.to_py_object(),
+            Some(Terminal::IsMatch(pattern)) => {
+                text::is_match_in_string(&result, pattern).to_py_object()
+            }
+            Some(Terminal::Capture(pattern)) => {
+                text::capture_regex_in_string(&result, pattern).to_py_object()
+            }
+            Some(Terminal::Split(pattern)) => {
+                text::split_by_regexp_string(&result, pattern).to_py_object()
+            }
+        }
+    }
+}
+
+fn compile_regex(pattern: &str, case: bool) -> PyResult<Regex> {
+    RegexBuilder::new(pattern)
+        .case_insensitive(case)
+        .build()
+        .map_err(|e| PyValueError::new_err(e.to_string()))
+}
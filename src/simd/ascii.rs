@@ -0,0 +1,668 @@
+//! ASCII-only case mapping, letter rotation, byte translation, byte search,
+//! byte classification and splitting on byte classes
+
+use crate::simd::{LANES_U8, U8s};
+use core::simd::Select;
+use core::simd::cmp::{SimdOrd, SimdPartialEq, SimdPartialOrd};
+use core::simd::prelude::SimdUint;
+
+// ========================================================================== //
+//                         Scalar Implementations                             //
+// ========================================================================== //
+
+/// Adds `delta` to every byte in `lo..=hi`, copying the rest unchanged.
+#[inline]
+fn shift_range_scalar(input: &[u8], out: &mut [u8], lo: u8, hi: u8, delta: u8) {
+    for (dst, &b) in out.iter_mut().zip(input) {
+        *dst = if (lo..=hi).contains(&b) {
+            b.wrapping_add(delta)
+        } else {
+            b
+        };
+    }
+}
+
+/// Rotates an ASCII letter by `n` (< 26) places within its case.
+#[inline]
+fn rotate_letter_scalar(b: u8, n: u8) -> u8 {
+    let base = match b {
+        b'a'..=b'z' => b'a',
+        b'A'..=b'Z' => b'A',
+        _ => return b,
+    };
+    base + (b - base + n) % 26
+}
+
+// ========================================================================== //
+//                           SIMD Implementations                             //
+// ========================================================================== //
+
+/// Adds `delta` to the lanes of `chunk` in `lo..=hi`.
+///
+/// One wrapping subtraction and compare finds the range, and a mask select
+/// picks the shifted lanes; every other byte, including all bytes >= 0x80 of
+/// multi-byte UTF-8 sequences, is kept as is.
+#[inline(always)]
+fn shift_range_lanes(chunk: U8s, lo: u8, hi: u8, delta: u8) -> U8s {
+    let in_range = (chunk - U8s::splat(lo)).simd_le(U8s::splat(hi - lo));
+    in_range.select(chunk + U8s::splat(delta), chunk)
+}
+
+/// SIMD body shared by `ascii_upper` and `ascii_lower`. The tail shorter
+/// than one vector is handled by the scalar routine.
+#[inline]
+fn shift_range_simd(input: &[u8], out: &mut [u8], lo: u8, hi: u8, delta: u8) {
+    assert!(out.len() >= input.len(), "output buffer too small");
+
+    let mut i = 0;
+    while i + LANES_U8 <= input.len() {
+        let chunk = U8s::from_slice(&input[i..i + LANES_U8]);
+        shift_range_lanes(chunk, lo, hi, delta).copy_to_slice(&mut out[i..i + LANES_U8]);
+        i += LANES_U8;
+    }
+
+    shift_range_scalar(&input[i..], &mut out[i..], lo, hi, delta);
+}
+
+/// `shift_range_simd` rewriting `bytes` in place.
+#[inline]
+fn shift_range_inplace(bytes: &mut [u8], lo: u8, hi: u8, delta: u8) {
+    let mut chunks = bytes.chunks_exact_mut(LANES_U8);
+    for chunk in &mut chunks {
+        shift_range_lanes(U8s::from_slice(chunk), lo, hi, delta).copy_to_slice(chunk);
+    }
+    for b in chunks.into_remainder() {
+        if (lo..=hi).contains(b) {
+            *b = b.wrapping_add(delta);
+        }
+    }
+}
+
+/// Branch-free rotation of the letters `lo..lo + 26` by `n` (< 26) places.
+///
+/// Returns the per-lane delta to add (wrapping): `n` for letters that stay
+/// inside the alphabet, `n - 26` for those that wrap around, 0 elsewhere.
+#[inline]
+fn rotate_delta(chunk: U8s, lo: u8, n: u8) -> U8s {
+    let one = U8s::splat(1);
+    let n_v = U8s::splat(n);
+    let off = chunk - U8s::splat(lo);
+    let is_letter = U8s::splat(26).saturating_sub(off).simd_min(one);
+    // `off + n` can't overflow for letters, and other lanes are masked out
+    let wraps = (off + n_v).saturating_sub(U8s::splat(25)).simd_min(one);
+    is_letter * (n_v - wraps * U8s::splat(26))
+}
+
+#[inline]
+fn rotate_letters_simd(chunk: U8s, n: u8) -> U8s {
+    chunk + rotate_delta(chunk, b'a', n) + rotate_delta(chunk, b'A', n)
+}
+
+/// Uppercases the ASCII letters of `input` into `out`.
+///
+/// Only `a..=z` change; non-ASCII bytes pass through untouched, so valid
+/// UTF-8 input yields valid UTF-8 of the same length. `out` must be at least
+/// as long as `input`.
+#[inline]
+pub fn ascii_upper(input: &[u8], out: &mut [u8]) {
+    shift_range_simd(input, out, b'a', b'z', 0u8.wrapping_sub(0x20));
+}
+
+/// Lowercases the ASCII letters of `input` into `out`.
+///
+/// Only `A..=Z` change; non-ASCII bytes pass through untouched, so valid
+/// UTF-8 input yields valid UTF-8 of the same length. `out` must be at least
+/// as long as `input`.
+#[inline]
+pub fn ascii_lower(input: &[u8], out: &mut [u8]) {
+    shift_range_simd(input, out, b'A', b'Z', 0x20);
+}
+
+/// `ascii_upper` appending to `out`.
+#[inline]
+pub fn ascii_to_upper_simd(input: &[u8], out: &mut Vec<u8>) {
+    let start = out.len();
+    out.resize(start + input.len(), 0);
+    ascii_upper(input, &mut out[start..]);
+}
+
+/// `ascii_lower` appending to `out`.
+#[inline]
+pub fn ascii_to_lower_simd(input: &[u8], out: &mut Vec<u8>) {
+    let start = out.len();
+    out.resize(start + input.len(), 0);
+    ascii_lower(input, &mut out[start..]);
+}
+
+/// `ascii_upper` rewriting `bytes` in place.
+#[inline]
+pub fn ascii_upper_inplace(bytes: &mut [u8]) {
+    shift_range_inplace(bytes, b'a', b'z', 0u8.wrapping_sub(0x20));
+}
+
+/// `ascii_lower` rewriting `bytes` in place.
+#[inline]
+pub fn ascii_lower_inplace(bytes: &mut [u8]) {
+    shift_range_inplace(bytes, b'A', b'Z', 0x20);
+}
+
+/// Swaps the case of the ASCII letters of `input` into `out`.
+///
+/// Letters differ from their other case only in bit 0x20, which is flipped
+/// with a 0/1 lane flag; every other byte passes through, so valid UTF-8
+/// input yields valid UTF-8 of the same length. `out` must be at least as
+/// long as `input`.
+pub fn ascii_swapcase(input: &[u8], out: &mut [u8]) {
+    assert!(out.len() >= input.len(), "output buffer too small");
+
+    let one = U8s::splat(1);
+    let width = U8s::splat(26);
+    let case_bit = U8s::splat(0x20);
+
+    let mut i = 0;
+    while i + LANES_U8 <= input.len() {
+        let chunk = U8s::from_slice(&input[i..i + LANES_U8]);
+        // Folding to lowercase first leaves one range to test
+        let folded = chunk | case_bit;
+        let is_letter = width
+            .saturating_sub(folded - U8s::splat(b'a'))
+            .simd_min(one);
+        (chunk ^ (is_letter * case_bit)).copy_to_slice(&mut out[i..i + LANES_U8]);
+        i += LANES_U8;
+    }
+    for (dst, &b) in out[i..].iter_mut().zip(&input[i..]) {
+        *dst = if b.is_ascii_alphabetic() { b ^ 0x20 } else { b };
+    }
+}
+
+/// Rotates the ASCII letters of `input` by `n` places into `out` (Caesar
+/// cipher; `n = 13` is ROT13).
+///
+/// Case is kept and every other byte passes through, so valid UTF-8 input
+/// yields valid UTF-8 of the same length. `out` must be at least as long as
+/// `input`.
+pub fn caesar_shift(input: &[u8], out: &mut [u8], n: u8) {
+    assert!(out.len() >= input.len(), "output buffer too small");
+    let n = n % 26;
+
+    let mut i = 0;
+    while i + LANES_U8 <= input.len() {
+        let chunk = U8s::from_slice(&input[i..i + LANES_U8]);
+        rotate_letters_simd(chunk, n).copy_to_slice(&mut out[i..i + LANES_U8]);
+        i += LANES_U8;
+    }
+    for (dst, &b) in out[i..].iter_mut().zip(&input[i..]) {
+        *dst = rotate_letter_scalar(b, n);
+    }
+}
+
+/// `caesar_shift` rewriting `bytes` in place.
+pub fn caesar_shift_inplace(bytes: &mut [u8], n: u8) {
+    let n = n % 26;
+
+    let mut chunks = bytes.chunks_exact_mut(LANES_U8);
+    for chunk in &mut chunks {
+        rotate_letters_simd(U8s::from_slice(chunk), n).copy_to_slice(chunk);
+    }
+    for b in chunks.into_remainder() {
+        *b = rotate_letter_scalar(*b, n);
+    }
+}
+
+/// Maps every byte of `input`, all ASCII, through `lut` into `out`.
+///
+/// The table is split into vector-wide pieces, each looked up with a
+/// dynamic swizzle; the piece a byte falls in is picked by its high bits.
+/// `out` must be at least as long as `input`.
+pub fn translate_ascii(input: &[u8], out: &mut [u8], lut: &[u8; 128]) {
+    assert!(out.len() >= input.len(), "output buffer too small");
+    debug_assert!(input.is_ascii());
+
+    let tables: [U8s; 128 / LANES_U8] =
+        core::array::from_fn(|k| U8s::from_slice(&lut[k * LANES_U8..(k + 1) * LANES_U8]));
+    let low_mask = U8s::splat((LANES_U8 - 1) as u8);
+    let high_shift = U8s::splat(LANES_U8.trailing_zeros() as u8);
+
+    let mut i = 0;
+    while i + LANES_U8 <= input.len() {
+        let chunk = U8s::from_slice(&input[i..i + LANES_U8]);
+        let low = chunk & low_mask;
+        let high = chunk >> high_shift;
+        let mut mapped = U8s::splat(0);
+        for (k, table) in tables.iter().enumerate() {
+            mapped = high
+                .simd_eq(U8s::splat(k as u8))
+                .select(table.swizzle_dyn(low), mapped);
+        }
+        mapped.copy_to_slice(&mut out[i..i + LANES_U8]);
+        i += LANES_U8;
+    }
+    for (dst, &b) in out[i..].iter_mut().zip(&input[i..]) {
+        *dst = lut[b as usize];
+    }
+}
+
+/// Position of the first `needle` byte in `haystack`, like `memchr`.
+///
+/// Whole vectors are compared at once; the tail shorter than one vector is
+/// scanned byte by byte.
+pub fn find_byte(haystack: &[u8], needle: u8) -> Option<usize> {
+    let needle_v = U8s::splat(needle);
+
+    let mut chunks = haystack.chunks_exact(LANES_U8);
+    for (n, chunk) in (&mut chunks).enumerate() {
+        let mask = U8s::from_slice(chunk).simd_eq(needle_v).to_bitmask();
+        if mask != 0 {
+            return Some(n * LANES_U8 + mask.trailing_zeros() as usize);
+        }
+    }
+    let tail = chunks.remainder();
+    tail.iter()
+        .position(|&b| b == needle)
+        .map(|i| haystack.len() - tail.len() + i)
+}
+
+/// Position of the first byte of `haystack` other than `byte`, the length of
+/// the run of `byte` it starts with.
+///
+/// Whole vectors are compared at once, the first mismatch found from the
+/// inverted compare bitmask; the tail is scanned byte by byte.
+pub fn find_not_byte(haystack: &[u8], byte: u8) -> Option<usize> {
+    let byte_v = U8s::splat(byte);
+
+    let mut chunks = haystack.chunks_exact(LANES_U8);
+    for (n, chunk) in (&mut chunks).enumerate() {
+        let mask = U8s::from_slice(chunk).simd_ne(byte_v).to_bitmask();
+        if mask != 0 {
+            return Some(n * LANES_U8 + mask.trailing_zeros() as usize);
+        }
+    }
+    let tail = chunks.remainder();
+    tail.iter()
+        .position(|&b| b != byte)
+        .map(|i| haystack.len() - tail.len() + i)
+}
+
+/// Position of the last byte of `haystack` other than `byte`, scanning
+/// vectors back from the end as `find_not_byte` does from the start.
+pub fn rfind_not_byte(haystack: &[u8], byte: u8) -> Option<usize> {
+    let byte_v = U8s::splat(byte);
+
+    let mut chunks = haystack.rchunks_exact(LANES_U8);
+    for (n, chunk) in (&mut chunks).enumerate() {
+        let mask = U8s::from_slice(chunk).simd_ne(byte_v).to_bitmask();
+        if mask != 0 {
+            // Lane k is bit k of the 64-bit mask
+            let last = 63 - mask.leading_zeros() as usize;
+            return Some(haystack.len() - (n + 1) * LANES_U8 + last);
+        }
+    }
+    chunks.remainder().iter().rposition(|&b| b != byte)
+}
+
+/// Number of `needle` bytes in `haystack`.
+///
+/// Each vector adds the popcount of its compare bitmask; the tail shorter
+/// than one vector is counted byte by byte.
+pub fn count_byte(haystack: &[u8], needle: u8) -> usize {
+    let needle_v = U8s::splat(needle);
+
+    let mut chunks = haystack.chunks_exact(LANES_U8);
+    let mut count = 0;
+    for chunk in &mut chunks {
+        count += U8s::from_slice(chunk)
+            .simd_eq(needle_v)
+            .to_bitmask()
+            .count_ones() as usize;
+    }
+    count + chunks.remainder().iter().filter(|&&b| b == needle).count()
+}
+
+/// True if every byte of `input` falls in one of the inclusive `ranges`.
+///
+/// Each range is one wrapping subtraction and compare per vector. With ASCII
+/// ranges, any byte of a multi-byte UTF-8 sequence fails. True for an empty
+/// `input`.
+pub fn all_in_ranges(input: &[u8], ranges: &[(u8, u8)]) -> bool {
+    let mut chunks = input.chunks_exact(LANES_U8);
+    for chunk in &mut chunks {
+        let chunk = U8s::from_slice(chunk);
+        let inside = ranges
+            .iter()
+            .map(|&(lo, hi)| (chunk - U8s::splat(lo)).simd_le(U8s::splat(hi - lo)))
+            .reduce(|a, b| a | b);
+        if !inside.is_some_and(|inside| inside.all()) {
+            return false;
+        }
+    }
+    chunks
+        .remainder()
+        .iter()
+        .all(|b| ranges.iter().any(|&(lo, hi)| (lo..=hi).contains(b)))
+}
+
+/// Byte ranges of the fields of `input` separated by runs of bytes in the
+/// inclusive `ranges`, like `str.split()` with no separator: runs count as
+/// one separator and no empty fields are produced, at either end included.
+///
+/// Each vector is classified into a bitmask of separator bytes, and field
+/// boundaries are found at its set and clear bits, so long fields and long
+/// runs cost one compare per range per vector. With ASCII ranges, bytes of
+/// multi-byte UTF-8 sequences are never separators and every field is a
+/// valid `str` boundary.
+pub fn split_on_ranges(input: &[u8], ranges: &[(u8, u8)]) -> Vec<core::ops::Range<usize>> {
+    let is_separator = |b: u8| ranges.iter().any(|&(lo, hi)| (lo..=hi).contains(&b));
+    let lanes = if LANES_U8 == 64 {
+        u64::MAX
+    } else {
+        (1u64 << LANES_U8) - 1
+    };
+    let mut fields = Vec::new();
+    let mut start = None;
+
+    let mut chunks = input.chunks_exact(LANES_U8);
+    for (n, chunk) in (&mut chunks).enumerate() {
+        let chunk = U8s::from_slice(chunk);
+        let separators = ranges
+            .iter()
+            .map(|&(lo, hi)| (chunk - U8s::splat(lo)).simd_le(U8s::splat(hi - lo)))
+            .fold(0, |mask, inside| mask | inside.to_bitmask());
+        let base = n * LANES_U8;
+        let mut pos = 0;
+        // Alternate between the next separator, ending the current field,
+        // and the next other byte, starting one
+        while pos < LANES_U8 {
+            let wanted = if start.is_some() {
+                separators
+            } else {
+                !separators & lanes
+            };
+            let next = wanted & (u64::MAX << pos);
+            if next == 0 {
+                break;
+            }
+            pos = next.trailing_zeros() as usize;
+            match start.take() {
+                Some(s) => fields.push(s..base + pos),
+                None => start = Some(base + pos),
+            }
+        }
+    }
+
+    let base = input.len() - chunks.remainder().len();
+    for (k, &b) in chunks.remainder().iter().enumerate() {
+        match (start, is_separator(b)) {
+            (Some(s), true) => {
+                fields.push(s..base + k);
+                start = None;
+            }
+            (None, false) => start = Some(base + k),
+            _ => {}
+        }
+    }
+    if let Some(s) = start {
+        fields.push(s..input.len());
+    }
+    fields
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn upper(input: &str) -> String {
+        let mut out = vec![0; input.len()];
+        ascii_upper(input.as_bytes(), &mut out);
+        String::from_utf8(out).unwrap()
+    }
+
+    fn lower(input: &str) -> String {
+        let mut out = vec![0; input.len()];
+        ascii_lower(input.as_bytes(), &mut out);
+        String::from_utf8(out).unwrap()
+    }
+
+    #[test]
+    fn ascii_case_empty() {
+        assert_eq!(upper(""), "");
+        assert_eq!(lower(""), "");
+    }
+
+    #[test]
+    fn ascii_case_matches_std() {
+        let text: String = (0u8..0x80).map(char::from).collect::<String>().repeat(5);
+        assert_eq!(upper(&text), text.to_ascii_uppercase());
+        assert_eq!(lower(&text), text.to_ascii_lowercase());
+    }
+
+    #[test]
+    fn ascii_case_leaves_non_ascii_untouched() {
+        let text = "straße ÄÖÜ äöü café ǅ ﬁ 😀 mixed Case ".repeat(20);
+        assert_eq!(upper(&text), text.to_ascii_uppercase());
+        assert_eq!(lower(&text), text.to_ascii_lowercase());
+        assert!(upper(&text).contains("STRAßE ÄÖÜ äöü CAFé"));
+    }
+
+    #[test]
+    fn ascii_case_every_byte() {
+        let bytes: Vec<u8> = (0..=255u8).cycle().take(1000).collect();
+        let mut out = vec![0; bytes.len()];
+        ascii_upper(&bytes, &mut out);
+        let expected: Vec<u8> = bytes.iter().map(u8::to_ascii_uppercase).collect();
+        assert_eq!(out, expected);
+        ascii_lower(&bytes, &mut out);
+        let expected: Vec<u8> = bytes.iter().map(u8::to_ascii_lowercase).collect();
+        assert_eq!(out, expected);
+    }
+
+    #[test]
+    fn ascii_case_variants_match_std() {
+        // Every length around a few vectors, after a prefix already in `out`
+        let bytes: Vec<u8> = (0..=255u8)
+            .cycle()
+            .skip(50)
+            .take(4 * LANES_U8 + 7)
+            .collect();
+        for len in 0..bytes.len() {
+            let input = &bytes[..len];
+            let upper = input.to_ascii_uppercase();
+            let lower = input.to_ascii_lowercase();
+
+            let mut out = b"prefix".to_vec();
+            ascii_to_upper_simd(input, &mut out);
+            assert_eq!(out[6..], upper);
+            out.truncate(6);
+            ascii_to_lower_simd(input, &mut out);
+            assert_eq!((&out[..6], &out[6..]), (&b"prefix"[..], &lower[..]));
+
+            let mut inplace = input.to_vec();
+            ascii_upper_inplace(&mut inplace);
+            assert_eq!(inplace, upper);
+            ascii_lower_inplace(&mut inplace);
+            assert_eq!(inplace, lower);
+        }
+    }
+
+    #[test]
+    fn translate_ascii_every_byte() {
+        let input: Vec<u8> = (0..0x80u8).cycle().take(1000).collect();
+        let lut: [u8; 128] = core::array::from_fn(|b| (b as u8).wrapping_mul(37) % 0x80);
+        for start in [0, 1, 17, 100] {
+            let mut out = vec![0; input.len() - start];
+            translate_ascii(&input[start..], &mut out, &lut);
+            let expected: Vec<u8> = input[start..].iter().map(|&b| lut[b as usize]).collect();
+            assert_eq!(out, expected, "from {start}");
+        }
+    }
+
+    #[test]
+    fn find_byte_matches_position() {
+        let bytes: Vec<u8> = (0..=255u8).cycle().take(1000).collect();
+        for needle in [0, b'\t', b'a', 0x80, 0xFF] {
+            for start in [0, 1, 31, 32, 63, 64, 100, 990] {
+                let hay = &bytes[start..];
+                let expected = hay.iter().position(|&b| b == needle);
+                assert_eq!(
+                    find_byte(hay, needle),
+                    expected,
+                    "needle {needle} from {start}"
+                );
+            }
+        }
+        assert_eq!(find_byte(b"", b'\t'), None);
+        assert_eq!(find_byte(&[b'x'; 200], b'\t'), None);
+    }
+
+    #[test]
+    fn not_byte_scans_match_scalar() {
+        for len in 0..3 * LANES_U8 + 5 {
+            for inner in 0..=len.min(2 * LANES_U8 + 2) {
+                // A run of `inner` other bytes, padded on both sides
+                for pad in [0, 1, LANES_U8 - 1, LANES_U8, len] {
+                    let pad = pad.min(len - inner);
+                    let mut bytes = vec![b' '; len];
+                    bytes[pad..pad + inner].fill(b'x');
+                    if inner > 2 {
+                        bytes[pad + 1] = b' ';
+                    }
+                    let first = bytes.iter().position(|&b| b != b' ');
+                    let last = bytes.iter().rposition(|&b| b != b' ');
+                    assert_eq!(find_not_byte(&bytes, b' '), first, "{len} {inner} {pad}");
+                    assert_eq!(rfind_not_byte(&bytes, b' '), last, "{len} {inner} {pad}");
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn count_byte_matches_scalar() {
+        let mut state = 0x2545_F491_4F6C_DD1Du64;
+        let bytes: Vec<u8> = (0..1000)
+            .map(|_| {
+                state ^= state << 13;
+                state ^= state >> 7;
+                state ^= state << 17;
+                // Few distinct values, so every needle occurs often
+                b",;\n\xC3\xA9\0"[(state % 6) as usize]
+            })
+            .collect();
+        for len in (0..bytes.len())
+            .step_by(7)
+            .chain([LANES_U8 - 1, LANES_U8, LANES_U8 + 1])
+        {
+            let haystack = &bytes[..len];
+            for needle in [b',', b';', b'\n', 0, 0xC3, 0xA9, b'x'] {
+                let expected = haystack.iter().filter(|&&b| b == needle).count();
+                assert_eq!(count_byte(haystack, needle), expected, "{len} {needle}");
+            }
+        }
+        // Matches only in the tail
+        let mut tail = vec![b'a'; 2 * LANES_U8 + 3];
+        tail[2 * LANES_U8 + 1] = b',';
+        assert_eq!(count_byte(&tail, b','), 1);
+    }
+
+    #[test]
+    fn ascii_swapcase_every_byte() {
+        let bytes: Vec<u8> = (0..=255u8).cycle().take(1000).collect();
+        let mut out = vec![0; bytes.len()];
+        ascii_swapcase(&bytes, &mut out);
+        let expected: Vec<u8> = bytes
+            .iter()
+            .map(|&b| match b {
+                b'a'..=b'z' => b.to_ascii_uppercase(),
+                b'A'..=b'Z' => b.to_ascii_lowercase(),
+                _ => b,
+            })
+            .collect();
+        assert_eq!(out, expected);
+    }
+
+    fn caesar_reference(bytes: &[u8], n: u8) -> Vec<u8> {
+        bytes
+            .iter()
+            .map(|&b| match b {
+                b'a'..=b'z' => b'a' + (b - b'a' + n % 26) % 26,
+                b'A'..=b'Z' => b'A' + (b - b'A' + n % 26) % 26,
+                _ => b,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn caesar_shift_every_byte() {
+        let bytes: Vec<u8> = (0..=255u8).cycle().take(1000).collect();
+        for n in [0, 1, 3, 13, 25, 26, 27, 255] {
+            let mut out = vec![0; bytes.len()];
+            caesar_shift(&bytes, &mut out, n);
+            assert_eq!(out, caesar_reference(&bytes, n), "n = {n}");
+
+            let mut inplace = bytes.clone();
+            caesar_shift_inplace(&mut inplace, n);
+            assert_eq!(inplace, out, "n = {n}");
+        }
+    }
+
+    #[test]
+    fn rot13_roundtrip() {
+        let text = "Hello, World! Why did the chicken cross the road? ÄÖÜ 😀 ".repeat(20);
+        let mut once = vec![0; text.len()];
+        caesar_shift(text.as_bytes(), &mut once, 13);
+        assert!(once.starts_with(b"Uryyb, Jbeyq!"));
+        caesar_shift_inplace(&mut once, 13);
+        assert_eq!(once, text.as_bytes());
+    }
+
+    #[test]
+    fn split_on_ranges_matches_scalar() {
+        let ranges = [(b'\t', b'\r'), (0x1c, b' ')];
+        let mut state = 0x2545_f491_4f6c_dd1du64;
+        for len in [0, 1, 2, 15, 16, 31, 63, 64, 65, 127, 128, 200, 1000] {
+            for density in [0, 1, 4, 16, 64] {
+                let bytes: Vec<u8> = (0..len)
+                    .map(|_| {
+                        state ^= state << 13;
+                        state ^= state >> 7;
+                        state ^= state << 17;
+                        if density > 0 && state % 64 < density {
+                            [b' ', b'\t', b'\n', 0x1f][(state >> 8) as usize % 4]
+                        } else {
+                            b'a' + (state >> 8) as u8 % 26
+                        }
+                    })
+                    .collect();
+                let got: Vec<&[u8]> = split_on_ranges(&bytes, &ranges)
+                    .into_iter()
+                    .map(|r| &bytes[r])
+                    .collect();
+                let expected: Vec<&[u8]> = bytes
+                    .split(|b| ranges.iter().any(|&(lo, hi)| (lo..=hi).contains(b)))
+                    .filter(|field| !field.is_empty())
+                    .collect();
+                assert_eq!(got, expected, "len {len}, density {density}");
+            }
+        }
+        assert!(split_on_ranges(&[b' '; 200], &ranges).is_empty());
+        assert_eq!(split_on_ranges(&[b'x'; 200], &ranges), vec![0..200]);
+    }
+
+    #[test]
+    fn all_in_ranges_every_byte() {
+        let ranges = [(b'0', b'9'), (0x1c, b' '), (0xf0, 0xff)];
+        let inside = |b: u8| ranges.iter().any(|&(lo, hi)| (lo..=hi).contains(&b));
+        for b in 0..=255u8 {
+            for len in [1, 15, 16, 31, 64, 100] {
+                let mut bytes = vec![b'5'; len];
+                assert!(all_in_ranges(&bytes, &ranges));
+                bytes[len / 2] = b;
+                assert_eq!(
+                    all_in_ranges(&bytes, &ranges),
+                    inside(b),
+                    "byte {b:#x}, len {len}"
+                );
+            }
+        }
+        assert!(all_in_ranges(b"", &ranges));
+        assert!(!all_in_ranges(b"5", &[]));
+    }
+}
@@ -0,0 +1,226 @@
+//! Runtime selection of the vector width used by the hot transcoding kernels.
+//!
+//! Wheels are built for a baseline CPU, so the compile-time `target_feature`
+//! lane selection in `simd/mod.rs` would leave AVX2/AVX-512 unused. Kernels
+//! that matter for throughput are compiled once per width and picked here
+//! from the features detected when the module is first used.
+
+use std::sync::OnceLock;
+
+/// Instruction set a kernel instantiation is compiled for, widest last.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum SimdLevel {
+    /// The compile-time lane widths: 128-bit on a generic build (SSE2,
+    /// NEON, WASM-SIMD), wider if the binary was built with extra features.
+    Baseline,
+    /// 256-bit vectors.
+    Avx2,
+    /// 512-bit vectors.
+    Avx512,
+}
+
+static LEVEL: OnceLock<SimdLevel> = OnceLock::new();
+static VBMI2: OnceLock<bool> = OnceLock::new();
+
+impl SimdLevel {
+    /// Widest level supported by the running CPU.
+    pub fn detect() -> Self {
+        #[cfg(target_arch = "x86_64")]
+        {
+            if is_x86_feature_detected!("avx512f") && is_x86_feature_detected!("avx512bw") {
+                return Self::Avx512;
+            }
+            if is_x86_feature_detected!("avx2") {
+                return Self::Avx2;
+            }
+        }
+        Self::Baseline
+    }
+
+    /// Level used by the dispatching kernels, detected once per process.
+    pub fn current() -> Self {
+        *LEVEL.get_or_init(Self::detect)
+    }
+
+    /// Every level the running CPU can execute, narrowest first.
+    pub fn available() -> Vec<Self> {
+        [Self::Baseline, Self::Avx2, Self::Avx512]
+            .into_iter()
+            .filter(|&level| level <= Self::detect())
+            .collect()
+    }
+
+    /// Short name for diagnostics.
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Avx512 => "avx512",
+            Self::Avx2 => "avx2",
+            Self::Baseline => baseline_name(),
+        }
+    }
+}
+
+fn baseline_name() -> &'static str {
+    if cfg!(all(target_arch = "x86_64", target_feature = "avx512bw")) {
+        "avx512 (compile-time)"
+    } else if cfg!(all(target_arch = "x86_64", target_feature = "avx2")) {
+        "avx2 (compile-time)"
+    } else if cfg!(target_arch = "x86_64") {
+        "sse2"
+    } else if cfg!(target_arch = "aarch64") {
+        "neon"
+    } else if cfg!(all(target_arch = "wasm32", target_feature = "simd128")) {
+        "wasm-simd128"
+    } else {
+        "portable"
+    }
+}
+
+/// Whether kernels with a variant built on the AVX-512 VBMI2 byte compress
+/// instruction may use it at the `Avx512` level, detected once per process.
+pub fn has_avx512_vbmi2() -> bool {
+    *VBMI2.get_or_init(|| {
+        #[cfg(target_arch = "x86_64")]
+        {
+            SimdLevel::detect() == SimdLevel::Avx512 && is_x86_feature_detected!("avx512vbmi2")
+        }
+        #[cfg(not(target_arch = "x86_64"))]
+        {
+            false
+        }
+    })
+}
+
+/// Name of the implementation the transcoding kernels dispatch to.
+pub fn simd_implementation() -> &'static str {
+    SimdLevel::current().name()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn picks_widest_available() {
+        let level = SimdLevel::current();
+        assert_eq!(level, SimdLevel::detect());
+        assert_eq!(SimdLevel::available().last(), Some(&level));
+
+        #[cfg(target_arch = "x86_64")]
+        {
+            let avx512 =
+                is_x86_feature_detected!("avx512f") && is_x86_feature_detected!("avx512bw");
+            let avx2 = is_x86_feature_detected!("avx2");
+            let expected = if avx512 {
+                SimdLevel::Avx512
+            } else if avx2 {
+                SimdLevel::Avx2
+            } else {
+                SimdLevel::Baseline
+            };
+            assert_eq!(level, expected);
+        }
+    }
+
+    #[test]
+    fn levels_produce_identical_output() {
+        let text: String = "ascii é ж 中 😀 mixed ".repeat(40);
+        let ucs1: Vec<u8> = (0..=255u8).cycle().take(1000).collect();
+        let ucs2: Vec<u16> = text
+            .encode_utf16()
+            .filter(|w| !(0xD800..0xE000).contains(w))
+            .collect();
+        let ucs4: Vec<u32> = text.chars().map(|c| c as u32).collect();
+        let bump = bumpalo::Bump::new();
+
+        for level in SimdLevel::available() {
+            unsafe {
+                let expected: String = ucs1.iter().map(|&b| b as char).collect();
+                assert_eq!(
+                    crate::simd::ucs1::ucs1_to_utf8_bump_at(level, &ucs1, &bump),
+                    expected
+                );
+                let utf16: Vec<u16> = text.encode_utf16().collect();
+                let mut decoded = vec![0u16; utf16.len()];
+                let len =
+                    crate::simd::ucs2::utf8_to_ucs2_simd_at(level, text.as_bytes(), &mut decoded);
+                assert_eq!(&decoded[..len], &utf16[..]);
+                let expected: Vec<u32> = text.chars().map(|c| c as u32).collect();
+                let mut decoded = vec![0u32; expected.len()];
+                let len =
+                    crate::simd::ucs4::utf8_to_ucs4_simd_at(level, text.as_bytes(), &mut decoded);
+                assert_eq!(&decoded[..len], &expected[..]);
+                let latin1: String = ucs1.iter().map(|&b| b as char).collect();
+                let ascii = "plain ascii text ".repeat(20) + &latin1;
+                let expected: Vec<u8> = ascii.chars().map(|c| c as u8).collect();
+                let mut decoded = vec![0u8; expected.len()];
+                let len =
+                    crate::simd::ucs1::utf8_to_ucs1_simd_at(level, ascii.as_bytes(), &mut decoded);
+                assert_eq!(&decoded[..len], &expected[..]);
+                let expected = String::from_utf16(&ucs2).unwrap();
+                assert_eq!(
+                    crate::simd::ucs2::ucs2_to_utf8_bump_at(level, &ucs2, &bump),
+                    expected
+                );
+                assert_eq!(
+                    crate::simd::ucs4::ucs4_to_utf8_bump_at(level, &ucs4, &bump),
+                    text
+                );
+                assert_eq!(
+                    crate::simd::analyze_utf8_simd_at(level, text.as_bytes()),
+                    crate::simd::analyze_utf8_scalar(text.as_bytes()),
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn prefetching_inputs_convert_to_the_end() {
+        use crate::simd::{PREFETCH_DISTANCE, PREFETCH_MIN_BYTES};
+
+        // Non-ASCII only within the prefetch distance of the end, and lengths
+        // that leave a partial chunk behind
+        let text: String = "é ж 中 😀 ".repeat(PREFETCH_DISTANCE / 8);
+        let bump = bumpalo::Bump::new();
+        for extra in [0, 7] {
+            let ucs1: Vec<u8> = std::iter::repeat_n(b'a', PREFETCH_MIN_BYTES - 200 + extra)
+                .chain(std::iter::repeat_n(0xE9, 200))
+                .collect();
+            let expected1: String = ucs1.iter().map(|&b| b as char).collect();
+
+            let pad = "a".repeat(PREFETCH_MIN_BYTES / 2 + extra);
+            let expected2: String = pad.clone() + &text.replace('😀', "中");
+            let ucs2: Vec<u16> = expected2.encode_utf16().collect();
+
+            let pad = "a".repeat(PREFETCH_MIN_BYTES / 4 + extra);
+            let expected4: String = pad + &text;
+            let ucs4: Vec<u32> = expected4.chars().map(|c| c as u32).collect();
+
+            assert_eq!(crate::simd::ucs1_to_utf8(&ucs1), expected1);
+            assert_eq!(crate::simd::ucs2_to_utf8(&ucs2), expected2.as_bytes());
+            assert_eq!(crate::simd::ucs4_to_utf8(&ucs4), expected4.as_bytes());
+            for level in SimdLevel::available() {
+                unsafe {
+                    assert_eq!(
+                        crate::simd::ucs1::ucs1_to_utf8_bump_at(level, &ucs1, &bump),
+                        expected1
+                    );
+                    assert_eq!(
+                        crate::simd::ucs2::ucs2_to_utf8_bump_at(level, &ucs2, &bump),
+                        expected2
+                    );
+                    assert_eq!(
+                        crate::simd::ucs4::ucs4_to_utf8_bump_at(level, &ucs4, &bump),
+                        expected4
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn available_starts_at_baseline() {
+        assert_eq!(SimdLevel::available()[0], SimdLevel::Baseline);
+        assert!(!simd_implementation().is_empty());
+    }
+}
@@ -3,21 +3,41 @@
 //! This module provides branch-free codecs for converting between Python's internal
 //! fixed-width string formats (UCS-1, UCS-2, UCS-4) and UTF-8. It uses the
 //! portable SIMD API (`core::simd`) to compile for AVX2/AVX-512 on x86-64,
-//! NEON on Apple M-series, and WASM-SIMD.
+//! NEON on Apple M-series, and WASM-SIMD. On x86-64 the hot kernels are also
+//! compiled for AVX2/AVX-512 and selected at runtime, see `dispatch`.
 
 #![allow(dead_code)]
 
+use core::mem;
+use core::simd::Simd;
 use core::simd::cmp::{SimdPartialEq, SimdPartialOrd};
 use core::simd::prelude::SimdUint;
-use core::simd::{LaneCount, Simd, SupportedLaneCount};
+use core::sync::atomic::{AtomicUsize, Ordering};
+use pyo3::ffi as pyo3_ffi;
+use pyo3::{Py, PyAny};
 
+pub mod ascii;
+pub mod dispatch;
+pub mod tune;
 pub mod ucs1;
 pub mod ucs2;
 pub mod ucs4;
-
+pub mod utf8;
+
+pub use ascii::{
+    all_in_ranges, ascii_lower, ascii_lower_inplace, ascii_swapcase, ascii_to_lower_simd,
+    ascii_to_upper_simd, ascii_upper, ascii_upper_inplace, caesar_shift, caesar_shift_inplace,
+    count_byte, find_byte, find_not_byte, rfind_not_byte, split_on_ranges, translate_ascii,
+};
+pub use dispatch::{SimdLevel, simd_implementation};
+pub use tune::{
+    DEFAULT_SIMD_THRESHOLDS, SimdThresholds, autotune_simd_thresholds, set_simd_thresholds,
+    simd_thresholds,
+};
 pub use ucs1::{ucs1_to_utf8, ucs1_to_utf8_bump, utf8_to_ucs1_simd};
 pub use ucs2::{ucs2_to_utf8, ucs2_to_utf8_bump, utf8_to_ucs2_simd};
 pub use ucs4::{ucs4_to_utf8, ucs4_to_utf8_bump, utf8_to_ucs4_simd};
+pub use utf8::{Utf8ErrorPos, validate_utf8_simd};
 
 // ========================================================================== //
 //                        SIMD Lane-Width Selection                         //
@@ -113,14 +133,24 @@ pub(crate) const LANES_U32: usize = 4;
 //                         Performance Thresholds                             //
 // ========================================================================== //
 
+// Read at the entry of every kernel; set with `set_simd_thresholds` or
+// measured for the running CPU with `autotune_simd_thresholds`
+
 /// Minimum input size (in bytes) to prefer SIMD for UTF-8 analysis and decoding.
-pub(crate) const SIMD_THRESHOLD_BYTES: usize = 64;
+pub(crate) static SIMD_THRESHOLD_BYTES: AtomicUsize =
+    AtomicUsize::new(DEFAULT_SIMD_THRESHOLDS.bytes);
 /// Minimum input size (in code units) to prefer SIMD for UCS-1 -> UTF-8.
-pub(crate) const SIMD_THRESHOLD_UCS1: usize = 96;
+pub(crate) static SIMD_THRESHOLD_UCS1: AtomicUsize = AtomicUsize::new(DEFAULT_SIMD_THRESHOLDS.ucs1);
 /// Minimum input size (in code units) to prefer SIMD for UCS-2 -> UTF-8.
-pub(crate) const SIMD_THRESHOLD_UCS2: usize = 48;
+pub(crate) static SIMD_THRESHOLD_UCS2: AtomicUsize = AtomicUsize::new(DEFAULT_SIMD_THRESHOLDS.ucs2);
 /// Minimum input size (in code units) to prefer SIMD for UCS-4 -> UTF-8.
-pub(crate) const SIMD_THRESHOLD_UCS4: usize = 32;
+pub(crate) static SIMD_THRESHOLD_UCS4: AtomicUsize = AtomicUsize::new(DEFAULT_SIMD_THRESHOLDS.ucs4);
+/// Minimum input size (in bytes) to prefetch ahead in the UCS -> UTF-8 loops.
+/// Smaller inputs are served well enough by the hardware prefetcher.
+pub(crate) const PREFETCH_MIN_BYTES: usize = 1 << 20;
+/// How far ahead of the current chunk (in bytes) the UCS -> UTF-8 loops
+/// prefetch.
+pub(crate) const PREFETCH_DISTANCE: usize = 1024;
 
 // ========================================================================== //
 //                           SIMD Helper Functions                            //
@@ -149,19 +179,16 @@ pub(crate) fn split_u8x32(v: Simd<u8, 32>) -> (Simd<u8, 16>, Simd<u8, 16>) {
 
 /// Extracts the bytes from a SIMD vector into an array.
 #[inline(always)]
-pub(crate) fn simd_to_bytes<const N: usize>(v: Simd<u8, N>) -> [u8; N]
-where
-    LaneCount<N>: SupportedLaneCount,
-{
+pub(crate) fn simd_to_bytes<const N: usize>(v: Simd<u8, N>) -> [u8; N] {
     v.to_array()
 }
 
 /// Extracts the low byte of each `u16` lane, assuming ASCII content.
+///
+/// Narrows the lanes by value, so unlike reinterpreting the vector's memory
+/// as bytes it gives the same result on big-endian targets.
 #[inline(always)]
-pub(crate) fn simd_u16_to_ascii_bytes<const N: usize>(v: Simd<u16, N>) -> [u8; N]
-where
-    LaneCount<N>: SupportedLaneCount,
-{
+pub(crate) fn simd_u16_to_ascii_bytes<const N: usize>(v: Simd<u16, N>) -> [u8; N] {
     let mut result = [0u8; N];
     let array = v.to_array();
     for i in 0..N {
@@ -172,10 +199,7 @@ where
 
 /// Extracts the low byte of each `u32` lane, assuming ASCII content.
 #[inline(always)]
-pub(crate) fn simd_u32_to_ascii_bytes<const N: usize>(v: Simd<u32, N>) -> [u8; N]
-where
-    LaneCount<N>: SupportedLaneCount,
-{
+pub(crate) fn simd_u32_to_ascii_bytes<const N: usize>(v: Simd<u32, N>) -> [u8; N] {
     let mut result = [0u8; N];
     let array = v.to_array();
     for i in 0..N {
@@ -188,6 +212,62 @@ where
 //                          Shared Helper Routines                            //
 // ========================================================================== //
 
+/// Whether the UCS -> UTF-8 loops prefetch ahead over `input`: only for
+/// multi-megabyte inputs, on targets with a prefetch instruction.
+#[inline(always)]
+pub(crate) fn wants_prefetch<T>(input: &[T]) -> bool {
+    cfg!(any(target_arch = "x86_64", target_arch = "aarch64"))
+        && mem::size_of_val(input) >= PREFETCH_MIN_BYTES
+}
+
+/// Hints the CPU to load the cache line `PREFETCH_DISTANCE` bytes past
+/// `input[i]`. Does nothing once that is past the end of `input`.
+#[inline(always)]
+pub(crate) fn prefetch_ahead<T>(input: &[T], i: usize) {
+    let ahead = i + PREFETCH_DISTANCE / mem::size_of::<T>();
+    if ahead >= input.len() {
+        return;
+    }
+    let ptr = unsafe { input.as_ptr().add(ahead) } as *const i8;
+    #[cfg(target_arch = "x86_64")]
+    unsafe {
+        use core::arch::x86_64::{_MM_HINT_T0, _mm_prefetch};
+        _mm_prefetch::<_MM_HINT_T0>(ptr);
+    }
+    #[cfg(target_arch = "aarch64")]
+    unsafe {
+        core::arch::asm!(
+            "prfm pldl1keep, [{0}]",
+            in(reg) ptr,
+            options(nostack, readonly, preserves_flags)
+        );
+    }
+    #[cfg(not(any(target_arch = "x86_64", target_arch = "aarch64")))]
+    let _ = ptr;
+}
+
+/// UTF-8 form of U+FFFD, which lone surrogates of Python strings are read
+/// as: they have no UTF-8 form of their own, and text handed to Rust must be
+/// valid UTF-8. Strings a function leaves unchanged are returned as the
+/// input object, surrogates included.
+pub(crate) const REPLACEMENT_UTF8: [u8; 3] = [0xEF, 0xBF, 0xBD];
+
+/// Decodes the character starting at `input[i]` as `(codepoint, length)`, or
+/// `None` if the bytes there aren't a valid UTF-8 sequence.
+#[inline]
+pub(crate) fn decode_utf8_at(input: &[u8], i: usize) -> Option<(u32, usize)> {
+    let len = match input[i] {
+        0x00..=0x7F => 1,
+        0xC0..=0xDF => 2,
+        0xE0..=0xEF => 3,
+        0xF0..=0xF7 => 4,
+        _ => return None,
+    };
+    let bytes = input.get(i..i + len)?;
+    let ch = core::str::from_utf8(bytes).ok()?.chars().next()?;
+    Some((ch as u32, len))
+}
+
 #[inline(always)]
 pub(crate) fn push_utf8_4_bump(cp: u32, out: &mut bumpalo::collections::Vec<u8>) {
     out.extend_from_slice(&[
@@ -250,32 +330,64 @@ fn analyze_utf8_scalar(input: &[u8]) -> (usize, u32) {
 /// SIMD overhead. For longer inputs, it processes the data in chunks,
 /// using a fast path for pure ASCII blocks.
 pub fn analyze_utf8_simd(input: &[u8]) -> (usize, u32) {
-    if input.len() < SIMD_THRESHOLD_BYTES {
+    unsafe { analyze_utf8_simd_at(SimdLevel::current(), input) }
+}
+
+/// `analyze_utf8_simd` using the kernel compiled for `level`.
+///
+/// # Safety
+///
+/// The running CPU must support `level`, see `SimdLevel::available`.
+pub(crate) unsafe fn analyze_utf8_simd_at(level: SimdLevel, input: &[u8]) -> (usize, u32) {
+    if input.len() < SIMD_THRESHOLD_BYTES.load(Ordering::Relaxed) {
         return analyze_utf8_scalar(input);
     }
 
+    match level {
+        #[cfg(target_arch = "x86_64")]
+        SimdLevel::Avx512 => analyze_utf8_simd_avx512(input),
+        #[cfg(target_arch = "x86_64")]
+        SimdLevel::Avx2 => analyze_utf8_simd_avx2(input),
+        _ => analyze_utf8_lanes::<LANES_U8>(input),
+    }
+}
+
+#[cfg(target_arch = "x86_64")]
+#[target_feature(enable = "avx2")]
+unsafe fn analyze_utf8_simd_avx2(input: &[u8]) -> (usize, u32) {
+    analyze_utf8_lanes::<32>(input)
+}
+
+#[cfg(target_arch = "x86_64")]
+#[target_feature(enable = "avx512f,avx512bw")]
+unsafe fn analyze_utf8_simd_avx512(input: &[u8]) -> (usize, u32) {
+    analyze_utf8_lanes::<64>(input)
+}
+
+#[inline(always)]
+fn analyze_utf8_lanes<const N: usize>(input: &[u8]) -> (usize, u32) {
     let mut char_count = 0usize;
     let mut max_codepoint = 0u32;
     let mut i = 0;
 
     // SIMD loop
-    while i + LANES_U8 <= input.len() {
-        let chunk = U8s::from_slice(&input[i..i + LANES_U8]);
+    while i + N <= input.len() {
+        let chunk = Simd::<u8, N>::from_slice(&input[i..i + N]);
 
         // Fast path for pure ASCII blocks.
-        let ascii_mask = chunk.simd_lt(U8s::splat(0x80));
+        let ascii_mask = chunk.simd_lt(Simd::<u8, N>::splat(0x80));
         if ascii_mask.all() {
-            char_count += LANES_U8;
+            char_count += N;
             max_codepoint = max_codepoint.max(chunk.reduce_max() as u32);
-            i += LANES_U8;
+            i += N;
             continue;
         }
 
         // In mixed-content blocks, count characters by identifying all non-continuation
         // bytes. A UTF-8 continuation byte has the form `10xxxxxx`, so any byte
         // where `(byte & 0xC0) != 0x80` marks the start of a new character.
-        let continuation_mask = chunk & U8s::splat(0xC0);
-        let is_start_byte = continuation_mask.simd_ne(U8s::splat(0x80));
+        let continuation_mask = chunk & Simd::<u8, N>::splat(0xC0);
+        let is_start_byte = continuation_mask.simd_ne(Simd::<u8, N>::splat(0x80));
         char_count += is_start_byte.to_bitmask().count_ones() as usize;
 
         // To find the max codepoint, we take the max ASCII value from the chunk
@@ -284,7 +396,7 @@ pub fn analyze_utf8_simd(input: &[u8]) -> (usize, u32) {
         max_codepoint = max_codepoint.max(max_ascii_in_chunk as u32);
 
         let bitmask = is_start_byte.to_bitmask();
-        for k in 0..LANES_U8 {
+        for k in 0..N {
             if (bitmask >> k) & 1 != 0 {
                 let byte = input[i + k];
                 if byte >= 0xC0 {
@@ -304,7 +416,7 @@ pub fn analyze_utf8_simd(input: &[u8]) -> (usize, u32) {
                 }
             }
         }
-        i += LANES_U8;
+        i += N;
     }
 
     // Handle the remainder with the scalar routine.
@@ -315,19 +427,59 @@ pub fn analyze_utf8_simd(input: &[u8]) -> (usize, u32) {
     (char_count, max_codepoint)
 }
 
+/// Borrows the buffer of a pure-ASCII Python string as `&str`.
+///
+/// ASCII strings are stored one byte per character, which is already valid
+/// UTF-8, so no conversion is needed. Returns `None` for any other object.
+///
+/// The result points into the string object itself, so it borrows the `Py`
+/// handle keeping it alive. Safe without the GIL: str contents are immutable.
+#[inline]
+pub fn borrow_ascii_pystring(o: &Py<PyAny>) -> Option<&str> {
+    let o = o.as_ptr();
+    unsafe {
+        if pyo3_ffi::PyUnicode_Check(o) == 0 || pyo3_ffi::PyUnicode_IS_ASCII(o) == 0 {
+            return None;
+        }
+        let len = pyo3_ffi::PyUnicode_GET_LENGTH(o) as usize;
+        let data = pyo3_ffi::PyUnicode_DATA(o) as *const u8;
+        Some(core::str::from_utf8_unchecked(std::slice::from_raw_parts(
+            data, len,
+        )))
+    }
+}
+
 /// Converts a Python string object to a UTF-8 string slice in a `bumpalo` arena.
 ///
 /// This function inspects the internal representation of a `PyObject` and dispatches
 /// to the appropriate UCS-1, UCS-2, or UCS-4 to UTF-8 conversion routine.
 ///
-/// # Safety
+/// The result lives in `bump` unless the string is pure ASCII: then it is a
+/// borrow of the object's own buffer (see `ucs1_to_utf8_bump`). Both the arena
+/// and the `Py` handle must therefore outlive it, which the shared `'a`
+/// enforces:
 ///
-/// The caller must ensure the `PyObject` pointer is valid, non-null, and points
-/// to a Python unicode object. The GIL must also be held.
-pub fn convert_pystring<'a>(o: *mut pyo3::ffi::PyObject, bump: &'a bumpalo::Bump) -> &'a str {
+/// ```compile_fail
+/// # use pyo3::prelude::*;
+/// # fn f(o: Py<PyAny>, bump: &bumpalo::Bump) {
+/// let s = yurki::simd::convert_pystring(&o, bump);
+/// drop(o); // `s` may point into `o`
+/// println!("{s}");
+/// # }
+/// ```
+///
+/// Lone surrogates, which have no UTF-8 form, are read as U+FFFD (see
+/// `REPLACEMENT_UTF8`).
+///
+/// Safe without the GIL: the handle keeps the string alive and str contents
+/// are immutable.
+///
+/// # Panics
+///
+/// If `o` is not a Python `str`.
+pub fn convert_pystring<'a>(o: &'a Py<PyAny>, bump: &'a bumpalo::Bump) -> &'a str {
+    let o = o.as_ptr();
     unsafe {
-        use pyo3::ffi as pyo3_ffi;
-        assert!(!o.is_null());
         assert!(pyo3_ffi::PyUnicode_Check(o) != 0);
         if pyo3_ffi::PyUnicode_READY(o) != 0 {
             panic!("PyUnicode_READY failed");
@@ -356,3 +508,50 @@ pub fn convert_pystring<'a>(o: *mut pyo3::ffi::PyObject, bump: &'a bumpalo::Bump
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Endianness: the wide paths narrow lanes by value and store packed
+    // UTF-8 with `to_le`, so these checks mean the same on either byte order
+
+    #[test]
+    fn ascii_bytes_take_the_low_byte_of_each_lane() {
+        // High bytes set too, which a byte reinterpretation would pick up on
+        // one byte order or the other
+        let wide: Vec<u32> = (0..256u32).map(|i| i * 0x0101_0101 ^ 0xA500).collect();
+        for chunk in wide.chunks_exact(16) {
+            let low: Vec<u8> = chunk.iter().map(|&w| w.to_le_bytes()[0]).collect();
+            let narrow: Vec<u16> = chunk.iter().map(|&w| w as u16).collect();
+            assert_eq!(
+                simd_u32_to_ascii_bytes(Simd::<u32, 16>::from_slice(chunk)),
+                *low
+            );
+            assert_eq!(
+                simd_u16_to_ascii_bytes(Simd::<u16, 16>::from_slice(&narrow)),
+                *low
+            );
+        }
+    }
+
+    #[test]
+    fn wide_ascii_runs_match_scalar() {
+        // Every length up to a few vectors, so the SIMD ASCII path, the
+        // tail and the switch to packed multi-byte stores all run
+        let bump = bumpalo::Bump::new();
+        for len in 0..200 {
+            for suffix in ["", "é", "中", "😀"] {
+                let text = "a".repeat(len) + suffix + &"b".repeat(len % 67);
+                let ucs2: Vec<u16> = text.encode_utf16().collect();
+                let ucs4: Vec<u32> = text.chars().map(|c| c as u32).collect();
+                if suffix != "😀" {
+                    assert_eq!(ucs2_to_utf8(&ucs2), text.as_bytes());
+                    assert_eq!(ucs2_to_utf8_bump(&ucs2, &bump), text.as_str());
+                }
+                assert_eq!(ucs4_to_utf8(&ucs4), text.as_bytes());
+                assert_eq!(ucs4_to_utf8_bump(&ucs4, &bump), text.as_str());
+            }
+        }
+    }
+}
@@ -0,0 +1,147 @@
+//! Runtime tuning of the input sizes from which the kernels use SIMD.
+//!
+//! Below its threshold a kernel runs the scalar routine, which wins on short
+//! strings where the vector setup and tail cost more than they save. The
+//! crossover depends on the CPU: the defaults were measured on x86-64 with
+//! AVX-512, and `autotune_simd_thresholds` measures it on the running one.
+
+use crate::simd::{
+    SIMD_THRESHOLD_BYTES, SIMD_THRESHOLD_UCS1, SIMD_THRESHOLD_UCS2, SIMD_THRESHOLD_UCS4,
+    analyze_utf8_simd, ucs1_to_utf8_bump, ucs2_to_utf8_bump, ucs4_to_utf8_bump,
+};
+use bumpalo::Bump;
+use core::sync::atomic::{AtomicUsize, Ordering};
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+/// Input sizes from which the kernels use SIMD: `bytes` for UTF-8 analysis,
+/// decoding and validation, in bytes, and the others for UCS-1/2/4 to
+/// UTF-8 encoding, in code units.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SimdThresholds {
+    pub bytes: usize,
+    pub ucs1: usize,
+    pub ucs2: usize,
+    pub ucs4: usize,
+}
+
+pub const DEFAULT_SIMD_THRESHOLDS: SimdThresholds = SimdThresholds {
+    bytes: 64,
+    ucs1: 96,
+    ucs2: 48,
+    ucs4: 32,
+};
+
+pub fn simd_thresholds() -> SimdThresholds {
+    SimdThresholds {
+        bytes: SIMD_THRESHOLD_BYTES.load(Ordering::Relaxed),
+        ucs1: SIMD_THRESHOLD_UCS1.load(Ordering::Relaxed),
+        ucs2: SIMD_THRESHOLD_UCS2.load(Ordering::Relaxed),
+        ucs4: SIMD_THRESHOLD_UCS4.load(Ordering::Relaxed),
+    }
+}
+
+/// Set the thresholds, from the next kernel call on. Any value is correct,
+/// 0 always using SIMD and `usize::MAX` never.
+pub fn set_simd_thresholds(thresholds: SimdThresholds) {
+    SIMD_THRESHOLD_BYTES.store(thresholds.bytes, Ordering::Relaxed);
+    SIMD_THRESHOLD_UCS1.store(thresholds.ucs1, Ordering::Relaxed);
+    SIMD_THRESHOLD_UCS2.store(thresholds.ucs2, Ordering::Relaxed);
+    SIMD_THRESHOLD_UCS4.store(thresholds.ucs4, Ordering::Relaxed);
+}
+
+// Sizes tried as thresholds, in code units
+const CANDIDATES: [usize; 10] = [8, 16, 24, 32, 48, 64, 96, 128, 192, 256];
+// Calls per measurement, and measurements per size and path keeping the fastest
+const CALLS: usize = 200;
+const ROUNDS: usize = 3;
+
+/// Time both paths of the kernel `convert` runs on `len` code units, with
+/// `threshold` forced to always and to never use SIMD.
+fn time_paths(
+    threshold: &AtomicUsize,
+    len: usize,
+    convert: &mut impl FnMut(usize, &Bump),
+) -> (Duration, Duration) {
+    let mut bump = Bump::new();
+    let mut time = |forced: usize| {
+        threshold.store(forced, Ordering::Relaxed);
+        (0..ROUNDS)
+            .map(|_| {
+                let start = Instant::now();
+                for _ in 0..CALLS {
+                    convert(len, &bump);
+                }
+                let elapsed = start.elapsed();
+                bump.reset();
+                elapsed
+            })
+            .min()
+            .unwrap_or_default()
+    };
+    (time(0), time(usize::MAX))
+}
+
+/// Smallest candidate from which SIMD is faster at every larger candidate,
+/// stored into `threshold`. If the scalar routine still wins at the largest,
+/// the measurement is taken as noise and the previous value kept.
+fn calibrate(threshold: &AtomicUsize, mut convert: impl FnMut(usize, &Bump)) -> usize {
+    let previous = threshold.load(Ordering::Relaxed);
+    let mut crossover = None;
+    for &len in CANDIDATES.iter().rev() {
+        let (simd, scalar) = time_paths(threshold, len, &mut convert);
+        if simd >= scalar {
+            break;
+        }
+        crossover = Some(len);
+    }
+    let chosen = crossover.unwrap_or(previous);
+    threshold.store(chosen, Ordering::Relaxed);
+    chosen
+}
+
+/// Measure where SIMD starts to pay off on the running CPU, over synthetic
+/// mixed-script strings, and use the results. Takes a fraction of a
+/// second; results may still vary between runs on a loaded machine.
+pub fn autotune_simd_thresholds() -> SimdThresholds {
+    let longest = CANDIDATES[CANDIDATES.len() - 1];
+    let sample = "Grüße, café — Привет, мир! 中文 text 😀 ".repeat(longest);
+    let latin1: Vec<u8> = "Le café où Zoë a lu « Ça déjà » über Straße. "
+        .chars()
+        .map(|c| c as u8)
+        .cycle()
+        .take(longest)
+        .collect();
+    let ucs2: Vec<u16> = sample
+        .chars()
+        .filter(|&c| c <= '\u{FFFF}')
+        .map(|c| c as u16)
+        .collect();
+    let ucs4: Vec<u32> = sample.chars().map(|c| c as u32).collect();
+    let utf8 = sample.as_bytes();
+
+    let bytes = calibrate(&SIMD_THRESHOLD_BYTES, |len, _| {
+        // Whole characters only
+        let len = (0..=len)
+            .rev()
+            .find(|&at| sample.is_char_boundary(at))
+            .unwrap_or(0);
+        black_box(analyze_utf8_simd(black_box(&utf8[..len])));
+    });
+    let ucs1 = calibrate(&SIMD_THRESHOLD_UCS1, |len, bump| {
+        black_box(ucs1_to_utf8_bump(black_box(&latin1[..len]), bump));
+    });
+    let ucs2 = calibrate(&SIMD_THRESHOLD_UCS2, |len, bump| {
+        black_box(ucs2_to_utf8_bump(black_box(&ucs2[..len]), bump));
+    });
+    let ucs4 = calibrate(&SIMD_THRESHOLD_UCS4, |len, bump| {
+        black_box(ucs4_to_utf8_bump(black_box(&ucs4[..len]), bump));
+    });
+
+    SimdThresholds {
+        bytes,
+        ucs1,
+        ucs2,
+        ucs4,
+    }
+}
@@ -1,7 +1,13 @@
 //! UCS1 (Latin-1) ↔ UTF-8 conversions
 
-use crate::simd::{LANES_U8, SIMD_THRESHOLD_BYTES, SIMD_THRESHOLD_UCS1, U8s};
+use crate::simd::SimdLevel;
+use crate::simd::dispatch::has_avx512_vbmi2;
+use crate::simd::{
+    LANES_U8, SIMD_THRESHOLD_BYTES, SIMD_THRESHOLD_UCS1, U8s, prefetch_ahead, wants_prefetch,
+};
 use core::simd::cmp::SimdPartialOrd;
+use core::simd::{Select, Simd};
+use core::sync::atomic::Ordering;
 use std::borrow::Cow;
 
 // ========================================================================== //
@@ -111,10 +117,148 @@ fn utf8_to_ucs1_scalar(input: &[u8], output: &mut [u8]) -> usize {
 //                      UCS-1 (Latin-1) to UTF-8                              //
 // ========================================================================== //
 
+/// Shuffle patterns compacting 8 interleaved (lead, trail) byte pairs into
+/// UTF-8, indexed by the mask of the lanes that are ASCII: those keep only
+/// their lead byte, the byte itself. Unused positions are left at 0.
+static PAIR_COMPACT: [[u8; 16]; 256] = pair_compact_patterns();
+
+const fn pair_compact_patterns() -> [[u8; 16]; 256] {
+    let mut table = [[0u8; 16]; 256];
+    let mut mask = 0;
+    while mask < 256 {
+        let mut out = 0;
+        let mut lane = 0;
+        while lane < 8 {
+            table[mask][out] = 2 * lane as u8;
+            out += 1;
+            if (mask >> lane) & 1 == 0 {
+                table[mask][out] = 2 * lane as u8 + 1;
+                out += 1;
+            }
+            lane += 1;
+        }
+        mask += 1;
+    }
+    table
+}
+
+/// `bytes.swizzle_dyn(idx)` for the 16-byte shuffles of `expand_latin1_lanes`.
+///
+/// `swizzle_dyn` only becomes `pshufb` when the whole crate is built for
+/// SSSE3, not inside the kernels enabling AVX2 or AVX-512, so those pass
+/// `SSSE3` to use the instruction directly.
+#[inline(always)]
+fn shuffle_16<const SSSE3: bool>(bytes: Simd<u8, 16>, idx: Simd<u8, 16>) -> Simd<u8, 16> {
+    #[cfg(target_arch = "x86_64")]
+    if SSSE3 {
+        // All indices are below 16, where `pshufb` and `swizzle_dyn` agree
+        return unsafe { core::arch::x86_64::_mm_shuffle_epi8(bytes.into(), idx.into()).into() };
+    }
+    bytes.swizzle_dyn(idx)
+}
+
+/// Encodes a chunk of Latin-1 bytes as UTF-8 without a branch per byte.
+///
+/// Every lane is given a lead and a trail byte, the lead of ASCII lanes
+/// being the byte itself, and the two vectors are interleaved into pairs.
+/// Each group of 8 pairs is then compacted with one shuffle from
+/// `PAIR_COMPACT`, dropping the trail bytes of ASCII lanes, and stored
+/// whole; the next group overwrites the unused end. Returns the number of
+/// bytes written.
+///
+/// # Safety
+///
+/// `dst` must be valid for `2 * N` bytes of writes. `N` must be a multiple
+/// of 16. With `SSSE3`, the running CPU must support it.
+#[inline(always)]
+unsafe fn expand_latin1_lanes<const N: usize, const SSSE3: bool>(
+    chunk: Simd<u8, N>,
+    dst: *mut u8,
+) -> usize {
+    let is_ascii = chunk.simd_lt(Simd::splat(0x80));
+    let lead = is_ascii.select(chunk, (chunk >> 6) | Simd::splat(0xC0));
+    let trail = (chunk & Simd::splat(0x3F)) | Simd::splat(0x80);
+    let (front, back) = lead.interleave(trail);
+    let pairs = [front.to_array(), back.to_array()];
+    let ascii = is_ascii.to_bitmask();
+
+    let mut written = 0;
+    for group in 0..N / 8 {
+        let at = 16 * group;
+        let mask = (ascii >> (8 * group)) as u8;
+        let bytes = Simd::<u8, 16>::from_slice(&pairs[at / N][at % N..at % N + 16]);
+        let packed = shuffle_16::<SSSE3>(bytes, Simd::from_array(PAIR_COMPACT[mask as usize]));
+        unsafe {
+            dst.add(written)
+                .cast::<[u8; 16]>()
+                .write_unaligned(packed.to_array())
+        };
+        written += 16 - mask.count_ones() as usize;
+    }
+    written
+}
+
+/// Encodes 64 Latin-1 bytes as UTF-8 with the AVX-512 VBMI2 byte compress.
+///
+/// Each half of the chunk is widened to 16-bit lanes holding the lead byte
+/// low and the trail byte high, the lead of ASCII lanes being the byte
+/// itself and its trail zero. `vpcompressb` then keeps every lead and the
+/// non-zero trails, packing the half into one store in place of the
+/// per-group shuffles of `expand_latin1_lanes`. Returns the number of bytes
+/// written.
+///
+/// # Safety
+///
+/// `dst` must be valid for 128 bytes of writes, and the running CPU must
+/// support AVX-512 BW and VBMI2.
+#[cfg(target_arch = "x86_64")]
+#[target_feature(enable = "avx512f,avx512bw,avx512vbmi2")]
+#[inline]
+unsafe fn expand_latin1_vbmi2(chunk: &[u8; 64], dst: *mut u8) -> usize {
+    use core::arch::x86_64::*;
+
+    let mut written = 0;
+    for half in 0..2 {
+        let bytes = unsafe { _mm256_loadu_si256(chunk.as_ptr().add(32 * half).cast()) };
+        let wide = _mm512_cvtepu8_epi16(bytes);
+        let ascii = _mm512_cmplt_epu16_mask(wide, _mm512_set1_epi16(0x80));
+        let lead = _mm512_or_si512(_mm512_srli_epi16::<6>(wide), _mm512_set1_epi16(0xC0));
+        let trail = _mm512_slli_epi16::<8>(_mm512_or_si512(
+            _mm512_and_si512(wide, _mm512_set1_epi16(0x3F)),
+            _mm512_set1_epi16(0x80),
+        ));
+        let pairs = _mm512_mask_mov_epi16(_mm512_or_si512(lead, trail), ascii, wide);
+        // Every lead, and the trails that are there
+        let keep = _mm512_test_epi8_mask(pairs, pairs) | 0x5555_5555_5555_5555;
+        let packed = _mm512_maskz_compress_epi8(keep, pairs);
+        unsafe { _mm512_storeu_si512(dst.add(written).cast(), packed) };
+        written += keep.count_ones() as usize;
+    }
+    written
+}
+
+/// `expand_latin1_vbmi2` with `VBMI2`, else `expand_latin1_lanes`.
+///
+/// # Safety
+///
+/// As for the kernel selected; with `VBMI2`, `N` must be 64.
+#[inline(always)]
+unsafe fn expand_latin1<const N: usize, const SSSE3: bool, const VBMI2: bool>(
+    chunk: Simd<u8, N>,
+    dst: *mut u8,
+) -> usize {
+    #[cfg(target_arch = "x86_64")]
+    if VBMI2 {
+        return unsafe { expand_latin1_vbmi2(chunk.as_array()[..].try_into().unwrap(), dst) };
+    }
+    unsafe { expand_latin1_lanes::<N, SSSE3>(chunk, dst) }
+}
+
 /// Converts a UCS-1 (Latin-1) slice to a UTF-8 string in a `bumpalo` arena.
 ///
 /// This function uses SIMD for performance on larger inputs.
 /// - For pure ASCII input, it returns a borrowed `&str` without allocation.
+///   The result then points into `input`, not the arena.
 /// - For mixed ASCII/Latin-1, it returns a `&str` allocated in the arena.
 ///
 /// The implementation processes chunks of the input using SIMD vectors. If a
@@ -122,18 +266,74 @@ fn utf8_to_ucs1_scalar(input: &[u8], output: &mut [u8]) -> usize {
 /// bytes, they are expanded into their 2-byte UTF-8 representation.
 #[inline]
 pub fn ucs1_to_utf8_bump<'a>(input: &'a [u8], bump: &'a bumpalo::Bump) -> &'a str {
+    unsafe { ucs1_to_utf8_bump_at(SimdLevel::current(), input, bump) }
+}
+
+/// `ucs1_to_utf8_bump` using the kernel compiled for `level`.
+///
+/// # Safety
+///
+/// The running CPU must support `level`, see `SimdLevel::available`.
+#[inline]
+pub(crate) unsafe fn ucs1_to_utf8_bump_at<'a>(
+    level: SimdLevel,
+    input: &'a [u8],
+    bump: &'a bumpalo::Bump,
+) -> &'a str {
     // Use scalar for short strings to avoid SIMD overhead
-    if input.len() < SIMD_THRESHOLD_UCS1 {
+    if input.len() < SIMD_THRESHOLD_UCS1.load(Ordering::Relaxed) {
         return ucs1_to_utf8_scalar_bump(input, bump);
     }
 
+    match level {
+        #[cfg(target_arch = "x86_64")]
+        SimdLevel::Avx512 if has_avx512_vbmi2() => ucs1_to_utf8_bump_vbmi2(input, bump),
+        #[cfg(target_arch = "x86_64")]
+        SimdLevel::Avx512 => ucs1_to_utf8_bump_avx512(input, bump),
+        #[cfg(target_arch = "x86_64")]
+        SimdLevel::Avx2 => ucs1_to_utf8_bump_avx2(input, bump),
+        _ => ucs1_to_utf8_bump_lanes::<LANES_U8, false, false>(input, bump),
+    }
+}
+
+#[cfg(target_arch = "x86_64")]
+#[target_feature(enable = "avx2")]
+unsafe fn ucs1_to_utf8_bump_avx2<'a>(input: &'a [u8], bump: &'a bumpalo::Bump) -> &'a str {
+    ucs1_to_utf8_bump_lanes::<32, true, false>(input, bump)
+}
+
+#[cfg(target_arch = "x86_64")]
+#[target_feature(enable = "avx512f,avx512bw")]
+unsafe fn ucs1_to_utf8_bump_avx512<'a>(input: &'a [u8], bump: &'a bumpalo::Bump) -> &'a str {
+    ucs1_to_utf8_bump_lanes::<64, true, false>(input, bump)
+}
+
+#[cfg(target_arch = "x86_64")]
+#[target_feature(enable = "avx512f,avx512bw,avx512vbmi2")]
+unsafe fn ucs1_to_utf8_bump_vbmi2<'a>(input: &'a [u8], bump: &'a bumpalo::Bump) -> &'a str {
+    ucs1_to_utf8_bump_lanes::<64, true, true>(input, bump)
+}
+
+/// With `VBMI2`, `N` must be 64 and the running CPU must support
+/// `expand_latin1_vbmi2`.
+#[inline(always)]
+fn ucs1_to_utf8_bump_lanes<'a, const N: usize, const SSSE3: bool, const VBMI2: bool>(
+    input: &'a [u8],
+    bump: &'a bumpalo::Bump,
+) -> &'a str {
+    let prefetch = wants_prefetch(input);
+
     /* 1. All-ASCII detection (vector + scalar tail) */
-    if input
-        .chunks_exact(LANES_U8)
-        .all(|c| U8s::from_slice(c).simd_lt(U8s::splat(0x80)).all())
-        && input[input.len() - input.len() % LANES_U8..]
-            .iter()
-            .all(|&b| b < 0x80)
+    if input.chunks_exact(N).enumerate().all(|(k, c)| {
+        if prefetch {
+            prefetch_ahead(input, k * N);
+        }
+        Simd::<u8, N>::from_slice(c)
+            .simd_lt(Simd::<u8, N>::splat(0x80))
+            .all()
+    }) && input[input.len() - input.len() % N..]
+        .iter()
+        .all(|&b| b < 0x80)
     {
         return unsafe { core::str::from_utf8_unchecked(input) };
     }
@@ -143,27 +343,26 @@ pub fn ucs1_to_utf8_bump<'a>(input: &'a [u8], bump: &'a bumpalo::Bump) -> &'a st
     let mut i = 0;
 
     /* 3. SIMD loop */
-    while i + LANES_U8 <= input.len() {
-        let chunk = U8s::from_slice(&input[i..i + LANES_U8]);
-        let is_ascii = chunk.simd_lt(U8s::splat(0x80));
+    while i + N <= input.len() {
+        if prefetch {
+            prefetch_ahead(input, i);
+        }
+        let chunk = Simd::<u8, N>::from_slice(&input[i..i + N]);
+        let is_ascii = chunk.simd_lt(Simd::<u8, N>::splat(0x80));
 
         if is_ascii.all() {
             out.extend_from_slice(chunk.as_array());
         } else {
-            // Hybrid SIMD-scalar expansion for mixed content
-            let high_bytes = (chunk >> 6) | U8s::splat(0xC0);
-            let low_bytes = (chunk & U8s::splat(0x3F)) | U8s::splat(0x80);
-
-            for j in 0..LANES_U8 {
-                if is_ascii.test(j) {
-                    out.push(chunk[j]);
-                } else {
-                    out.push(high_bytes[j]);
-                    out.push(low_bytes[j]);
-                }
+            // Branch-free expansion for mixed content
+            out.reserve(2 * N);
+            unsafe {
+                let len = out.len();
+                let dst = out.as_mut_ptr().add(len);
+                let written = expand_latin1::<N, SSSE3, VBMI2>(chunk, dst);
+                out.set_len(len + written);
             }
         }
-        i += LANES_U8;
+        i += N;
     }
 
     /* 4. Scalar tail */
@@ -191,44 +390,53 @@ pub fn ucs1_to_utf8_bump<'a>(input: &'a [u8], bump: &'a bumpalo::Bump) -> &'a st
 #[inline]
 pub fn ucs1_to_utf8<'a>(input: &'a [u8]) -> Cow<'a, str> {
     // Use scalar for short strings to avoid SIMD overhead
-    if input.len() < SIMD_THRESHOLD_UCS1 {
+    if input.len() < SIMD_THRESHOLD_UCS1.load(Ordering::Relaxed) {
         return ucs1_to_utf8_scalar(input);
     }
 
+    let prefetch = wants_prefetch(input);
+
     /* 1. All-ASCII detection (vector + scalar tail) */
-    if input
-        .chunks_exact(LANES_U8)
-        .all(|c| U8s::from_slice(c).simd_lt(U8s::splat(0x80)).all())
-        && input[input.len() - input.len() % LANES_U8..]
-            .iter()
-            .all(|&b| b < 0x80)
+    if input.chunks_exact(LANES_U8).enumerate().all(|(k, c)| {
+        if prefetch {
+            prefetch_ahead(input, k * LANES_U8);
+        }
+        U8s::from_slice(c).simd_lt(U8s::splat(0x80)).all()
+    }) && input[input.len() - input.len() % LANES_U8..]
+        .iter()
+        .all(|&b| b < 0x80)
     {
         return Cow::Borrowed(unsafe { core::str::from_utf8_unchecked(input) });
     }
 
     /* 2. Over-allocate and convert in a single pass */
     let mut out: Vec<u8> = Vec::with_capacity(input.len() * 2);
+    let ssse3 = SimdLevel::current() != SimdLevel::Baseline;
     let mut i = 0;
 
     /* 3. SIMD loop */
     while i + LANES_U8 <= input.len() {
+        if prefetch {
+            prefetch_ahead(input, i);
+        }
         let chunk = U8s::from_slice(&input[i..i + LANES_U8]);
         let is_ascii = chunk.simd_lt(U8s::splat(0x80));
 
         if is_ascii.all() {
             out.extend_from_slice(chunk.as_array());
         } else {
-            // Hybrid SIMD-scalar expansion for mixed content
-            let high_bytes = (chunk >> 6) | U8s::splat(0xC0);
-            let low_bytes = (chunk & U8s::splat(0x3F)) | U8s::splat(0x80);
-
-            for j in 0..LANES_U8 {
-                if is_ascii.test(j) {
-                    out.push(chunk[j]);
+            // Branch-free expansion for mixed content
+            out.reserve(2 * LANES_U8);
+            unsafe {
+                let len = out.len();
+                let dst = out.as_mut_ptr().add(len);
+                // Every level above the baseline implies SSSE3
+                let written = if ssse3 {
+                    expand_latin1_lanes::<LANES_U8, true>(chunk, dst)
                 } else {
-                    out.push(high_bytes[j]);
-                    out.push(low_bytes[j]);
-                }
+                    expand_latin1_lanes::<LANES_U8, false>(chunk, dst)
+                };
+                out.set_len(len + written);
             }
         }
         i += LANES_U8;
@@ -254,24 +462,61 @@ pub fn ucs1_to_utf8<'a>(input: &'a [u8]) -> Cow<'a, str> {
 /// directly. For chunks containing multi-byte characters, it falls back to a
 /// scalar routine.
 pub fn utf8_to_ucs1_simd(input: &[u8], output: &mut [u8]) -> usize {
+    unsafe { utf8_to_ucs1_simd_at(SimdLevel::current(), input, output) }
+}
+
+/// `utf8_to_ucs1_simd` using the kernel compiled for `level`.
+///
+/// # Safety
+///
+/// The running CPU must support `level`, see `SimdLevel::available`.
+#[inline]
+pub(crate) unsafe fn utf8_to_ucs1_simd_at(
+    level: SimdLevel,
+    input: &[u8],
+    output: &mut [u8],
+) -> usize {
     // Use scalar for short strings to avoid SIMD overhead
-    if input.len() < SIMD_THRESHOLD_BYTES {
+    if input.len() < SIMD_THRESHOLD_BYTES.load(Ordering::Relaxed) {
         return utf8_to_ucs1_scalar(input, output);
     }
 
+    match level {
+        #[cfg(target_arch = "x86_64")]
+        SimdLevel::Avx512 => utf8_to_ucs1_avx512(input, output),
+        #[cfg(target_arch = "x86_64")]
+        SimdLevel::Avx2 => utf8_to_ucs1_avx2(input, output),
+        _ => utf8_to_ucs1_lanes::<LANES_U8>(input, output),
+    }
+}
+
+#[cfg(target_arch = "x86_64")]
+#[target_feature(enable = "avx2")]
+unsafe fn utf8_to_ucs1_avx2(input: &[u8], output: &mut [u8]) -> usize {
+    utf8_to_ucs1_lanes::<32>(input, output)
+}
+
+#[cfg(target_arch = "x86_64")]
+#[target_feature(enable = "avx512f,avx512bw")]
+unsafe fn utf8_to_ucs1_avx512(input: &[u8], output: &mut [u8]) -> usize {
+    utf8_to_ucs1_lanes::<64>(input, output)
+}
+
+#[inline(always)]
+fn utf8_to_ucs1_lanes<const N: usize>(input: &[u8], output: &mut [u8]) -> usize {
     let mut out_pos = 0;
     let mut i = 0;
 
     // SIMD ASCII fast path
-    while i + LANES_U8 <= input.len() && out_pos + LANES_U8 <= output.len() {
-        let chunk = &input[i..i + LANES_U8];
-        let v = U8s::from_slice(chunk);
+    while i + N <= input.len() && out_pos + N <= output.len() {
+        let chunk = &input[i..i + N];
+        let v = Simd::<u8, N>::from_slice(chunk);
 
-        if v.simd_lt(U8s::splat(0x80)).all() {
+        if v.simd_lt(Simd::splat(0x80)).all() {
             // Pure ASCII - direct copy
-            output[out_pos..out_pos + LANES_U8].copy_from_slice(chunk);
-            out_pos += LANES_U8;
-            i += LANES_U8;
+            output[out_pos..out_pos + N].copy_from_slice(chunk);
+            out_pos += N;
+            i += N;
         } else {
             break; // Exit SIMD loop for mixed content
         }
@@ -347,6 +592,20 @@ mod tests {
         assert_eq!(ucs1_to_utf8_bump(&b, &bump), "Héllö");
     }
 
+    #[test]
+    fn ucs1_bump_borrows_ascii_input() {
+        let bump = bumpalo::Bump::new();
+        for len in [5, 1000] {
+            let ascii = vec![b'a'; len];
+            let result = ucs1_to_utf8_bump(&ascii, &bump);
+            assert_eq!(result.as_ptr(), ascii.as_ptr());
+
+            let latin1 = vec![0xE9; len];
+            let result = ucs1_to_utf8_bump(&latin1, &bump);
+            assert!(!latin1.as_ptr_range().contains(&result.as_ptr()));
+        }
+    }
+
     #[test]
     fn ucs1_mixed_content() {
         let mixed = b"Hello \xE9\xE8\xEA world \xFF!";
@@ -412,6 +671,104 @@ mod tests {
         }
     }
 
+    #[test]
+    fn ucs1_every_byte_in_every_lane() {
+        // Each byte at every position of a chunk, among ASCII only and among
+        // scattered accents, so every shuffle group and pattern side is hit
+        for b in 0..=255u8 {
+            for background in [b'a', 0xE9] {
+                for pos in 0..128 {
+                    let mut input: Vec<u8> = (0..200)
+                        .map(|k| if k % 7 == 3 { background } else { b'x' })
+                        .collect();
+                    input[pos] = b;
+                    let expected = ucs1_to_utf8_scalar(&input);
+                    assert_eq!(ucs1_to_utf8(&input), expected, "byte {b:#x} at {pos}");
+                    for level in SimdLevel::available() {
+                        let bump = bumpalo::Bump::new();
+                        let got = unsafe { ucs1_to_utf8_bump_at(level, &input, &bump) };
+                        assert_eq!(got, expected, "{level:?}: byte {b:#x} at {pos}");
+                    }
+                }
+            }
+        }
+    }
+
+    /// The VBMI2 kernel's output for one chunk against the portable
+    /// `expand_latin1_lanes`, which is the reference.
+    #[cfg(target_arch = "x86_64")]
+    fn check_vbmi2_chunk(chunk: &[u8; 64]) {
+        let mut want = [0u8; 128];
+        let mut got = [0u8; 128];
+        let (want_len, got_len) = unsafe {
+            (
+                expand_latin1_lanes::<64, false>(Simd::from_array(*chunk), want.as_mut_ptr()),
+                expand_latin1_vbmi2(chunk, got.as_mut_ptr()),
+            )
+        };
+        assert_eq!(got[..got_len], want[..want_len], "{chunk:x?}");
+    }
+
+    #[test]
+    #[cfg(target_arch = "x86_64")]
+    fn vbmi2_matches_portable_over_every_alphabet() {
+        if !has_avx512_vbmi2() {
+            return;
+        }
+        // The whole alphabet in order, then every two-byte alphabet in a
+        // different mix per pair, so each pair of values meets in each half
+        for start in (0..256).step_by(64) {
+            check_vbmi2_chunk(&core::array::from_fn(|k| (start + k) as u8));
+        }
+        let mut state = 0x2545_F491_4F6C_DD1Du64;
+        for a in 0..=255u8 {
+            for b in a..=255u8 {
+                state ^= state << 13;
+                state ^= state >> 7;
+                state ^= state << 17;
+                check_vbmi2_chunk(&core::array::from_fn(|k| {
+                    if state >> k & 1 == 1 { b } else { a }
+                }));
+            }
+        }
+    }
+
+    #[test]
+    #[cfg(target_arch = "x86_64")]
+    fn vbmi2_matches_portable_on_random_mixes() {
+        if !has_avx512_vbmi2() {
+            return;
+        }
+        let mut state = 0x9E37_79B9_7F4A_7C15u64;
+        let mut next = || {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            state
+        };
+        let bump = bumpalo::Bump::new();
+        for round in 0..2000 {
+            // From all Latin-1 to mostly ASCII with a few accents
+            let density = round % 8 + 1;
+            let len = 64 + next() as usize % 300;
+            let input: Vec<u8> = (0..len)
+                .map(|_| {
+                    let r = next();
+                    if r % 8 < density {
+                        r as u8 >> 1
+                    } else {
+                        (r >> 8) as u8 | 0x80
+                    }
+                })
+                .collect();
+            for chunk in input.chunks_exact(64) {
+                check_vbmi2_chunk(chunk.try_into().unwrap());
+            }
+            let got = unsafe { ucs1_to_utf8_bump_at(SimdLevel::Avx512, &input, &bump) };
+            assert_eq!(got, ucs1_to_utf8_scalar(&input), "{input:x?}");
+        }
+    }
+
     #[test]
     fn simd_vs_scalar_consistency() {
         // Test that SIMD and scalar paths produce identical results
@@ -1,10 +1,15 @@
 //! UCS2 (UTF-16) ↔ UTF-8 conversions
 
+use crate::simd::SimdLevel;
 use crate::simd::{
-    LANES_U8, LANES_U16, SIMD_THRESHOLD_BYTES, SIMD_THRESHOLD_UCS2, U8s, U16s, push_utf8_4,
-    push_utf8_4_bump, simd_u16_to_ascii_bytes,
+    LANES_U8, LANES_U16, REPLACEMENT_UTF8, SIMD_THRESHOLD_BYTES, SIMD_THRESHOLD_UCS2, U16s,
+    decode_utf8_at, prefetch_ahead, push_utf8_4, push_utf8_4_bump, simd_u16_to_ascii_bytes,
+    wants_prefetch,
 };
-use core::simd::cmp::SimdPartialOrd;
+use core::simd::cmp::{SimdPartialEq, SimdPartialOrd};
+use core::simd::num::SimdUint;
+use core::simd::{Mask, Select, Simd};
+use core::sync::atomic::Ordering;
 
 // ========================================================================== //
 //                         Scalar Implementations                             //
@@ -13,10 +18,10 @@ use core::simd::cmp::SimdPartialOrd;
 /// Converts a UCS-2 (UTF-16) slice to a UTF-8 string in a `bumpalo` arena.
 ///
 /// This function provides a scalar fallback for short inputs. It correctly
-/// handles surrogate pairs.
+/// handles surrogate pairs, and reads lone surrogates as U+FFFD.
 #[inline]
 fn ucs2_to_utf8_scalar_bump<'a>(input: &[u16], bump: &'a bumpalo::Bump) -> &'a str {
-    let mut out = bumpalo::collections::Vec::with_capacity_in(input.len() * 3, bump);
+    let mut out = bumpalo::collections::Vec::with_capacity_in(ucs2_utf8_len_scalar(input), bump);
 
     let mut i = 0;
     while i < input.len() {
@@ -27,18 +32,13 @@ fn ucs2_to_utf8_scalar_bump<'a>(input: &[u16], bump: &'a bumpalo::Bump) -> &'a s
                 out.push((0xC0 | (w >> 6)) as u8);
                 out.push((0x80 | (w & 0x3F)) as u8);
             }
-            0xD800..=0xDBFF => {
-                // High surrogate: assume valid pair
-                if i + 1 < input.len() {
-                    let lo = input[i + 1];
-                    let cp = 0x10000 + (((w as u32 & 0x3FF) << 10) | (lo as u32 & 0x3FF));
+            0xD800..=0xDFFF => match surrogate_pair(input, i) {
+                Some(cp) => {
                     push_utf8_4_bump(cp, &mut out);
                     i += 1; // skip low surrogate
                 }
-            }
-            0xDC00..=0xDFFF => {
-                // Isolated low surrogate - skip
-            }
+                None => out.extend_from_slice(&REPLACEMENT_UTF8),
+            },
             _ => {
                 out.push((0xE0 | (w >> 12)) as u8);
                 out.push((0x80 | ((w >> 6) & 0x3F)) as u8);
@@ -54,7 +54,7 @@ fn ucs2_to_utf8_scalar_bump<'a>(input: &[u16], bump: &'a bumpalo::Bump) -> &'a s
 
 #[inline]
 fn ucs2_to_utf8_scalar(input: &[u16]) -> Vec<u8> {
-    let mut out = Vec::with_capacity(input.len() * 3);
+    let mut out = Vec::with_capacity(ucs2_utf8_len_scalar(input));
 
     let mut i = 0;
     while i < input.len() {
@@ -65,18 +65,13 @@ fn ucs2_to_utf8_scalar(input: &[u16]) -> Vec<u8> {
                 out.push((0xC0 | (w >> 6)) as u8);
                 out.push((0x80 | (w & 0x3F)) as u8);
             }
-            0xD800..=0xDBFF => {
-                // High surrogate: assume valid pair.
-                if i + 1 < input.len() {
-                    let lo = input[i + 1];
-                    let cp = 0x10000 + (((w as u32 & 0x3FF) << 10) | (lo as u32 & 0x3FF));
+            0xD800..=0xDFFF => match surrogate_pair(input, i) {
+                Some(cp) => {
                     push_utf8_4(cp, &mut out);
                     i += 1; // Skip low surrogate.
                 }
-            }
-            0xDC00..=0xDFFF => {
-                // Isolated low surrogate, skip.
-            }
+                None => out.extend_from_slice(&REPLACEMENT_UTF8),
+            },
             _ => {
                 out.push((0xE0 | (w >> 12)) as u8);
                 out.push((0x80 | ((w >> 6) & 0x3F)) as u8);
@@ -89,49 +84,128 @@ fn ucs2_to_utf8_scalar(input: &[u16]) -> Vec<u8> {
     out
 }
 
+/// The supplementary character encoded by the surrogate pair at `units[i]`,
+/// or `None` if `units[i]` is a lone surrogate.
+///
+/// Python strings may hold lone surrogates (`surrogateescape`, `chr(0xD800)`);
+/// they have no UTF-8 form and are read as U+FFFD (see `REPLACEMENT_UTF8`).
+#[inline(always)]
+fn surrogate_pair(units: &[u16], i: usize) -> Option<u32> {
+    let hi = units[i];
+    let lo = *units.get(i + 1)?;
+    if !(0xD800..=0xDBFF).contains(&hi) || !(0xDC00..=0xDFFF).contains(&lo) {
+        return None;
+    }
+    Some(0x10000 + (((hi as u32 & 0x3FF) << 10) | (lo as u32 & 0x3FF)))
+}
+
+/// Exact UTF-8 length of `input`, with lone surrogates as U+FFFD, so the
+/// output is allocated once at its final size.
+#[inline]
+fn ucs2_utf8_len_scalar(input: &[u16]) -> usize {
+    let mut len = 0;
+    let mut i = 0;
+    while i < input.len() {
+        len += match input[i] {
+            0x0000..=0x007F => 1,
+            0x0080..=0x07FF => 2,
+            0xD800..=0xDFFF if surrogate_pair(input, i).is_some() => {
+                i += 1; // The low surrogate is part of the 4 bytes
+                4
+            }
+            _ => 3,
+        };
+        i += 1;
+    }
+    len
+}
+
+/// `ucs2_utf8_len_scalar` counting whole chunks in vector lanes, see
+/// `bmp_utf8_len`. Chunks with surrogates are counted by the scalar routine, split like the
+/// conversion splits them so no pair is cut in two.
+#[inline(always)]
+fn ucs2_utf8_len_lanes<const N: usize>(input: &[u16]) -> usize {
+    let mut len = 0;
+    let mut i = 0;
+    while i + N <= input.len() {
+        let chunk = Simd::<u16, N>::from_slice(&input[i..i + N]);
+        let surrogate = (chunk & Simd::splat(0xF800)).simd_eq(Simd::splat(0xD800));
+        if surrogate.any() {
+            let n = if (0xD800..=0xDBFF).contains(&input[i + N - 1]) {
+                N - 1
+            } else {
+                N
+            };
+            len += ucs2_utf8_len_scalar(&input[i..i + n]);
+            i += n;
+            continue;
+        }
+        len += bmp_utf8_len(chunk);
+        i += N;
+    }
+    len + ucs2_utf8_len_scalar(&input[i..])
+}
+
+/// UTF-8 length of a chunk without surrogates: a code unit takes one byte,
+/// plus one from U+0080 and another from U+0800.
+#[inline(always)]
+fn bmp_utf8_len<const N: usize>(chunk: Simd<u16, N>) -> usize {
+    let wide = chunk.simd_ge(Simd::splat(0x80)).to_bitmask().count_ones()
+        + chunk.simd_ge(Simd::splat(0x800)).to_bitmask().count_ones();
+    N + wide as usize
+}
+
 /// Converts a UTF-8 slice to UCS-2 (UTF-16).
 ///
-/// This function provides a scalar fallback for short inputs. It encodes
-/// supplementary plane characters as surrogate pairs.
+/// This function provides a scalar fallback for short inputs and blocks
+/// holding 4-byte sequences. It encodes supplementary plane characters as
+/// surrogate pairs and skips invalid bytes.
 #[inline]
 fn utf8_to_ucs2_scalar(input: &[u8], output: &mut [u16]) -> usize {
     let mut out_pos = 0;
     let mut i = 0;
 
     while i < input.len() && out_pos < output.len() {
-        let byte = input[i];
-        if byte < 0x80 {
-            output[out_pos] = byte as u16;
-            out_pos += 1;
-            i += 1;
-        } else {
-            // Simple UTF-8 decoding
-            if let Ok(s) = core::str::from_utf8(&input[i..]) {
-                if let Some(ch) = s.chars().next() {
-                    let cp = ch as u32;
-                    if cp <= 0xFFFF && (cp < 0xD800 || cp > 0xDFFF) {
-                        output[out_pos] = cp as u16;
-                        out_pos += 1;
-                    } else if cp > 0xFFFF && out_pos + 1 < output.len() {
-                        // Encode as surrogate pair
-                        let cp = cp - 0x10000;
-                        output[out_pos] = 0xD800 | ((cp >> 10) as u16);
-                        output[out_pos + 1] = 0xDC00 | ((cp & 0x3FF) as u16);
-                        out_pos += 2;
-                    }
-                    i += ch.len_utf8();
-                } else {
-                    i += 1;
-                }
-            } else {
-                i += 1;
-            }
+        let (consumed, written) = utf8_to_ucs2_char(input, i, &mut output[out_pos..]);
+        if consumed == 0 {
+            break;
         }
+        i += consumed;
+        out_pos += written;
     }
 
     out_pos
 }
 
+/// Converts the character at `input[i]` into `output`, returning the bytes
+/// consumed and code units written. Consumes nothing if a surrogate pair
+/// doesn't fit.
+#[inline(always)]
+fn utf8_to_ucs2_char(input: &[u8], i: usize, output: &mut [u16]) -> (usize, usize) {
+    let byte = input[i];
+    if byte < 0x80 {
+        output[0] = byte as u16;
+        return (1, 1);
+    }
+    match decode_utf8_at(input, i) {
+        Some((cp, len)) if cp <= 0xFFFF => {
+            output[0] = cp as u16;
+            (len, 1)
+        }
+        Some((cp, len)) => {
+            if output.len() < 2 {
+                return (0, 0);
+            }
+            // Encode as surrogate pair
+            let cp = cp - 0x10000;
+            output[0] = 0xD800 | ((cp >> 10) as u16);
+            output[1] = 0xDC00 | ((cp & 0x3FF) as u16);
+            (len, 2)
+        }
+        None => (1, 0),
+    }
+}
+
 // ========================================================================== //
 //                       UCS-2 (UTF-16) to UTF-8                              //
 // ========================================================================== //
@@ -148,14 +222,13 @@ fn expand_ucs2_block_bump(block: &[u16], out: &mut bumpalo::collections::Vec<u8>
                 out.push((0xC0 | (w >> 6)) as u8);
                 out.push((0x80 | (w & 0x3F)) as u8);
             }
-            0xD800..=0xDBFF => {
-                // High surrogate: assume valid pair.
-                let lo = block[j + 1];
-                let cp = 0x10000 + (((w as u32 & 0x3FF) << 10) | (lo as u32 & 0x3FF));
-                push_utf8_4_bump(cp, out);
-                j += 1; // Skip low surrogate.
-            }
-            0xDC00..=0xDFFF => unsafe { core::hint::unreachable_unchecked() },
+            0xD800..=0xDFFF => match surrogate_pair(block, j) {
+                Some(cp) => {
+                    push_utf8_4_bump(cp, out);
+                    j += 1; // Skip low surrogate.
+                }
+                None => out.extend_from_slice(&REPLACEMENT_UTF8),
+            },
             _ => {
                 out.push((0xE0 | (w >> 12)) as u8);
                 out.push((0x80 | ((w >> 6) & 0x3F)) as u8);
@@ -178,14 +251,13 @@ fn expand_ucs2_block(block: &[u16], out: &mut Vec<u8>) {
                 out.push((0xC0 | (w >> 6)) as u8);
                 out.push((0x80 | (w & 0x3F)) as u8);
             }
-            0xD800..=0xDBFF => {
-                // High surrogate: assume valid pair.
-                let lo = block[j + 1];
-                let cp = 0x10000 + (((w as u32 & 0x3FF) << 10) | (lo as u32 & 0x3FF));
-                push_utf8_4(cp, out);
-                j += 1; // Skip low surrogate.
-            }
-            0xDC00..=0xDFFF => unsafe { core::hint::unreachable_unchecked() },
+            0xD800..=0xDFFF => match surrogate_pair(block, j) {
+                Some(cp) => {
+                    push_utf8_4(cp, out);
+                    j += 1; // Skip low surrogate.
+                }
+                None => out.extend_from_slice(&REPLACEMENT_UTF8),
+            },
             _ => {
                 out.push((0xE0 | (w >> 12)) as u8);
                 out.push((0x80 | ((w >> 6) & 0x3F)) as u8);
@@ -196,51 +268,207 @@ fn expand_ucs2_block(block: &[u16], out: &mut Vec<u8>) {
     }
 }
 
+/// Returns whether every code unit of `chunk` encodes to exactly three UTF-8
+/// bytes, i.e. lies in U+0800..=U+FFFF and is not a surrogate.
+#[inline(always)]
+fn all_three_byte<const N: usize>(chunk: Simd<u16, N>) -> bool {
+    let wide = chunk.simd_ge(Simd::splat(0x0800));
+    let surrogate = (chunk & Simd::splat(0xF800)).simd_eq(Simd::splat(0xD800));
+    (wide & !surrogate).all()
+}
+
+/// Encodes a chunk of 3-byte code units (see `all_three_byte`) as UTF-8.
+///
+/// The three bytes of each lane are computed in vector lanes and packed into
+/// a little-endian `u32`, which is then stored at a stride of 3: every store
+/// spills one byte that the next lane overwrites.
+///
+/// # Safety
+///
+/// `dst` must be valid for `3 * N + 1` bytes of writes.
+#[inline(always)]
+unsafe fn encode_three_byte_lanes<const N: usize>(chunk: Simd<u16, N>, dst: *mut u8) {
+    let w: Simd<u32, N> = chunk.cast();
+    let lead = Simd::splat(0xE0) | (w >> 12);
+    let mid = Simd::splat(0x80) | ((w >> 6) & Simd::splat(0x3F));
+    let last = Simd::splat(0x80) | (w & Simd::splat(0x3F));
+    let packed = (lead | (mid << 8) | (last << 16)).to_array();
+    for (k, &bytes) in packed.iter().enumerate() {
+        unsafe { dst.add(3 * k).cast::<u32>().write_unaligned(bytes.to_le()) };
+    }
+}
+
+/// Bytes `encode_bmp_lanes` may write past the end of its output.
+const BMP_LANES_SPILL: usize = 3;
+
+/// Returns whether `chunk` holds a surrogate, which needs the scalar routine.
+#[inline(always)]
+fn has_surrogate<const N: usize>(chunk: Simd<u16, N>) -> bool {
+    (chunk & Simd::splat(0xF800))
+        .simd_eq(Simd::splat(0xD800))
+        .any()
+}
+
+/// Inclusive prefix sum across the lanes of `v`, in log2(N) shifted adds.
+#[inline(always)]
+fn prefix_sum_lanes<const N: usize>(mut v: Simd<u32, N>) -> Simd<u32, N> {
+    v += v.shift_elements_right::<1>(0);
+    if N > 2 {
+        v += v.shift_elements_right::<2>(0);
+    }
+    if N > 4 {
+        v += v.shift_elements_right::<4>(0);
+    }
+    if N > 8 {
+        v += v.shift_elements_right::<8>(0);
+    }
+    if N > 16 {
+        v += v.shift_elements_right::<16>(0);
+    }
+    v
+}
+
+/// Encodes a chunk of code units without surrogates, of any mix of 1, 2 and
+/// 3 UTF-8 bytes, as UTF-8.
+///
+/// Every lane is encoded at all three sizes and the one it needs selected,
+/// packed into a little-endian `u32`. The output offset of each lane is the
+/// prefix sum of the sizes before it, and the lanes are stored in order at
+/// those offsets: every store spills up to three bytes that later lanes
+/// overwrite. Returns the number of bytes written.
+///
+/// # Safety
+///
+/// `dst` must be valid for `bmp_utf8_len(chunk) + BMP_LANES_SPILL` bytes of
+/// writes.
+#[inline(always)]
+unsafe fn encode_bmp_lanes<const N: usize>(chunk: Simd<u16, N>, dst: *mut u8) -> usize {
+    let w: Simd<u32, N> = chunk.cast();
+    let two = w.simd_ge(Simd::splat(0x80));
+    let three = w.simd_ge(Simd::splat(0x800));
+
+    let last = Simd::splat(0x80) | (w & Simd::splat(0x3F));
+    let packed2 = (Simd::splat(0xC0) | (w >> 6)) | (last << 8);
+    let packed3 = (Simd::splat(0xE0) | (w >> 12))
+        | ((Simd::splat(0x80) | ((w >> 6) & Simd::splat(0x3F))) << 8)
+        | (last << 16);
+    let packed = three.select(packed3, two.select(packed2, w));
+
+    let one = Simd::<u32, N>::splat(1);
+    let zero = Simd::<u32, N>::splat(0);
+    let sizes = one + two.select(one, zero) + three.select(one, zero);
+    let ends = prefix_sum_lanes(sizes);
+    let offsets = (ends - sizes).to_array();
+    let packed = packed.to_array();
+
+    for k in 0..N {
+        unsafe {
+            dst.add(offsets[k] as usize)
+                .cast::<u32>()
+                .write_unaligned(packed[k].to_le())
+        };
+    }
+    ends[N - 1] as usize
+}
+
 /// Converts a UCS-2 (UTF-16) slice to a UTF-8 string in a `bumpalo` arena.
 ///
 /// This function uses SIMD for performance on larger inputs. It checks for ASCII
-/// fast paths and falls back to a scalar routine for blocks containing
-/// surrogate pairs, which require special handling.
+/// and all-3-byte (e.g. CJK) fast paths, encodes other mixes of 1, 2 and
+/// 3-byte characters in vector lanes, and falls back to a scalar routine for
+/// blocks containing surrogate pairs, which require special handling.
 #[inline]
 pub fn ucs2_to_utf8_bump<'a>(input: &[u16], bump: &'a bumpalo::Bump) -> &'a str {
-    if input.len() < SIMD_THRESHOLD_UCS2 {
+    unsafe { ucs2_to_utf8_bump_at(SimdLevel::current(), input, bump) }
+}
+
+/// `ucs2_to_utf8_bump` using the kernel compiled for `level`.
+///
+/// # Safety
+///
+/// The running CPU must support `level`, see `SimdLevel::available`.
+#[inline]
+pub(crate) unsafe fn ucs2_to_utf8_bump_at<'a>(
+    level: SimdLevel,
+    input: &[u16],
+    bump: &'a bumpalo::Bump,
+) -> &'a str {
+    if input.len() < SIMD_THRESHOLD_UCS2.load(Ordering::Relaxed) {
         return ucs2_to_utf8_scalar_bump(input, bump);
     }
 
-    let mut out = bumpalo::collections::Vec::with_capacity_in(input.len() * 3, bump);
+    match level {
+        #[cfg(target_arch = "x86_64")]
+        SimdLevel::Avx512 => ucs2_to_utf8_bump_avx512(input, bump),
+        #[cfg(target_arch = "x86_64")]
+        SimdLevel::Avx2 => ucs2_to_utf8_bump_avx2(input, bump),
+        _ => ucs2_to_utf8_bump_lanes::<LANES_U16>(input, bump),
+    }
+}
+
+#[cfg(target_arch = "x86_64")]
+#[target_feature(enable = "avx2")]
+unsafe fn ucs2_to_utf8_bump_avx2<'a>(input: &[u16], bump: &'a bumpalo::Bump) -> &'a str {
+    ucs2_to_utf8_bump_lanes::<16>(input, bump)
+}
+
+#[cfg(target_arch = "x86_64")]
+#[target_feature(enable = "avx512f,avx512bw")]
+unsafe fn ucs2_to_utf8_bump_avx512<'a>(input: &[u16], bump: &'a bumpalo::Bump) -> &'a str {
+    ucs2_to_utf8_bump_lanes::<32>(input, bump)
+}
+
+#[inline(always)]
+fn ucs2_to_utf8_bump_lanes<'a, const N: usize>(input: &[u16], bump: &'a bumpalo::Bump) -> &'a str {
+    // Sized exactly, plus the bytes the lane encoders spill past the last
+    // character they write
+    let capacity = ucs2_utf8_len_lanes::<N>(input) + BMP_LANES_SPILL;
+    let mut out = bumpalo::collections::Vec::with_capacity_in(capacity, bump);
+    let prefetch = wants_prefetch(input);
     let mut i = 0;
 
-    while i + LANES_U16 <= input.len() {
-        let chunk = U16s::from_slice(&input[i..i + LANES_U16]);
-        let is_ascii = chunk.simd_le(U16s::splat(0x7F));
+    while i + N <= input.len() {
+        if prefetch {
+            prefetch_ahead(input, i);
+        }
+        let chunk = Simd::<u16, N>::from_slice(&input[i..i + N]);
+        let is_ascii = chunk.simd_le(Simd::<u16, N>::splat(0x7F));
 
         if is_ascii.all() {
             // Fast path for pure ASCII
             let ascii_bytes = simd_u16_to_ascii_bytes(chunk);
             out.extend_from_slice(&ascii_bytes);
-        } else {
-            // Check for the complex case (surrogates) and use a faster path if not present.
-            let has_surrogates = chunk.simd_ge(U16s::splat(0xD800)).any();
-            if has_surrogates {
-                // Fallback for blocks with surrogates, which require look-ahead.
-                expand_ucs2_block_bump(&input[i..i + LANES_U16], &mut out);
+        } else if all_three_byte(chunk) {
+            // Fast path for pure 3-byte text, e.g. CJK
+            out.reserve(3 * N + 1);
+            unsafe {
+                let len = out.len();
+                encode_three_byte_lanes(chunk, out.as_mut_ptr().add(len));
+                out.set_len(len + 3 * N);
+            }
+        } else if has_surrogate(chunk) {
+            // Fallback for blocks with surrogates, which require look-ahead.
+            // A pair split by the chunk boundary is left to the next chunk.
+            let len = if (0xD800..=0xDBFF).contains(&input[i + N - 1]) {
+                N - 1
             } else {
-                // Faster path for 1/2/3-byte characters (no surrogates).
-                for &w in &input[i..i + LANES_U16] {
-                    if w <= 0x007F {
-                        out.push(w as u8);
-                    } else if w <= 0x07FF {
-                        out.push((0xC0 | (w >> 6)) as u8);
-                        out.push((0x80 | (w & 0x3F)) as u8);
-                    } else {
-                        out.push((0xE0 | (w >> 12)) as u8);
-                        out.push((0x80 | ((w >> 6) & 0x3F)) as u8);
-                        out.push((0x80 | (w & 0x3F)) as u8);
-                    }
-                }
+                N
+            };
+            expand_ucs2_block_bump(&input[i..i + len], &mut out);
+            i += len;
+            continue;
+        } else {
+            // Mixed 1/2/3-byte characters, e.g. Cyrillic with spaces
+            let bytes = bmp_utf8_len(chunk);
+            out.reserve(bytes + BMP_LANES_SPILL);
+            unsafe {
+                let len = out.len();
+                let written = encode_bmp_lanes(chunk, out.as_mut_ptr().add(len));
+                debug_assert_eq!(written, bytes);
+                out.set_len(len + bytes);
             }
         }
-        i += LANES_U16;
+        i += N;
     }
 
     // Handle the final tail
@@ -248,6 +476,7 @@ pub fn ucs2_to_utf8_bump<'a>(input: &[u16], bump: &'a bumpalo::Bump) -> &'a str
         expand_ucs2_block_bump(&input[i..], &mut out);
     }
 
+    debug_assert_eq!(out.len() + BMP_LANES_SPILL, capacity);
     let slice = out.into_bump_slice();
     unsafe { core::str::from_utf8_unchecked(slice) }
 }
@@ -258,14 +487,20 @@ pub fn ucs2_to_utf8_bump<'a>(input: &[u16], bump: &'a bumpalo::Bump) -> &'a str
 /// `ucs2_to_utf8_bump`, but allocates on the heap.
 #[inline]
 pub fn ucs2_to_utf8(input: &[u16]) -> Vec<u8> {
-    if input.len() < SIMD_THRESHOLD_UCS2 {
+    if input.len() < SIMD_THRESHOLD_UCS2.load(Ordering::Relaxed) {
         return ucs2_to_utf8_scalar(input);
     }
 
-    let mut out: Vec<u8> = Vec::with_capacity(input.len() * 3);
+    // Sized exactly, plus the bytes the lane encoders spill
+    let mut out: Vec<u8> =
+        Vec::with_capacity(ucs2_utf8_len_lanes::<LANES_U16>(input) + BMP_LANES_SPILL);
+    let prefetch = wants_prefetch(input);
     let mut i = 0;
 
     while i + LANES_U16 <= input.len() {
+        if prefetch {
+            prefetch_ahead(input, i);
+        }
         let chunk = U16s::from_slice(&input[i..i + LANES_U16]);
         let is_ascii = chunk.simd_le(U16s::splat(0x7F));
 
@@ -273,26 +508,34 @@ pub fn ucs2_to_utf8(input: &[u16]) -> Vec<u8> {
             // Fast path for pure ASCII
             let ascii_bytes = simd_u16_to_ascii_bytes(chunk);
             out.extend_from_slice(&ascii_bytes);
-        } else {
-            // Check for the complex case (surrogates) and use a faster path if not present.
-            let has_surrogates = chunk.simd_ge(U16s::splat(0xD800)).any();
-            if has_surrogates {
-                // Fallback for blocks with surrogates, which require look-ahead.
-                expand_ucs2_block(&input[i..i + LANES_U16], &mut out);
+        } else if all_three_byte(chunk) {
+            // Fast path for pure 3-byte text, e.g. CJK
+            out.reserve(3 * LANES_U16 + 1);
+            unsafe {
+                let len = out.len();
+                encode_three_byte_lanes(chunk, out.as_mut_ptr().add(len));
+                out.set_len(len + 3 * LANES_U16);
+            }
+        } else if has_surrogate(chunk) {
+            // Fallback for blocks with surrogates, which require look-ahead.
+            // A pair split by the chunk boundary is left to the next chunk.
+            let len = if (0xD800..=0xDBFF).contains(&input[i + LANES_U16 - 1]) {
+                LANES_U16 - 1
             } else {
-                // Faster path for 1/2/3-byte characters (no surrogates).
-                for &w in &input[i..i + LANES_U16] {
-                    if w <= 0x007F {
-                        out.push(w as u8);
-                    } else if w <= 0x07FF {
-                        out.push((0xC0 | (w >> 6)) as u8);
-                        out.push((0x80 | (w & 0x3F)) as u8);
-                    } else {
-                        out.push((0xE0 | (w >> 12)) as u8);
-                        out.push((0x80 | ((w >> 6) & 0x3F)) as u8);
-                        out.push((0x80 | (w & 0x3F)) as u8);
-                    }
-                }
+                LANES_U16
+            };
+            expand_ucs2_block(&input[i..i + len], &mut out);
+            i += len;
+            continue;
+        } else {
+            // Mixed 1/2/3-byte characters, e.g. Cyrillic with spaces
+            let bytes = bmp_utf8_len(chunk);
+            out.reserve(bytes + BMP_LANES_SPILL);
+            unsafe {
+                let len = out.len();
+                let written = encode_bmp_lanes(chunk, out.as_mut_ptr().add(len));
+                debug_assert_eq!(written, bytes);
+                out.set_len(len + bytes);
             }
         }
         i += LANES_U16;
@@ -308,39 +551,114 @@ pub fn ucs2_to_utf8(input: &[u16]) -> Vec<u8> {
 
 /// Converts a UTF-8 slice to UCS-2 (UTF-16) using SIMD acceleration.
 ///
-/// This function is optimized for inputs that are primarily ASCII. It processes
-/// the input in SIMD-sized chunks, and if a chunk is pure ASCII, it is
-/// zero-extended to `u16`. For chunks containing multi-byte characters, it
-/// falls back to a scalar routine.
+/// Pure-ASCII chunks are zero-extended to `u16`. Chunks of 1, 2 and 3-byte
+/// sequences (all of the BMP, e.g. CJK text) are decoded in vector lanes as
+/// well: every byte is decoded as if it started a sequence, and the lanes of
+/// the actual start bytes are kept. Chunks with 4-byte sequences, which
+/// become surrogate pairs, fall back to the scalar routine.
+///
+/// Returns the number of code units written, which stops early if `output`
+/// is too short. Invalid UTF-8 is skipped by the scalar routine; the vector
+/// path expects valid input, as from a `&str`.
 pub fn utf8_to_ucs2_simd(input: &[u8], output: &mut [u16]) -> usize {
+    unsafe { utf8_to_ucs2_simd_at(SimdLevel::current(), input, output) }
+}
+
+/// `utf8_to_ucs2_simd` using the kernel compiled for `level`.
+///
+/// # Safety
+///
+/// The running CPU must support `level`, see `SimdLevel::available`.
+#[inline]
+pub(crate) unsafe fn utf8_to_ucs2_simd_at(
+    level: SimdLevel,
+    input: &[u8],
+    output: &mut [u16],
+) -> usize {
     // Use scalar for short strings to avoid SIMD overhead
-    if input.len() < SIMD_THRESHOLD_BYTES {
+    if input.len() < SIMD_THRESHOLD_BYTES.load(Ordering::Relaxed) {
         return utf8_to_ucs2_scalar(input, output);
     }
 
+    match level {
+        #[cfg(target_arch = "x86_64")]
+        SimdLevel::Avx512 => utf8_to_ucs2_avx512(input, output),
+        #[cfg(target_arch = "x86_64")]
+        SimdLevel::Avx2 => utf8_to_ucs2_avx2(input, output),
+        _ => utf8_to_ucs2_lanes::<LANES_U8>(input, output),
+    }
+}
+
+#[cfg(target_arch = "x86_64")]
+#[target_feature(enable = "avx2")]
+unsafe fn utf8_to_ucs2_avx2(input: &[u8], output: &mut [u16]) -> usize {
+    utf8_to_ucs2_lanes::<32>(input, output)
+}
+
+// Same chunk width as AVX2: the start-byte compaction is scalar either way,
+// and 64-byte chunks leave more of short strings to the scalar tail
+#[cfg(target_arch = "x86_64")]
+#[target_feature(enable = "avx512f,avx512bw")]
+unsafe fn utf8_to_ucs2_avx512(input: &[u8], output: &mut [u16]) -> usize {
+    utf8_to_ucs2_lanes::<32>(input, output)
+}
+
+#[inline(always)]
+fn utf8_to_ucs2_lanes<const N: usize>(input: &[u8], output: &mut [u16]) -> usize {
     let mut out_pos = 0;
     let mut i = 0;
 
-    // SIMD ASCII fast path
-    while i + LANES_U8 <= input.len() && out_pos + LANES_U8 <= output.len() {
-        let chunk = U8s::from_slice(&input[i..i + LANES_U8]);
+    // A chunk yields at most N code units, and its last sequence may run
+    // up to 2 bytes past it
+    while i + N + 2 <= input.len() && out_pos + N <= output.len() {
+        let b0 = Simd::<u8, N>::from_slice(&input[i..i + N]);
 
-        if chunk.simd_lt(U8s::splat(0x80)).all() {
+        if b0.simd_lt(Simd::splat(0x80)).all() {
             // Pure ASCII - zero-extend to u16
-            let mut wide_array = [0u16; LANES_U8];
-            for (i, &byte) in chunk.as_array().iter().enumerate() {
-                wide_array[i] = byte as u16;
+            b0.cast::<u16>()
+                .copy_to_slice(&mut output[out_pos..out_pos + N]);
+            out_pos += N;
+            i += N;
+            continue;
+        }
+
+        if b0.simd_ge(Simd::splat(0xF0)).any() {
+            // 4-byte sequences: scalar, up to the first boundary past the chunk
+            let end = i + N;
+            while i < end {
+                let (consumed, written) = utf8_to_ucs2_char(input, i, &mut output[out_pos..]);
+                i += consumed;
+                out_pos += written;
             }
-            output[out_pos..out_pos + LANES_U8].copy_from_slice(&wide_array);
-            out_pos += LANES_U8;
-            i += LANES_U8;
-        } else {
-            // Scalar fallback for the block and then continue.
-            let written = utf8_to_ucs2_scalar(&input[i..], &mut output[out_pos..]);
-            out_pos += written;
-            // This is a rough approximation to advance `i`. A more robust
-            // solution would be to count the bytes consumed by the scalar function.
-            i += LANES_U8;
+            continue;
+        }
+
+        // Decode each byte as the start of a 1, 2 or 3-byte sequence, with
+        // the 2 bytes following it
+        let w0 = b0.cast::<u16>();
+        let w1 = Simd::<u8, N>::from_slice(&input[i + 1..i + 1 + N]).cast::<u16>();
+        let w2 = Simd::<u8, N>::from_slice(&input[i + 2..i + 2 + N]).cast::<u16>();
+        let low6 = Simd::splat(0x3F);
+        let two = ((w0 & Simd::splat(0x1F)) << 6) | (w1 & low6);
+        let three = ((w0 & Simd::splat(0x0F)) << 12) | ((w1 & low6) << 6) | (w2 & low6);
+        let one_byte: Mask<i16, N> = w0.simd_lt(Simd::splat(0x80));
+        let two_bytes: Mask<i16, N> = w0.simd_lt(Simd::splat(0xE0));
+        let units = one_byte.select(w0, two_bytes.select(two, three));
+
+        // Keep the lanes of start bytes (anything but 10xxxxxx), in order
+        let starts = (b0 & Simd::splat(0xC0)).simd_ne(Simd::splat(0x80));
+        let units = units.to_array();
+        let mut starts = starts.to_bitmask();
+        while starts != 0 {
+            output[out_pos] = units[starts.trailing_zeros() as usize];
+            out_pos += 1;
+            starts &= starts - 1;
+        }
+
+        // Continue after the last sequence begun in this chunk
+        i += N;
+        while i < input.len() && input[i] & 0xC0 == 0x80 {
+            i += 1;
         }
     }
 
@@ -433,6 +751,67 @@ mod tests {
         assert_eq!(ucs2_to_utf8_bump(&utf16, &bump), korean);
     }
 
+    #[test]
+    fn ucs2_three_byte_blocks() {
+        let texts = [
+            "日本語のテキスト。中文文本，한국어".repeat(40),
+            "\u{0800}\u{D7FF}\u{E000}\u{FFFF}".repeat(64),
+            // 3-byte runs broken up by 1/2-byte characters and surrogate pairs
+            format!(
+                "{}é{}a{}😀{}",
+                "漢".repeat(37),
+                "字".repeat(70),
+                "か".repeat(33),
+                "ナ".repeat(90)
+            ),
+        ];
+        for text in &texts {
+            let utf16: Vec<u16> = text.encode_utf16().collect();
+            for start in 0..utf16.len().min(40) {
+                let units = &utf16[start..];
+                let Ok(expected) = String::from_utf16(units) else {
+                    continue;
+                };
+                assert_eq!(ucs2_to_utf8(units), expected.as_bytes(), "at {start}");
+                for level in SimdLevel::available() {
+                    let bump = bumpalo::Bump::new();
+                    let got = unsafe { ucs2_to_utf8_bump_at(level, units, &bump) };
+                    assert_eq!(got, expected, "{level:?} at {start}");
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn ucs2_every_bmp_code_point() {
+        // Every BMP code point outside the surrogates in order, then spaced
+        // by 1 and 2-byte characters so each one passes through different
+        // lanes of mixed-width chunks
+        let bmp: Vec<u16> = (0..=0xFFFF)
+            .filter(|w| !(0xD800..0xE000).contains(w))
+            .collect();
+        let mut mixed = Vec::new();
+        for (k, &w) in bmp.iter().enumerate() {
+            mixed.push(w);
+            mixed.extend([0x61, 0xE9, 0x416].iter().take(k % 4));
+        }
+
+        for units in [&bmp, &mixed] {
+            let expected = ucs2_to_utf8_scalar(units);
+            assert_eq!(expected, String::from_utf16(units).unwrap().as_bytes());
+            assert_eq!(ucs2_to_utf8(units), expected);
+            for level in SimdLevel::available() {
+                let bump = bumpalo::Bump::new();
+                let got = unsafe { ucs2_to_utf8_bump_at(level, units, &bump) };
+                assert_eq!(got.as_bytes(), expected, "{level:?}");
+
+                let mut decoded = vec![0u16; units.len()];
+                let len = unsafe { utf8_to_ucs2_simd_at(level, got.as_bytes(), &mut decoded) };
+                assert_eq!(&decoded[..len], &units[..], "{level:?}");
+            }
+        }
+    }
+
     #[test]
     fn utf8_to_ucs2_basic() {
         let ascii = "Hello";
@@ -443,6 +822,70 @@ mod tests {
         assert_eq!(len2, 5);
     }
 
+    fn cjk_corpus() -> String {
+        let pieces = [
+            "漢字かなカナ",
+            "한국어 텍스트",
+            "Ωμέγα",
+            "ж",
+            "é",
+            "plain ascii ",
+            "😀",
+            "中文。",
+        ];
+        (0..600)
+            .map(|i| pieces[(i * 7 + i / 5) % pieces.len()])
+            .collect()
+    }
+
+    #[test]
+    fn utf8_to_ucs2_matches_std_on_cjk_corpus() {
+        let corpus = cjk_corpus();
+        let starts = corpus.char_indices().map(|(i, _)| i).take(80);
+        for start in starts {
+            let text = &corpus[start..];
+            let expected: Vec<u16> = text.encode_utf16().collect();
+            for level in SimdLevel::available() {
+                let mut exact = vec![0u16; expected.len()];
+                let len = unsafe { utf8_to_ucs2_simd_at(level, text.as_bytes(), &mut exact) };
+                assert_eq!(len, expected.len(), "{level:?} at {start}");
+                assert_eq!(exact, expected, "{level:?} at {start}");
+
+                let mut roomy = vec![0u16; expected.len() + 100];
+                let len = unsafe { utf8_to_ucs2_simd_at(level, text.as_bytes(), &mut roomy) };
+                assert_eq!(&roomy[..len], &expected[..], "{level:?} at {start}");
+            }
+        }
+    }
+
+    #[test]
+    fn utf8_to_ucs2_bmp_only_blocks() {
+        // No 4-byte sequences, so every chunk takes the vector decode
+        let text = "日本語のテキストと Кириллица and ümlauts ".repeat(50);
+        let expected: Vec<u16> = text.encode_utf16().collect();
+        let mut out = vec![0u16; expected.len()];
+        assert_eq!(utf8_to_ucs2_simd(text.as_bytes(), &mut out), expected.len());
+        assert_eq!(out, expected);
+    }
+
+    #[test]
+    fn utf8_to_ucs2_short_output() {
+        let text = "中".repeat(200);
+        let mut out = vec![0u16; 50];
+        assert_eq!(utf8_to_ucs2_simd(text.as_bytes(), &mut out), 50);
+        assert!(out.iter().all(|&w| w == 0x4E2D));
+    }
+
+    #[test]
+    fn utf8_to_ucs2_skips_invalid_bytes() {
+        let mut out = [0u16; 8];
+        let len = utf8_to_ucs2_simd(b"ab\xFFc\xE4\xB8d", &mut out);
+        assert_eq!(
+            &out[..len],
+            &[b'a' as u16, b'b' as u16, b'c' as u16, b'd' as u16]
+        );
+    }
+
     #[test]
     fn roundtrip_utf8_ucs2() {
         let test_cases = vec!["Hello", "café", "🦀", "Hello, 世界!"];
@@ -455,4 +898,161 @@ mod tests {
             assert_eq!(case.as_bytes(), &back_to_utf8);
         }
     }
+
+    #[test]
+    fn ucs2_lone_surrogates() {
+        // Lone highs and lows, a pair, and a high before a non-surrogate, at
+        // every offset across the vector paths and the scalar tail
+        let pieces: [&[u16]; 6] = [
+            &[0xD800],
+            &[0xDC80],
+            &[0xD83D, 0xDE00],
+            &[0xDBFF, 0x0041],
+            &[0xDFFF, 0xD800],
+            &[0x4E2D, 0x00E9],
+        ];
+        for pad in 0..40 {
+            for piece in pieces {
+                for filler in [0x0061, 0x00E9, 0x4E2D] {
+                    let mut units = vec![filler; pad];
+                    units.extend_from_slice(piece);
+                    units.extend(std::iter::repeat_n(filler, 70 - pad));
+                    let expected = String::from_utf16_lossy(&units);
+                    assert_eq!(ucs2_to_utf8(&units), expected.as_bytes(), "{units:x?}");
+                    for level in SimdLevel::available() {
+                        let bump = bumpalo::Bump::new();
+                        let got = unsafe { ucs2_to_utf8_bump_at(level, &units, &bump) };
+                        assert_eq!(got, expected, "{level:?} {units:x?}");
+                    }
+                }
+            }
+        }
+        for units in [&[0xDC80][..], &[0xD800], &[0x61, 0xDBFF], &[0xDC00, 0xD800]] {
+            let expected = String::from_utf16_lossy(units);
+            assert_eq!(ucs2_to_utf8(units), expected.as_bytes());
+            let bump = bumpalo::Bump::new();
+            assert_eq!(ucs2_to_utf8_bump(units, &bump), expected);
+        }
+    }
+
+    #[test]
+    fn ucs2_pair_straddles_chunk_boundary() {
+        // The high surrogate at every offset of the widest chunk, so at some
+        // level it is the last unit and its low opens the next chunk
+        for filler in [0x0061, 0x00E9, 0x4E2D] {
+            for offset in 0..2 * 32 {
+                let mut units = vec![filler; offset];
+                units.extend_from_slice(&[0xD83D, 0xDE00]);
+                units.extend(std::iter::repeat_n(filler, 3 * 32 - offset));
+                let expected = String::from_utf16(&units).unwrap();
+                assert_eq!(ucs2_to_utf8(&units), expected.as_bytes(), "at {offset}");
+                for level in SimdLevel::available() {
+                    let bump = bumpalo::Bump::new();
+                    let got = unsafe { ucs2_to_utf8_bump_at(level, &units, &bump) };
+                    assert_eq!(got, expected, "{level:?} at {offset}");
+                }
+            }
+        }
+        // Pairs back to back keep every boundary mid-pair after an odd start
+        for start in 0..2 {
+            let mut units = vec![0x0061; start];
+            for _ in 0..3 * 32 {
+                units.extend_from_slice(&[0xD83D, 0xDE00]);
+            }
+            let expected = String::from_utf16(&units).unwrap();
+            assert_eq!(ucs2_to_utf8(&units), expected.as_bytes());
+            for level in SimdLevel::available() {
+                let bump = bumpalo::Bump::new();
+                let got = unsafe { ucs2_to_utf8_bump_at(level, &units, &bump) };
+                assert_eq!(got, expected, "{level:?} from {start}");
+            }
+        }
+    }
+
+    #[test]
+    fn ucs2_adversarial_buffers() {
+        // Code units drawn mostly from the surrogate range, so highs and lows
+        // meet in every order and across every chunk boundary
+        let units_from = [
+            0x0041, 0x00E9, 0x4E2D, 0xD800, 0xDBFF, 0xDC00, 0xDFFF, 0xD83D, 0xDE00,
+        ];
+        let mut state = 0x2545F4914F6CDD1Du64;
+        let mut next = move || {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            state
+        };
+        for len in 0..200 {
+            let units: Vec<u16> = (0..len)
+                .map(|_| units_from[next() as usize % units_from.len()])
+                .collect();
+            let expected = String::from_utf16_lossy(&units);
+            assert_eq!(ucs2_to_utf8(&units), expected.as_bytes(), "{units:x?}");
+            for level in SimdLevel::available() {
+                let bump = bumpalo::Bump::new();
+                let got = unsafe { ucs2_to_utf8_bump_at(level, &units, &bump) };
+                assert_eq!(got, expected, "{level:?} {units:x?}");
+            }
+        }
+    }
+
+    #[test]
+    fn utf8_to_ucs2_random_mixed() {
+        // Runs of ASCII broken by 2, 3 and 4-byte characters, so chunks are
+        // mixed and sequences straddle their boundaries
+        let pool = ['a', 'z', ' ', 'é', 'ÿ', 'Ω', '中', 'ー', '😀', '🦀'];
+        let mut state = 0x9E3779B97F4A7C15u64;
+        let mut next = move || {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            state
+        };
+        for len in (0..4096).step_by(7) {
+            let text: String = (0..len)
+                .map(|_| match next() % 16 {
+                    0..=9 => 'x',
+                    _ => pool[next() as usize % pool.len()],
+                })
+                .collect();
+            let expected: Vec<u16> = text.encode_utf16().collect();
+            for level in SimdLevel::available() {
+                // Exactly sized, as for new strings, and with room to spare
+                for spare in [0, 64] {
+                    let mut out = vec![0u16; expected.len() + spare];
+                    let n = unsafe { utf8_to_ucs2_simd_at(level, text.as_bytes(), &mut out) };
+                    assert_eq!(&out[..n], &expected[..], "{level:?} {text:?}");
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn utf8_len_is_exact() {
+        let mut texts: Vec<Vec<u16>> = [
+            "",
+            "ascii only",
+            "日本語のテキスト。中文文本，한국어",
+            "é ж 中 😀 mixed",
+        ]
+        .iter()
+        .map(|t| t.repeat(7).encode_utf16().collect())
+        .collect();
+        // Lone surrogates, and pairs at every offset around chunk ends
+        texts.push([0xD800, 0x41, 0xDC00, 0xDBFF].repeat(30));
+        for offset in 0..40 {
+            let mut units = vec![0x4E2D; offset];
+            units.extend_from_slice(&[0xD83D, 0xDE00, 0xD800]);
+            units.extend(std::iter::repeat_n(0x61, 40 - offset));
+            texts.push(units);
+        }
+        for units in &texts {
+            let expected = String::from_utf16_lossy(units).len();
+            assert_eq!(ucs2_utf8_len_scalar(units), expected, "{units:x?}");
+            assert_eq!(ucs2_utf8_len_lanes::<8>(units), expected, "{units:x?}");
+            assert_eq!(ucs2_utf8_len_lanes::<16>(units), expected, "{units:x?}");
+            assert_eq!(ucs2_utf8_len_lanes::<32>(units), expected, "{units:x?}");
+        }
+    }
 }
@@ -1,10 +1,15 @@
 //! UCS4 (UTF-32) ↔ UTF-8 conversions
 
+use crate::simd::SimdLevel;
 use crate::simd::{
-    LANES_U8, LANES_U32, SIMD_THRESHOLD_BYTES, SIMD_THRESHOLD_UCS4, U8s, U32s, push_utf8_4,
-    push_utf8_4_bump, simd_u32_to_ascii_bytes,
+    LANES_U8, LANES_U32, REPLACEMENT_UTF8, SIMD_THRESHOLD_BYTES, SIMD_THRESHOLD_UCS4, U32s,
+    decode_utf8_at, prefetch_ahead, push_utf8_4, push_utf8_4_bump, simd_u32_to_ascii_bytes,
+    wants_prefetch,
 };
 use core::simd::cmp::SimdPartialOrd;
+use core::simd::num::SimdUint;
+use core::simd::{Select, Simd};
+use core::sync::atomic::Ordering;
 
 // ========================================================================== //
 //                         Scalar Implementations                             //
@@ -15,7 +20,7 @@ use core::simd::cmp::SimdPartialOrd;
 /// This function provides a scalar fallback for short inputs.
 #[inline]
 fn ucs4_to_utf8_scalar_bump<'a>(input: &[u32], bump: &'a bumpalo::Bump) -> &'a str {
-    let mut out = bumpalo::collections::Vec::with_capacity_in(input.len() * 4, bump);
+    let mut out = bumpalo::collections::Vec::with_capacity_in(ucs4_utf8_len_scalar(input), bump);
 
     for &cp in input {
         push_utf32_scalar_bump(cp, &mut out);
@@ -27,7 +32,7 @@ fn ucs4_to_utf8_scalar_bump<'a>(input: &[u32], bump: &'a bumpalo::Bump) -> &'a s
 
 #[inline]
 fn ucs4_to_utf8_scalar(input: &[u32]) -> Vec<u8> {
-    let mut out = Vec::with_capacity(input.len() * 4);
+    let mut out = Vec::with_capacity(ucs4_utf8_len_scalar(input));
 
     for &cp in input {
         push_utf32_scalar(cp, &mut out);
@@ -38,37 +43,40 @@ fn ucs4_to_utf8_scalar(input: &[u32]) -> Vec<u8> {
 
 /// Converts a UTF-8 slice to UCS-4 (UTF-32).
 ///
-/// This function provides a scalar fallback for short inputs.
+/// This function provides a scalar fallback for short inputs and blocks
+/// holding multi-byte sequences. It skips invalid bytes.
 #[inline]
 fn utf8_to_ucs4_scalar(input: &[u8], output: &mut [u32]) -> usize {
     let mut out_pos = 0;
     let mut i = 0;
 
     while i < input.len() && out_pos < output.len() {
-        let byte = input[i];
-        if byte < 0x80 {
-            output[out_pos] = byte as u32;
-            out_pos += 1;
-            i += 1;
-        } else {
-            // Simple UTF-8 decoding
-            if let Ok(s) = core::str::from_utf8(&input[i..]) {
-                if let Some(ch) = s.chars().next() {
-                    output[out_pos] = ch as u32;
-                    out_pos += 1;
-                    i += ch.len_utf8();
-                } else {
-                    i += 1;
-                }
-            } else {
-                i += 1;
-            }
-        }
+        let (consumed, written) = utf8_to_ucs4_char(input, i, &mut output[out_pos..]);
+        i += consumed;
+        out_pos += written;
     }
 
     out_pos
 }
 
+/// Converts the character at `input[i]` into `output`, returning the bytes
+/// consumed and code points written.
+#[inline(always)]
+fn utf8_to_ucs4_char(input: &[u8], i: usize, output: &mut [u32]) -> (usize, usize) {
+    let byte = input[i];
+    if byte < 0x80 {
+        output[0] = byte as u32;
+        return (1, 1);
+    }
+    match decode_utf8_at(input, i) {
+        Some((cp, len)) => {
+            output[0] = cp;
+            (len, 1)
+        }
+        None => (1, 0),
+    }
+}
+
 #[inline(always)]
 fn push_utf32_scalar_bump(cp: u32, out: &mut bumpalo::collections::Vec<u8>) {
     match cp {
@@ -77,6 +85,8 @@ fn push_utf32_scalar_bump(cp: u32, out: &mut bumpalo::collections::Vec<u8>) {
             out.push((0xC0 | (cp >> 6)) as u8);
             out.push((0x80 | (cp & 0x3F)) as u8);
         }
+        // Lone surrogate, see `REPLACEMENT_UTF8`
+        0xD800..=0xDFFF => out.extend_from_slice(&REPLACEMENT_UTF8),
         0x0800..=0xFFFF => {
             out.push((0xE0 | (cp >> 12)) as u8);
             out.push((0x80 | ((cp >> 6) & 0x3F)) as u8);
@@ -94,6 +104,8 @@ fn push_utf32_scalar(cp: u32, out: &mut Vec<u8>) {
             out.push((0xC0 | (cp >> 6)) as u8);
             out.push((0x80 | (cp & 0x3F)) as u8);
         }
+        // Lone surrogate, see `REPLACEMENT_UTF8`
+        0xD800..=0xDFFF => out.extend_from_slice(&REPLACEMENT_UTF8),
         0x0800..=0xFFFF => {
             out.push((0xE0 | (cp >> 12)) as u8);
             out.push((0x80 | ((cp >> 6) & 0x3F)) as u8);
@@ -103,6 +115,40 @@ fn push_utf32_scalar(cp: u32, out: &mut Vec<u8>) {
     }
 }
 
+/// Exact UTF-8 length of `input`, with surrogates as U+FFFD, so the output
+/// is allocated once at its final size.
+#[inline]
+fn ucs4_utf8_len_scalar(input: &[u32]) -> usize {
+    input
+        .iter()
+        .map(|&cp| match cp {
+            0x0000..=0x007F => 1,
+            0x0080..=0x07FF => 2,
+            0x0800..=0xFFFF => 3,
+            _ => 4,
+        })
+        .sum()
+}
+
+/// `ucs4_utf8_len_scalar` counting whole chunks in vector lanes: a code
+/// point takes one byte, plus one from U+0080, U+0800 and U+10000 each.
+/// Lanes accumulate their counts and are summed once at the end.
+#[inline(always)]
+fn ucs4_utf8_len_lanes<const N: usize>(input: &[u32]) -> usize {
+    let chunks = input.chunks_exact(N);
+    let tail = ucs4_utf8_len_scalar(chunks.remainder());
+    let one = Simd::<u32, N>::splat(1);
+    let zero = Simd::<u32, N>::splat(0);
+    let mut wide = zero;
+    for chunk in chunks {
+        let chunk = Simd::<u32, N>::from_slice(chunk);
+        wide += chunk.simd_ge(Simd::splat(0x80)).select(one, zero)
+            + chunk.simd_ge(Simd::splat(0x800)).select(one, zero)
+            + chunk.simd_ge(Simd::splat(0x10000)).select(one, zero);
+    }
+    input.len() - input.len() % N + tail + wide.reduce_sum() as usize
+}
+
 // ========================================================================== //
 //                       UCS-4 (UTF-32) to UTF-8                              //
 // ========================================================================== //
@@ -114,16 +160,58 @@ fn push_utf32_scalar(cp: u32, out: &mut Vec<u8>) {
 /// supplementary-plane characters.
 #[inline]
 pub fn ucs4_to_utf8_bump<'a>(input: &[u32], bump: &'a bumpalo::Bump) -> &'a str {
-    if input.len() < SIMD_THRESHOLD_UCS4 {
+    unsafe { ucs4_to_utf8_bump_at(SimdLevel::current(), input, bump) }
+}
+
+/// `ucs4_to_utf8_bump` using the kernel compiled for `level`.
+///
+/// # Safety
+///
+/// The running CPU must support `level`, see `SimdLevel::available`.
+#[inline]
+pub(crate) unsafe fn ucs4_to_utf8_bump_at<'a>(
+    level: SimdLevel,
+    input: &[u32],
+    bump: &'a bumpalo::Bump,
+) -> &'a str {
+    if input.len() < SIMD_THRESHOLD_UCS4.load(Ordering::Relaxed) {
         return ucs4_to_utf8_scalar_bump(input, bump);
     }
 
-    let mut out = bumpalo::collections::Vec::with_capacity_in(input.len() * 4, bump);
+    match level {
+        #[cfg(target_arch = "x86_64")]
+        SimdLevel::Avx512 => ucs4_to_utf8_bump_avx512(input, bump),
+        #[cfg(target_arch = "x86_64")]
+        SimdLevel::Avx2 => ucs4_to_utf8_bump_avx2(input, bump),
+        _ => ucs4_to_utf8_bump_lanes::<LANES_U32>(input, bump),
+    }
+}
+
+#[cfg(target_arch = "x86_64")]
+#[target_feature(enable = "avx2")]
+unsafe fn ucs4_to_utf8_bump_avx2<'a>(input: &[u32], bump: &'a bumpalo::Bump) -> &'a str {
+    ucs4_to_utf8_bump_lanes::<8>(input, bump)
+}
+
+#[cfg(target_arch = "x86_64")]
+#[target_feature(enable = "avx512f,avx512bw")]
+unsafe fn ucs4_to_utf8_bump_avx512<'a>(input: &[u32], bump: &'a bumpalo::Bump) -> &'a str {
+    ucs4_to_utf8_bump_lanes::<16>(input, bump)
+}
+
+#[inline(always)]
+fn ucs4_to_utf8_bump_lanes<'a, const N: usize>(input: &[u32], bump: &'a bumpalo::Bump) -> &'a str {
+    let capacity = ucs4_utf8_len_lanes::<N>(input);
+    let mut out = bumpalo::collections::Vec::with_capacity_in(capacity, bump);
+    let prefetch = wants_prefetch(input);
     let mut i = 0;
 
-    while i + LANES_U32 <= input.len() {
-        let chunk = U32s::from_slice(&input[i..i + LANES_U32]);
-        let is_ascii = chunk.simd_le(U32s::splat(0x7F));
+    while i + N <= input.len() {
+        if prefetch {
+            prefetch_ahead(input, i);
+        }
+        let chunk = Simd::<u32, N>::from_slice(&input[i..i + N]);
+        let is_ascii = chunk.simd_le(Simd::<u32, N>::splat(0x7F));
 
         if is_ascii.all() {
             // Fast path for pure ASCII
@@ -131,20 +219,22 @@ pub fn ucs4_to_utf8_bump<'a>(input: &[u32], bump: &'a bumpalo::Bump) -> &'a str
             out.extend_from_slice(&ascii_bytes);
         } else {
             // Check for the complex case (4-byte UTF-8) and use a faster path if not present.
-            let has_supplementary = chunk.simd_gt(U32s::splat(0xFFFF)).any();
+            let has_supplementary = chunk.simd_gt(Simd::<u32, N>::splat(0xFFFF)).any();
             if has_supplementary {
                 // Fallback for blocks with supplementary-plane characters.
-                for &cp in &input[i..i + LANES_U32] {
+                for &cp in &input[i..i + N] {
                     push_utf32_scalar_bump(cp, &mut out);
                 }
             } else {
                 // Faster path for 1/2/3-byte characters.
-                for &cp in &input[i..i + LANES_U32] {
+                for &cp in &input[i..i + N] {
                     if cp <= 0x007F {
                         out.push(cp as u8);
                     } else if cp <= 0x07FF {
                         out.push((0xC0 | (cp >> 6)) as u8);
                         out.push((0x80 | (cp & 0x3F)) as u8);
+                    } else if (0xD800..=0xDFFF).contains(&cp) {
+                        out.extend_from_slice(&REPLACEMENT_UTF8);
                     } else {
                         out.push((0xE0 | (cp >> 12)) as u8);
                         out.push((0x80 | ((cp >> 6) & 0x3F)) as u8);
@@ -153,7 +243,7 @@ pub fn ucs4_to_utf8_bump<'a>(input: &[u32], bump: &'a bumpalo::Bump) -> &'a str
                 }
             }
         }
-        i += LANES_U32;
+        i += N;
     }
 
     // Handle the final tail
@@ -163,6 +253,7 @@ pub fn ucs4_to_utf8_bump<'a>(input: &[u32], bump: &'a bumpalo::Bump) -> &'a str
         }
     }
 
+    debug_assert_eq!(out.len(), capacity);
     let slice = out.into_bump_slice();
     unsafe { core::str::from_utf8_unchecked(slice) }
 }
@@ -173,14 +264,18 @@ pub fn ucs4_to_utf8_bump<'a>(input: &[u32], bump: &'a bumpalo::Bump) -> &'a str
 /// `ucs4_to_utf8_bump`, but allocates on the heap.
 #[inline]
 pub fn ucs4_to_utf8(input: &[u32]) -> Vec<u8> {
-    if input.len() < SIMD_THRESHOLD_UCS4 {
+    if input.len() < SIMD_THRESHOLD_UCS4.load(Ordering::Relaxed) {
         return ucs4_to_utf8_scalar(input);
     }
 
-    let mut out: Vec<u8> = Vec::with_capacity(input.len() * 4);
+    let mut out: Vec<u8> = Vec::with_capacity(ucs4_utf8_len_lanes::<LANES_U32>(input));
+    let prefetch = wants_prefetch(input);
     let mut i = 0;
 
     while i + LANES_U32 <= input.len() {
+        if prefetch {
+            prefetch_ahead(input, i);
+        }
         let chunk = U32s::from_slice(&input[i..i + LANES_U32]);
         let is_ascii = chunk.simd_le(U32s::splat(0x7F));
 
@@ -204,6 +299,8 @@ pub fn ucs4_to_utf8(input: &[u32]) -> Vec<u8> {
                     } else if cp <= 0x07FF {
                         out.push((0xC0 | (cp >> 6)) as u8);
                         out.push((0x80 | (cp & 0x3F)) as u8);
+                    } else if (0xD800..=0xDFFF).contains(&cp) {
+                        out.extend_from_slice(&REPLACEMENT_UTF8);
                     } else {
                         out.push((0xE0 | (cp >> 12)) as u8);
                         out.push((0x80 | ((cp >> 6) & 0x3F)) as u8);
@@ -229,35 +326,74 @@ pub fn ucs4_to_utf8(input: &[u32]) -> Vec<u8> {
 ///
 /// This function is optimized for inputs that are primarily ASCII. It processes
 /// the input in SIMD-sized chunks, and if a chunk is pure ASCII, it is
-/// zero-extended to `u32`. For chunks containing multi-byte characters, it
-/// falls back to a scalar routine.
+/// zero-extended to `u32`. Chunks containing multi-byte characters are decoded
+/// by a scalar routine, which resumes at the first character past the chunk.
 pub fn utf8_to_ucs4_simd(input: &[u8], output: &mut [u32]) -> usize {
+    unsafe { utf8_to_ucs4_simd_at(SimdLevel::current(), input, output) }
+}
+
+/// `utf8_to_ucs4_simd` using the kernel compiled for `level`.
+///
+/// # Safety
+///
+/// The running CPU must support `level`, see `SimdLevel::available`.
+#[inline]
+pub(crate) unsafe fn utf8_to_ucs4_simd_at(
+    level: SimdLevel,
+    input: &[u8],
+    output: &mut [u32],
+) -> usize {
     // Use scalar for short strings to avoid SIMD overhead
-    if input.len() < SIMD_THRESHOLD_BYTES {
+    if input.len() < SIMD_THRESHOLD_BYTES.load(Ordering::Relaxed) {
         return utf8_to_ucs4_scalar(input, output);
     }
 
+    match level {
+        #[cfg(target_arch = "x86_64")]
+        SimdLevel::Avx512 => utf8_to_ucs4_avx512(input, output),
+        #[cfg(target_arch = "x86_64")]
+        SimdLevel::Avx2 => utf8_to_ucs4_avx2(input, output),
+        _ => utf8_to_ucs4_lanes::<LANES_U8>(input, output),
+    }
+}
+
+#[cfg(target_arch = "x86_64")]
+#[target_feature(enable = "avx2")]
+unsafe fn utf8_to_ucs4_avx2(input: &[u8], output: &mut [u32]) -> usize {
+    utf8_to_ucs4_lanes::<32>(input, output)
+}
+
+#[cfg(target_arch = "x86_64")]
+#[target_feature(enable = "avx512f,avx512bw")]
+unsafe fn utf8_to_ucs4_avx512(input: &[u8], output: &mut [u32]) -> usize {
+    utf8_to_ucs4_lanes::<64>(input, output)
+}
+
+#[inline(always)]
+fn utf8_to_ucs4_lanes<const N: usize>(input: &[u8], output: &mut [u32]) -> usize {
     let mut out_pos = 0;
     let mut i = 0;
 
     // SIMD ASCII fast path
-    while i + LANES_U8 <= input.len() && out_pos + LANES_U8 <= output.len() {
-        let chunk = U8s::from_slice(&input[i..i + LANES_U8]);
+    while i + N <= input.len() && out_pos + N <= output.len() {
+        let chunk = Simd::<u8, N>::from_slice(&input[i..i + N]);
 
-        if chunk.simd_lt(U8s::splat(0x80)).all() {
+        if chunk.simd_lt(Simd::splat(0x80)).all() {
             // Pure ASCII - zero-extend to u32
-            let array = chunk.to_array();
-            for j in 0..LANES_U8 {
-                output[out_pos + j] = array[j] as u32;
-            }
-            out_pos += LANES_U8;
-            i += LANES_U8;
+            chunk
+                .cast::<u32>()
+                .copy_to_slice(&mut output[out_pos..out_pos + N]);
+            out_pos += N;
+            i += N;
         } else {
-            // Scalar fallback for the block and then continue.
-            let written = utf8_to_ucs4_scalar(&input[i..], &mut output[out_pos..]);
-            out_pos += written;
-            // This is a rough approximation to advance `i`.
-            i += LANES_U8;
+            // Scalar up to the first boundary past the chunk: the characters
+            // begun in it fit, as the chunk has room for one per byte
+            let end = i + N;
+            while i < end {
+                let (consumed, written) = utf8_to_ucs4_char(input, i, &mut output[out_pos..]);
+                i += consumed;
+                out_pos += written;
+            }
         }
     }
 
@@ -383,4 +519,86 @@ mod tests {
         let utf8_output = ucs4_to_utf8(&unicode_input);
         assert!(utf8_output.len() <= unicode_input.len() * 4);
     }
+
+    #[test]
+    fn ucs4_lone_surrogates() {
+        let lossy = |cps: &[u32]| -> String {
+            cps.iter()
+                .map(|&cp| char::from_u32(cp).unwrap_or('\u{FFFD}'))
+                .collect()
+        };
+        for pad in 0..40 {
+            for surrogate in [0xD800, 0xDBFF, 0xDC80, 0xDFFF] {
+                for filler in [0x61, 0xE9, 0x4E2D, 0x1F600] {
+                    let mut cps = vec![filler; pad];
+                    cps.push(surrogate);
+                    cps.extend(std::iter::repeat_n(filler, 40 - pad));
+                    let expected = lossy(&cps);
+                    assert_eq!(ucs4_to_utf8(&cps), expected.as_bytes(), "{cps:x?}");
+                    for level in SimdLevel::available() {
+                        let bump = bumpalo::Bump::new();
+                        let got = unsafe { ucs4_to_utf8_bump_at(level, &cps, &bump) };
+                        assert_eq!(got, expected, "{level:?} {cps:x?}");
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn utf8_to_ucs4_random_mixed() {
+        // Runs of ASCII broken by 2, 3 and 4-byte characters, so chunks are
+        // mixed and sequences straddle their boundaries
+        let pool = ['a', 'z', ' ', 'é', 'ÿ', 'Ω', '中', 'ー', '😀', '🦀'];
+        let mut state = 0x9E3779B97F4A7C15u64;
+        let mut next = move || {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            state
+        };
+        for len in (0..4096).step_by(7) {
+            let text: String = (0..len)
+                .map(|_| match next() % 16 {
+                    0..=9 => 'x',
+                    _ => pool[next() as usize % pool.len()],
+                })
+                .collect();
+            let expected: Vec<u32> = text.chars().map(|c| c as u32).collect();
+            for level in SimdLevel::available() {
+                // Exactly sized, as for new strings, and with room to spare
+                for spare in [0, 64] {
+                    let mut out = vec![0u32; expected.len() + spare];
+                    let n = unsafe { utf8_to_ucs4_simd_at(level, text.as_bytes(), &mut out) };
+                    assert_eq!(&out[..n], &expected[..], "{level:?} {text:?}");
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn utf8_len_is_exact() {
+        let lossy = |cps: &[u32]| -> usize {
+            cps.iter()
+                .map(|&cp| char::from_u32(cp).map_or(3, char::len_utf8))
+                .sum()
+        };
+        let mut texts: Vec<Vec<u32>> = [
+            "",
+            "ascii",
+            "é ж 中 😀 mixed",
+            "\u{7F}\u{80}\u{7FF}\u{800}\u{FFFF}\u{10000}",
+        ]
+        .iter()
+        .map(|t| t.repeat(9).chars().map(|c| c as u32).collect())
+        .collect();
+        texts.push([0xD800, 0xDFFF, 0x61, 0x10FFFF].repeat(21));
+        for cps in &texts {
+            let expected = lossy(cps);
+            assert_eq!(ucs4_utf8_len_scalar(cps), expected, "{cps:x?}");
+            assert_eq!(ucs4_utf8_len_lanes::<4>(cps), expected, "{cps:x?}");
+            assert_eq!(ucs4_utf8_len_lanes::<8>(cps), expected, "{cps:x?}");
+            assert_eq!(ucs4_utf8_len_lanes::<16>(cps), expected, "{cps:x?}");
+        }
+    }
 }
@@ -0,0 +1,241 @@
+//! UTF-8 validation of untrusted bytes
+
+use crate::simd::{LANES_U8, SIMD_THRESHOLD_BYTES, SimdLevel};
+use core::simd::Simd;
+use core::simd::cmp::{SimdPartialEq, SimdPartialOrd};
+use core::sync::atomic::Ordering;
+
+/// Where `validate_utf8_simd` found the first invalid sequence, with the
+/// meaning of `core::str::Utf8Error`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Utf8ErrorPos {
+    /// Length of the valid prefix, the byte offset of the invalid sequence.
+    pub valid_up_to: usize,
+    /// Length of the invalid sequence, `None` if the input ends inside one.
+    pub error_len: Option<usize>,
+}
+
+// ========================================================================== //
+//                         Scalar Implementations                             //
+// ========================================================================== //
+
+/// Validates `input[start..]`, where `start` is a character boundary with a
+/// valid prefix before it, reporting offsets into all of `input`.
+#[inline]
+fn validate_utf8_scalar(input: &[u8], start: usize) -> Result<(), Utf8ErrorPos> {
+    match core::str::from_utf8(&input[start..]) {
+        Ok(_) => Ok(()),
+        Err(err) => Err(Utf8ErrorPos {
+            valid_up_to: start + err.valid_up_to(),
+            error_len: err.error_len(),
+        }),
+    }
+}
+
+/// First character boundary at most three bytes before `i`, when all bytes
+/// before `i` are valid but the last character may be unfinished.
+#[inline]
+fn char_start(input: &[u8], i: usize) -> usize {
+    let mut start = i.saturating_sub(3);
+    while start < i && input[start] & 0xC0 == 0x80 {
+        start += 1;
+    }
+    start
+}
+
+// ========================================================================== //
+//                           SIMD Implementations                             //
+// ========================================================================== //
+
+/// Checks that `input` is valid UTF-8, as `core::str::from_utf8` does, and
+/// reports where the first invalid sequence starts so errors can name the
+/// line and column.
+///
+/// Implements the range-based validation of Keiser and Lemire with compares
+/// only: every byte is checked against the three bytes before it, loaded
+/// unaligned from the input. A byte must be a continuation exactly when one
+/// of them is a lead still expecting one, lead bytes must be in range, and
+/// the second byte after `E0`, `ED`, `F0` and `F4` must be in the range that
+/// rules out overlong forms, surrogates and code points above U+10FFFF.
+/// Pure-ASCII vectors after a complete character are skipped with one
+/// compare. Once a vector fails, the exact position comes from the scalar
+/// routine, run from the last character that starts before the vector.
+#[inline]
+pub fn validate_utf8_simd(input: &[u8]) -> Result<(), Utf8ErrorPos> {
+    unsafe { validate_utf8_simd_at(SimdLevel::current(), input) }
+}
+
+/// `validate_utf8_simd` using the kernel compiled for `level`.
+///
+/// # Safety
+///
+/// The running CPU must support `level`, see `SimdLevel::available`.
+#[inline]
+pub(crate) unsafe fn validate_utf8_simd_at(
+    level: SimdLevel,
+    input: &[u8],
+) -> Result<(), Utf8ErrorPos> {
+    if input.len() < SIMD_THRESHOLD_BYTES.load(Ordering::Relaxed) {
+        return validate_utf8_scalar(input, 0);
+    }
+
+    match level {
+        #[cfg(target_arch = "x86_64")]
+        SimdLevel::Avx512 => validate_utf8_avx512(input),
+        #[cfg(target_arch = "x86_64")]
+        SimdLevel::Avx2 => validate_utf8_avx2(input),
+        _ => validate_utf8_lanes::<LANES_U8>(input),
+    }
+}
+
+#[cfg(target_arch = "x86_64")]
+#[target_feature(enable = "avx2")]
+unsafe fn validate_utf8_avx2(input: &[u8]) -> Result<(), Utf8ErrorPos> {
+    validate_utf8_lanes::<32>(input)
+}
+
+#[cfg(target_arch = "x86_64")]
+#[target_feature(enable = "avx512f,avx512bw")]
+unsafe fn validate_utf8_avx512(input: &[u8]) -> Result<(), Utf8ErrorPos> {
+    validate_utf8_lanes::<64>(input)
+}
+
+/// The `N` bytes from `i - back`, with zeros, which need nothing after
+/// them, standing in for the bytes before the input.
+#[inline(always)]
+fn load_back<const N: usize>(input: &[u8], i: usize, back: usize) -> Simd<u8, N> {
+    if i >= back {
+        return Simd::from_slice(&input[i - back..i - back + N]);
+    }
+    Simd::from_array(core::array::from_fn(|k| {
+        (k + i).checked_sub(back).map_or(0, |at| input[at])
+    }))
+}
+
+/// Whether the `N` bytes at `i` break UTF-8, given the bytes before them.
+#[inline(always)]
+fn has_errors<const N: usize>(input: &[u8], i: usize) -> bool {
+    let splat = Simd::<u8, N>::splat;
+    let b = Simd::<u8, N>::from_slice(&input[i..i + N]);
+    let prev1 = load_back::<N>(input, i, 1);
+    let prev2 = load_back::<N>(input, i, 2);
+    let prev3 = load_back::<N>(input, i, 3);
+
+    // A continuation byte where, and only where, a lead expects one
+    let continuation = (b & splat(0xC0)).simd_eq(splat(0x80));
+    let expected =
+        prev1.simd_ge(splat(0xC0)) | prev2.simd_ge(splat(0xE0)) | prev3.simd_ge(splat(0xF0));
+    let mut errors = continuation ^ expected;
+
+    // Leads of overlong 2-byte forms, and of nothing at all
+    errors |= b.simd_eq(splat(0xC0)) | b.simd_eq(splat(0xC1)) | b.simd_ge(splat(0xF5));
+    // Second bytes out of range: overlong, surrogate, above U+10FFFF
+    errors |= prev1.simd_eq(splat(0xE0)) & b.simd_lt(splat(0xA0));
+    errors |= prev1.simd_eq(splat(0xED)) & b.simd_gt(splat(0x9F));
+    errors |= prev1.simd_eq(splat(0xF0)) & b.simd_lt(splat(0x90));
+    errors |= prev1.simd_eq(splat(0xF4)) & b.simd_gt(splat(0x8F));
+    errors.any()
+}
+
+#[inline(always)]
+fn validate_utf8_lanes<const N: usize>(input: &[u8]) -> Result<(), Utf8ErrorPos> {
+    let mut i = 0;
+    while i + N <= input.len() {
+        let ascii = Simd::<u8, N>::from_slice(&input[i..i + N])
+            .simd_lt(Simd::splat(0x80))
+            .all();
+        // Nothing before the vector may still expect a continuation byte
+        let complete = i < 3 || (input[i - 1] < 0xC0 && input[i - 2] < 0xE0 && input[i - 3] < 0xF0);
+        if !(ascii && complete) && has_errors::<N>(input, i) {
+            return validate_utf8_scalar(input, char_start(input, i));
+        }
+        i += N;
+    }
+
+    // The tail, and a character the last vector left unfinished
+    validate_utf8_scalar(input, char_start(input, i))
+}
+
+// ========================================================================== //
+//                                   Tests                                    //
+// ========================================================================== //
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn expected(bytes: &[u8]) -> Result<(), Utf8ErrorPos> {
+        validate_utf8_scalar(bytes, 0)
+    }
+
+    fn check(bytes: &[u8]) {
+        let want = expected(bytes);
+        assert_eq!(validate_utf8_simd(bytes), want, "{bytes:x?}");
+        for level in SimdLevel::available() {
+            let got = unsafe { validate_utf8_simd_at(level, bytes) };
+            assert_eq!(got, want, "{level:?}: {bytes:x?}");
+        }
+    }
+
+    #[test]
+    fn valid_text() {
+        let text = "ascii, é, ж, 中文, 😀, \u{10FFFF}, \u{FFFF}, \u{E000}, \u{D7FF} ".repeat(20);
+        for start in 0..8 {
+            check(&text.as_bytes()[start..]);
+        }
+        check(b"");
+        check(&[b'a'; 1000]);
+    }
+
+    #[test]
+    fn every_byte_pair_at_every_offset() {
+        // Each pair after ASCII, then ASCII or a continuation byte, so the
+        // vectors see every lead, overlong and range check, at both ends
+        for offset in [0, 1, 31, 62, 63, 64, 95] {
+            for first in 0..=255u8 {
+                for second in 0..=255u8 {
+                    for fill in [b'a', 0x80] {
+                        let mut bytes = vec![b'x'; 160];
+                        bytes[offset] = first;
+                        bytes[offset + 1] = second;
+                        bytes[offset + 2] = fill;
+                        check(&bytes);
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn truncated_sequences_at_the_end() {
+        for seq in ["é", "中", "😀"] {
+            for cut in 1..seq.len() {
+                for len in [63, 64, 65, 127, 128, 200] {
+                    let mut bytes = vec![b'a'; len];
+                    bytes.extend_from_slice(&seq.as_bytes()[..cut]);
+                    let err = validate_utf8_simd(&bytes).unwrap_err();
+                    assert_eq!((err.valid_up_to, err.error_len), (len, None));
+                    check(&bytes);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn random_corruption() {
+        let text = "Grüße, 世界! Ωμέγα 😀 plain ascii text ".repeat(30);
+        let mut state = 0x9E37_79B9_7F4A_7C15u64;
+        for _ in 0..2000 {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            let mut bytes = text.as_bytes().to_vec();
+            let at = state as usize % bytes.len();
+            bytes[at] = (state >> 32) as u8;
+            if state & 1 == 0 {
+                bytes.truncate(at + 1 + (state >> 40) as usize % 4);
+            }
+            check(&bytes);
+        }
+    }
+}
@@ -0,0 +1,115 @@
+//! Lazy mapping over Python iterators, processed in fixed-size batches.
+
+use std::sync::Arc;
+
+use pyo3::exceptions::{PyTypeError, PyValueError};
+use pyo3::prelude::*;
+use pyo3::types::{PyIterator, PyList, PyString};
+
+use crate::core;
+use crate::pipeline::Pipeline;
+
+/// Iterator returned by `map_iter`.
+///
+/// Pulls up to `batch_size` strings from the source under the GIL, runs the
+/// pipeline over the batch with `map_pylist` and yields results in order. At
+/// most one batch is materialized at a time, so memory stays bounded by
+/// `batch_size` regardless of the source length.
+#[pyclass(module = "yurki.internal")]
+pub struct MapIter {
+    source: Py<PyIterator>,
+    pipeline: Arc<Pipeline>,
+    jobs: usize,
+    batch_size: usize,
+    batch: Option<Py<PyList>>,
+    position: usize,
+    exhausted: bool,
+}
+
+impl MapIter {
+    pub fn new(
+        iterable: &Bound<PyAny>,
+        pipeline: Pipeline,
+        jobs: usize,
+        batch_size: usize,
+    ) -> PyResult<Self> {
+        if batch_size == 0 {
+            return Err(PyValueError::new_err("batch size must be > 0"));
+        }
+        if jobs == 0 {
+            return Err(PyValueError::new_err("jobs must be > 0"));
+        }
+
+        Ok(Self {
+            source: iterable.try_iter()?.unbind(),
+            pipeline: Arc::new(pipeline),
+            jobs,
+            batch_size,
+            batch: None,
+            position: 0,
+            exhausted: false,
+        })
+    }
+
+    // Pull the next batch from the source and map it; `None` once drained
+    fn next_batch(&mut self, py: Python) -> PyResult<Option<Py<PyList>>> {
+        let mut source = self.source.bind(py).clone();
+        let input = PyList::empty(py);
+
+        while input.len() < self.batch_size {
+            let Some(item) = source.next() else {
+                self.exhausted = true;
+                break;
+            };
+            let item = item?;
+            if !item.is_instance_of::<PyString>() {
+                return Err(PyTypeError::new_err(format!(
+                    "map_iter expects str items, got {}",
+                    item.get_type().name()?
+                )));
+            }
+            input.append(item)?;
+        }
+
+        if input.is_empty() {
+            return Ok(None);
+        }
+
+        let pipeline = self.pipeline.clone();
+        let make_func = move || unsafe {
+            let pipeline = pipeline.clone();
+            move |s: &str| pipeline.apply(s)
+        };
+
+        let jobs = self.jobs.min(input.len());
+        let output = core::map_pylist(py, &input, jobs, false, None, make_func)?;
+        Ok(Some(output.bind(py).downcast::<PyList>()?.clone().unbind()))
+    }
+}
+
+#[pymethods]
+impl MapIter {
+    fn __iter__(slf: PyRef<'_, Self>) -> PyRef<'_, Self> {
+        slf
+    }
+
+    fn __next__(&mut self, py: Python) -> PyResult<Option<PyObject>> {
+        loop {
+            if let Some(batch) = &self.batch {
+                let batch = batch.bind(py);
+                if self.position < batch.len() {
+                    let item = batch.get_item(self.position)?;
+                    self.position += 1;
+                    return Ok(Some(item.unbind()));
+                }
+            }
+
+            self.batch = None;
+            self.position = 0;
+            if self.exhausted {
+                return Ok(None);
+            }
+            self.batch = self.next_batch(py)?;
+        }
+    }
+}
@@ -1,5 +1,7 @@
+use crate::simd;
 use regex::Regex;
 use std::borrow::Cow;
+use std::collections::HashMap;
 
 pub fn find_in_string<'a>(string: &'a str, _pattern: &Regex) -> Cow<'a, str> {
     _pattern
@@ -8,10 +10,66 @@ pub fn find_in_string<'a>(string: &'a str, _pattern: &Regex) -> Cow<'a, str> {
         .unwrap_or(Cow::Borrowed(""))
 }
 
+pub fn find_in_string_opt<'a>(string: &'a str, pattern: &Regex) -> Option<&'a str> {
+    pattern.find(string).map(|m| m.as_str())
+}
+
+pub fn find_all_in_string<'a>(string: &'a str, pattern: &Regex) -> Vec<Cow<'a, str>> {
+    pattern
+        .find_iter(string)
+        .map(|m| Cow::Borrowed(m.as_str()))
+        .collect()
+}
+
+/// Like `find_all_in_string`, but the search for the next match resumes one
+/// character past the previous match start instead of past its end, so
+/// matches may overlap (`aa` is found 3 times in `aaaa`). At most one match
+/// starts at each position.
+///
+/// Every match restarts the search, so a string of n characters with matches
+/// of length m costs O(n·m) rather than O(n).
+pub fn find_all_overlapping<'a>(string: &'a str, pattern: &Regex) -> Vec<Cow<'a, str>> {
+    let mut matches = Vec::new();
+    let mut start = 0;
+    while let Some(m) = pattern.find_at(string, start) {
+        matches.push(Cow::Borrowed(m.as_str()));
+        match string[m.start()..].chars().next() {
+            Some(c) => start = m.start() + c.len_utf8(),
+            None => break,
+        }
+    }
+    matches
+}
+
 pub fn is_match_in_string(string: &str, pattern: &Regex) -> bool {
     pattern.is_match(string)
 }
 
+/// Wrap `pattern` so it only matches at the start of the input, or with
+/// `full` only across the whole input. `\A` and `\z` ignore `(?m)`, so the
+/// anchors hold for multi-line patterns too, as they do in Python.
+///
+/// `pattern` is parsed on its own first: wrapped, an unbalanced pattern like
+/// `a)|(b` would otherwise be accepted.
+pub fn anchored_pattern(pattern: &str, full: bool) -> Result<String, regex::Error> {
+    Regex::new(pattern)?;
+    Ok(if full {
+        format!(r"\A(?:{pattern})\z")
+    } else {
+        format!(r"\A(?:{pattern})")
+    })
+}
+
+/// Python's `re.match`; `pattern` must be built from `anchored_pattern(_, false)`.
+pub fn is_match_at_start(string: &str, pattern: &Regex) -> bool {
+    pattern.is_match(string)
+}
+
+/// Python's `re.fullmatch`; `pattern` must be built from `anchored_pattern(_, true)`.
+pub fn is_fullmatch_in_string(string: &str, pattern: &Regex) -> bool {
+    pattern.is_match(string)
+}
+
 pub fn capture_regex_in_string<'a>(string: &'a str, _pattern: &Regex) -> Vec<Cow<'a, str>> {
     _pattern
         .captures(string)
@@ -26,10 +84,118 @@ pub fn capture_regex_in_string<'a>(string: &'a str, _pattern: &Regex) -> Vec<Cow
         .unwrap_or_else(Vec::new)
 }
 
+/// Groups 1.. of the first match of `pattern` in `string`, `None` for groups
+/// that did not take part in it. All `None` when nothing matches.
+pub fn capture_groups<'a>(string: &'a str, pattern: &Regex) -> Vec<Option<&'a str>> {
+    match pattern.captures(string) {
+        Some(caps) => caps.iter().skip(1).map(|m| m.map(|m| m.as_str())).collect(),
+        None => vec![None; pattern.captures_len() - 1],
+    }
+}
+
+/// Byte range of group `group` of the first match of `pattern` in `string`,
+/// `None` when nothing matches or the group did not take part in the match.
+pub fn capture_group_span(
+    string: &str,
+    pattern: &Regex,
+    group: usize,
+) -> Option<std::ops::Range<usize>> {
+    if group == 0 {
+        return pattern.find(string).map(|m| m.range());
+    }
+    pattern.captures(string)?.get(group).map(|m| m.range())
+}
+
 pub fn split_by_regexp_string<'a>(string: &'a str, _pattern: &Regex) -> Vec<Cow<'a, str>> {
     _pattern.split(string).map(Cow::Borrowed).collect()
 }
 
+/// Split `string` into at most `n` fields. Returns the fields and whether
+/// the row had more than `n` (the rest are dropped).
+pub fn split_fields<'a>(string: &'a str, pattern: &Regex, n: usize) -> (Vec<&'a str>, bool) {
+    let mut fields: Vec<&str> = pattern.split(string).take(n + 1).collect();
+    let overflow = fields.len() > n;
+    fields.truncate(n);
+    (fields, overflow)
+}
+
+/// Python's `str.split()` with no separator: fields between runs of
+/// whitespace, with no empty fields, so leading and trailing whitespace
+/// yield none.
+///
+/// By default only ASCII whitespace separates, found with a SIMD scan.
+/// With `unicode`, every character `str.isspace` accepts does, and strings
+/// with non-ASCII characters are scanned one character at a time.
+pub fn split_whitespace_in_string(string: &str, unicode: bool) -> Vec<Cow<'_, str>> {
+    if unicode && !string.is_ascii() {
+        return string
+            .split(is_py_space)
+            .filter(|field| !field.is_empty())
+            .map(Cow::Borrowed)
+            .collect();
+    }
+    simd::split_on_ranges(string.as_bytes(), CharClass::Space.ascii_ranges())
+        .into_iter()
+        .map(|range| Cow::Borrowed(&string[range]))
+        .collect()
+}
+
+/// Whether `str.isspace` accepts `c`: White_Space plus the separators
+/// 0x1C-0x1F.
+fn is_py_space(c: char) -> bool {
+    c.is_whitespace() || ('\x1c'..='\x1f').contains(&c)
+}
+
+/// Python's `str.strip(chars)`, on the `left` and/or `right` end only for
+/// `lstrip` and `rstrip`. Without `chars`, whitespace is stripped.
+///
+/// A single ASCII character, as padding of fixed-width fields usually is,
+/// is skipped a vector at a time; other sets go character by character.
+pub fn strip_in_string<'a>(
+    string: &'a str,
+    chars: Option<&str>,
+    left: bool,
+    right: bool,
+) -> &'a str {
+    if let Some(&[byte]) = chars.map(str::as_bytes) {
+        // Stops at the first other byte, a character boundary
+        let bytes = string.as_bytes();
+        let start = if left {
+            simd::find_not_byte(bytes, byte).unwrap_or(bytes.len())
+        } else {
+            0
+        };
+        let end = if right {
+            simd::rfind_not_byte(&bytes[start..], byte).map_or(start, |i| start + i + 1)
+        } else {
+            bytes.len()
+        };
+        return &string[start..end];
+    }
+
+    let strip = |c: char| match chars {
+        Some(chars) => chars.contains(c),
+        None => is_py_space(c),
+    };
+    match (left, right) {
+        (true, true) => string.trim_matches(strip),
+        (true, false) => string.trim_start_matches(strip),
+        (false, true) => string.trim_end_matches(strip),
+        (false, false) => string,
+    }
+}
+
+/// Byte ranges of the first `count` matches of `pattern` in `string`, or of
+/// all of them when `count` is 0.
+pub fn match_spans(string: &str, pattern: &Regex, count: usize) -> Vec<(usize, usize)> {
+    let limit = if count == 0 { usize::MAX } else { count };
+    pattern
+        .find_iter(string)
+        .take(limit)
+        .map(|m| (m.start(), m.end()))
+        .collect()
+}
+
 pub fn replace_regexp_in_string<'a>(
     string: &'a str,
     _pattern: &Regex,
@@ -42,3 +208,410 @@ pub fn replace_regexp_in_string<'a>(
         _pattern.replacen(string, count, replacement)
     }
 }
+
+/// Byte offsets of the occurrences of `needle`, left to right as repeated
+/// `str.find` calls give them. Without `overlapping` the search resumes
+/// after each match, with it one character after the match start. An empty
+/// needle matches at every character boundary, including the end.
+pub fn match_indices_in_string(string: &str, needle: &str, overlapping: bool) -> Vec<usize> {
+    if let &[byte] = needle.as_bytes() {
+        let bytes = string.as_bytes();
+        let mut offsets = Vec::new();
+        let mut from = 0;
+        while let Some(at) = simd::find_byte(&bytes[from..], byte) {
+            offsets.push(from + at);
+            from += at + 1;
+        }
+        return offsets;
+    }
+    if !overlapping || needle.is_empty() {
+        return string.match_indices(needle).map(|(at, _)| at).collect();
+    }
+
+    // Matches start on character boundaries, so resume one character later
+    let step = needle.chars().next().map_or(1, char::len_utf8);
+    let mut offsets = Vec::new();
+    let mut from = 0;
+    while let Some(at) = string[from..].find(needle) {
+        offsets.push(from + at);
+        from += at + step;
+    }
+    offsets
+}
+
+/// Turn increasing byte offsets into `string` into character offsets.
+pub fn byte_to_char_offsets(string: &str, offsets: &mut [usize]) {
+    if string.is_ascii() {
+        return;
+    }
+    let (mut byte, mut chars) = (0, 0);
+    for offset in offsets {
+        chars += string[byte..*offset].chars().count();
+        byte = *offset;
+        *offset = chars;
+    }
+}
+
+/// Why `fill_template` rejected a row.
+#[derive(Debug, PartialEq)]
+pub enum TemplateError {
+    /// A placeholder named a key missing from the values.
+    MissingKey(String),
+    /// Unbalanced braces, with the message `str.format` gives.
+    Malformed(&'static str),
+}
+
+/// Replace each `{key}` in `string` with `values[key]`, like
+/// `str.format_map` with plain names: `{{` and `}}` stand for literal braces,
+/// and the key is everything up to the closing brace. Strings without braces
+/// are returned as is.
+pub fn fill_template<'a>(
+    string: &'a str,
+    values: &HashMap<String, String>,
+) -> Result<Cow<'a, str>, TemplateError> {
+    let Some(first) = string.find(['{', '}']) else {
+        return Ok(Cow::Borrowed(string));
+    };
+
+    let bytes = string.as_bytes();
+    let mut out = String::with_capacity(string.len());
+    out.push_str(&string[..first]);
+    let mut i = first;
+    while i < bytes.len() {
+        let (brace, next) = (bytes[i], bytes.get(i + 1).copied());
+        if next == Some(brace) {
+            out.push(brace as char);
+            i += 2;
+        } else if brace == b'}' {
+            return Err(TemplateError::Malformed(
+                "Single '}' encountered in format string",
+            ));
+        } else {
+            let field = &string[i + 1..];
+            let end = field.find(['{', '}']).ok_or(TemplateError::Malformed(
+                "expected '}' before end of string",
+            ))?;
+            if field.as_bytes()[end] == b'{' {
+                return Err(TemplateError::Malformed("unexpected '{' in field name"));
+            }
+            let key = &field[..end];
+            let value = values
+                .get(key)
+                .ok_or_else(|| TemplateError::MissingKey(key.to_string()))?;
+            out.push_str(value);
+            i += end + 2;
+        }
+
+        // Literal text up to the next brace
+        let literal = string[i..]
+            .find(['{', '}'])
+            .map_or(bytes.len(), |at| i + at);
+        out.push_str(&string[i..literal]);
+        i = literal;
+    }
+    Ok(Cow::Owned(out))
+}
+
+/// Byte offset `n` characters after byte offset `from`, or `None` if the
+/// string ends sooner.
+fn advance_chars(string: &str, ascii: bool, from: usize, n: usize) -> Option<usize> {
+    if ascii {
+        return (from + n <= string.len()).then_some(from + n);
+    }
+    let mut count = 0;
+    for (at, _) in string[from..].char_indices() {
+        if count == n {
+            return Some(from + at);
+        }
+        count += 1;
+    }
+    (count == n).then_some(string.len())
+}
+
+/// Byte range of the characters `start..end` of `string`, with the indices
+/// adjusted like the optional range of `str.startswith`: negative ones count
+/// from the end and `end` is clamped to the length. `None` when `start` lies
+/// past `end`, where CPython matches nothing, not even an empty prefix.
+pub fn char_range_to_bytes(
+    string: &str,
+    start: Option<isize>,
+    end: Option<isize>,
+) -> Option<std::ops::Range<usize>> {
+    let (start, end) = (start.unwrap_or(0), end.unwrap_or(isize::MAX));
+    let ascii = string.is_ascii();
+    // Only negative indices need the length up front
+    let len = if ascii {
+        string.len() as isize
+    } else if start < 0 || end < 0 {
+        char_len(string) as isize
+    } else {
+        0
+    };
+    let from_end = |i: isize| {
+        if i < 0 {
+            (i + len).max(0) as usize
+        } else {
+            i as usize
+        }
+    };
+    let (start, end) = (from_end(start), from_end(end));
+    if start > end {
+        return None;
+    }
+
+    let start_byte = advance_chars(string, ascii, 0, start)?;
+    let end_byte = advance_chars(string, ascii, start_byte, end - start).unwrap_or(string.len());
+    Some(start_byte..end_byte)
+}
+
+/// `str.startswith` and, with `suffix`, `str.endswith`: whether the
+/// characters `start..end` of `string` begin or end with one of `affixes`.
+pub fn has_affix(
+    string: &str,
+    affixes: &[String],
+    start: Option<isize>,
+    end: Option<isize>,
+    suffix: bool,
+) -> bool {
+    let Some(range) = char_range_to_bytes(string, start, end) else {
+        return false;
+    };
+    let region = &string[range];
+    affixes.iter().any(|affix| {
+        if suffix {
+            region.ends_with(affix.as_str())
+        } else {
+            region.starts_with(affix.as_str())
+        }
+    })
+}
+
+pub fn char_len(string: &str) -> usize {
+    string.chars().count()
+}
+
+pub fn ascii_upper_bytes(bytes: &mut [u8]) {
+    simd::ascii_upper_inplace(bytes);
+}
+
+pub fn ascii_upper_in_string(string: &str) -> String {
+    let mut out = vec![0; string.len()];
+    simd::ascii_upper(string.as_bytes(), &mut out);
+    // ASCII case mapping keeps UTF-8 valid
+    unsafe { String::from_utf8_unchecked(out) }
+}
+
+pub fn ascii_lower_in_string(string: &str) -> String {
+    let mut out = vec![0; string.len()];
+    simd::ascii_lower(string.as_bytes(), &mut out);
+    unsafe { String::from_utf8_unchecked(out) }
+}
+
+pub fn caesar_shift_bytes(bytes: &mut [u8], n: u8) {
+    simd::caesar_shift_inplace(bytes, n);
+}
+
+pub fn caesar_shift_in_string(string: &str, n: u8) -> String {
+    let mut out = vec![0; string.len()];
+    simd::caesar_shift(string.as_bytes(), &mut out, n);
+    // Only ASCII letters move, so UTF-8 stays valid
+    unsafe { String::from_utf8_unchecked(out) }
+}
+
+/// Python's `str.expandtabs`: each tab becomes spaces up to the next
+/// multiple of `tabsize` columns, counted in characters and reset by `\n`
+/// and `\r`. A `tabsize` of 0 or less drops the tabs. Borrows `string` when
+/// it has no tab.
+pub fn expandtabs_in_string(string: &str, tabsize: isize) -> Cow<'_, str> {
+    let bytes = string.as_bytes();
+    let Some(first_tab) = simd::find_byte(bytes, b'\t') else {
+        return Cow::Borrowed(string);
+    };
+
+    let mut out = String::with_capacity(string.len() + 8);
+    let mut column = 0;
+    let mut start = 0;
+    let mut tab = Some(first_tab);
+    while let Some(pos) = tab {
+        let segment = &string[start..pos];
+        out.push_str(segment);
+        column = match segment.rfind(['\n', '\r']) {
+            Some(line_start) => segment[line_start + 1..].chars().count(),
+            None => column + segment.chars().count(),
+        };
+        if tabsize > 0 {
+            let tabsize = tabsize as usize;
+            let spaces = tabsize - column % tabsize;
+            out.extend(std::iter::repeat_n(' ', spaces));
+            column += spaces;
+        }
+        start = pos + 1;
+        tab = simd::find_byte(&bytes[start..], b'\t').map(|i| start + i);
+    }
+    out.push_str(&string[start..]);
+    Cow::Owned(out)
+}
+
+pub fn concat_with_sep(a: &str, sep: &str, b: &str) -> String {
+    let mut out = String::with_capacity(a.len() + sep.len() + b.len());
+    out.push_str(a);
+    out.push_str(sep);
+    out.push_str(b);
+    out
+}
+
+pub fn to_lower_in_string(string: &str) -> Cow<'_, str> {
+    if string.is_ascii() {
+        if string.bytes().any(|b| b.is_ascii_uppercase()) {
+            let mut out = Vec::with_capacity(string.len());
+            simd::ascii_to_lower_simd(string.as_bytes(), &mut out);
+            // ASCII case mapping keeps UTF-8 valid
+            Cow::Owned(unsafe { String::from_utf8_unchecked(out) })
+        } else {
+            Cow::Borrowed(string)
+        }
+    } else {
+        Cow::Owned(string.to_lowercase())
+    }
+}
+
+pub fn to_upper_in_string(string: &str) -> Cow<'_, str> {
+    if string.is_ascii() {
+        if string.bytes().any(|b| b.is_ascii_lowercase()) {
+            let mut out = Vec::with_capacity(string.len());
+            simd::ascii_to_upper_simd(string.as_bytes(), &mut out);
+            Cow::Owned(unsafe { String::from_utf8_unchecked(out) })
+        } else {
+            Cow::Borrowed(string)
+        }
+    } else {
+        Cow::Owned(string.to_uppercase())
+    }
+}
+
+/// Python's `str.swapcase`: uppercase characters are lowercased and
+/// lowercase ones uppercased, with full mappings (`ß` becomes `SS`), and `Σ`
+/// becomes `ς` at the end of a word. Borrows `string` when it has no cased
+/// characters.
+pub fn swapcase_in_string(string: &str) -> Cow<'_, str> {
+    if string.is_ascii() {
+        if !string.bytes().any(|b| b.is_ascii_alphabetic()) {
+            return Cow::Borrowed(string);
+        }
+        let mut out = vec![0; string.len()];
+        simd::ascii_swapcase(string.as_bytes(), &mut out);
+        // Only ASCII letters change, so UTF-8 stays valid
+        return Cow::Owned(unsafe { String::from_utf8_unchecked(out) });
+    }
+    if !string.chars().any(|c| c.is_uppercase() || c.is_lowercase()) {
+        return Cow::Borrowed(string);
+    }
+
+    // Whether a `Σ` is final depends on the characters around it;
+    // `str::to_lowercase` applies that rule, so its choices are read back
+    let mut sigmas = if string.contains('Σ') {
+        lowercase_sigmas(string)
+    } else {
+        Vec::new()
+    }
+    .into_iter();
+
+    let mut out = String::with_capacity(string.len());
+    for c in string.chars() {
+        if c == 'Σ' {
+            out.push(sigmas.next().unwrap_or('σ'));
+        } else if c.is_uppercase() {
+            out.extend(c.to_lowercase());
+        } else if c.is_lowercase() {
+            out.extend(c.to_uppercase());
+        } else {
+            out.push(c);
+        }
+    }
+    Cow::Owned(out)
+}
+
+/// What each `Σ` of `string` lowercases to, in order: `ς` or `σ`.
+fn lowercase_sigmas(string: &str) -> Vec<char> {
+    let lower = string.to_lowercase();
+    let mut lower = lower.chars();
+    let mut sigmas = Vec::new();
+    for c in string.chars() {
+        if c == 'Σ' {
+            sigmas.extend(lower.next());
+        } else {
+            lower.by_ref().take(c.to_lowercase().count()).for_each(drop);
+        }
+    }
+    sigmas
+}
+
+/// Character classes of the `str.isdigit` family of predicates.
+#[derive(Clone, Copy, Debug)]
+pub enum CharClass {
+    Digit,
+    Alpha,
+    Alnum,
+    Space,
+}
+
+impl CharClass {
+    /// ASCII characters of the class, as inclusive byte ranges.
+    fn ascii_ranges(self) -> &'static [(u8, u8)] {
+        match self {
+            CharClass::Digit => &[(b'0', b'9')],
+            CharClass::Alpha => &[(b'A', b'Z'), (b'a', b'z')],
+            CharClass::Alnum => &[(b'0', b'9'), (b'A', b'Z'), (b'a', b'z')],
+            // `str.isspace` counts the separators 0x1C-0x1F too
+            CharClass::Space => &[(b'\t', b'\r'), (0x1c, b' ')],
+        }
+    }
+
+    /// Pattern matching a non-empty string of the class with CPython's
+    /// Unicode definitions, for `is_class_in_string`.
+    pub fn unicode_pattern(self) -> Regex {
+        let class = match self {
+            // Decimal digits, and the other characters with a digit value
+            // (superscripts, circled and parenthesized digits, ...)
+            CharClass::Digit => concat!(
+                r"\p{Nd}\x{B2}\x{B3}\x{B9}\x{1369}-\x{1371}\x{19DA}\x{2070}\x{2074}-\x{2079}",
+                r"\x{2080}-\x{2089}\x{2460}-\x{2468}\x{2474}-\x{247C}\x{2488}-\x{2490}\x{24EA}",
+                r"\x{24F5}-\x{24FD}\x{24FF}\x{2776}-\x{277E}\x{2780}-\x{2788}\x{278A}-\x{2792}",
+                r"\x{10A40}-\x{10A43}\x{10E60}-\x{10E68}\x{11052}-\x{1105A}\x{1F100}-\x{1F10A}",
+            ),
+            CharClass::Alpha => r"\p{L}",
+            CharClass::Alnum => r"\p{L}\p{N}",
+            CharClass::Space => r"\s\x1C-\x1F",
+        };
+        Regex::new(&format!(r"\A[{class}]+\z")).expect("invalid character class pattern")
+    }
+}
+
+/// Python's `str.isdigit`, `str.isalpha`, ... for `class`: true if `string`
+/// is non-empty and all its characters are in `class`.
+///
+/// ASCII is checked with SIMD range tests. A string with other characters
+/// is matched against `unicode`, from `CharClass::unicode_pattern`, or
+/// without it fails: only ASCII characters are counted in the class.
+pub fn is_class_in_string(string: &str, class: CharClass, unicode: Option<&Regex>) -> bool {
+    if string.is_empty() {
+        return false;
+    }
+    if simd::all_in_ranges(string.as_bytes(), class.ascii_ranges()) {
+        return true;
+    }
+    match unicode {
+        Some(pattern) if !string.is_ascii() => pattern.is_match(string),
+        _ => false,
+    }
+}
+
+pub fn replace_in_string<'a>(string: &'a str, from: &str, to: &str, count: usize) -> Cow<'a, str> {
+    if !string.contains(from) {
+        Cow::Borrowed(string)
+    } else if count == 0 {
+        Cow::Owned(string.replace(from, to))
+    } else {
+        Cow::Owned(string.replacen(from, to, count))
+    }
+}
@@ -0,0 +1,145 @@
+//! Per-character substitution with a `str.translate` table.
+
+use std::borrow::Cow;
+use std::collections::HashMap;
+
+use pyo3::exceptions::{PyTypeError, PyValueError};
+use pyo3::prelude::*;
+use pyo3::types::{PyDict, PySequence};
+
+use crate::simd;
+
+/// A `str.translate` table read once from Python, shared across workers.
+///
+/// Characters without an entry pass through; an entry may be empty, which
+/// deletes the character.
+pub struct TranslateTable {
+    ascii: [Option<String>; 128],
+    other: HashMap<char, String>,
+    /// `ascii` as a byte map, when every ASCII character maps to one ASCII
+    /// character or to itself
+    lut: Option<[u8; 128]>,
+}
+
+impl TranslateTable {
+    /// Read `table`, a dict keyed by code point or a sequence indexed by it
+    /// (a 256-entry list for Latin-1, say). Values are a code point, a `str`,
+    /// or `None` to delete, as for `str.translate`. Dict keys that aren't
+    /// code points are ignored, since `str.translate` never looks them up.
+    pub fn from_py(table: &Bound<PyAny>) -> PyResult<Self> {
+        let mut entries = Vec::new();
+        if let Ok(dict) = table.downcast::<PyDict>() {
+            for (key, value) in dict.iter() {
+                if let Some(c) = key.extract::<u32>().ok().and_then(char::from_u32) {
+                    entries.push((c, value));
+                }
+            }
+        } else if let Ok(sequence) = table.downcast::<PySequence>() {
+            for idx in 0..sequence.len()?.min(char::MAX as usize + 1) {
+                if let Some(c) = char::from_u32(idx as u32) {
+                    entries.push((c, sequence.get_item(idx)?));
+                }
+            }
+        } else {
+            return Err(PyTypeError::new_err(format!(
+                "translate table must be a dict or a sequence, got {}",
+                table.get_type().name()?
+            )));
+        }
+
+        let mut ascii = [const { None }; 128];
+        let mut other = HashMap::new();
+        for (c, value) in entries {
+            let replacement = replacement(&value)?;
+            if c.is_ascii() {
+                ascii[c as usize] = Some(replacement);
+            } else {
+                other.insert(c, replacement);
+            }
+        }
+
+        let mut lut: [u8; 128] = std::array::from_fn(|b| b as u8);
+        let byte_map = ascii
+            .iter()
+            .zip(lut.iter_mut())
+            .all(|(entry, byte)| match entry {
+                None => true,
+                Some(s) if s.len() == 1 && s.is_ascii() => {
+                    *byte = s.as_bytes()[0];
+                    true
+                }
+                Some(_) => false,
+            });
+
+        Ok(Self {
+            ascii,
+            other,
+            lut: byte_map.then_some(lut),
+        })
+    }
+
+    fn get(&self, c: char) -> Option<&str> {
+        if c.is_ascii() {
+            self.ascii[c as usize].as_deref()
+        } else {
+            self.other.get(&c).map(String::as_str)
+        }
+    }
+
+    /// `string` with every character that has an entry replaced by it.
+    /// Borrows `string` when the result is the same.
+    pub fn apply<'a>(&self, string: &'a str) -> Cow<'a, str> {
+        if let Some(lut) = &self.lut
+            && string.is_ascii()
+        {
+            let mut out = vec![0; string.len()];
+            simd::translate_ascii(string.as_bytes(), &mut out, lut);
+            if out == string.as_bytes() {
+                return Cow::Borrowed(string);
+            }
+            // ASCII mapped to ASCII stays valid UTF-8
+            return Cow::Owned(unsafe { String::from_utf8_unchecked(out) });
+        }
+
+        let Some((first, _)) = string.char_indices().find(|&(_, c)| self.get(c).is_some()) else {
+            return Cow::Borrowed(string);
+        };
+        let mut out = String::with_capacity(string.len());
+        out.push_str(&string[..first]);
+        for c in string[first..].chars() {
+            match self.get(c) {
+                Some(replacement) => out.push_str(replacement),
+                None => out.push(c),
+            }
+        }
+        if out == string {
+            Cow::Borrowed(string)
+        } else {
+            Cow::Owned(out)
+        }
+    }
+}
+
+/// A table value as replacement text, with `str.translate`'s errors.
+fn replacement(value: &Bound<PyAny>) -> PyResult<String> {
+    if value.is_none() {
+        return Ok(String::new());
+    }
+    if let Ok(s) = value.extract::<String>() {
+        return Ok(s);
+    }
+    if let Ok(code) = value.extract::<i64>() {
+        return u32::try_from(code)
+            .ok()
+            .filter(|&code| code < 0x110000)
+            .ok_or_else(|| PyValueError::new_err("character mapping must be in range(0x110000)"))
+            .and_then(|code| {
+                char::from_u32(code)
+                    .map(String::from)
+                    .ok_or_else(|| PyValueError::new_err("cannot map to a surrogate code point"))
+            });
+    }
+    Err(PyTypeError::new_err(
+        "character mapping must return integer, None or str",
+    ))
+}
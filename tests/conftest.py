@@ -0,0 +1,13 @@
+import pytest
+
+import yurki
+
+
+@pytest.fixture(autouse=True, scope="session")
+def honor_jobs():
+    # Run every map with the requested jobs, however small its input, so the
+    # parallel engine stays covered by small test data
+    previous = yurki.internal.get_parallel_threshold()
+    yurki.internal.set_parallel_threshold(0)
+    yield
+    yurki.internal.set_parallel_threshold(previous)
@@ -0,0 +1,74 @@
+import pytest
+
+import yurki
+
+
+JOBS = [1, 4]
+
+DATA = ["", "a", "abc", "abcdef", "éabcé", "日本語テキスト", "😀ab😀", "ab" * 50] * 50
+
+RANGES = [
+    (None, None),
+    (0, None),
+    (1, None),
+    (2, None),
+    (3, None),
+    (10, None),
+    (-1, None),
+    (-3, None),
+    (-100, None),
+    (1, -1),
+    (2, 2),
+    (2, 1),
+    (0, 100),
+    (-10, -20),
+    (None, 3),
+]
+
+AFFIXES = ["", "a", "ab", "b", "c", "cd", "é", "本語", "😀", "bab", ("x", "c"), ("é", "😀", "b"), ()]
+
+
+def call(method, data, affix, start, end, **kwargs):
+    func = getattr(yurki.internal, f"{method}_string")
+    return func(data, affix, start, end, **kwargs)
+
+
+class TestAffix:
+    @pytest.mark.parametrize("method", ["startswith", "endswith"])
+    @pytest.mark.parametrize("start, end", RANGES)
+    def test_matches_cpython(self, method, start, end):
+        for affix in AFFIXES:
+            expected = [getattr(s, method)(affix, start, end) for s in DATA]
+            assert call(method, DATA, affix, start, end, jobs=4) == expected, (affix, start, end)
+
+    def test_offset_range(self):
+        assert yurki.internal.startswith_string(["abcdef"], "cd", 2) == [True]
+        assert yurki.internal.startswith_string(["abcdef"], "cd", 2, 3) == [False]
+        assert yurki.internal.endswith_string(["abcdef"], "cd", 0, 4) == [True]
+        assert yurki.internal.endswith_string(["abcdef"], "cd", 3, 4) == [False]
+
+    def test_start_past_end(self):
+        # An empty prefix still fails when the region starts past the string
+        assert yurki.internal.startswith_string(["abc"], "", 3) == [True]
+        assert yurki.internal.startswith_string(["abc"], "", 5) == [False]
+        assert yurki.internal.endswith_string(["abc"], "", 2, 1) == [False]
+
+    def test_non_ascii_offsets_are_characters(self):
+        assert yurki.internal.startswith_string(["éé日x"], "日", 2) == [True]
+        assert yurki.internal.endswith_string(["éé日x"], "日", 0, -1) == [True]
+
+    @pytest.mark.parametrize("jobs", JOBS)
+    def test_inplace(self, jobs):
+        data = ["abc", "xbc"] * 100
+        result = yurki.internal.startswith_string(data, "a", jobs=jobs, inplace=True)
+        assert result is data
+        assert data[:2] == [True, False]
+
+    def test_type_errors(self):
+        with pytest.raises(TypeError, match="startswith first arg must be str or a tuple of str, not int"):
+            yurki.internal.startswith_string(["a"], 1)
+        with pytest.raises(TypeError, match="tuple for endswith must only contain str, not int"):
+            yurki.internal.endswith_string(["a"], ("a", 1))
+
+    def test_empty_list(self):
+        assert yurki.internal.startswith_string([], "a", jobs=4) == []
@@ -0,0 +1,71 @@
+import re
+
+import pytest
+
+import yurki
+
+
+JOBS = [1, 4]
+
+DATA = [
+    "abc",
+    "xabc",
+    "abcx",
+    "ab",
+    "",
+    "x\nabc",
+    "abcabc",
+    "привет мир",
+    "мир привет",
+]
+
+PATTERNS = [
+    r"abc",
+    r"a|ab",
+    r"a|abc",
+    r"b",
+    r"(abc)+",
+    r"\w+",
+    r"^abc$",
+    r"(?m)^abc$",
+    r"",
+    r"привет|мир",
+]
+
+
+def python(data, pattern, method):
+    compiled = re.compile(pattern)
+    return [bool(getattr(compiled, method)(s)) for s in data]
+
+
+class TestAnchoredMatch:
+    @pytest.mark.parametrize("jobs", JOBS)
+    @pytest.mark.parametrize("pattern", PATTERNS)
+    def test_against_re(self, jobs, pattern):
+        assert yurki.regexp.is_match(DATA, pattern, jobs=jobs) == python(DATA, pattern, "search")
+        assert yurki.regexp.is_match_at_start(DATA, pattern, jobs=jobs) == python(DATA, pattern, "match")
+        assert yurki.regexp.is_fullmatch(DATA, pattern, jobs=jobs) == python(DATA, pattern, "fullmatch")
+
+    @pytest.mark.parametrize("jobs", JOBS)
+    def test_case_insensitive(self, jobs):
+        data = ["ABC", "xabc", "Abcd"]
+        assert yurki.regexp.is_match_at_start(data, r"abc", case=True, jobs=jobs) == [True, False, True]
+        assert yurki.regexp.is_fullmatch(data, r"abc", case=True, jobs=jobs) == [True, False, False]
+
+    @pytest.mark.parametrize("jobs", JOBS)
+    def test_inplace_and_slice(self, jobs):
+        data = ["1a", "a1", "11", "a"]
+        assert yurki.regexp.is_fullmatch(data, r"\d+", jobs=jobs, start=1) == [False, True, False]
+        result = yurki.regexp.is_match_at_start(data, r"\d", jobs=jobs, inplace=True)
+        assert result is data
+        assert data == [True, False, True, False]
+
+    def test_empty_list(self):
+        assert yurki.regexp.is_match_at_start([], r"a", jobs=1) == []
+        assert yurki.regexp.is_fullmatch([], r"a", jobs=1) == []
+
+    def test_invalid_pattern(self):
+        with pytest.raises(ValueError):
+            yurki.regexp.is_match_at_start(["a"], r"(", jobs=1)
+        with pytest.raises(ValueError):
+            yurki.regexp.is_fullmatch(["a"], r"a)|(b", jobs=1)
@@ -0,0 +1,69 @@
+import pytest
+
+import yurki
+
+
+DEFAULT_CONFIG = (256, 16, 32, 100)
+
+
+class TestArenaConfig:
+    def test_default(self):
+        assert yurki.internal.get_arena_config() == DEFAULT_CONFIG
+
+    def test_set_and_process(self):
+        try:
+            yurki.internal.set_arena_config(64, 1, 2, batch_size=10)
+            assert yurki.internal.get_arena_config() == (64, 1, 2, 10)
+
+            data = [f"row {i} " + "x" * 1000 for i in range(2_000)]
+            expected = [f"{i}" for i in range(2_000)]
+            assert yurki.regexp.find(data, r"\d+", jobs=1) == expected
+            assert yurki.regexp.find(data, r"\d+", jobs=4) == expected
+        finally:
+            yurki.internal.set_arena_config(*DEFAULT_CONFIG)
+
+    @pytest.mark.parametrize("jobs", [1, 4])
+    def test_many_small_calls(self, jobs):
+        # Arenas are pooled across calls and must come back reset
+        data = [f"row {i}" for i in range(10)]
+        expected = [str(i) for i in range(10)]
+        for _ in range(2_000):
+            assert yurki.regexp.find(data, r"\d+", jobs=jobs) == expected
+
+    def test_pool_after_config_change(self):
+        data = [f"row {i} " + "y" * 100 for i in range(1_000)]
+        expected = [str(i) for i in range(1_000)]
+        try:
+            assert yurki.regexp.find(data, r"\d+", jobs=4) == expected
+            yurki.internal.set_arena_config(1, 1, 1, batch_size=1)
+            assert yurki.regexp.find(data, r"\d+", jobs=4) == expected
+        finally:
+            yurki.internal.set_arena_config(*DEFAULT_CONFIG)
+        assert yurki.regexp.find(data, r"\d+", jobs=4) == expected
+
+    @pytest.mark.parametrize(
+        "config",
+        [
+            (256, 32, 16, 100),  # free < reset
+            (256, 0, 32, 100),  # zero reset
+            (64 * 1024, 16, 32, 100),  # initial > reset
+            (256, 16, 32, 0),  # zero batch size
+        ],
+    )
+    def test_invalid(self, config):
+        with pytest.raises(ValueError):
+            yurki.internal.set_arena_config(*config)
+        assert yurki.internal.get_arena_config() == DEFAULT_CONFIG
+
+
+class TestBenchSmallCalls:
+    @pytest.mark.benchmark(group="small-calls")
+    @pytest.mark.parametrize("jobs", [1, 4], ids=lambda j: f"jobs={j}")
+    def test_100k_calls_10_items(self, jobs, benchmark):
+        data = [f"row {i}" for i in range(10)]
+
+        def run():
+            for _ in range(100_000):
+                yurki.regexp.find(data, r"\d+", jobs=jobs)
+
+        benchmark.pedantic(run, rounds=1, iterations=1)
@@ -0,0 +1,55 @@
+import random
+import string
+
+import pytest
+
+import yurki
+
+
+JOBS = [1, 4]
+
+
+def generate_test_data(size):
+    rng = random.Random(size)
+    alphabet = string.ascii_letters + string.digits + " _-"
+    return ["".join(rng.choices(alphabet, k=rng.randint(10, 200))) for _ in range(size)]
+
+
+class TestAsciiCase:
+    @pytest.mark.parametrize("jobs", JOBS)
+    def test_ascii(self, jobs):
+        data = generate_test_data(2_000)
+        assert yurki.internal.ascii_upper_string(data, jobs=jobs) == [s.upper() for s in data]
+        assert yurki.internal.ascii_lower_string(data, jobs=jobs) == [s.lower() for s in data]
+
+    @pytest.mark.parametrize("jobs", JOBS)
+    def test_non_ascii_untouched(self, jobs):
+        data = ["straße", "ÄÖÜ äöü", "café Olé", "ǅ ﬁ", "😀 Mixed Case " * 20, ""]
+        upper = yurki.internal.ascii_upper_string(data, jobs=jobs)
+        lower = yurki.internal.ascii_lower_string(data, jobs=jobs)
+        assert upper == ["STRAßE", "ÄÖÜ äöü", "CAFé OLé", "ǅ ﬁ", "😀 MIXED CASE " * 20, ""]
+        assert lower == ["straße", "ÄÖÜ äöü", "café olé", "ǅ ﬁ", "😀 mixed case " * 20, ""]
+
+    @pytest.mark.parametrize("jobs", JOBS)
+    def test_inplace(self, jobs):
+        data = ["Hello", "World"]
+        result = yurki.internal.ascii_upper_string(data, jobs=jobs, inplace=True)
+        assert result is data
+        assert data == ["HELLO", "WORLD"]
+
+
+class TestBenchAsciiUpper:
+    @pytest.fixture
+    def test_data(self):
+        return generate_test_data(100_000)
+
+    @pytest.mark.benchmark(group="ascii-upper")
+    @pytest.mark.parametrize("jobs", JOBS, ids=lambda j: f"jobs={j}")
+    def test_ascii_upper_rust(self, jobs, benchmark, test_data):
+        result = benchmark(yurki.internal.ascii_upper_string, test_data, jobs=jobs)
+        assert result == [s.upper() for s in test_data]
+
+    @pytest.mark.benchmark(group="ascii-upper")
+    def test_ascii_upper_python(self, benchmark, test_data):
+        result = benchmark(lambda data: [s.upper() for s in data], test_data)
+        assert result == [s.upper() for s in test_data]
@@ -0,0 +1,36 @@
+import pytest
+
+import yurki
+
+
+JOBS = [1, 4]
+
+MIXED = ["plain ascii 1", "", "café 2", "привет 3", "😀 emoji 4", "x" * 1000 + " 5", "ÿ" * 100 + " 6"]
+
+
+class TestAsciiFastPath:
+    @pytest.mark.parametrize("jobs", JOBS)
+    def test_all_ascii(self, jobs):
+        data = [f"row {i} " + "a" * (i % 70) for i in range(5_000)]
+        assert yurki.regexp.find(data, r"\d+", jobs=jobs) == [str(i) for i in range(5_000)]
+        expected = [s.rstrip("a") + "b" * (i % 70 > 0) for i, s in enumerate(data)]
+        assert yurki.regexp.replace(data, r"a+", "b", jobs=jobs) == expected
+
+    @pytest.mark.parametrize("jobs", JOBS)
+    def test_mixed_list(self, jobs):
+        data = MIXED * 500
+        expected = [s.split(" ")[-1] for s in data]
+        assert yurki.regexp.find(data, r"\d+$", jobs=jobs) == expected
+        assert yurki.regexp.split(data, r" ", jobs=jobs) == [s.split(" ") for s in data]
+
+    @pytest.mark.parametrize("jobs", JOBS)
+    def test_inplace_mixed(self, jobs):
+        data = MIXED * 100
+        expected = [s.split(" ")[0] for s in data]
+        yurki.regexp.replace(data, r" .*", "", jobs=jobs, inplace=True)
+        assert data == expected
+
+    @pytest.mark.parametrize("jobs", JOBS)
+    def test_yurki_strings_as_input(self, jobs):
+        first = yurki.regexp.find(MIXED * 10, r"\S+", jobs=jobs)
+        assert yurki.regexp.find(first, r".+", jobs=jobs) == [s.split(" ")[0] for s in MIXED * 10]
@@ -0,0 +1,70 @@
+import pytest
+
+import yurki
+
+
+JOBS = [1, 2, 4]
+
+
+def private_strings(n):
+    # Built at runtime, so each string is referenced only by the list
+    return ["".join(["row ", str(i), " abc"]) for i in range(n)]
+
+
+class TestAsciiUpperInplace:
+    @pytest.mark.parametrize("jobs", JOBS)
+    def test_private_strings_mutated_in_place(self, jobs):
+        data = private_strings(1_000)
+        ids = [id(s) for s in data]
+        result = yurki.internal.ascii_upper_inplace(data, jobs=jobs)
+        assert result is data
+        assert data == [f"ROW {i} ABC" for i in range(1_000)]
+        assert [id(s) for s in data] == ids
+
+    @pytest.mark.parametrize("jobs", JOBS)
+    def test_shared_strings_untouched(self, jobs):
+        data = private_strings(100)
+        kept = data[::2]
+        yurki.internal.ascii_upper_inplace(data, jobs=jobs)
+        assert data == [f"ROW {i} ABC" for i in range(100)]
+        assert kept == [f"row {i} abc" for i in range(0, 100, 2)]
+
+    def test_interned_strings_untouched(self):
+        data = ["hello", "", "x"]
+        yurki.internal.ascii_upper_inplace(data)
+        assert data == ["HELLO", "", "X"]
+        assert "hello" == "hel" + "lo"
+        assert "x" == chr(120)
+
+    @pytest.mark.parametrize("jobs", JOBS)
+    def test_non_ascii(self, jobs):
+        data = ["".join(["café ", str(i)]) for i in range(50)] + ["".join(["日本 abc", "😀"])]
+        yurki.internal.ascii_upper_inplace(data, jobs=jobs)
+        assert data == [f"CAFé {i}" for i in range(50)] + ["日本 ABC😀"]
+
+    def test_hash_updated(self):
+        data = private_strings(10)
+        seen = {s: i for i, s in enumerate(data[:5])}
+        yurki.internal.ascii_upper_inplace(data)
+        assert {s: i for i, s in enumerate(data)}["ROW 3 ABC"] == 3
+        assert hash(data[7]) == hash("ROW 7 ABC")
+        assert list(seen) == [f"row {i} abc" for i in range(5)]
+
+    def test_yurki_list_items_untouched(self):
+        results = yurki.regexp.replace(private_strings(10), "row", "item")
+        data = list(results)
+        yurki.internal.ascii_upper_inplace(data)
+        assert data == [f"ITEM {i} ABC" for i in range(10)]
+        assert list(results) == [f"item {i} abc" for i in range(10)]
+
+    def test_rejects_yurki_list(self):
+        results = yurki.regexp.replace(private_strings(10), "row", "item")
+        with pytest.raises(TypeError, match="fixed-size"):
+            yurki.internal.ascii_upper_inplace(results)
+
+    def test_rejects_non_str(self):
+        with pytest.raises(TypeError, match="index 1"):
+            yurki.internal.ascii_upper_inplace(["a", 1])
+
+    def test_empty(self):
+        assert yurki.internal.ascii_upper_inplace([]) == []
@@ -0,0 +1,69 @@
+import codecs
+import sys
+
+import pytest
+
+import yurki
+
+
+JOBS = [1, 2, 4, 7]
+
+
+def caesar(s, n):
+    def rotate(c):
+        if "a" <= c <= "z":
+            return chr((ord(c) - ord("a") + n) % 26 + ord("a"))
+        if "A" <= c <= "Z":
+            return chr((ord(c) - ord("A") + n) % 26 + ord("A"))
+        return c
+
+    return "".join(rotate(c) for c in s)
+
+
+class TestCaesar:
+    @pytest.mark.parametrize("jobs", JOBS)
+    def test_rot13(self, jobs):
+        data = ["Hello, World!", "", "abcxyz ABCXYZ", "café Ünïcode 😀 zZ", "x" * 1_000] * 200
+        assert yurki.internal.rot13(data, jobs=jobs) == [codecs.encode(s, "rot13") for s in data]
+
+    @pytest.mark.parametrize("jobs", JOBS)
+    @pytest.mark.parametrize("inplace", [False, True])
+    def test_rot13_roundtrip(self, jobs, inplace):
+        data = [f"Row {i}: The quick brown fox, ÿ {i * 7}" for i in range(100_000)]
+        expected = list(data)
+        once = yurki.internal.rot13(data, jobs=jobs, inplace=inplace)
+        assert once != expected
+        assert yurki.internal.rot13(once, jobs=jobs, inplace=inplace) == expected
+
+    @pytest.mark.parametrize("n", [0, 1, 3, 25, 26, 27, -1, -13, 1_000])
+    def test_caesar_shift(self, n):
+        data = ["abcdefghijklmnopqrstuvwxyz", "ABCDEFGHIJKLMNOPQRSTUVWXYZ", "0-9 !? é"] * 50
+        expected = [caesar(s, n) for s in data]
+        assert yurki.internal.caesar_shift(data, n, jobs=2) == expected
+        assert yurki.internal.caesar_shift(list(data), n, jobs=2, inplace=True) == expected
+
+    @pytest.mark.parametrize("jobs", JOBS)
+    def test_inplace_returns_same_list(self, jobs):
+        data = [f"item {i}" for i in range(1_000)]
+        result = yurki.internal.rot13(data, jobs=jobs, inplace=True)
+        assert result is data
+        assert data[0] == "vgrz 0"
+
+    @pytest.mark.parametrize("jobs", JOBS)
+    def test_inplace_leaves_shared_strings_alone(self, jobs):
+        shared = "".join(["shared ", "value"])
+        interned = sys.intern("".join(["interned ", "value"]))
+        data = [shared, interned, "literal"] + [f"private {i}" for i in range(1_000)]
+        yurki.internal.rot13(data, jobs=jobs, inplace=True)
+        assert shared == "shared " + "value"
+        assert interned == "interned " + "value"
+        assert data[:3] == ["funerq inyhr", "vagrearq inyhr", "yvgreny"]
+        assert data[3] == "cevingr 0"
+
+    def test_inplace_rejects_non_strings(self):
+        with pytest.raises(TypeError):
+            yurki.internal.rot13(["a", 1], inplace=True)
+
+    def test_empty(self):
+        assert yurki.internal.rot13([]) == []
+        assert yurki.internal.caesar_shift([], 3, inplace=True) == []
@@ -0,0 +1,49 @@
+import os
+import time
+import subprocess
+
+import pytest
+
+import yurki
+
+
+JOBS = [1, 4]
+
+
+def generate_test_data(size):
+    return [f"{'abc ' * 50}{i}" for i in range(size)]
+
+
+def interrupt_after(delay):
+    # The call holds the GIL, so the signal must come from outside the interpreter
+    return subprocess.Popen(["sh", "-c", f"sleep {delay}; kill -INT {os.getpid()}"])
+
+
+class TestCancellation:
+    @pytest.mark.parametrize("jobs", JOBS)
+    @pytest.mark.parametrize("inplace", [False, True])
+    def test_keyboard_interrupt(self, jobs, inplace):
+        data = generate_test_data(1_000_000)
+        killer = interrupt_after(0.2)
+        with pytest.raises(KeyboardInterrupt):
+            yurki.regexp.replace(data, r"(\w+) (\w+)", "$2 $1", count=0, jobs=jobs, inplace=inplace)
+        killer.wait()
+
+        # Module stays usable after an interrupted call
+        assert yurki.regexp.find(["a1"], r"\d", jobs=jobs) == ["1"]
+
+    @pytest.mark.parametrize("jobs", JOBS)
+    def test_workers_stop_early(self, jobs):
+        data = generate_test_data(1_000_000)
+        killer = interrupt_after(0.2)
+        start = time.monotonic()
+        with pytest.raises(KeyboardInterrupt):
+            yurki.regexp.replace(data, r"abc", "xyz", count=0, jobs=jobs, inplace=True)
+        elapsed = time.monotonic() - start
+        killer.wait()
+
+        # Aborted shortly after the signal, leaving the tail of every range untouched
+        assert elapsed < 5
+        assert all(isinstance(s, str) for s in data)
+        assert data[-1].startswith("abc")
+        assert any(s.startswith("xyz") for s in data[:1000])
@@ -0,0 +1,77 @@
+import re
+import sys
+
+import pytest
+
+import yurki
+
+
+JOBS = [1, 2, 4, 7]
+
+
+def capture_columns(data, pattern):
+    n = re.compile(pattern).groups
+    columns = [[] for _ in range(n)]
+    for s in data:
+        m = re.search(pattern, s)
+        for k, column in enumerate(columns):
+            column.append(m.group(k + 1) if m else None)
+    return tuple(columns)
+
+
+class TestCaptureColumns:
+    @pytest.mark.parametrize("jobs", JOBS)
+    def test_capture_columns(self, jobs):
+        data = [f"id {i}-{i * 2} end" if i % 3 else "no digits" for i in range(10_000)]
+        result = yurki.regexp.capture_columns(data, r"(\d+)-(\d+)", jobs=jobs)
+        assert isinstance(result, tuple)
+        assert all(isinstance(column, yurki.internal.List) for column in result)
+        assert result == capture_columns(data, r"(\d+)-(\d+)")
+
+    @pytest.mark.parametrize("jobs", JOBS)
+    def test_optional_groups(self, jobs):
+        data = ["ab", "a", "b", "xax"] * 100
+        result = yurki.regexp.capture_columns(data, r"(a)(b)?", jobs=jobs)
+        assert result == capture_columns(data, r"(a)(b)?")
+        assert result[1][:4] == ["b", None, None, None]
+
+    def test_matches_capture(self):
+        data = ["name: John 42", "name: Ann", "age only 7"]
+        pattern = r"name: (\w+)(?: (\d+))?"
+        columns = yurki.regexp.capture_columns(data, pattern, jobs=1)
+        rows = yurki.regexp.capture(data, pattern, jobs=1)
+        assert columns == (["John", "Ann", None], ["42", None, None])
+        # capture keeps the whole match first and "" for groups that did not match
+        assert [row[1:] for row in rows] == [["John", "42"], ["Ann", ""], []]
+
+    @pytest.mark.parametrize("jobs", JOBS)
+    def test_unicode_and_named_groups(self, jobs):
+        data = ["café=日本語", "naïve=🙂", "none"]
+        result = yurki.regexp.capture_columns(data, r"(?P<key>\w+)=(?P<value>.+)", jobs=jobs)
+        assert result == (["café", "naïve", None], ["日本語", "🙂", None])
+
+    def test_case(self):
+        assert yurki.regexp.capture_columns(["KEY=1"], r"(key)=(\d)", jobs=1) == ([None], [None])
+        assert yurki.regexp.capture_columns(["KEY=1"], r"(key)=(\d)", case=True, jobs=1) == (["KEY"], ["1"])
+
+    def test_refcount(self):
+        data = ["key=value", "nothing"] * 500
+        before = sys.getrefcount(None)
+        keys, values = yurki.regexp.capture_columns(data, r"(\w+)=(\w+)", jobs=4)
+        assert sys.getrefcount(keys[0]) == 2
+        assert keys[1::2] == [None] * 500
+        del keys, values
+        assert sys.getrefcount(None) == before
+
+    def test_invalid_arguments(self):
+        with pytest.raises(ValueError, match="no capture groups"):
+            yurki.regexp.capture_columns(["a"], r"\w+", jobs=1)
+        with pytest.raises(ValueError, match="no capture groups"):
+            yurki.regexp.capture_columns(["a"], r"(?:a)", jobs=1)
+        with pytest.raises(ValueError):
+            yurki.regexp.capture_columns(["a"], r"(unclosed", jobs=1)
+        with pytest.raises(TypeError):
+            yurki.regexp.capture_columns(["a", 1], r"(a)", jobs=1)
+
+    def test_empty(self):
+        assert yurki.regexp.capture_columns([], r"(a)(b)", jobs=4) == ([], [])
@@ -0,0 +1,96 @@
+import unicodedata
+
+import pytest
+
+import yurki
+
+
+JOBS = [1, 4]
+
+PREDICATES = ["isdigit", "isalpha", "isalnum", "isspace"]
+
+DATA = [
+    "",
+    "0123456789",
+    "12a",
+    "abcXYZ",
+    "abc1",
+    " \t\n\r\x0b\x0c",
+    "\x1c\x1d\x1e\x1f",
+    "a b",
+    "x" * 100,
+    "7" * 100 + "x",
+    " " * 65,
+    "²³",
+    "①②",
+    "٣٤٥",
+    "é",
+    "日本語",
+    "Ⅻ",
+    " 　",
+    "😀",
+    "abc😀",
+]
+
+
+def predicate(name):
+    return getattr(yurki.internal, f"{name}_string")
+
+
+def ascii_only(name, s):
+    return s.isascii() and getattr(s, name)()
+
+
+class TestCharClass:
+    @pytest.mark.parametrize("jobs", JOBS)
+    @pytest.mark.parametrize("name", PREDICATES)
+    def test_unicode_matches_str(self, name, jobs):
+        result = predicate(name)(DATA * 20, unicode=True, jobs=jobs)
+        assert result == [getattr(s, name)() for s in DATA * 20]
+
+    @pytest.mark.parametrize("jobs", JOBS)
+    @pytest.mark.parametrize("name", PREDICATES)
+    def test_ascii_only_by_default(self, name, jobs):
+        result = predicate(name)(DATA * 20, jobs=jobs)
+        assert result == [ascii_only(name, s) for s in DATA * 20]
+
+    @pytest.mark.parametrize("name", PREDICATES)
+    def test_every_ascii_character(self, name):
+        chars = [chr(c) for c in range(128)]
+        for unicode in (False, True):
+            assert predicate(name)(chars, unicode=unicode) == [getattr(c, name)() for c in chars]
+            # Long enough for whole vectors, with the character last
+            padded = [("0a" if name == "isalnum" else "") + c * 70 for c in chars]
+            expected = [getattr(s, name)() for s in padded]
+            assert predicate(name)(padded, unicode=unicode) == expected
+
+    @pytest.mark.parametrize("name", PREDICATES)
+    def test_every_code_point(self, name):
+        # Characters assigned after the interpreter's Unicode version may differ
+        chars = [
+            chr(cp)
+            for cp in range(0x80, 0x110000)
+            if not 0xD800 <= cp < 0xE000 and unicodedata.category(chr(cp)) != "Cn"
+        ]
+        result = predicate(name)(chars, unicode=True)
+        assert [c for c, got in zip(chars, result) if got != getattr(c, name)()] == []
+        assert not any(predicate(name)(chars))
+
+    @pytest.mark.parametrize("name", PREDICATES)
+    def test_empty_string_is_false(self, name):
+        assert predicate(name)(["", ""], unicode=True) == [False, False]
+        assert predicate(name)([""]) == [False]
+
+    def test_results_are_bools(self):
+        assert all(type(b) is bool for b in yurki.internal.isdigit_string(["1", "a", ""]))
+
+    @pytest.mark.parametrize("jobs", JOBS)
+    def test_inplace_and_slice(self, jobs):
+        data = ["1", "a", "2", "b"]
+        assert yurki.internal.isalpha_string(data, jobs=jobs, start=1) == [True, False, True]
+        result = yurki.internal.isdigit_string(data, jobs=jobs, inplace=True, step=2)
+        assert result is data
+        assert data == [True, "a", True, "b"]
+
+    def test_empty_list(self):
+        assert yurki.internal.isspace_string([], jobs=4) == []
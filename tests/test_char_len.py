@@ -0,0 +1,41 @@
+import sys
+
+import pytest
+
+import yurki
+
+
+JOBS = [1, 2, 4, 7]
+
+
+class TestCharLen:
+    @pytest.mark.parametrize("jobs", JOBS)
+    def test_char_len(self, jobs):
+        data = ["", "a", "café", "日本語", "🙂🙃", "x" * 10_000] * 500
+        assert yurki.internal.char_len(data, jobs=jobs) == [len(s) for s in data]
+
+    @pytest.mark.parametrize("jobs", JOBS)
+    def test_inplace(self, jobs):
+        data = [f"row {i}" for i in range(1_000)]
+        expected = [len(s) for s in data]
+        result = yurki.internal.char_len(data, jobs=jobs, inplace=True)
+        assert result is data
+        assert data == expected
+
+    @pytest.mark.parametrize("jobs", JOBS)
+    def test_slice(self, jobs):
+        data = ["a" * i for i in range(100)]
+        assert yurki.internal.char_len(data, jobs=jobs, start=10, step=3) == list(range(10, 100, 3))
+
+    def test_empty(self):
+        assert yurki.internal.char_len([]) == []
+
+    @pytest.mark.parametrize("jobs", JOBS)
+    def test_refcount(self, jobs):
+        data = ["abc"] * 100_000 + ["y" * 1_000] * 1_000
+        small_before = sys.getrefcount(3)
+        result = yurki.internal.char_len(data, jobs=jobs)
+        assert result.count(3) == 100_000
+        assert sys.getrefcount(result[-1]) == 2
+        del result
+        assert sys.getrefcount(3) == small_before
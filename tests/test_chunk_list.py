@@ -0,0 +1,49 @@
+import gc
+import sys
+
+import pytest
+
+import yurki
+
+
+class TestChunkList:
+    @pytest.mark.parametrize("size", [1, 2, 3, 7, 100, 101, 1000])
+    def test_flattening_reproduces_the_list(self, size):
+        data = [f"row {i} é" for i in range(100)]
+        chunks = yurki.internal.chunk_list(data, size)
+        assert isinstance(chunks, yurki.internal.List)
+        assert all(isinstance(chunk, yurki.internal.List) for chunk in chunks)
+        assert [item for chunk in chunks for item in chunk] == data
+        assert [len(chunk) for chunk in chunks[:-1]] == [size] * (len(chunks) - 1)
+        assert 0 < len(chunks[-1]) <= size
+
+    def test_chunks_share_the_objects(self):
+        data = ["a" * 10, "é" * 10, "😀" * 10, 1, None]
+        chunks = yurki.internal.chunk_list(data, 2)
+        assert [[id(x) for x in chunk] for chunk in chunks] == [
+            [id(data[0]), id(data[1])],
+            [id(data[2]), id(data[3])],
+            [id(data[4])],
+        ]
+
+    def test_references(self):
+        # Built at runtime, as constants may be interned and immortal
+        item = "".join(["x", "é"]) * 50
+        before = sys.getrefcount(item)
+        chunks = yurki.internal.chunk_list([item] * 10, 3)
+        assert sys.getrefcount(item) == before + 10
+        del chunks
+        gc.collect()
+        assert sys.getrefcount(item) == before
+
+    def test_yurki_list_input(self):
+        data = yurki.regexp.find(["a1", "b2", "c3"], r"\d")
+        assert yurki.internal.chunk_list(data, 2) == [["1", "2"], ["3"]]
+
+    def test_empty(self):
+        assert yurki.internal.chunk_list([], 3) == []
+
+    @pytest.mark.parametrize("size", [0, -1])
+    def test_size_must_be_positive(self, size):
+        with pytest.raises(ValueError, match="size must be positive"):
+            yurki.internal.chunk_list(["a"], size)
@@ -0,0 +1,44 @@
+import pytest
+
+import yurki
+
+
+JOBS = [1, 4]
+PATTERNS = {"number": r"^\d+$", "email": r"^\S+@\S+$", "word": r"^\w+$"}
+
+
+class TestClassifier:
+    @pytest.mark.parametrize("jobs", JOBS)
+    @pytest.mark.parametrize("inplace", [False, True])
+    def test_classify(self, jobs, inplace):
+        data = ["123", "me@host", "hello", "two words"] * 10
+        expected = ["number", "email", "word", "other"] * 10
+        clf = yurki.regexp.classifier(PATTERNS, default="other")
+        assert clf.classify(data, jobs=jobs, inplace=inplace) == expected
+
+    @pytest.mark.parametrize("jobs", JOBS)
+    def test_multi(self, jobs):
+        clf = yurki.regexp.classifier(PATTERNS)
+        result = clf.classify(["123", "me@host", "-"], multi=True, jobs=jobs)
+        assert result == [["number", "word"], ["email"], []]
+
+    def test_priority(self):
+        clf = yurki.regexp.classifier(PATTERNS, priority=["word"])
+        assert clf.labels == ["word", "number", "email"]
+        assert clf.classify(["123"]) == ["word"]
+        assert clf.classify(["123"], multi=True) == [["word", "number"]]
+
+    def test_case(self):
+        assert yurki.regexp.classifier({"x": "abc"}).classify(["ABC"]) == [""]
+        assert yurki.regexp.classifier({"x": "abc"}, case=True).classify(["ABC"]) == ["x"]
+
+    def test_invalid(self):
+        with pytest.raises(ValueError):
+            yurki.regexp.classifier({"bad": "(unclosed"})
+        with pytest.raises(ValueError):
+            yurki.regexp.classifier(PATTERNS, priority=["missing"])
+
+    def test_empty(self):
+        clf = yurki.regexp.classifier({}, default="none")
+        assert len(clf) == 0
+        assert clf.classify(["a", "b"]) == ["none", "none"]
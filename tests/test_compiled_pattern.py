@@ -0,0 +1,75 @@
+from concurrent.futures import ThreadPoolExecutor
+
+import pytest
+
+import yurki
+
+
+JOBS = [1, 4]
+
+
+class TestCompiledPattern:
+    @pytest.mark.parametrize("jobs", JOBS)
+    def test_reusable(self, jobs):
+        pattern = yurki.regexp.compile(r"\d+")
+        data = ["a1b22", "none", "333"]
+
+        assert pattern.findall(data, jobs=jobs) == [["1", "22"], [], ["333"]]
+        assert pattern.match(data, jobs=jobs) == [True, False, True]
+        assert pattern.replace(data, "N", jobs=jobs) == ["aNb22", "none", "N"]
+        assert pattern.replace(data, "N", count=0, jobs=jobs) == ["aNbN", "none", "N"]
+        # Second round on the same instance gives the same results
+        assert pattern.findall(data, jobs=jobs) == [["1", "22"], [], ["333"]]
+
+    def test_flags(self):
+        assert yurki.regexp.compile(r"^b", multiline=True).match(["a\nb"]) == [True]
+        assert yurki.regexp.compile(r"^b").match(["a\nb"]) == [False]
+        assert yurki.regexp.compile(r"a.b", dotall=True).match(["a\nb"]) == [True]
+        assert yurki.regexp.compile(r"HELLO", case=True).match(["hello"]) == [True]
+
+        pattern = yurki.regexp.compile(r"x", case=True, multiline=True)
+        assert (pattern.pattern, pattern.case, pattern.multiline, pattern.dotall) == ("x", True, True, False)
+
+    def test_invalid_pattern(self):
+        with pytest.raises(ValueError):
+            yurki.regexp.compile(r"(unclosed")
+
+    @pytest.mark.parametrize("inplace", [False, True])
+    def test_inplace(self, inplace):
+        data = ["x1", "y2"]
+        result = yurki.regexp.compile(r"\d").replace(data, "#", inplace=inplace)
+        assert result == ["x#", "y#"]
+        assert (data == result) == inplace
+
+    def test_thread_safe(self):
+        pattern = yurki.regexp.compile(r"(\w+)@(\w+)")
+        data = [f"user{i}@host{i}" for i in range(1_000)]
+        expected = [f"host{i}@user{i}" for i in range(1_000)]
+
+        with ThreadPoolExecutor(max_workers=8) as pool:
+            results = list(pool.map(lambda _: pattern.replace(data, "$2@$1", jobs=2), range(16)))
+
+        assert all(result == expected for result in results)
+
+    @pytest.mark.parametrize("jobs", JOBS)
+    def test_findall_overlapping(self, jobs):
+        pattern = yurki.regexp.compile(r"aa")
+        assert pattern.findall(["aaaa"], jobs=jobs) == [["aa", "aa"]]
+        assert pattern.findall(["aaaa"], jobs=jobs, overlapping=True) == [["aa", "aa", "aa"]]
+
+        codons = yurki.regexp.compile(r"ATG|TGA|GAT")
+        assert codons.findall(["ATGATGA", "CCC"], jobs=jobs, overlapping=True) == [
+            ["ATG", "TGA", "GAT", "ATG", "TGA"],
+            [],
+        ]
+
+    def test_findall_overlapping_unicode_and_anchors(self):
+        assert yurki.regexp.compile(r"日本").findall(["日本日本"], overlapping=True) == [["日本", "日本"]]
+        assert yurki.regexp.compile(r"é.").findall(["éééé"], overlapping=True) == [["éé", "éé", "éé"]]
+        # Later searches keep the context of the whole string
+        assert yurki.regexp.compile(r"^ab").findall(["abab"], overlapping=True) == [["ab"]]
+        assert yurki.regexp.compile(r"\bb\w").findall(["ab bc"], overlapping=True) == [["bc"]]
+
+    def test_findall_overlapping_empty_matches(self):
+        assert yurki.regexp.compile(r"a*").findall(["aab"], overlapping=True) == [["aa", "a", "", ""]]
+        assert yurki.regexp.compile(r"x?").findall([""], overlapping=True) == [[""]]
@@ -0,0 +1,47 @@
+import pytest
+
+import yurki
+
+
+JOBS = [1, 2, 4, 7]
+
+
+class TestConcatPairwise:
+    @pytest.mark.parametrize("jobs", JOBS)
+    def test_concat(self, jobs):
+        a = [f"id{i}" for i in range(10_000)]
+        b = [f"{i * 7}" for i in range(10_000)]
+        result = yurki.internal.concat_pairwise(a, b, "|", jobs=jobs)
+        assert result == [f"{x}|{y}" for x, y in zip(a, b)]
+
+    @pytest.mark.parametrize("jobs", JOBS)
+    def test_unicode(self, jobs):
+        a = ["café", "日本", "🙂", "", "x"]
+        b = ["naïve", "語", "", "🙃", ""]
+        result = yurki.internal.concat_pairwise(a, b, " → ", jobs=jobs)
+        assert result == [x + " → " + y for x, y in zip(a, b)]
+
+    @pytest.mark.parametrize("jobs", JOBS)
+    def test_empty_separator(self, jobs):
+        a = ["ab", "ü", ""] * 100
+        b = ["cd", "ß", ""] * 100
+        assert yurki.internal.concat_pairwise(a, b, "", jobs=jobs) == [x + y for x, y in zip(a, b)]
+        assert yurki.internal.concat_pairwise(a, b, jobs=jobs) == [x + y for x, y in zip(a, b)]
+
+    @pytest.mark.parametrize("jobs", JOBS)
+    def test_inplace(self, jobs):
+        a = ["a", "b", "c"]
+        result = yurki.internal.concat_pairwise(a, ["1", "2", "3"], "-", jobs=jobs, inplace=True)
+        assert result is a
+        assert a == ["a-1", "b-2", "c-3"]
+
+    def test_length_mismatch(self):
+        with pytest.raises(ValueError, match="equal length"):
+            yurki.internal.concat_pairwise(["a", "b"], ["a"], ":", jobs=4)
+
+    def test_non_string(self):
+        with pytest.raises(TypeError):
+            yurki.internal.concat_pairwise(["a"], [1], ":")
+
+    def test_empty(self):
+        assert yurki.internal.concat_pairwise([], [], ":") == []
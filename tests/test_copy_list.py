@@ -0,0 +1,62 @@
+import gc
+import sys
+
+import pytest
+
+import yurki
+
+
+JOBS = [1, 4]
+
+DATA = [f"row {i} é {'😀' * (i % 3)}" for i in range(3_000)]
+
+
+class MyStr(str):
+    pass
+
+
+class TestCopyList:
+    @pytest.mark.parametrize("jobs", JOBS)
+    def test_copy_shares_the_strings(self, jobs):
+        result = yurki.internal.copy_list(DATA, jobs=jobs)
+        assert isinstance(result, yurki.internal.List)
+        assert result == DATA
+        assert all(a is b for a, b in zip(result, DATA))
+        assert gc.is_tracked(result)
+
+    @pytest.mark.parametrize("jobs", JOBS)
+    def test_references(self, jobs):
+        # Built at runtime, as constants may be interned and immortal
+        item = "".join(["x", "é"]) * 50
+        before = sys.getrefcount(item)
+        result = yurki.internal.copy_list([item] * 100, jobs=jobs)
+        assert sys.getrefcount(item) == before + 100
+        del result
+        gc.collect()
+        assert sys.getrefcount(item) == before
+
+    @pytest.mark.parametrize("jobs", JOBS)
+    def test_survives_inplace_on_original(self, jobs):
+        data = list(DATA)
+        copy = yurki.internal.copy_list(data, jobs=jobs)
+        yurki.regexp.replace(data, r"row", "line", jobs=jobs, inplace=True)
+        assert copy == DATA
+        assert data == [s.replace("row", "line") for s in DATA]
+
+    def test_yurki_strings_are_shared(self):
+        data = yurki.regexp.find(DATA, r"row \d+")
+        result = yurki.internal.copy_list(data, jobs=4)
+        assert all(a is b for a, b in zip(result, data))
+
+    def test_subclasses_become_new_strings(self):
+        data = [MyStr("abc"), MyStr("é")]
+        result = yurki.internal.copy_list(data)
+        assert result == ["abc", "é"]
+        assert not any(isinstance(s, MyStr) for s in result)
+
+    def test_empty(self):
+        assert yurki.internal.copy_list([], jobs=4) == []
+
+    def test_rejects_non_strings(self):
+        with pytest.raises(TypeError):
+            yurki.internal.copy_list(["a", 1])
@@ -0,0 +1,42 @@
+import random
+
+import pytest
+
+import yurki
+
+
+JOBS = [1, 4]
+
+
+def random_rows(n, seed=7):
+    rng = random.Random(seed)
+    return ["".join(rng.choice("ab,;\té😀") for _ in range(rng.randrange(200))) for _ in range(n)]
+
+
+class TestCountCharByte:
+    @pytest.mark.parametrize("jobs", JOBS)
+    @pytest.mark.parametrize("ch", [",", ";", "\t", "a", "x", "\0"])
+    def test_matches_str_count(self, jobs, ch):
+        data = random_rows(2_000)
+        assert yurki.internal.count_char_byte(data, ch, jobs=jobs) == [s.count(ch) for s in data]
+
+    def test_tail(self):
+        # Matches past the last full vector of every row length
+        data = ["a" * n + "," for n in range(200)]
+        assert yurki.internal.count_char_byte(data, ",") == [1] * 200
+
+    @pytest.mark.parametrize("jobs", JOBS)
+    def test_slice_and_inplace(self, jobs):
+        data = ["a,b", ",,", ""] * 100
+        assert yurki.internal.count_char_byte(data, ",", jobs=jobs, start=1, step=3) == [2] * 100
+        result = yurki.internal.count_char_byte(data, ",", jobs=jobs, inplace=True)
+        assert result is data
+        assert data[:3] == [1, 2, 0]
+
+    @pytest.mark.parametrize("ch", ["", "ab", "é", "😀"])
+    def test_not_single_ascii(self, ch):
+        with pytest.raises(ValueError, match="single ASCII character"):
+            yurki.internal.count_char_byte(["x"], ch)
+
+    def test_empty_list(self):
+        assert yurki.internal.count_char_byte([], ",", jobs=4) == []
@@ -0,0 +1,34 @@
+import pytest
+
+import yurki
+
+
+JOBS = [1, 2, 4]
+
+
+class TestEncodeUtf8:
+    @pytest.mark.parametrize("jobs", JOBS)
+    def test_encode(self, jobs):
+        data = ["", "a", "café", "日本語", "🙂🙃", "x" * 1000] * 200
+        assert yurki.internal.encode_utf8(data, jobs=jobs) == [s.encode() for s in data]
+
+    @pytest.mark.parametrize("jobs", JOBS)
+    def test_empty_strings(self, jobs):
+        result = yurki.internal.encode_utf8([""] * 100, jobs=jobs)
+        assert result == [b""] * 100
+        assert all(type(b) is bytes for b in result)
+
+    @pytest.mark.parametrize("jobs", JOBS)
+    def test_large_payloads(self, jobs):
+        data = ["é" * 2_000_000, "a" * 5_000_000, "😀" * 1_000_000]
+        assert yurki.internal.encode_utf8(data, jobs=jobs) == [s.encode() for s in data]
+
+    @pytest.mark.parametrize("jobs", JOBS)
+    def test_inplace(self, jobs):
+        data = [f"row {i}" for i in range(100)]
+        result = yurki.internal.encode_utf8(data, jobs=jobs, inplace=True, start=50)
+        assert result is data
+        assert data == [f"row {i}" for i in range(50)] + [f"row {i}".encode() for i in range(50, 100)]
+
+    def test_empty_list(self):
+        assert yurki.internal.encode_utf8([]) == []
@@ -0,0 +1,36 @@
+import pytest
+
+import yurki
+
+
+JOBS = [1, 2, 4, 7]
+
+
+class TestEquals:
+    @pytest.mark.parametrize("jobs", JOBS)
+    def test_equals(self, jobs):
+        a = [f"row {i}" for i in range(1_000)]
+        b = [f"row {i}" if i % 3 else f"Row {i}" for i in range(1_000)]
+
+        result = yurki.internal.equals(a, b, jobs=jobs)
+        assert result == [x == y for x, y in zip(a, b)]
+
+    @pytest.mark.parametrize("jobs", JOBS)
+    def test_unicode(self, jobs):
+        a = ["café", "naïve", "日本語", "🙂", ""]
+        b = ["café", "naive", "日本語", "🙃", ""]
+        assert yurki.internal.equals(a, b, jobs=jobs) == [True, False, True, False, True]
+
+    @pytest.mark.parametrize("jobs", JOBS)
+    def test_inplace(self, jobs):
+        a = ["a", "b", "c"]
+        result = yurki.internal.equals(a, ["a", "x", "c"], jobs=jobs, inplace=True)
+        assert result is a
+        assert a == [True, False, True]
+
+    def test_length_mismatch(self):
+        with pytest.raises(ValueError, match="equal length"):
+            yurki.internal.equals(["a", "b"], ["a"])
+
+    def test_empty(self):
+        assert yurki.internal.equals([], []) == []
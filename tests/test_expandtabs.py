@@ -0,0 +1,71 @@
+import pytest
+
+import yurki
+
+
+JOBS = [1, 4]
+
+DATA = [
+    "",
+    "no tabs here",
+    "\t",
+    "a\tb",
+    "ab\tc\td",
+    "1234567\t8",
+    "12345678\t9",
+    "\t\t",
+    "col1\tcol2\tcol3",
+    "line\tone\nline\ttwo",
+    "abc\n\tindented",
+    "abc\r\tcarriage",
+    "abc\r\n\tcrlf",
+    "x\x0b\tvertical tab",
+    "日本語\tcjk",
+    "😀\temoji",
+    "é\tlatin\nü\tnext",
+    "trailing\t",
+    "a" * 100 + "\t" + "b" * 100 + "\t",
+]
+
+
+class TestExpandtabs:
+    @pytest.mark.parametrize("jobs", JOBS)
+    @pytest.mark.parametrize("tabsize", [8, 4, 1, 3, 0, -2])
+    def test_matches_str(self, jobs, tabsize):
+        result = yurki.internal.expandtabs_string(DATA * 50, tabsize=tabsize, jobs=jobs)
+        assert result == [s.expandtabs(tabsize) for s in DATA * 50]
+
+    def test_default_tabsize(self):
+        assert yurki.internal.expandtabs_string(["a\tb"]) == ["a       b"]
+
+    def test_column_resets_after_newline(self):
+        data = ["abcdef\n\tx", "abcdef\r\tx", "abcdef\tx\n12\ty"]
+        assert yurki.internal.expandtabs_string(data, tabsize=4) == [
+            "abcdef\n    x",
+            "abcdef\r    x",
+            "abcdef  x\n12  y",
+        ]
+
+    def test_multiline(self):
+        text = "def f():\n\tif x:\n\t\treturn 1\n\treturn 2\n"
+        assert yurki.internal.expandtabs_string([text], tabsize=4) == [text.expandtabs(4)]
+
+    @pytest.mark.parametrize("jobs", JOBS)
+    def test_untouched_strings_are_inputs(self, jobs):
+        data = [f"row {i}" if i % 10 else f"row\t{i}" for i in range(1_000)]
+        result = yurki.internal.expandtabs_string(data, jobs=jobs)
+        for i, (before, after) in enumerate(zip(data, result)):
+            if i % 10:
+                assert after is before
+            else:
+                assert after == before.expandtabs()
+
+    @pytest.mark.parametrize("jobs", JOBS)
+    def test_inplace_and_slice(self, jobs):
+        data = ["a\tb", "c\td", "e\tf"]
+        result = yurki.internal.expandtabs_string(data, tabsize=2, jobs=jobs, inplace=True, start=1)
+        assert result is data
+        assert data == ["a\tb", "c d", "e f"]
+
+    def test_empty_list(self):
+        assert yurki.internal.expandtabs_string([], jobs=4) == []
@@ -0,0 +1,234 @@
+import gc
+import os
+import re
+import tempfile
+import threading
+
+import pytest
+
+import yurki
+
+
+JOBS = [1, 4]
+
+DATA = [f"row {i}, é {i * 3}" for i in range(2_000)]
+
+
+@pytest.fixture
+def fast_types():
+    previous = yurki.internal.get_fast_types()
+    yield yurki.internal.set_fast_types
+    yurki.internal.set_fast_types(*previous)
+
+
+def list_type(fast):
+    return yurki.internal.List if fast else list
+
+
+def string_type(fast):
+    return yurki.internal.String if fast and yurki.internal.fast_string_enabled() else str
+
+
+class TestFastTypes:
+    def test_default(self):
+        assert yurki.internal.get_fast_types() == (yurki.internal.fast_string_enabled(), True)
+
+    def test_set_and_get(self, fast_types):
+        fast_types(strings=False)
+        assert yurki.internal.get_fast_types() == (False, True)
+        assert not yurki.internal.fast_string_enabled()
+        fast_types(lists=False)
+        assert yurki.internal.get_fast_types() == (yurki.internal.fast_string_enabled(), False)
+        fast_types()
+        assert yurki.internal.get_fast_types() == (yurki.internal.fast_string_enabled(), True)
+
+    def test_set_fast_string(self, fast_types):
+        fast_types(strings=True, lists=False)
+        yurki.set_fast_string(False)
+        assert yurki.internal.get_fast_types() == (False, False)
+        yurki.set_fast_string(True)
+        assert yurki.internal.get_fast_types() == (yurki.internal.fast_string_enabled(), False)
+
+    @pytest.mark.parametrize("jobs", JOBS)
+    def test_set_fast_string_keeps_values(self, fast_types, jobs):
+        results = {}
+        for enabled in [True, False]:
+            yurki.set_fast_string(enabled)
+            results[enabled] = yurki.regexp.replace(DATA, r"é (\d+)", "ü$1", jobs=jobs)
+            assert all(type(s) is string_type(enabled) for s in results[enabled])
+        assert results[True] == results[False]
+        assert results[False] == [re.sub(r"é (\d+)", r"ü\1", s) for s in DATA]
+        assert [hash(s) for s in results[True]] == [hash(s) for s in results[False]]
+
+    @pytest.mark.parametrize("jobs", JOBS)
+    @pytest.mark.parametrize("strings", [False, True])
+    @pytest.mark.parametrize("lists", [False, True])
+    def test_result_types(self, fast_types, jobs, strings, lists):
+        fast_types(strings=strings, lists=lists)
+        result = yurki.regexp.replace(DATA, r"row", "line", jobs=jobs)
+        assert type(result) is list_type(lists)
+        assert result == [s.replace("row", "line") for s in DATA]
+        assert all(type(s) is string_type(strings) for s in result)
+        assert gc.is_tracked(result)
+
+    @pytest.mark.parametrize("jobs", JOBS)
+    @pytest.mark.parametrize("lists", [False, True])
+    def test_nested_results(self, fast_types, jobs, lists):
+        fast_types(strings=False, lists=lists)
+        result = yurki.regexp.split(DATA, r", ", jobs=jobs)
+        assert type(result) is list_type(lists)
+        assert result == [s.split(", ") for s in DATA]
+        for parts in result:
+            assert type(parts) is list_type(lists)
+            assert gc.is_tracked(parts)
+            assert all(type(part) is str for part in parts)
+
+    @pytest.mark.parametrize("jobs", JOBS)
+    def test_plain_results_are_exact(self, fast_types, jobs):
+        fast_types(strings=False, lists=False)
+        result = yurki.regexp.split(DATA, r", ", jobs=jobs)
+        # Plain lists are mutable, unlike yurki.List
+        result.append(["x"])
+        result[0][0] = "first"
+        assert result[0] == ["first", DATA[0].split(", ")[1]]
+
+    @pytest.mark.parametrize("jobs", JOBS)
+    def test_inplace_and_interned(self, fast_types, jobs):
+        fast_types(strings=False, lists=False)
+        data = [f"k{i % 10}" for i in range(5_000)]
+        result = yurki.regexp.find(data, r"k\d", jobs=jobs, intern_results=True)
+        assert type(result) is list and result == data
+        yurki.regexp.replace(data, r"k", "key", jobs=jobs, inplace=True)
+        assert data == [f"key{i % 10}" for i in range(5_000)]
+        assert all(type(s) is str for s in data)
+
+    def test_empty(self, fast_types):
+        fast_types(lists=False)
+        result = yurki.regexp.replace([], r"a", "b", jobs=4)
+        assert type(result) is list and result == []
+
+    @pytest.mark.parametrize("jobs", JOBS)
+    def test_split_columns(self, fast_types, jobs):
+        fast_types(strings=False, lists=False)
+        columns = yurki.internal.split_columns(DATA, r", ", 2, jobs=jobs)
+        assert all(type(column) is list for column in columns)
+        assert columns[0] == [s.split(", ")[0] for s in DATA]
+
+    @pytest.mark.parametrize("lists", [False, True])
+    def test_list_utilities(self, fast_types, lists):
+        fast_types(strings=False, lists=lists)
+        chunks = yurki.internal.chunk_list(DATA, 300)
+        assert type(chunks) is list_type(lists)
+        assert all(type(chunk) is list_type(lists) and gc.is_tracked(chunk) for chunk in chunks)
+        assert [s for chunk in chunks for s in chunk] == DATA
+
+        copy = yurki.internal.copy_list(DATA, jobs=4)
+        assert type(copy) is list_type(lists) and copy == DATA
+
+        reversed_ = yurki.internal.reversed_list(DATA)
+        assert type(reversed_) is list_type(lists) and reversed_ == DATA[::-1]
+        assert gc.is_tracked(reversed_)
+
+    @pytest.mark.parametrize("jobs", JOBS)
+    @pytest.mark.parametrize("lists", [False, True])
+    def test_sort_and_group(self, fast_types, jobs, lists):
+        fast_types(strings=False, lists=lists)
+        result = yurki.internal.sort_strings(DATA, jobs=jobs)
+        assert type(result) is list_type(lists) and result == sorted(DATA)
+        assert gc.is_tracked(result)
+
+        groups = yurki.internal.group_by_regex(DATA, r"row (\d)", jobs=jobs)
+        assert all(type(rows) is list_type(lists) for rows in groups.values())
+        assert all(type(key) is str for key in groups)
+        assert groups["1"] == [s for s in DATA if s.startswith("row 1")]
+
+    @pytest.mark.parametrize("jobs", JOBS)
+    def test_plain_mode_is_exact_everywhere(self, fast_types, jobs):
+        # Nothing built with both types off may be a yurki subclass
+        fast_types(strings=False, lists=False)
+        results = [
+            yurki.regexp.find(DATA, r"é \d+", jobs=jobs),
+            yurki.regexp.split(DATA, r", ", jobs=jobs),
+            yurki.internal.chunk_list(DATA, 7),
+            yurki.internal.sort_strings(DATA, reverse=True, jobs=jobs),
+            *yurki.internal.group_by_regex(DATA, r"(\d)$", jobs=jobs).values(),
+        ]
+
+        def exact(obj):
+            if isinstance(obj, list):
+                return type(obj) is list and all(exact(item) for item in obj)
+            return type(obj) is str
+
+        assert all(exact(result) for result in results)
+
+    @pytest.mark.parametrize("jobs", JOBS)
+    def test_plain_mode_converts_fast_inputs(self, fast_types, jobs):
+        # Unchanged yurki.String inputs are not handed back as results
+        fast_types(strings=True, lists=True)
+        fast = yurki.regexp.replace(["abc x", "def y"], r"x", "Q", jobs=jobs)
+        assert type(fast[0]) is string_type(True)
+        fast_types(strings=False, lists=False)
+        result = yurki.regexp.replace(fast, r"zzz", "q", jobs=jobs)
+        assert type(result) is list
+        assert [type(s) for s in result] == [str, str]
+        assert result == ["abc Q", "def y"]
+
+    def test_process_file(self, fast_types):
+        fast_types(strings=False, lists=False)
+        with tempfile.NamedTemporaryFile("w", suffix=".txt", delete=False, encoding="utf-8") as f:
+            f.write("".join(f"line {i}\n" for i in range(10_000)))
+        try:
+            result = yurki.internal.process_file(f.name, [("find", r"\d+")], jobs=4)
+            assert type(result) is list
+            assert result == [str(i) for i in range(10_000)]
+            assert all(type(s) is str for s in result[10:100])
+        finally:
+            os.unlink(f.name)
+
+    @pytest.mark.parametrize("jobs", JOBS)
+    @pytest.mark.parametrize("fast", [False, True])
+    def test_toggled_during_map(self, fast_types, jobs, fast):
+        # A map keeps the types it started with; the switch applies to the next call
+        fast_types(strings=fast, lists=fast)
+        expected = string_type(fast)
+        # No single-character parts: those are cached plain strings either way
+        data = [f"row {i}" for i in range(10, 50_010)]
+
+        def progress(done, total):
+            yurki.internal.set_fast_types(strings=done % 2 == 0, lists=done % 3 == 0)
+
+        result = yurki.regexp.split(data, r" ", jobs=jobs, progress=progress)
+        assert result == [s.split(" ") for s in data]
+        assert type(result) is list_type(fast)
+        assert all(type(parts) is list_type(fast) for parts in result)
+        assert all(type(part) is expected for parts in result for part in parts)
+
+        fast_types(strings=not fast, lists=not fast)
+        result = yurki.regexp.split(data[:10], r" ", jobs=jobs)
+        assert type(result) is list_type(not fast)
+        assert all(type(part) is string_type(not fast) for parts in result for part in parts)
+
+    @pytest.mark.parametrize("jobs", JOBS)
+    def test_toggled_from_another_thread(self, fast_types, jobs):
+        # Plain maps release the GIL while workers run, letting other threads switch
+        fast_types(strings=False, lists=False)
+        data = [f"row {i}, é" for i in range(200_000)]
+        stop = threading.Event()
+
+        def toggle():
+            while not stop.is_set():
+                yurki.internal.set_fast_types(strings=True, lists=True)
+                yurki.internal.set_fast_types(strings=False, lists=False)
+
+        thread = threading.Thread(target=toggle)
+        thread.start()
+        try:
+            for _ in range(3):
+                yurki.internal.set_fast_types(strings=False, lists=False)
+                result = yurki.regexp.split(data, r", ", jobs=jobs)
+                kinds = {type(result)} | {type(parts) for parts in result}
+                assert len(kinds) == 1
+                assert len({type(part) for parts in result for part in parts}) == 1
+        finally:
+            stop.set()
+            thread.join()
@@ -0,0 +1,70 @@
+import pytest
+
+import yurki
+
+
+JOBS = [1, 4]
+
+VALUES = {"name": "Ann", "city": "Zürich", "n": 3, "emoji": "😀", "": "empty key"}
+
+DATA = [f"Dear {{name}}, row {i} from {{city}} has {{n}} items {{emoji}}" for i in range(2_000)] + [
+    "",
+    "no placeholders",
+    "{name}",
+    "{name}{city}",
+    "é{n}é",
+]
+
+
+class TestFillTemplate:
+    @pytest.mark.parametrize("jobs", JOBS)
+    def test_matches_format_map(self, jobs):
+        result = yurki.internal.fill_template(DATA, VALUES, jobs=jobs)
+        assert result == [s.format_map(VALUES) for s in DATA]
+
+    def test_escaped_braces(self):
+        data = ["{{name}}", "{{{name}}}", "}}{{", "a {{ b }} c", "{{}}"]
+        assert yurki.internal.fill_template(data, VALUES) == [s.format_map(VALUES) for s in data]
+        assert yurki.internal.fill_template(["{{{name}}}"], VALUES) == ["{Ann}"]
+
+    def test_keys_are_literal(self):
+        # No attribute, index or format spec parsing: the key is the raw text
+        values = {"a.b": "1", "x[0]": "2", "y:>3": "3"}
+        assert yurki.internal.fill_template(["{a.b}{x[0]}{y:>3}"], values) == ["123"]
+        assert yurki.internal.fill_template(["[{}]"], VALUES) == ["[empty key]"]
+
+    def test_values_use_str(self):
+        assert yurki.internal.fill_template(["{a} {b} {c}"], {"a": 1.5, "b": None, "c": [1]}) == ["1.5 None [1]"]
+
+    @pytest.mark.parametrize("jobs", JOBS)
+    def test_missing_key(self, jobs):
+        data = ["{name}"] * 1_000 + ["{first}", "{second}"]
+        with pytest.raises(KeyError) as info:
+            yurki.internal.fill_template(data, VALUES, jobs=jobs)
+        assert info.value.args == ("first",)
+
+    @pytest.mark.parametrize(
+        "template, message",
+        [
+            ("a } b", "Single '}' encountered in format string"),
+            ("{name", "expected '}' before end of string"),
+            ("{na{me}}", "unexpected '{' in field name"),
+        ],
+    )
+    def test_malformed(self, template, message):
+        with pytest.raises(ValueError, match=message):
+            template.format_map(VALUES)
+        with pytest.raises(ValueError, match=message):
+            yurki.internal.fill_template([template], VALUES)
+
+    def test_unchanged_rows_are_inputs(self):
+        data = ["plain text", "more text"]
+        result = yurki.internal.fill_template(data, VALUES)
+        assert all(after is before for before, after in zip(data, result))
+
+    def test_non_str_key(self):
+        with pytest.raises(TypeError, match="mapping keys must be str, not int"):
+            yurki.internal.fill_template(["{1}"], {1: "x"})
+
+    def test_empty_list(self):
+        assert yurki.internal.fill_template([], VALUES, jobs=4) == []
@@ -1,4 +1,5 @@
 import re
+import sys
 
 import pytest
 
@@ -48,6 +49,32 @@ class TestFind:
         result = yurki.regexp.find(data=data, pattern=pattern, jobs=jobs, inplace=False)
         assert result == expected
 
+    @pytest.mark.parametrize("jobs", [2, 3, 7, 16])
+    def test_order_preserved(self, jobs):
+        data = [f"row {i}" for i in range(10_001)]
+        expected = [str(i) for i in range(10_001)]
+        assert yurki.regexp.find(data, r"\d+", jobs=jobs) == expected
+
+    @pytest.mark.parametrize("jobs", JOBS)
+    @pytest.mark.parametrize("inplace", [False, True])
+    def test_no_match_none(self, jobs, inplace):
+        data = ["a", "row 1", "b", "row 22"]
+        result = yurki.regexp.find(data, r"\d+", jobs=jobs, inplace=inplace, no_match="none")
+        assert result == [None, "1", None, "22"]
+
+    def test_no_match_invalid(self):
+        with pytest.raises(ValueError, match="no_match"):
+            yurki.regexp.find(["a"], r"\d+", no_match="null")
+
+    @pytest.mark.parametrize("jobs", JOBS)
+    def test_no_match_none_refcount(self, jobs):
+        data = ["x" if i % 2 else str(i) for i in range(1_000_000)]
+        before = sys.getrefcount(None)
+        result = yurki.regexp.find(data, r"\d+", jobs=jobs, no_match="none")
+        assert result.count(None) == 500_000
+        del result
+        assert sys.getrefcount(None) == before
+
 
 class TestBenchFindShort:
     @pytest.fixture
@@ -0,0 +1,56 @@
+import re
+
+import pytest
+
+import yurki
+
+
+JOBS = [1, 4]
+
+DATA = [" ".join(f"w{i}_{j}" for j in range(i % 7)) for i in range(3_000)] + ["", "é 中 😀", "a  b"]
+
+
+def expected_rows(data, pattern):
+    return [(piece, row) for row, s in enumerate(data) for piece in re.split(pattern, s)]
+
+
+class TestFlatSplit:
+    @pytest.mark.parametrize("jobs", JOBS)
+    def test_token_count_is_sum_of_row_splits(self, jobs):
+        result = yurki.regexp.flat_split(DATA, r" ", jobs=jobs)
+        assert type(result) is yurki.internal.List
+        assert len(result) == sum(len(re.split(r" ", s)) for s in DATA)
+        assert result == [piece for piece, _ in expected_rows(DATA, r" ")]
+
+    @pytest.mark.parametrize("jobs", JOBS)
+    def test_rows_map_tokens_back(self, jobs):
+        tokens, rows = yurki.regexp.flat_split(DATA, r"\s", jobs=jobs, rows=True)
+        assert len(tokens) == len(rows)
+        assert list(zip(tokens, rows)) == expected_rows(DATA, r"\s")
+        assert all(type(row) is int for row in rows)
+
+    @pytest.mark.parametrize("jobs", JOBS)
+    def test_case_insensitive(self, jobs):
+        data = ["aXbxc", "X"]
+        assert yurki.regexp.flat_split(data, r"x", case=True, jobs=jobs) == ["a", "b", "c", "", ""]
+        assert yurki.regexp.flat_split(data, r"x", jobs=jobs) == ["aXb", "c", "X"]
+
+    def test_empty_list(self):
+        assert yurki.regexp.flat_split([], r" ", jobs=4) == []
+        assert yurki.regexp.flat_split([], r" ", rows=True) == ([], [])
+
+    def test_invalid_pattern(self):
+        with pytest.raises(ValueError):
+            yurki.internal.flat_split(["a"], r"(")
+
+    @pytest.mark.parametrize("jobs", JOBS)
+    def test_plain_types(self, jobs):
+        previous = yurki.internal.get_fast_types()
+        yurki.internal.set_fast_types(strings=False, lists=False)
+        try:
+            tokens, rows = yurki.regexp.flat_split(DATA, r" ", jobs=jobs, rows=True)
+        finally:
+            yurki.internal.set_fast_types(*previous)
+        assert type(tokens) is list and type(rows) is list
+        assert all(type(piece) is str for piece in tokens)
+        assert list(zip(tokens, rows)) == expected_rows(DATA, r" ")
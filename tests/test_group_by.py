@@ -0,0 +1,86 @@
+import random
+import re
+
+import pytest
+
+import yurki
+
+
+JOBS = [1, 4]
+
+
+def expected_groups(data, pattern, group=1):
+    groups = {}
+    for s in data:
+        m = re.search(pattern, s)
+        groups.setdefault(m and m.group(group), []).append(s)
+    return groups
+
+
+def random_rows(n, seed=5):
+    rng = random.Random(seed)
+    prefixes = ["user", "admin", "guest", "é文", "😀"]
+    return [
+        f"{rng.choice(prefixes)}:{rng.randrange(1000)}" if rng.random() < 0.9 else "no key"
+        for _ in range(n)
+    ]
+
+
+class TestGroupByRegex:
+    @pytest.mark.parametrize("jobs", JOBS)
+    def test_prefix_buckets(self, jobs):
+        data = random_rows(10_000)
+        result = yurki.internal.group_by_regex(data, r"^([^:]+):", jobs=jobs)
+        expected = expected_groups(data, r"^([^:]+):")
+        assert result == expected
+        # Keys in order of first appearance, rows in list order
+        assert list(result) == list(expected)
+        assert all(isinstance(bucket, yurki.internal.List) for bucket in result.values())
+
+    @pytest.mark.parametrize("jobs", JOBS)
+    def test_buckets_hold_the_rows(self, jobs):
+        data = ["".join(["a-", str(i)]) for i in range(100)]
+        result = yurki.internal.group_by_regex(data, r"^(\w)-", jobs=jobs)
+        assert [id(s) for s in result["a"]] == [id(s) for s in data]
+
+    def test_no_match_bucket(self):
+        data = ["a-1", "b-2", "a-3", "x"]
+        result = yurki.internal.group_by_regex(data, r"^(\w)-")
+        assert result == {"a": ["a-1", "a-3"], "b": ["b-2"], None: ["x"]}
+        assert list(result) == ["a", "b", None]
+
+    def test_unused_group_goes_to_none(self):
+        result = yurki.internal.group_by_regex(["a1", "b", "c2"], r"\w(\d)?", group=1)
+        assert result == {"1": ["a1"], None: ["b"], "2": ["c2"]}
+
+    @pytest.mark.parametrize("group", [0, 2, "key"])
+    def test_group_selection(self, group):
+        data = ["k=v1", "k=v2", "j=v1"]
+        pattern = r"(\w)=(?P<key>\w+)"
+        assert yurki.internal.group_by_regex(data, pattern, group=group) == expected_groups(
+            data, pattern, group
+        )
+
+    def test_case_insensitive(self):
+        result = yurki.internal.group_by_regex(["A1", "a2"], r"(a)", case=True)
+        assert result == {"A": ["A1"], "a": ["a2"]}
+
+    def test_empty(self):
+        assert yurki.internal.group_by_regex([], r"(x)") == {}
+
+    @pytest.mark.parametrize("group", [3, -1, "missing"])
+    def test_no_such_group(self, group):
+        with pytest.raises(IndexError, match="no such group"):
+            yurki.internal.group_by_regex(["a"], r"(a)(b)?", group=group)
+
+    def test_default_group_needs_one(self):
+        with pytest.raises(IndexError, match="no such group"):
+            yurki.internal.group_by_regex(["a"], r"a")
+
+    def test_invalid_pattern(self):
+        with pytest.raises(ValueError):
+            yurki.internal.group_by_regex(["a"], r"(")
+
+    def test_rejects_non_str(self):
+        with pytest.raises(TypeError):
+            yurki.internal.group_by_regex(["a", 1], r"(a)")
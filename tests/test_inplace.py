@@ -0,0 +1,50 @@
+import sys
+
+import pytest
+
+import yurki
+
+
+JOBS = [1, 4]
+
+
+class TestInplace:
+    @pytest.mark.parametrize("jobs", JOBS)
+    def test_rejects_yurki_list(self, jobs):
+        result = yurki.regexp.find(["a1", "b2"], r"\w")
+        assert isinstance(result, yurki.internal.List)
+
+        with pytest.raises(TypeError, match="fixed-size"):
+            yurki.regexp.find(result, r"\d", jobs=jobs, inplace=True)
+        assert result == ["a", "b"]
+
+        # Non-inplace processing of a yurki.List is fine
+        assert yurki.regexp.find(result, r"\w", jobs=jobs) == ["a", "b"]
+
+    @pytest.mark.parametrize("jobs", JOBS)
+    def test_builtin_list(self, jobs):
+        data = ["a1", "b2"]
+        result = yurki.regexp.find(data, r"\d", jobs=jobs, inplace=True)
+        assert result is data
+        assert data == ["1", "2"]
+
+    @pytest.mark.parametrize("jobs", JOBS)
+    def test_releases_replaced_items(self, jobs):
+        item = "".join(["row ", "42"])
+        data = [item] * 1_000
+        before = sys.getrefcount(item)
+
+        yurki.regexp.find(data, r"\d+", jobs=jobs, inplace=True)
+        assert data == ["42"] * 1_000
+        assert sys.getrefcount(item) == before - 1_000
+
+    @pytest.mark.parametrize("jobs", JOBS)
+    def test_large_run_keeps_counts(self, jobs):
+        # Distinct objects, so a leak or double release shows on every one
+        items = ["".join(["row ", str(i % 500)]) for i in range(500)]
+        data = items * 400
+        before = [sys.getrefcount(item) for item in items]
+
+        yurki.regexp.replace(data, r"row", "line", jobs=jobs, inplace=True)
+        assert data == [f"line {i % 500}" for i in range(200_000)]
+        assert [sys.getrefcount(item) for item in items] == [count - 400 for count in before]
@@ -0,0 +1,77 @@
+import sys
+
+import pytest
+
+import yurki
+
+
+JOBS = [1, 2, 4, 7]
+
+
+class TestInternResults:
+    @pytest.mark.parametrize("jobs", JOBS)
+    def test_find_matches_plain(self, jobs):
+        data = [f"user{i % 50} logged in" for i in range(20_000)]
+        expected = yurki.regexp.find(data, r"user\d+", jobs=jobs)
+        assert yurki.regexp.find(data, r"user\d+", jobs=jobs, intern_results=True) == expected
+
+    @pytest.mark.parametrize("jobs", JOBS)
+    def test_equal_results_share_object(self, jobs):
+        data = [f"user{i % 50} logged in" for i in range(20_000)]
+        result = yurki.regexp.find(data, r"user\d+", jobs=jobs, intern_results=True)
+        assert len({id(s) for s in result}) == 50
+
+    @pytest.mark.parametrize("jobs", JOBS)
+    def test_refcount(self, jobs):
+        data = ["key"] * 10_000 + ["other"] * 5
+        result = yurki.regexp.replace(data, r"^", "", jobs=jobs, intern_results=True)
+        first = result[0]
+        # one per list slot, plus `first` and the getrefcount argument
+        assert sys.getrefcount(first) == 10_000 + 2
+        assert sys.getrefcount(result[-1]) == 5 + 1
+        del result
+        assert sys.getrefcount(first) == 2
+
+    @pytest.mark.parametrize("jobs", JOBS)
+    def test_nested_results(self, jobs):
+        data = ["a-b-c", "b-c-a"] * 1_000
+        expected = yurki.regexp.split(data, r"-", jobs=jobs)
+        result = yurki.regexp.split(data, r"-", jobs=jobs, intern_results=True)
+        assert result == expected
+        assert len({id(part) for parts in result for part in parts}) == 3
+
+        captured = yurki.regexp.capture(data, r"(\w)-(\w)", jobs=jobs, intern_results=True)
+        assert captured == yurki.regexp.capture(data, r"(\w)-(\w)", jobs=jobs)
+
+    @pytest.mark.parametrize("jobs", JOBS)
+    def test_slice(self, jobs):
+        data = [f"v{i % 3}" for i in range(1_000)]
+        result = yurki.regexp.find(data, r"v\d", jobs=jobs, start=10, step=7, intern_results=True)
+        assert result == data[10::7]
+        assert len({id(s) for s in result}) == 3
+
+    def test_pipeline(self):
+        data = [f"id={i % 4}" for i in range(1_000)]
+        result = yurki.internal.pipeline(data, [("find", r"\d")], jobs=4, intern_results=True)
+        assert result == [str(i % 4) for i in range(1_000)]
+        assert len({id(s) for s in result}) == 4
+
+    def test_inplace_rejected(self):
+        with pytest.raises(ValueError):
+            yurki.regexp.find(["abc"], r"b", jobs=1, inplace=True, intern_results=True)
+
+    @pytest.mark.parametrize("jobs", JOBS)
+    def test_progress_error(self, jobs):
+        data = ["same"] * 50_000
+
+        def progress(done, total):
+            raise RuntimeError("stop")
+
+        with pytest.raises(RuntimeError):
+            yurki.regexp.find(
+                data, r"s\w+", jobs=jobs, progress=progress, progress_interval=1_000, intern_results=True
+            )
+        assert yurki.regexp.find(data, r"s\w+", jobs=jobs, intern_results=True) == data
+
+    def test_empty(self):
+        assert yurki.regexp.find([], r"a", jobs=1, intern_results=True) == []
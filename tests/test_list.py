@@ -0,0 +1,461 @@
+import copy
+import ctypes
+import gc
+import pickle
+import subprocess
+import sys
+import weakref
+
+import pytest
+
+import yurki
+
+
+JOBS = [1, 4]
+
+
+class TestList:
+    @pytest.mark.parametrize("jobs", JOBS)
+    def test_empty(self, jobs):
+        for _ in range(10_000):
+            result = yurki.regexp.find([], r"\w", jobs=jobs)
+            assert isinstance(result, yurki.internal.List)
+            assert len(result) == 0
+            assert result == []
+
+        with pytest.raises(IndexError):
+            result[0] = "a"
+
+    @pytest.mark.parametrize("jobs", JOBS)
+    def test_index(self, jobs):
+        data = [f"item {i}" for i in range(100)]
+        result = yurki.regexp.find(data, r".+", jobs=jobs)
+        assert result[0] == "item 0"
+        assert result[-1] == "item 99"
+        assert result[-100] == "item 0"
+        assert result[True] == "item 1"
+        for index in (100, -101, 2**62, -(2**62)):
+            with pytest.raises(IndexError, match="list index out of range"):
+                result[index]
+        with pytest.raises(IndexError):
+            result[2**100]
+        with pytest.raises(TypeError, match="list indices must be integers or slices, not str"):
+            result["0"]
+
+    @pytest.mark.parametrize("jobs", JOBS)
+    def test_slice(self, jobs):
+        data = [f"item {i}" for i in range(100)]
+        result = yurki.regexp.find(data, r".+", jobs=jobs)
+        slices = [
+            slice(10, 20),
+            slice(None, None, 3),
+            slice(-5, None),
+            slice(None, -90),
+            slice(None, None, -1),
+            slice(80, 10, -7),
+            slice(50, 10),
+            slice(200, 300),
+            slice(-300, 300, 2),
+            slice(5, 5),
+        ]
+        for s in slices:
+            part = result[s]
+            assert isinstance(part, yurki.internal.List)
+            assert part == data[s]
+        with pytest.raises(ValueError, match="slice step cannot be zero"):
+            result[::0]
+
+    def test_slice_refcount(self):
+        result = yurki.regexp.find([f"x{i}" * 5 for i in range(10)], r".+", jobs=1)
+        item = result[3]
+        before = sys.getrefcount(item)
+        part = result[2:5]
+        assert sys.getrefcount(item) == before + 1
+        assert part[1] is item
+        del result
+        assert part == [f"x{i}" * 5 for i in range(2, 5)]
+        del part
+        assert sys.getrefcount(item) == before - 1
+
+    def test_empty_slices(self):
+        result = yurki.regexp.find([], r"\w", jobs=1)
+        assert result[:] == []
+        assert isinstance(result[::-1], yurki.internal.List)
+        with pytest.raises(IndexError):
+            result[0]
+        with pytest.raises(IndexError):
+            result[-1]
+
+    @pytest.mark.parametrize("jobs", JOBS)
+    def test_concat(self, jobs):
+        a = yurki.regexp.find(["a1", "b2", "c3"], r"\w\d", jobs=jobs)
+        b = yurki.regexp.find(["d4", "e5"], r"\w\d", jobs=jobs)
+        result = a + b
+        assert isinstance(result, yurki.internal.List)
+        assert result == ["a1", "b2", "c3", "d4", "e5"]
+        assert a + [] == a and isinstance(a + [], yurki.internal.List)
+        assert a + ["z"] == ["a1", "b2", "c3", "z"]
+        assert isinstance(a + ["z"], yurki.internal.List)
+        # The builtin list on the left does the concatenation
+        assert ["z"] + a == ["z", "a1", "b2", "c3"]
+        with pytest.raises(TypeError, match='can only concatenate list \\(not "tuple"\\) to list'):
+            a + ("z",)
+
+    def test_concat_refcount(self):
+        a = yurki.regexp.find([f"x{i}" * 5 for i in range(3)], r".+", jobs=1)
+        item = a[0]
+        before = sys.getrefcount(item)
+        result = a + a
+        assert sys.getrefcount(item) == before + 2
+        del result
+        assert sys.getrefcount(item) == before
+
+    def test_sum(self):
+        empty = yurki.regexp.find([], r"\w", jobs=1)
+        parts = [yurki.regexp.find([f"{i}a", f"{i}b"], r"\w+", jobs=1) for i in range(50)]
+        result = sum(parts, empty)
+        assert isinstance(result, yurki.internal.List)
+        assert result == [f"{i}{c}" for i in range(50) for c in "ab"]
+        assert empty == []
+
+    @pytest.mark.parametrize("jobs", JOBS)
+    def test_repeat(self, jobs):
+        a = yurki.regexp.find(["a1", "b2"], r"\w\d", jobs=jobs)
+        for n in (0, 1, 3, -2):
+            for result in (a * n, n * a):
+                assert isinstance(result, yurki.internal.List)
+                assert result == ["a1", "b2"] * n
+        empty = yurki.regexp.find([], r"\w", jobs=jobs)
+        assert isinstance(empty * 5, yurki.internal.List) and empty * 5 == []
+        with pytest.raises(MemoryError):
+            a * (sys.maxsize // 2)
+        with pytest.raises(TypeError):
+            a * 1.5
+
+    def test_inplace_operators_still_blocked(self):
+        a = yurki.regexp.find(["a1"], r"\w\d", jobs=1)
+        with pytest.raises(TypeError, match="immutable"):
+            a += ["b"]
+        with pytest.raises(TypeError, match="immutable"):
+            a *= 2
+        plain = ["x"]
+        alias = plain
+        plain += a
+        assert alias is plain and alias == ["x", "a1"]
+
+    @pytest.mark.parametrize("jobs", JOBS)
+    def test_tracked_by_gc(self, jobs):
+        result = yurki.regexp.split([f"a-{i}" for i in range(1_000)], r"-", jobs=jobs)
+        assert gc.is_tracked(result)
+        assert all(gc.is_tracked(parts) for parts in result)
+        assert gc.is_tracked(result[2:10]) and gc.is_tracked(result + result) and gc.is_tracked(result * 2)
+        assert gc.get_referents(result[0]) == ["a", "0"]
+
+    @pytest.mark.parametrize("jobs", JOBS)
+    def test_cycle_collected(self, jobs):
+        class Holder:
+            pass
+
+        result = yurki.regexp.split(["a-b", "c-d"], r"-", jobs=jobs)
+        holder = Holder()
+        holder.parts = result[1]
+        result[1][0] = holder
+        alive = weakref.ref(holder)
+        del result, holder
+        gc.collect()
+        assert alive() is None
+
+    def test_self_cycle_collected(self):
+        class Holder:
+            pass
+
+        result = yurki.regexp.find(["a1", "b2"], r"\w\d", jobs=1)
+        result[0] = result
+        result[1] = Holder()
+        alive = weakref.ref(result[1])
+        del result
+        gc.collect()
+        assert alive() is None
+
+    def test_inplace_nested_tracked(self):
+        data = ["a-b", "c-d"]
+        yurki.regexp.split(data, r"-", jobs=2, inplace=True)
+        assert all(gc.is_tracked(parts) for parts in data)
+
+    @pytest.mark.parametrize("protocol", range(pickle.HIGHEST_PROTOCOL + 1))
+    def test_pickle(self, protocol):
+        result = yurki.regexp.split(["a-b", "c-d-e", ""], r"-", jobs=1)
+        restored = pickle.loads(pickle.dumps(result, protocol=protocol))
+        assert type(restored) is yurki.internal.List
+        assert restored == [["a", "b"], ["c", "d", "e"], [""]]
+        assert all(type(parts) is yurki.internal.List for parts in restored)
+
+    def test_pickle_protocol_5_empty(self):
+        result = yurki.regexp.find([], r"\w", jobs=1)
+        restored = pickle.loads(pickle.dumps(result, protocol=5))
+        assert type(restored) is yurki.internal.List and restored == []
+
+    def test_copy(self):
+        result = yurki.regexp.split(["a-b", "c-d"], r"-", jobs=1)
+        shallow = copy.copy(result)
+        assert type(shallow) is yurki.internal.List
+        assert shallow == result and shallow is not result
+        assert shallow[0] is result[0]
+
+    def test_list_from(self):
+        items = ["a", object(), ["nested"], 3]
+        for source in (items, tuple(items), iter(items), (x for x in items)):
+            result = yurki.list_from(source)
+            assert type(result) is yurki.internal.List
+            assert all(a is b for a, b in zip(result, items, strict=True))
+            assert gc.is_tracked(result)
+        assert yurki.list_from({"k": 1}) == ["k"]
+        assert yurki.list_from(range(3)) == [0, 1, 2]
+        assert yurki.list_from("ab") == ["a", "b"]
+        assert yurki.list_from([]) == [] and yurki.internal.list_from is yurki.list_from
+
+    def test_list_from_refcount(self):
+        item = object()
+        before = sys.getrefcount(item)
+        result = yurki.list_from(item for _ in range(10))
+        assert sys.getrefcount(item) == before + 10
+        del result
+        assert sys.getrefcount(item) == before
+
+    def test_list_from_errors(self):
+        def failing():
+            yield "a"
+            raise RuntimeError("stop")
+
+        with pytest.raises(TypeError):
+            yurki.list_from(1)
+        with pytest.raises(RuntimeError, match="stop"):
+            yurki.list_from(failing())
+
+    def test_list_from_grows_and_compacts(self):
+        # Generators give no length hint; the list is exactly sized anyway
+        empty_size = yurki.list_from([]).__sizeof__()
+        for n in [0, 1, 3, 4, 5, 17, 1_000, 100_000]:
+            result = yurki.list_from(f"s{i}" for i in range(n))
+            assert len(result) == n
+            assert result == [f"s{i}" for i in range(n)]
+            assert result.__sizeof__() == empty_size + n * ctypes.sizeof(ctypes.c_void_p)
+
+    @pytest.mark.parametrize("hint", [0, 2, 50, 10_000, 2**60])
+    def test_list_from_wrong_length_hint(self, hint):
+        class Hinted:
+            def __iter__(self):
+                return iter(range(50))
+
+            def __length_hint__(self):
+                return hint
+
+        result = yurki.list_from(Hinted())
+        assert result == list(range(50))
+        assert result.__sizeof__() == yurki.list_from([]).__sizeof__() + 50 * ctypes.sizeof(ctypes.c_void_p)
+
+    def test_list_from_failing_length_hint(self):
+        class Broken:
+            def __iter__(self):
+                return iter("ab")
+
+            def __length_hint__(self):
+                raise RuntimeError("hint")
+
+        with pytest.raises(RuntimeError, match="hint"):
+            yurki.list_from(Broken())
+
+    def test_list_from_error_releases_items(self):
+        item = object()
+        before = sys.getrefcount(item)
+
+        def failing():
+            for _ in range(100):
+                yield item
+            raise RuntimeError("stop")
+
+        with pytest.raises(RuntimeError):
+            yurki.list_from(failing())
+        assert sys.getrefcount(item) == before
+
+    @pytest.mark.parametrize("jobs", JOBS)
+    def test_list_from_as_input(self, jobs):
+        data = yurki.list_from(f"item {i}" for i in range(1_000))
+        result = yurki.regexp.replace(data, r"item", "row", jobs=jobs)
+        assert result == [f"row {i}" for i in range(1_000)]
+
+    def test_deepcopy_shared_elements(self):
+        result = yurki.regexp.find(["a1", "b2", "c3"], r"\w\d", jobs=1)
+        shared = ["mutable"]
+        result[0] = shared
+        result[2] = shared
+        copied = copy.deepcopy(result)
+        assert type(copied) is yurki.internal.List
+        assert copied == result
+        assert copied[0] is not shared
+        assert copied[0] is copied[2]
+        assert copied[1] is result[1]
+
+    def test_deepcopy_cycle(self):
+        result = yurki.regexp.find(["a1", "b2"], r"\w\d", jobs=1)
+        result[0] = result
+        copied = copy.deepcopy(result)
+        assert copied[0] is copied
+        assert copied[1] == "b2"
+        result[0] = None
+        copied[0] = None
+
+    def test_unpickle_in_fresh_interpreter(self):
+        data = pickle.dumps(yurki.regexp.split(["a-b"], r"-", jobs=1), protocol=5)
+        code = "import pickle, sys; r = pickle.loads(sys.stdin.buffer.read()); print(type(r).__name__, r)"
+        out = subprocess.run([sys.executable, "-c", code], input=data, capture_output=True, check=True)
+        assert out.stdout.decode().strip() == "List [['a', 'b']]"
+
+    def test_negative_index_assignment(self):
+        result = yurki.regexp.find(["a1", "b2", "c3"], r"\w\d", jobs=1)
+        result[-1] = "z"
+        result[-3] = "x"
+        assert result == ["x", "b2", "z"]
+        with pytest.raises(IndexError):
+            result[-4] = "w"
+        with pytest.raises(IndexError):
+            result[3] = "w"
+        with pytest.raises(TypeError, match="integers or slices"):
+            result["0"] = "w"
+
+    def test_delete_blocked(self):
+        result = yurki.regexp.find(["a1", "b2", "c3"], r"\w\d", jobs=1)
+        with pytest.raises(TypeError, match="immutable"):
+            del result[0]
+        with pytest.raises(TypeError, match="immutable"):
+            del result[-1]
+        with pytest.raises(TypeError, match="immutable"):
+            del result[0:2]
+        assert result == ["a1", "b2", "c3"]
+
+    def test_slice_assignment_blocked(self):
+        result = yurki.regexp.find(["a1", "b2", "c3", "d4"], r"\w\d", jobs=1)
+        with pytest.raises(TypeError, match="slice assignment not allowed"):
+            result[1:3] = ["x", "y"]
+        with pytest.raises(TypeError, match="slice assignment not allowed"):
+            result.__setitem__(slice(None, None, -1), result)
+        with pytest.raises(TypeError, match="slice deletion not allowed"):
+            result.__delitem__(slice(0, 2))
+        assert result == ["a1", "b2", "c3", "d4"]
+
+    def test_growing_slice_assignment_regression(self):
+        # Used to resize the storage allocated with the object: abort in realloc
+        result = yurki.regexp.find(["a1", "b2", "c3"], r"\w\d", jobs=1)
+        for _ in range(100):
+            with pytest.raises(TypeError, match="immutable"):
+                result[0:1] = ["q", "r", "s", "t"]
+            with pytest.raises(TypeError, match="immutable"):
+                del result[0:2]
+        assert result == ["a1", "b2", "c3"]
+        assert result[0:3] == ["a1", "b2", "c3"]
+
+    @pytest.mark.parametrize(
+        "mutate",
+        [
+            lambda lst: lst.append("x"),
+            lambda lst: lst.extend(["x"]),
+            lambda lst: lst.insert(0, "x"),
+            lambda lst: lst.remove("a1"),
+            lambda lst: lst.pop(),
+            lambda lst: lst.clear(),
+            lambda lst: lst.__iadd__(["x"]),
+            lambda lst: lst.__imul__(2),
+            lambda lst: lst.__imul__(0),
+            lambda lst: lst.__init__(),
+            lambda lst: lst.__init__(["p", "q", "r", "s"]),
+            lambda lst: lst.__setitem__(slice(0, 1), ["p", "q"]),
+            lambda lst: lst.__delitem__(0),
+            lambda lst: lst.__delitem__(slice(None)),
+        ],
+        ids=[
+            "append",
+            "extend",
+            "insert",
+            "remove",
+            "pop",
+            "clear",
+            "iadd",
+            "imul",
+            "imul_zero",
+            "init_empty",
+            "init_items",
+            "setitem_slice",
+            "delitem",
+            "delitem_slice",
+        ],
+    )
+    def test_resizing_methods_blocked(self, mutate):
+        result = yurki.regexp.find(["b2", "a1", "c3"], r"\w\d", jobs=1)
+        with pytest.raises(TypeError, match="immutable: cannot resize"):
+            mutate(result)
+        assert result == ["b2", "a1", "c3"]
+
+    def test_reordering_methods_allowed(self):
+        result = yurki.regexp.find(["b2", "a1", "c3"], r"\w\d", jobs=1)
+        result.sort()
+        assert result == ["a1", "b2", "c3"]
+        result.sort(key=lambda s: s[1], reverse=True)
+        assert result == ["c3", "b2", "a1"]
+        result.reverse()
+        assert result == ["a1", "b2", "c3"]
+        result[0] = "z9"
+        assert result == ["z9", "b2", "c3"]
+        assert type(result) is yurki.internal.List
+
+    @pytest.mark.parametrize("jobs", JOBS)
+    def test_count_and_index(self, jobs):
+        data = [f"{c}{i % 3}" for i, c in enumerate("abcabcabc" * 20)]
+        result = yurki.regexp.find(data, r"\w\d", jobs=jobs)
+        for value in ("a0", "b1", "c2", "a1"):
+            assert result.count(value) == data.count(value)
+        assert result.index("b1") == data.index("b1")
+        assert result.index("a0", 1) == data.index("a0", 1)
+        assert result.index("c2", -9, -1) == data.index("c2", -9, -1)
+        with pytest.raises(ValueError, match="is not in list"):
+            result.index("zz")
+        with pytest.raises(ValueError):
+            result.index("a0", 1, 3)
+
+    def test_count_and_index_mixed_items(self):
+        result = yurki.list_from([1, 2.0, True, "1", None])
+        assert result.count(1) == 2
+        assert result.index(2) == 1
+        assert result.index(None) == 4
+        assert yurki.list_from([]).count("a") == 0
+
+    def test_failed_sort_keeps_items(self):
+        data = [f"{c}{i}" for i, c in enumerate("qwertyuiopasdfgh")]
+        result = yurki.regexp.find(data, r"\w\d+", jobs=1)
+        before = [sys.getrefcount(s) for s in result]
+        calls = 0
+
+        def key(s):
+            nonlocal calls
+            calls += 1
+            if calls > 8:
+                raise RuntimeError("stop")
+            return s
+
+        with pytest.raises(RuntimeError):
+            result.sort(key=key)
+        assert sorted(result) == sorted(data)
+        with pytest.raises(TypeError):
+            result.sort(key=lambda s: None if s == "q0" else s)
+        assert sorted(result) == sorted(data)
+        assert sorted(sys.getrefcount(s) for s in result) == sorted(before)
+
+    def test_sort_key_cannot_resize(self):
+        result = yurki.regexp.find(["b2", "a1"], r"\w\d", jobs=1)
+
+        def key(s):
+            result.append(s)
+            return s
+
+        with pytest.raises(TypeError, match="immutable"):
+            result.sort(key=key)
+        assert sorted(result) == ["a1", "b2"]
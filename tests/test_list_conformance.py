@@ -0,0 +1,114 @@
+import operator
+import pickle
+
+import pytest
+
+import yurki
+
+
+JOBS = [1, 4]
+
+SIZES = [0, 1, 2, 3, 7, 64, 1_000]
+
+INDICES = [0, 1, 2, -1, -2, True, False, 2**40, -(2**40)]
+
+SLICES = [
+    slice(None),
+    slice(None, None, -1),
+    slice(1, None, 2),
+    slice(-3, None),
+    slice(5, 2),
+    slice(None, None, 3),
+    slice(-100, 100, -7),
+    slice(2**40, None),
+]
+
+
+def outcome(f, *args):
+    try:
+        return "ok", f(*args)
+    except Exception as e:
+        return type(e).__name__, str(e)
+
+
+def results(n, jobs):
+    # The same items as a map result (yurki.List slots filled by workers)
+    # and built from an iterable
+    data = [f"item {i}" for i in range(n)]
+    return data, [yurki.regexp.find(data, r".+", jobs=jobs), yurki.list_from(data)]
+
+
+READS = {
+    "iter": lambda l: [x for x in l],
+    "reversed": lambda l: list(reversed(l)),
+    "len": len,
+    "list": list,
+    "tuple": tuple,
+    "bool": bool,
+    "repr": repr,
+    "contains": lambda l: ("item 0" in l, "missing" in l),
+    "compare": lambda l: (l == l[:], l < [*l, "x"], l >= l[:-1]),
+    "length_hint": lambda l: (operator.length_hint(iter(l)), operator.length_hint(reversed(l))),
+    "partial_iter": lambda l: (lambda it: (next(it, None), list(it)))(iter(l)),
+    "pickled_iter": lambda l: (lambda it: (next(it, None), list(pickle.loads(pickle.dumps(it)))))(iter(l)),
+    "float_index": lambda l: l[1.0],
+    "str_index": lambda l: l["a"],
+}
+
+
+class TestListConformance:
+    @pytest.mark.parametrize("jobs", JOBS)
+    @pytest.mark.parametrize("n", SIZES)
+    @pytest.mark.parametrize("read", READS)
+    def test_reads_match_list(self, read, n, jobs):
+        data, lists = results(n, jobs)
+        expected = outcome(READS[read], data)
+        for yl in lists:
+            got = outcome(READS[read], yl)
+            assert got == expected
+
+    @pytest.mark.parametrize("jobs", JOBS)
+    @pytest.mark.parametrize("n", SIZES)
+    def test_indexing_matches_list(self, n, jobs):
+        data, lists = results(n, jobs)
+        indices = INDICES + [n, n - 1, -n, -n - 1]
+        for yl in lists:
+            for i in indices:
+                assert outcome(operator.getitem, yl, i) == outcome(operator.getitem, data, i)
+                assert outcome(yl.__getitem__, i) == outcome(data.__getitem__, i)
+            for s in SLICES:
+                assert yl[s] == data[s]
+                assert type(yl[s]) is yurki.internal.List
+
+    def test_index_errors(self):
+        for yl in (yurki.regexp.find([], r"."), yurki.list_from([]), yurki.list_from(["a"])):
+            with pytest.raises(IndexError, match="^list index out of range$"):
+                yl[len(yl)]
+            with pytest.raises(IndexError, match="^list index out of range$"):
+                yl[-len(yl) - 1]
+            with pytest.raises(TypeError, match="list indices must be integers or slices, not str"):
+                yl["0"]
+
+    @pytest.mark.parametrize("jobs", JOBS)
+    def test_nested_results(self, jobs):
+        data = [" ".join(f"w{j}" for j in range(i % 5)) for i in range(500)]
+        result = yurki.regexp.split(data, r" ", jobs=jobs)
+        expected = [s.split(" ") for s in data]
+        assert len(result) == len(expected)
+        for got, want in zip(result, expected, strict=True):
+            assert type(got) is yurki.internal.List
+            assert list(got) == want and len(got) == len(want)
+            assert list(reversed(got)) == want[::-1]
+            assert got[-1] == want[-1] and got[0] == want[0]
+
+    def test_iterator_sees_item_assignment(self):
+        yl = yurki.list_from(["a", "b", "c"])
+        it = iter(yl)
+        assert next(it) == "a"
+        yl[1] = "B"
+        assert list(it) == ["B", "c"]
+
+    def test_iterator_outlives_list(self):
+        it = iter(yurki.list_from(f"s{i}" for i in range(100)))
+        assert next(it) == "s0"
+        assert list(it) == [f"s{i}" for i in range(1, 100)]
@@ -0,0 +1,68 @@
+import gc
+import threading
+
+import pytest
+
+import yurki
+
+
+JOBS = [1, 4]
+
+
+class TestListMutation:
+    @pytest.mark.parametrize("jobs", JOBS)
+    def test_progress_mutates_input(self, jobs):
+        data = [f"row {i}" for i in range(10_000)]
+
+        def progress(n, total):
+            # Replaced strings are freed, the call must keep reading its own references
+            for i in range(len(data)):
+                data[i] = "".join(["x", "y"])
+            data.clear()
+
+        result = yurki.regexp.find(data, r"\d+", jobs=jobs, progress=progress, progress_interval=1_000)
+        assert result == [str(i) for i in range(10_000)]
+        assert data == []
+
+    @pytest.mark.parametrize("jobs", JOBS)
+    def test_inplace_resized_during_call(self, jobs):
+        data = [f"row {i}" for i in range(10_000)]
+
+        with pytest.raises(RuntimeError, match="changed size"):
+            yurki.regexp.find(data, r"\d+", jobs=jobs, inplace=True, progress=lambda n, total: data.clear(), progress_interval=1_000)
+
+    @pytest.mark.parametrize("jobs", JOBS)
+    def test_concurrent_writer_thread(self, jobs):
+        data = [f"row {i}" for i in range(20_000)]
+        expected = [str(i) for i in range(20_000)]
+        stop = threading.Event()
+
+        def writer():
+            while not stop.is_set():
+                for i in range(0, len(data), 7):
+                    data[i] = f"row {i}"
+
+        thread = threading.Thread(target=writer)
+        thread.start()
+        try:
+            for _ in range(20):
+                assert yurki.regexp.find(data, r"\d+", jobs=jobs) == expected
+        finally:
+            stop.set()
+            thread.join()
+
+    @pytest.mark.parametrize("jobs", JOBS)
+    def test_non_str_item(self, jobs):
+        with pytest.raises(TypeError, match="index 1"):
+            yurki.regexp.find(["a", 1, "b"], r"\w", jobs=jobs)
+
+    @pytest.mark.parametrize("jobs", JOBS)
+    @pytest.mark.parametrize("inplace", [False, True])
+    def test_non_str_item_mid_list(self, jobs, inplace):
+        # Used to be able to leave a partially filled result behind
+        data = ["a-b"] * 2_000 + [None] + ["c-d"] * 2_000
+        with pytest.raises(TypeError, match="index 2000"):
+            yurki.regexp.split(data, r"-", jobs=jobs, inplace=inplace)
+        gc.collect()
+        assert data[:2] == ["a-b", "a-b"] and data[2_000] is None and data[-1] == "c-d"
+        assert yurki.regexp.split(data[:3], r"-", jobs=jobs) == [["a", "b"]] * 3
@@ -0,0 +1,43 @@
+import pytest
+
+import yurki
+
+
+JOBS = [1, 4]
+
+
+class TestMapIter:
+    @pytest.mark.parametrize("jobs", JOBS)
+    @pytest.mark.parametrize("batch_size", [1, 7, 10_000])
+    def test_generator(self, jobs, batch_size):
+        data = (f"row {i}" for i in range(1_000))
+        result = yurki.internal.map_iter(data, [("find", r"\d+")], jobs=jobs, batch_size=batch_size)
+        assert list(result) == [str(i) for i in range(1_000)]
+
+    def test_lazy(self):
+        pulled = []
+
+        def source():
+            for i in range(100):
+                pulled.append(i)
+                yield f"{i}"
+
+        result = yurki.internal.map_iter(source(), [("is_match", "0")], batch_size=10)
+        assert next(result) is True
+        assert len(pulled) == 10
+
+    def test_empty(self):
+        assert list(yurki.internal.map_iter(iter([]), [("lower",)])) == []
+
+    def test_pipeline_ops(self):
+        result = yurki.internal.map_iter(["  A,B ", "C"], [("strip",), ("lower",), ("split", ",")], jobs=2)
+        assert list(result) == [["a", "b"], ["c"]]
+
+    def test_non_str_item(self):
+        with pytest.raises(TypeError):
+            list(yurki.internal.map_iter(["a", 1], [("lower",)]))
+
+    @pytest.mark.parametrize("kwargs", [{"inplace": True}, {"batch_size": 0}, {"jobs": 0}])
+    def test_invalid_args(self, kwargs):
+        with pytest.raises(ValueError):
+            yurki.internal.map_iter(["a"], [("lower",)], **kwargs)
@@ -0,0 +1,71 @@
+import pytest
+
+import yurki
+
+
+JOBS = [1, 4]
+
+DATA = [f"ab{'a' * (i % 5)}ab, é-ab 😀ab" for i in range(2_000)] + ["", "ab", "aaaa", "日本語日本"]
+
+
+def find_all(s, needle, overlapping=False):
+    offsets = []
+    at = s.find(needle)
+    while at != -1:
+        offsets.append(at)
+        at = s.find(needle, at + (1 if overlapping else max(len(needle), 1)))
+    return offsets
+
+
+def byte_offsets(s, offsets):
+    return [len(s[:at].encode()) for at in offsets]
+
+
+class TestMatchIndices:
+    @pytest.mark.parametrize("jobs", JOBS)
+    @pytest.mark.parametrize("needle", ["ab", "a", "é", "😀", "日本", "missing"])
+    def test_char_offsets_match_str_find(self, jobs, needle):
+        result = yurki.internal.match_indices_string(DATA, needle, chars=True, jobs=jobs)
+        assert result == [find_all(s, needle) for s in DATA]
+
+    @pytest.mark.parametrize("jobs", JOBS)
+    @pytest.mark.parametrize("needle", ["ab", "a", "é", "日本"])
+    def test_byte_offsets(self, jobs, needle):
+        result = yurki.internal.match_indices_string(DATA, needle, jobs=jobs)
+        assert result == [byte_offsets(s, find_all(s, needle)) for s in DATA]
+
+    @pytest.mark.parametrize("jobs", JOBS)
+    def test_overlapping(self, jobs):
+        data = ["aaaa", "ababa", "ééé", "日日日"]
+        for needle in ["aa", "aba", "éé", "日日"]:
+            result = yurki.internal.match_indices_string(data, needle, overlapping=True, chars=True, jobs=jobs)
+            assert result == [find_all(s, needle, overlapping=True) for s in data]
+        assert yurki.internal.match_indices_string(["aaaa"], "aa") == [[0, 2]]
+        assert yurki.internal.match_indices_string(["aaaa"], "aa", overlapping=True) == [[0, 1, 2]]
+
+    def test_empty_needle(self):
+        # Every position, like str.find("", i) for i in range(len(s) + 1)
+        data = ["", "ab", "é😀"]
+        assert yurki.internal.match_indices_string(data, "", chars=True) == [[0], [0, 1, 2], [0, 1, 2]]
+        assert yurki.internal.match_indices_string(data, "") == [[0], [0, 1, 2], [0, 2, 6]]
+        assert yurki.internal.match_indices_string(data, "", overlapping=True, chars=True) == [[0], [0, 1, 2], [0, 1, 2]]
+
+    @pytest.mark.parametrize("jobs", JOBS)
+    def test_long_rows(self, jobs):
+        # Longer than a vector, with needles in the SIMD body and the tail
+        data = [("x" * 70 + ",") * 3 + "x" * i for i in range(100)]
+        result = yurki.internal.match_indices_string(data, ",", jobs=jobs)
+        assert result == [[70, 141, 212]] * 100
+
+    @pytest.mark.parametrize("jobs", JOBS)
+    def test_slice_and_inplace(self, jobs):
+        data = ["a,b", "c", ",,"] * 100
+        assert yurki.internal.match_indices_string(data, ",", jobs=jobs, start=1, step=2) == [
+            find_all(s, ",") for s in data[1::2]
+        ]
+        result = yurki.internal.match_indices_string(data, ",", jobs=jobs, inplace=True)
+        assert result is data
+        assert data[:3] == [[1], [], [0, 1]]
+
+    def test_empty_list(self):
+        assert yurki.internal.match_indices_string([], "a", jobs=4) == []
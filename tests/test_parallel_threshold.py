@@ -0,0 +1,61 @@
+import pytest
+
+import yurki
+
+
+DEFAULT_THRESHOLD = 512 * 1024
+
+
+@pytest.fixture
+def threshold():
+    previous = yurki.internal.get_parallel_threshold()
+    yield yurki.internal.set_parallel_threshold
+    yurki.internal.set_parallel_threshold(previous)
+
+
+class TestParallelThreshold:
+    def test_set_and_get(self, threshold):
+        threshold(DEFAULT_THRESHOLD)
+        assert yurki.internal.get_parallel_threshold() == DEFAULT_THRESHOLD
+        threshold(0)
+        assert yurki.internal.get_parallel_threshold() == 0
+
+    @pytest.mark.parametrize("limit", [0, 1_000, DEFAULT_THRESHOLD, 2**63])
+    @pytest.mark.parametrize("size", [1, 100, 5_000])
+    def test_results_unchanged(self, threshold, limit, size):
+        threshold(limit)
+        data = [f"row {i} " + "x" * (i % 300) for i in range(size)]
+        expected = [f"row {i}" for i in range(size)]
+        for jobs in (1, 2, 8):
+            assert yurki.regexp.find(data, r"row \d+", jobs=jobs) == expected
+            pairs = yurki.internal.concat_pairwise(expected, expected, "/", jobs=jobs)
+            assert pairs == [f"{s}/{s}" for s in expected]
+
+    @pytest.mark.parametrize("limit", [0, 2**63])
+    def test_inplace_and_progress(self, threshold, limit):
+        threshold(limit)
+        calls = []
+        data = [f"k{i}" for i in range(3_000)]
+        yurki.regexp.replace(
+            data, r"k", "key", jobs=4, inplace=True, progress=lambda done, total: calls.append(done)
+        )
+        assert data == [f"key{i}" for i in range(3_000)]
+        assert calls[-1] == 3_000
+
+
+class TestBenchCrossover:
+    """Sequential vs parallel over growing inputs, with the switch disabled.
+
+    The smallest size where jobs=4 beats jobs=1 is the crossover point of
+    the machine; `set_parallel_threshold` takes its estimated size, about
+    (characters + 256 per row).
+    """
+
+    @pytest.mark.benchmark(group="crossover")
+    @pytest.mark.parametrize("rows", [100, 1_000, 4_000, 16_000, 64_000], ids=lambda n: f"rows={n}")
+    @pytest.mark.parametrize("row_len", [8, 64, 512], ids=lambda n: f"len={n}")
+    @pytest.mark.parametrize("jobs", [1, 4], ids=lambda j: f"jobs={j}")
+    def test_replace(self, threshold, rows, row_len, jobs, benchmark):
+        threshold(0)
+        data = [(f"{i} " * row_len)[:row_len] for i in range(rows)]
+        benchmark(yurki.regexp.replace, data, r"\d+", "N", jobs=jobs)
@@ -0,0 +1,60 @@
+import re
+
+import pytest
+
+import yurki
+
+
+JOBS = [1, 4]
+
+
+def pipeline_python(data):
+    return [re.sub(r"\s+", " ", s.strip().lower()).replace("world", "there") for s in data]
+
+
+class TestPipeline:
+    @pytest.mark.parametrize("jobs", JOBS)
+    @pytest.mark.parametrize("inplace", [False, True])
+    def test_string_ops(self, jobs, inplace):
+        data = [f"  Hello   World {i}  " for i in range(100)] + ["  ПРИВЕТ  "]
+        expected = pipeline_python(data)
+        ops = [("strip",), ("lower",), ("replace_regexp", r"\s+", " ", 0), ("replace", "world", "there")]
+        assert yurki.internal.pipeline(data, ops, jobs, inplace) == expected
+
+    @pytest.mark.parametrize("jobs", JOBS)
+    def test_terminal_ops(self, jobs):
+        data = ["  A-1,B-2  ", "none"]
+        assert yurki.internal.pipeline(data, [("strip",), ("lower",), ("split", ",")], jobs, False) == [
+            ["a-1", "b-2"],
+            ["none"],
+        ]
+        assert yurki.internal.pipeline(data, [("upper",), ("capture", r"(\w)-(\d)")], jobs, False) == [
+            ["A-1", "A", "1"],
+            [],
+        ]
+        assert yurki.internal.pipeline(data, [("find", r"b-\d", True), ("is_match", "B")], jobs, False) == [
+            True,
+            False,
+        ]
+
+    def test_empty_ops(self):
+        assert yurki.internal.pipeline(["a", "b"], [], 1, False) == ["a", "b"]
+
+    @pytest.mark.parametrize(
+        "ops",
+        [
+            [("unknown",)],
+            [("lower", "extra")],
+            [("replace", "a")],
+            [("find", "(unclosed")],
+            [("split", ","), ("lower",)],
+            [()],
+        ],
+    )
+    def test_invalid_ops(self, ops):
+        with pytest.raises(ValueError):
+            yurki.internal.pipeline(["a"], ops, 1, False)
+
+    def test_op_must_be_tuple(self):
+        with pytest.raises(TypeError):
+            yurki.internal.pipeline(["a"], ["lower"], 1, False)
@@ -0,0 +1,95 @@
+import ctypes
+import sysconfig
+
+import pytest
+
+import yurki
+
+
+JOBS = [1, 4]
+
+# PyASCIIObject: PyObject header, length, hash, state
+HASH_OFFSET = object.__basicsize__ + ctypes.sizeof(ctypes.c_ssize_t)
+STATE_OFFSET = HASH_OFFSET + ctypes.sizeof(ctypes.c_ssize_t)
+
+
+def cached_hash(s):
+    return ctypes.c_ssize_t.from_address(id(s) + HASH_OFFSET).value
+
+
+def make_strings(n):
+    # Replacing guarantees new result strings rather than the inputs
+    data = [
+        text for i in range(n // 4) for text in (f"ascii {i}", f"latin é {i}", f"wide 日本 {i}", f"astral 😀 {i}")
+    ]
+    return yurki.regexp.replace(data, r" ", "_", jobs=1)
+
+
+class TestHash:
+    def test_equals_str_hash(self):
+        for s in make_strings(4):
+            assert hash(s) == hash(str(s))
+            assert {s: 1}[str(s)] == 1
+
+    def test_hash_is_cached(self):
+        for s in make_strings(4):
+            assert cached_hash(s) == -1
+            first = hash(s)
+            assert cached_hash(s) == first
+            assert hash(s) == first
+
+    @pytest.mark.skipif(bool(sysconfig.get_config_var("Py_GIL_DISABLED")), reason="GIL build state layout")
+    def test_not_statically_allocated(self):
+        for s in make_strings(4):
+            state = ctypes.c_uint32.from_address(id(s) + STATE_OFFSET).value
+            assert state & (1 << 7) == 0
+
+
+class TestPrehash:
+    @pytest.mark.parametrize("jobs", JOBS)
+    def test_prehash(self, jobs):
+        data = make_strings(10_000) + ["plain str", "another str"]
+        assert yurki.internal.prehash(data, jobs=jobs) is data
+        for s in data:
+            assert cached_hash(s) == hash(str(s))
+
+    @pytest.mark.parametrize("jobs", JOBS)
+    def test_same_string_twice(self, jobs):
+        s = make_strings(4)[2]
+        yurki.internal.prehash([s] * 100, jobs=jobs)
+        assert cached_hash(s) == hash(str(s))
+
+    def test_str_subclass(self):
+        calls = []
+
+        class Key(str):
+            def __hash__(self):
+                calls.append(self)
+                return 7
+
+        data = ["abc", Key("key")]
+        yurki.internal.prehash(data, jobs=2)
+        assert calls == ["key"]
+
+    @pytest.mark.parametrize("jobs", JOBS)
+    def test_hash_clears_list(self, jobs):
+        data = []
+
+        class Key(str):
+            def __hash__(self):
+                data.clear()
+                return 1
+
+        strings = make_strings(100)
+        data.extend([Key("x")] + strings)
+        assert yurki.internal.prehash(data, jobs=jobs) is data
+        assert data == []
+        for s in strings:
+            assert cached_hash(s) == hash(str(s))
+
+    def test_not_str(self):
+        with pytest.raises(TypeError, match="index 1"):
+            yurki.internal.prehash(["abc", b"abc"], jobs=1)
+
+    def test_empty(self):
+        assert yurki.internal.prehash([], jobs=4) == []
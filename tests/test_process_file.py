@@ -0,0 +1,69 @@
+import pytest
+
+import yurki
+
+
+JOBS = [1, 4]
+
+
+class TestProcessFile:
+    @pytest.mark.parametrize("jobs", JOBS)
+    def test_process_file(self, tmp_path, jobs):
+        path = tmp_path / "input.txt"
+        path.write_bytes("".join(f"row {i}\n" for i in range(1_000)).encode())
+        result = yurki.internal.process_file(str(path), [("find", r"\d+")], jobs=jobs)
+        assert result == [str(i) for i in range(1_000)]
+
+    @pytest.mark.parametrize("jobs", JOBS)
+    def test_line_endings(self, tmp_path, jobs):
+        path = tmp_path / "input.txt"
+        path.write_bytes(b"Alpha\r\nbeta\n\ngamma")
+        assert yurki.internal.process_file(path, [("lower",)], jobs=jobs) == ["alpha", "beta", "", "gamma"]
+
+    def test_empty_file(self, tmp_path):
+        path = tmp_path / "input.txt"
+        path.write_bytes(b"")
+        assert yurki.internal.process_file(path, [("lower",)], jobs=4) == []
+
+    def test_invalid_utf8(self, tmp_path):
+        path = tmp_path / "input.txt"
+        path.write_bytes(b"ok\nbad \xff byte\n")
+        with pytest.raises(UnicodeDecodeError):
+            yurki.internal.process_file(path, [])
+        assert yurki.internal.process_file(path, [], errors="replace") == ["ok", "bad � byte"]
+
+    @pytest.mark.parametrize("jobs", JOBS)
+    def test_output_path(self, tmp_path, jobs):
+        path = tmp_path / "input.txt"
+        path.write_bytes("".join(f"Row {i}\n" for i in range(1_000)).encode())
+        output = tmp_path / "output.txt"
+
+        written = yurki.internal.process_file(path, [("lower",)], jobs=jobs, output_path=output)
+        assert written == 1_000
+        assert output.read_text() == "".join(f"row {i}\n" for i in range(1_000))
+
+    @pytest.mark.parametrize("jobs", JOBS)
+    def test_output_path_filter(self, tmp_path, jobs):
+        path = tmp_path / "input.txt"
+        path.write_bytes("".join(f"Row {i}\r\n" for i in range(1_000)).encode())
+        output = tmp_path / "output.txt"
+
+        written = yurki.internal.process_file(path, [("lower",), ("is_match", r"row \d*7$")], jobs=jobs, output_path=output)
+        assert written == 100
+        assert output.read_text() == "".join(f"Row {i}\n" for i in range(1_000) if i % 10 == 7)
+
+    def test_output_path_list_ops(self, tmp_path):
+        path = tmp_path / "input.txt"
+        path.write_bytes(b"a,b\n")
+        with pytest.raises(ValueError):
+            yurki.internal.process_file(path, [("split", ",")], output_path=tmp_path / "output.txt")
+
+    def test_invalid_args(self, tmp_path):
+        path = tmp_path / "input.txt"
+        path.write_bytes(b"a\n")
+        with pytest.raises(ValueError):
+            yurki.internal.process_file(path, [], encoding="latin-1")
+        with pytest.raises(ValueError):
+            yurki.internal.process_file(path, [], errors="ignore")
+        with pytest.raises(OSError):
+            yurki.internal.process_file(tmp_path / "missing.txt", [])
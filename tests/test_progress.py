@@ -0,0 +1,80 @@
+import pytest
+
+import yurki
+
+
+JOBS = [1, 4]
+
+
+class TestProgress:
+    @pytest.mark.parametrize("jobs", JOBS)
+    @pytest.mark.parametrize("inplace", [False, True])
+    def test_progress(self, jobs, inplace):
+        data = [f"row {i}" for i in range(10_000)]
+        calls = []
+        result = yurki.regexp.find(
+            data,
+            r"\d+",
+            jobs=jobs,
+            inplace=inplace,
+            progress=lambda n, total: calls.append((n, total)),
+            progress_interval=1_000,
+        )
+        assert result == [str(i) for i in range(10_000)]
+        assert calls[-1] == (10_000, 10_000)
+        assert all(total == 10_000 for _, total in calls)
+
+    @pytest.mark.parametrize("jobs", JOBS)
+    @pytest.mark.parametrize("size", [0, 999, 5_000])
+    def test_monotonic(self, jobs, size):
+        data = [f"row {i}" for i in range(size)]
+        calls = []
+        yurki.regexp.is_match(data, r"\d", jobs=jobs, progress=lambda n, total: calls.append(n), progress_interval=100)
+
+        assert calls == sorted(calls)
+        assert all(b - a >= 100 for a, b in zip(calls, calls[1:-1]))
+        # Exactly one final call at completion
+        assert calls[-1] == size
+        assert calls.count(size) == 1
+
+    @pytest.mark.parametrize("jobs", JOBS)
+    def test_compiled_and_classifier(self, jobs):
+        data = [f"row {i}" for i in range(3_000)]
+        calls = []
+
+        def progress(n, total):
+            calls.append((n, total))
+
+        pattern = yurki.regexp.compile(r"\d+")
+        pattern.findall(data, jobs=jobs, progress=progress, progress_interval=500)
+        pattern.match(data, jobs=jobs, progress=progress, progress_interval=500)
+        pattern.replace(data, "N", jobs=jobs, progress=progress, progress_interval=500)
+        yurki.regexp.classifier({"n": r"\d"}).classify(data, jobs=jobs, progress=progress, progress_interval=500)
+
+        assert calls.count((3_000, 3_000)) == 4
+
+    @pytest.mark.parametrize("jobs", JOBS)
+    def test_callback_error(self, jobs):
+        def progress(n, total):
+            raise RuntimeError("stop")
+
+        data = [f"row {i}" for i in range(10_000)]
+        with pytest.raises(RuntimeError, match="stop"):
+            yurki.regexp.replace(data, r"\d+", "N", jobs=jobs, progress=progress, progress_interval=100)
+
+    def test_invalid_interval(self):
+        with pytest.raises(ValueError):
+            yurki.regexp.find(["a"], r"a", progress=print, progress_interval=0)
+
+    @pytest.mark.parametrize("jobs", JOBS)
+    def test_callback_error_partial_result(self, jobs):
+        def progress(n, total):
+            if n > 0:
+                raise RuntimeError("stop")
+
+        # The partially filled result (nested lists included) is released, not returned
+        data = [f"a,b,{i}" for i in range(20_000)]
+        for _ in range(20):
+            with pytest.raises(RuntimeError, match="stop"):
+                yurki.regexp.split(data, r",", jobs=jobs, progress=progress, progress_interval=1_000)
+        assert yurki.regexp.split(data, r",", jobs=jobs)[-1] == ["a", "b", "19999"]
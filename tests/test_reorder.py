@@ -0,0 +1,68 @@
+import random
+
+import pytest
+
+import yurki
+
+
+JOBS = [1, 4]
+
+
+def random_rows(n, seed=11):
+    rng = random.Random(seed)
+    return ["".join(rng.choice("abAB é中😀\0") for _ in range(rng.randrange(6))) for _ in range(n)]
+
+
+class TestReversedList:
+    def test_reverses_into_a_new_list(self):
+        data = yurki.list_from(["a", "é", "😀", 1, None])
+        result = yurki.internal.reversed_list(data)
+        assert isinstance(result, yurki.internal.List)
+        assert result == [None, 1, "😀", "é", "a"]
+        assert data == ["a", "é", "😀", 1, None]
+        assert all(x is y for x, y in zip(result, reversed(data)))
+
+    def test_builtin_list_input(self):
+        data = ["x", "y", "z"]
+        assert yurki.internal.reversed_list(data) == ["z", "y", "x"]
+        assert data == ["x", "y", "z"]
+
+    def test_empty(self):
+        assert yurki.internal.reversed_list([]) == []
+
+
+class TestSortStrings:
+    @pytest.mark.parametrize("jobs", JOBS)
+    @pytest.mark.parametrize("reverse", [False, True])
+    def test_matches_sorted(self, jobs, reverse):
+        data = random_rows(5_000)
+        result = yurki.internal.sort_strings(data, reverse=reverse, jobs=jobs)
+        assert isinstance(result, yurki.internal.List)
+        assert result == sorted(data, reverse=reverse)
+
+    @pytest.mark.parametrize("jobs", JOBS)
+    @pytest.mark.parametrize("reverse", [False, True])
+    def test_stable(self, jobs, reverse):
+        # Equal strings built separately, so their order is visible by identity
+        data = ["".join(["b", "é"]), "a", "".join(["b", "é"]), "c", "".join(["b", "é"])]
+        result = yurki.internal.sort_strings(data, reverse=reverse, jobs=jobs)
+        expected = sorted(data, reverse=reverse)
+        assert [id(x) for x in result] == [id(x) for x in expected]
+
+    def test_original_unchanged(self):
+        data = yurki.regexp.find(["c1", "a2", "b3"], r"\w")
+        result = yurki.internal.sort_strings(data)
+        assert result == ["a", "b", "c"]
+        assert data == ["c", "a", "b"]
+
+    def test_code_point_order(self):
+        # Astral characters sort after the BMP, as in str comparison
+        data = ["￿", "😀", "퟿", "", "z"]
+        assert yurki.internal.sort_strings(data) == sorted(data)
+
+    def test_empty(self):
+        assert yurki.internal.sort_strings([]) == []
+
+    def test_rejects_non_str(self):
+        with pytest.raises(TypeError, match="expected str at index 1, got int"):
+            yurki.internal.sort_strings(["a", 1])
@@ -0,0 +1,72 @@
+import re
+
+import pytest
+
+import yurki
+
+
+JOBS = [1, 4]
+
+DATA = [f"id {i}: {i * 7} items, {i % 13} left" for i in range(2_000)] + ["", "no digits", "é12ü345", "😀7😀"]
+
+
+def double(s):
+    return str(int(s) * 2)
+
+
+def expected(pattern, func, data, count=0):
+    return [re.sub(pattern, lambda m: func(m.group(0)), s, count=count) for s in data]
+
+
+class TestReplaceCallback:
+    @pytest.mark.parametrize("jobs", JOBS)
+    def test_matches_re_sub(self, jobs):
+        result = yurki.regexp.replace_callback(DATA, r"\d+", double, count=0, jobs=jobs)
+        assert result == expected(r"\d+", double, DATA)
+
+    @pytest.mark.parametrize("jobs", JOBS)
+    @pytest.mark.parametrize("count", [1, 2, 5])
+    def test_count(self, jobs, count):
+        result = yurki.regexp.replace_callback(DATA, r"\d+", double, count=count, jobs=jobs)
+        assert result == expected(r"\d+", double, DATA, count=count)
+
+    def test_first_match_by_default(self):
+        assert yurki.regexp.replace_callback(["a1b2"], r"\d", lambda s: "#") == ["a#b2"]
+
+    def test_case_insensitive(self):
+        result = yurki.regexp.replace_callback(["aAbB"], r"a", str.upper, count=0, case=True)
+        assert result == ["AAbB"]
+
+    @pytest.mark.parametrize("jobs", JOBS)
+    def test_called_in_order(self, jobs):
+        seen = []
+
+        def record(s):
+            seen.append(s)
+            return s
+
+        yurki.regexp.replace_callback(DATA, r"\d+", record, count=0, jobs=jobs)
+        assert seen == [m.group(0) for s in DATA for m in re.finditer(r"\d+", s)]
+
+    def test_unmatched_rows_are_inputs(self):
+        data = ["no digits here", "nor here"]
+        result = yurki.regexp.replace_callback(data, r"\d", double)
+        assert all(after is before for before, after in zip(data, result))
+
+    def test_callback_error_propagates(self):
+        def fail(s):
+            raise KeyError(s)
+
+        with pytest.raises(KeyError, match="'1'"):
+            yurki.regexp.replace_callback(["a", "b1"], r"\d", fail)
+
+    def test_non_str_result(self):
+        with pytest.raises(TypeError, match="callback must return str, not int"):
+            yurki.regexp.replace_callback(["1"], r"\d", int)
+
+    def test_not_callable(self):
+        with pytest.raises(TypeError, match="func must be callable"):
+            yurki.internal.replace_regex_callback(["1"], r"\d", "x")
+
+    def test_empty_list(self):
+        assert yurki.regexp.replace_callback([], r"\d", double, jobs=4) == []
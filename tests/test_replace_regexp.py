@@ -118,6 +118,14 @@ class TestReplace:
         )
         assert result == expected
 
+    @pytest.mark.parametrize("jobs", JOBS)
+    def test_long_cjk_rows(self, jobs):
+        # Long BMP results are decoded to UCS-2 in vector chunks, at every alignment
+        row = "漢字かなカナ、한국어 텍스트。Ωμέγα ж é ascii "
+        data = [("x" * (i % 40)) + row * (1 + i % 30) for i in range(400)]
+        result = yurki.regexp.replace(data, r"。", "．", count=0, jobs=jobs)
+        assert result == [s.replace("。", "．") for s in data]
+
     @pytest.mark.parametrize("jobs", JOBS)
     def test_case_insensitive(self, jobs):
         data = ["Test string with TEST and more test content"]
@@ -0,0 +1,111 @@
+import sys
+
+import pytest
+
+import yurki
+
+
+JOBS = [1, 2, 4, 7]
+
+
+class TestReuseUnchanged:
+    @pytest.mark.parametrize("jobs", JOBS)
+    def test_unchanged_is_input(self, jobs):
+        data = [f"row {i}" if i % 100 else f"row {i} foo" for i in range(10_000)]
+        result = yurki.regexp.replace(data, r"foo", "bar", jobs=jobs)
+        assert result == [s.replace("foo", "bar") for s in data]
+        for i, (before, after) in enumerate(zip(data, result)):
+            if i % 100:
+                assert after is before
+            else:
+                assert after is not before
+
+    @pytest.mark.parametrize("jobs", JOBS)
+    @pytest.mark.parametrize("count", [0, 3])
+    def test_unmatched_rows_any_count(self, jobs, count):
+        # replace_all and replacen both borrow the input when nothing matches
+        data = [f"row {i}" if i % 10 else f"row {i} foo foo" for i in range(1_000)]
+        result = yurki.regexp.replace(data, r"foo", "bar", count=count, jobs=jobs)
+        assert result == [s.replace("foo", "bar", count or -1) for s in data]
+        assert all((after is before) == bool(i % 10) for i, (before, after) in enumerate(zip(data, result)))
+
+    @pytest.mark.parametrize("jobs", JOBS)
+    def test_compiled_pattern(self, jobs):
+        data = [f"row {i}" if i % 10 else f"row {i} foo" for i in range(1_000)]
+        result = yurki.regexp.compile(r"foo").replace(data, "bar", count=0, jobs=jobs)
+        assert all((after is before) == bool(i % 10) for i, (before, after) in enumerate(zip(data, result)))
+
+    @pytest.mark.parametrize("jobs", JOBS)
+    def test_refcount(self, jobs):
+        data = [f"value {i}" for i in range(1_000)]
+        first = data[0]
+        before = sys.getrefcount(first)
+        result = yurki.regexp.replace(data, r"missing", "x", jobs=jobs)
+        assert sys.getrefcount(first) == before + 1
+        del result
+        assert sys.getrefcount(first) == before
+
+    @pytest.mark.parametrize("jobs", JOBS)
+    def test_non_ascii(self, jobs):
+        data = [f"запись {i}" for i in range(1_000)]
+        result = yurki.regexp.replace(data, r"missing", "x", jobs=jobs)
+        assert result == data
+        assert all(after is before for before, after in zip(data, result))
+
+    @pytest.mark.parametrize("jobs", JOBS)
+    def test_partial_result_not_reused(self, jobs):
+        data = [f"key={i}" for i in range(1_000)]
+        result = yurki.regexp.find(data, r"key", jobs=jobs)
+        assert result == ["key"] * 1_000
+        assert all(after is not before for before, after in zip(data, result))
+
+    @pytest.mark.parametrize("jobs", JOBS)
+    def test_nested(self, jobs):
+        data = [f"nodelimiter{i}" for i in range(1_000)]
+        result = yurki.regexp.split(data, r"-", jobs=jobs)
+        assert all(parts[0] is s for s, parts in zip(data, result))
+        del data
+        assert result[10] == ["nodelimiter10"]
+
+    def test_subclass_not_reused(self):
+        class Tagged(str):
+            pass
+
+        data = [Tagged(f"item {i}") for i in range(100)]
+        result = yurki.regexp.replace(data, r"missing", "x", jobs=2)
+        assert result == data
+        assert all(type(s) is not Tagged for s in result)
+
+    @pytest.mark.parametrize("jobs", JOBS)
+    def test_fast_strings_reused(self, jobs):
+        data = yurki.regexp.replace([f"fast {i}" for i in range(1_000)], r"fast", "quick", jobs=jobs)
+        result = yurki.regexp.replace(data, r"missing", "x", jobs=jobs)
+        assert all(after is before for before, after in zip(data, result))
+
+    @pytest.mark.parametrize("jobs", JOBS)
+    def test_inplace(self, jobs):
+        data = [f"line {i}" for i in range(1_000)]
+        data[5] = "line foo"
+        originals = list(data)
+        yurki.regexp.replace(data, r"foo", "bar", jobs=jobs, inplace=True)
+        assert data[5] == "line bar"
+        assert data[6] == originals[6]
+
+    def test_interned_results_take_precedence(self):
+        data = [f"dup {i % 2}" for i in range(1_000)]
+        result = yurki.regexp.replace(data, r"missing", "x", jobs=4, intern_results=True)
+        assert result == data
+        assert len({id(s) for s in result}) == 2
+
+    @pytest.mark.parametrize("jobs", JOBS)
+    def test_progress_error(self, jobs):
+        data = [f"row {i}" for i in range(50_000)]
+        first = data[0]
+        before = sys.getrefcount(first)
+
+        def progress(done, total):
+            raise RuntimeError("stop")
+
+        with pytest.raises(RuntimeError):
+            yurki.regexp.replace(data, r"missing", "x", jobs=jobs, progress=progress, progress_interval=1_000)
+        assert sys.getrefcount(first) == before
@@ -0,0 +1,25 @@
+import platform
+
+import yurki
+
+
+class TestSimdDispatch:
+    def test_reports_implementation(self):
+        name = yurki.internal.simd_implementation()
+        assert isinstance(name, str) and name
+        assert yurki.internal.simd_implementation() == name
+
+    def test_x86_uses_runtime_level(self):
+        if platform.machine().lower() not in ("x86_64", "amd64"):
+            return
+        assert yurki.internal.simd_implementation() in (
+            "avx512",
+            "avx2",
+            "sse2",
+            "avx2 (compile-time)",
+            "avx512 (compile-time)",
+        )
+
+    def test_non_ascii_roundtrip(self):
+        data = ["é ж 中 😀 " * 50 + str(i) for i in range(200)]
+        assert yurki.regexp.find(data, r"\d+$", jobs=2) == [str(i) for i in range(200)]
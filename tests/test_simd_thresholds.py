@@ -0,0 +1,58 @@
+import os
+import subprocess
+import sys
+
+import pytest
+
+import yurki
+
+
+DEFAULTS = {"bytes": 64, "ucs1": 96, "ucs2": 48, "ucs4": 32}
+
+# Every kind of str, at lengths around the thresholds and a few vectors
+DATA = [(ch * n)[:n] + "x" for n in range(0, 300, 7) for ch in ["a", "é", "ж中", "😀", "aé中😀"]]
+
+
+@pytest.fixture
+def thresholds():
+    previous = yurki.internal.get_simd_thresholds()
+    yield yurki.internal.set_simd_thresholds
+    yurki.internal.set_simd_thresholds(**previous)
+
+
+def roundtrip(jobs=2):
+    # Inputs are read through the UCS kernels, results decoded from UTF-8
+    return yurki.regexp.replace(DATA, r"x$", "y", jobs=jobs)
+
+
+class TestSimdThresholds:
+    def test_defaults(self):
+        if os.environ.get("YURKI_AUTOTUNE") != "1":
+            assert yurki.internal.get_simd_thresholds() == DEFAULTS
+
+    def test_set_only_given(self, thresholds):
+        thresholds(**DEFAULTS)
+        thresholds(ucs2=5)
+        assert yurki.internal.get_simd_thresholds() == {**DEFAULTS, "ucs2": 5}
+        thresholds(bytes=0, ucs4=2**63)
+        assert yurki.internal.get_simd_thresholds() == {**DEFAULTS, "ucs2": 5, "bytes": 0, "ucs4": 2**63}
+
+    @pytest.mark.parametrize("value", [0, 1, 17, 2**63])
+    def test_results_unchanged(self, thresholds, value):
+        expected = [s[:-1] + "y" for s in DATA]
+        thresholds(bytes=value, ucs1=value, ucs2=value, ucs4=value)
+        assert roundtrip() == expected
+        assert yurki.internal.char_len(DATA) == [len(s) for s in DATA]
+
+    def test_autotune(self, thresholds):
+        tuned = yurki.internal.autotune_simd_thresholds()
+        assert set(tuned) == set(DEFAULTS)
+        assert all(isinstance(value, int) and value >= 0 for value in tuned.values())
+        assert yurki.internal.get_simd_thresholds() == tuned
+        assert roundtrip() == [s[:-1] + "y" for s in DATA]
+
+    def test_autotune_at_import(self):
+        code = "import yurki; print(sorted(yurki.internal.get_simd_thresholds().items()))"
+        env = {**os.environ, "YURKI_AUTOTUNE": "1"}
+        out = subprocess.run([sys.executable, "-c", code], env=env, capture_output=True, text=True, check=True)
+        assert out.stdout.startswith("[('bytes', ")
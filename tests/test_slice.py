@@ -0,0 +1,53 @@
+import pytest
+
+import yurki
+
+
+JOBS = [1, 4]
+
+
+class TestSlice:
+    @pytest.mark.parametrize("jobs", JOBS)
+    @pytest.mark.parametrize(
+        "start, stop, step",
+        [(None, None, None), (2, 7, None), (None, None, 2), (-3, None, None), (None, -2, 3), (None, None, -1), (8, 1, -3)],
+    )
+    def test_matches_python_slice(self, jobs, start, stop, step):
+        data = [f"row {i}" for i in range(11)]
+        result = yurki.regexp.find(data, r"\d+", jobs=jobs, start=start, stop=stop, step=step)
+        assert result == [s[4:] for s in data[start:stop:step]]
+
+    @pytest.mark.parametrize("jobs", JOBS)
+    def test_inplace_step(self, jobs):
+        data = [f"row {i}" for i in range(7)]
+        result = yurki.regexp.find(data, r"\d+", jobs=jobs, inplace=True, step=2)
+        assert result is data
+        assert data == ["0", "row 1", "2", "row 3", "4", "row 5", "6"]
+
+    @pytest.mark.parametrize("jobs", JOBS)
+    def test_inplace_negative_step(self, jobs):
+        data = [f"Row {i}" for i in range(5)]
+        yurki.internal.pipeline(data, [("lower",)], jobs=jobs, inplace=True, start=-1, stop=1, step=-2)
+        assert data == ["Row 0", "Row 1", "row 2", "Row 3", "row 4"]
+
+    @pytest.mark.parametrize("jobs", JOBS)
+    @pytest.mark.parametrize("start, stop, step", [(3, 3, None), (5, 1, None), (1, 5, -1), (100, None, None)])
+    def test_empty_range(self, jobs, start, stop, step):
+        data = ["a", "b", "c", "d", "e"]
+        assert yurki.regexp.find(data, r"\w", jobs=jobs, start=start, stop=stop, step=step) == []
+        assert yurki.regexp.find(data, r"\w", jobs=jobs, inplace=True, start=start, stop=stop, step=step) is data
+        assert data == ["a", "b", "c", "d", "e"]
+
+    def test_progress_total(self):
+        calls = []
+        data = [f"row {i}" for i in range(100)]
+        yurki.regexp.is_match(data, r"\d", progress=lambda n, total: calls.append((n, total)), step=4)
+        assert calls[-1] == (25, 25)
+
+    def test_zero_step(self):
+        with pytest.raises(ValueError, match="step"):
+            yurki.regexp.find(["a"], r"a", step=0)
+
+    def test_skips_non_str_outside_range(self):
+        data = ["a1", None, "b2", None]
+        assert yurki.regexp.find(data, r"\d", step=2) == ["1", "2"]
@@ -0,0 +1,60 @@
+import pytest
+
+import yurki
+
+
+JOBS = [1, 2, 8]
+
+
+class TestSmallInputs:
+    @pytest.mark.parametrize("jobs", JOBS)
+    @pytest.mark.parametrize("size", [0, 1, 2])
+    def test_replace(self, jobs, size):
+        data = [f"item {i}" for i in range(size)]
+        result = yurki.regexp.replace(data, r"item", "x", jobs=jobs)
+        assert isinstance(result, yurki.internal.List)
+        assert result == [f"x {i}" for i in range(size)]
+        assert data == [f"item {i}" for i in range(size)]
+
+    @pytest.mark.parametrize("jobs", JOBS)
+    @pytest.mark.parametrize("size", [0, 1, 2])
+    def test_replace_inplace(self, jobs, size):
+        data = [f"item {i}" for i in range(size)]
+        result = yurki.regexp.replace(data, r"item", "x", jobs=jobs, inplace=True)
+        assert result is data
+        assert data == [f"x {i}" for i in range(size)]
+
+    @pytest.mark.parametrize("jobs", JOBS)
+    @pytest.mark.parametrize("size", [0, 1, 2])
+    def test_nested(self, jobs, size):
+        data = [f"a-{i}" for i in range(size)]
+        assert yurki.regexp.split(data, r"-", jobs=jobs) == [["a", str(i)] for i in range(size)]
+        yurki.regexp.split(data, r"-", jobs=jobs, inplace=True)
+        assert data == [["a", str(i)] for i in range(size)]
+
+    @pytest.mark.parametrize("jobs", JOBS)
+    @pytest.mark.parametrize("size", [0, 1, 2])
+    def test_pairwise(self, jobs, size):
+        a = [f"a{i}" for i in range(size)]
+        b = [f"b{i}" for i in range(size)]
+        assert yurki.internal.concat_pairwise(a, b, sep="-", jobs=jobs) == [f"a{i}-b{i}" for i in range(size)]
+        yurki.internal.concat_pairwise(a, b, jobs=jobs, inplace=True)
+        assert a == [f"a{i}b{i}" for i in range(size)]
+
+    @pytest.mark.parametrize("jobs", JOBS)
+    def test_empty_progress(self, jobs):
+        calls = []
+        result = yurki.regexp.find([], r"\w", jobs=jobs, progress=lambda done, total: calls.append((done, total)))
+        assert result == []
+        assert calls == [(0, 0)]
+
+    def test_empty_selection(self):
+        data = ["a", "b", "c"]
+        assert yurki.regexp.find(data, r"\w", jobs=4, start=3) == []
+        assert yurki.regexp.find(data, r"\w", jobs=4, start=3, inplace=True) is data
+        assert data == ["a", "b", "c"]
+
+    @pytest.mark.parametrize("size", [63, 64, 65, 200])
+    def test_around_parallel_threshold(self, size):
+        data = [f"v{i}" for i in range(size)]
+        assert yurki.regexp.find(data, r"\d+", jobs=4) == [str(i) for i in range(size)]
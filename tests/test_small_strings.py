@@ -0,0 +1,51 @@
+import sys
+
+import pytest
+
+import yurki
+
+
+JOBS = [1, 2, 4, 7]
+
+
+class TestSmallStrings:
+    @pytest.mark.parametrize("jobs", JOBS)
+    def test_no_match_returns_shared_empty(self, jobs):
+        data = ["no digits here"] * 50_000
+        result = yurki.regexp.find(data, r"\d+", jobs=jobs)
+        assert result == [""] * len(data)
+        assert len({id(s) for s in result}) == 1
+        assert result[0] is ""[:0]
+
+    @pytest.mark.parametrize("jobs", JOBS)
+    def test_latin1_single_chars_are_shared(self, jobs):
+        chars = [chr(c) for c in range(256)]
+        data = [f"<{c}>" for c in chars] * 100
+        result = yurki.regexp.capture(data, r"(?s)<(.)>", jobs=jobs)
+        assert [groups[1] for groups in result] == chars * 100
+        for groups, c in zip(result, chars * 100):
+            assert groups[1] is c[:1]
+
+    @pytest.mark.parametrize("jobs", JOBS)
+    def test_other_strings_still_allocated(self, jobs):
+        data = ["Ā", "日", "😀", "12", "é" * 2] * 10
+        result = yurki.internal.ascii_upper_string(data, jobs=jobs)
+        assert result == data
+        assert all(r is not d for r, d in zip(result, data))
+
+    @pytest.mark.parametrize("jobs", JOBS)
+    def test_refcount_unchanged(self, jobs):
+        empty_before = sys.getrefcount("")
+        a_before = sys.getrefcount("a")
+        result = yurki.regexp.find(["xyz", "a"] * 100_000, r"a", jobs=jobs)
+        del result
+        assert sys.getrefcount("") == empty_before
+        assert sys.getrefcount("a") == a_before
+
+    @pytest.mark.parametrize("jobs", JOBS)
+    def test_inplace_with_cached_results(self, jobs):
+        data = [f"x{i}" for i in range(10_000)]
+        yurki.regexp.replace(data, r"x\d+", "", jobs=jobs, inplace=True)
+        assert data == [""] * 10_000
+        yurki.internal.ascii_upper_inplace(data, jobs=jobs)
+        assert data == [""] * 10_000
@@ -0,0 +1,86 @@
+import re
+import sys
+
+import pytest
+
+import yurki
+
+
+JOBS = [1, 2, 4, 7]
+
+
+def split_columns(data, pattern, n, fill=""):
+    columns = [[] for _ in range(n)]
+    for s in data:
+        fields = re.split(pattern, s)[:n]
+        fields += [fill] * (n - len(fields))
+        for column, field in zip(columns, fields):
+            column.append(field)
+    return tuple(columns)
+
+
+class TestSplitColumns:
+    @pytest.mark.parametrize("jobs", JOBS)
+    def test_split_columns(self, jobs):
+        data = [f"{i},name {i},{i * 2}" for i in range(10_000)]
+        result = yurki.regexp.split_columns(data, r",", 3, jobs=jobs)
+        assert isinstance(result, tuple)
+        assert result == split_columns(data, r",", 3)
+
+    @pytest.mark.parametrize("jobs", JOBS)
+    def test_missing_fields(self, jobs):
+        data = ["a;b;c", "d;e", "f", ""] * 100
+        assert yurki.regexp.split_columns(data, r";", 3, jobs=jobs) == split_columns(data, r";", 3)
+        assert yurki.regexp.split_columns(data, r";", 3, jobs=jobs, missing="none") == split_columns(
+            data, r";", 3, fill=None
+        )
+
+    @pytest.mark.parametrize("jobs", JOBS)
+    def test_truncate(self, jobs):
+        data = ["1 2 3 4 5", "6 7"] * 100
+        result = yurki.regexp.split_columns(data, r"\s+", 2, jobs=jobs)
+        assert result == (["1", "6"] * 100, ["2", "7"] * 100)
+
+    @pytest.mark.parametrize("jobs", JOBS)
+    def test_extra_error(self, jobs):
+        data = ["a,b"] * 5_000 + ["a,b,c"] + ["a"] * 5_000 + ["x,y,z"]
+        with pytest.raises(ValueError, match="row 5000: more than 2 fields"):
+            yurki.regexp.split_columns(data, r",", 2, jobs=jobs, extra="error")
+
+    @pytest.mark.parametrize("jobs", JOBS)
+    def test_unicode(self, jobs):
+        data = ["café|日本語|🙂", "naïve||ü"]
+        result = yurki.regexp.split_columns(data, r"\|", 3, jobs=jobs)
+        assert result == (["café", "naïve"], ["日本語", ""], ["🙂", "ü"])
+
+    def test_case(self):
+        data = ["aXbxc"]
+        assert yurki.regexp.split_columns(data, r"x", 3, jobs=1) == (["aXb"], ["c"], [""])
+        assert yurki.regexp.split_columns(data, r"x", 3, case=True, jobs=1) == (["a"], ["b"], ["c"])
+
+    def test_single_column(self):
+        assert yurki.regexp.split_columns(["a,b", "c"], r",", 1, jobs=1) == (["a", "c"],)
+
+    def test_refcount(self):
+        data = ["key,value"] * 1_000
+        before = sys.getrefcount(None)
+        keys, values, rest = yurki.regexp.split_columns(data, r",", 3, jobs=4, missing="none")
+        assert sys.getrefcount(keys[0]) == 2
+        assert rest == [None] * 1_000
+        del keys, values, rest
+        assert sys.getrefcount(None) == before
+
+    def test_invalid_arguments(self):
+        with pytest.raises(ValueError, match="n must be"):
+            yurki.regexp.split_columns(["a"], r",", 0, jobs=1)
+        with pytest.raises(ValueError, match="missing must be"):
+            yurki.regexp.split_columns(["a"], r",", 2, jobs=1, missing="null")
+        with pytest.raises(ValueError, match="extra must be"):
+            yurki.regexp.split_columns(["a"], r",", 2, jobs=1, extra="drop")
+        with pytest.raises(TypeError):
+            yurki.regexp.split_columns(["a", 1], r",", 2, jobs=1)
+        with pytest.raises(ValueError, match="regex parse error"):
+            yurki.internal.split_columns(["a b"], r"(", 2)
+
+    def test_empty(self):
+        assert yurki.regexp.split_columns([], r",", 2, jobs=4) == ([], [])
@@ -0,0 +1,69 @@
+import re
+
+import pytest
+
+import yurki
+
+
+JOBS = [1, 4]
+
+DATA = [
+    "",
+    " ",
+    "  a  b  ",
+    "a",
+    "one two\tthree\nfour\r\nfive",
+    "\x0b\x0cx\x1c\x1d\x1e\x1fy",
+    "x" * 200,
+    " " * 200,
+    ("word " * 50).strip(),
+    " \t " * 30 + "z" + "\n" * 70,
+    "é ü  中文\t😀",
+    "a\xa0b　c d",
+]
+
+
+def ascii_split(s):
+    # str.split() with only the ASCII whitespace separating
+    return [field for field in re.split(r"[\t-\r\x1c-\x20]+", s) if field]
+
+
+def split(data, **kwargs):
+    return yurki.internal.split_whitespace_string(data, **kwargs)
+
+
+class TestSplitWhitespace:
+    def test_no_empty_fields(self):
+        assert split(["  a  b  "]) == [["a", "b"]]
+
+    @pytest.mark.parametrize("jobs", JOBS)
+    def test_unicode_matches_str_split(self, jobs):
+        result = split(DATA * 50, unicode=True, jobs=jobs)
+        assert result == [s.split() for s in DATA * 50]
+
+    @pytest.mark.parametrize("jobs", JOBS)
+    def test_ascii_only_by_default(self, jobs):
+        result = split(DATA * 50, jobs=jobs)
+        assert result == [ascii_split(s) for s in DATA * 50]
+
+    def test_every_ascii_separator(self):
+        for c in map(chr, range(128)):
+            data = [f"a{c}b", f"{'a' * 70}{c * 3}{'b' * 70}"]
+            assert split(data) == [s.split() for s in data]
+            assert split(data, unicode=True) == [s.split() for s in data]
+
+    def test_unicode_spaces(self):
+        spaces = [chr(cp) for cp in range(0x80, 0x3001) if chr(cp).isspace()]
+        data = [f"a{c}b" for c in spaces]
+        assert split(data, unicode=True) == [["a", "b"]] * len(spaces)
+        assert split(data) == [[s] for s in data]
+
+    @pytest.mark.parametrize("jobs", JOBS)
+    def test_inplace_and_slice(self, jobs):
+        data = ["a b", " c ", "d\te", "f"]
+        assert split(data, jobs=jobs, start=1) == [["c"], ["d", "e"], ["f"]]
+        assert split(data, jobs=jobs, inplace=True, step=2) is data
+        assert data == [["a", "b"], " c ", ["d", "e"], "f"]
+
+    def test_empty_list(self):
+        assert split([], jobs=4) == []
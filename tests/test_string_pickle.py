@@ -0,0 +1,53 @@
+import copy
+import pickle
+
+import pytest
+
+import yurki
+
+
+pytestmark = pytest.mark.skipif(not yurki.internal.fast_string_enabled(), reason="yurki.String only")
+
+TEXTS = ["hello world", "café au lait", "日本語 text", "emoji 😀 here"]
+
+
+def make_strings():
+    # Replacing guarantees new result strings rather than the inputs
+    result = yurki.regexp.replace(TEXTS, r" ", "_", jobs=1)
+    assert all(type(s) is yurki.internal.String for s in result)
+    return result
+
+
+class TestStringPickle:
+    @pytest.mark.parametrize("protocol", range(pickle.HIGHEST_PROTOCOL + 1))
+    def test_pickle_as_str(self, protocol):
+        for s in make_strings():
+            restored = pickle.loads(pickle.dumps(s, protocol))
+            assert type(restored) is str
+            assert restored == s
+
+    def test_pickle_result_list(self):
+        result = make_strings()
+        restored = pickle.loads(pickle.dumps(result))
+        assert restored == [t.replace(" ", "_", 1) for t in TEXTS]
+        assert all(type(s) is str for s in restored)
+
+    def test_copy_returns_same_string(self):
+        for s in make_strings():
+            assert copy.copy(s) is s
+            assert copy.deepcopy(s) is s
+        result = make_strings()
+        assert copy.deepcopy(result) == result
+
+    def test_to_str(self):
+        for s in make_strings():
+            plain = s.to_str()
+            assert type(plain) is str
+            assert plain == s and hash(plain) == hash(s)
+            assert type(str(s)) is str and str(s) == s
+
+    def test_cannot_instantiate(self):
+        with pytest.raises(TypeError):
+            yurki.internal.String("abc")
+        with pytest.raises(TypeError):
+            str.__new__(yurki.internal.String, "abc")
@@ -0,0 +1,140 @@
+import ctypes
+import gc
+import os
+import re
+import sysconfig
+import tempfile
+
+import pytest
+
+import yurki
+
+
+# Characters follow the header: PyASCIIObject for ASCII strings, the larger
+# PyCompactUnicodeObject (utf8_length, utf8) for others
+ASCII_HEADER = object.__basicsize__ + 3 * ctypes.sizeof(ctypes.c_ssize_t)
+COMPACT_HEADER = ASCII_HEADER + 2 * ctypes.sizeof(ctypes.c_ssize_t)
+# PyASCIIObject.state follows length and hash; the kind is 3 bits past the
+# interned flags, which are wider in free-threaded builds
+STATE_OFFSET = object.__basicsize__ + 2 * ctypes.sizeof(ctypes.c_ssize_t)
+KIND_SHIFT = 8 if sysconfig.get_config_var("Py_GIL_DISABLED") else 2
+
+
+def unicode_kind(s):
+    return (ctypes.c_uint32.from_address(id(s) + STATE_OFFSET).value >> KIND_SHIFT) & 0b111
+
+
+def terminator(s):
+    kind = 4 if max(s) > "\uffff" else 2 if max(s) > "\xff" else 1
+    header = ASCII_HEADER if s.isascii() else COMPACT_HEADER
+    return ctypes.string_at(id(s) + header + len(s) * kind, kind)
+
+
+def expected_type():
+    # For results longer than one character; shorter ones are shared interpreter strings
+    return yurki.internal.String if yurki.internal.fast_string_enabled() else str
+
+
+class TestStringType:
+    def test_result_type(self):
+        result = yurki.regexp.replace(["hello world", "日本語 to"], r"o", "0", jobs=2)
+        assert result == ["hell0 world", "日本語 t0"]
+        assert all(type(s) is expected_type() for s in result)
+
+    def test_nested_result_type(self):
+        result = yurki.regexp.split(["ab,cd,ef"] * 10, r",", jobs=2)
+        assert all(type(part) is expected_type() for parts in result for part in parts)
+
+    def test_many_workers(self):
+        # With plain str, workers take turns on the GIL while the main thread waits
+        data = [f"row {i} ü {i * 3}" for i in range(200_000)]
+        result = yurki.regexp.find(data, r"\d+$", jobs=8)
+        assert result == [str(i * 3) for i in range(200_000)]
+
+    def test_inplace_and_interned(self):
+        data = [f"k{i % 10}" for i in range(50_000)]
+        result = yurki.regexp.find(data, r"k\d", jobs=4, intern_results=True)
+        assert result == data
+        assert len({id(s) for s in result}) == 10
+        yurki.regexp.replace(data, r"k", "key", jobs=4, inplace=True)
+        assert data == [f"key{i % 10}" for i in range(50_000)]
+
+    @pytest.mark.parametrize("char", ["a", "é", "日"])
+    @pytest.mark.parametrize("utf8_cache", [False, True])
+    def test_large_string(self, char, utf8_cache):
+        previous = yurki.internal.get_utf8_cache()
+        yurki.internal.set_utf8_cache(utf8_cache)
+        try:
+            size = 64 * 1024 * 1024 // len(char.encode())
+            (result,) = yurki.regexp.replace(["x" + char * size], r"^x", "y", jobs=1)
+            assert type(result) is expected_type()
+            assert len(result) == size + 1
+            assert result[0] == "y" and result[-1] == char
+            assert result.count(char) == size
+            del result
+            gc.collect()
+        finally:
+            yurki.internal.set_utf8_cache(previous)
+
+    def test_process_file(self):
+        with tempfile.NamedTemporaryFile("w", suffix=".txt", delete=False, encoding="utf-8") as f:
+            f.write("".join(f"line {i}\n" for i in range(10_000)))
+        try:
+            result = yurki.internal.process_file(f.name, [("find", r"\d+")], jobs=4)
+            assert result == [str(i) for i in range(10_000)]
+            assert all(type(s) is expected_type() for s in result[10:100])
+        finally:
+            os.unlink(f.name)
+
+    @pytest.mark.parametrize("jobs", [1, 4])
+    def test_two_byte_edge_content(self, jobs):
+        # The widest character decides the kind; place it around every
+        # vector boundary, next to latin-1 and ASCII text
+        wide = ["\u0100", "\u07ff", "\u0800", "\ud7ff", "\ue000", "\ufffd", "\uffff"]
+        data = [
+            prefix * n + w + suffix * (80 - n)
+            for w in wide
+            for n in range(0, 80, 7)
+            for prefix, suffix in [("a", "é"), ("ÿ", "a"), ("中", "\x80")]
+        ]
+        result = yurki.regexp.replace(data, r"^", "<", jobs=jobs)
+        assert result == ["<" + s for s in data]
+        assert all(type(s) is expected_type() for s in result)
+
+    @pytest.mark.parametrize("jobs", [1, 4])
+    def test_emoji_heavy_content(self, jobs):
+        # Astral characters, two UTF-16 code units each, around every vector
+        # boundary: the string is 4-byte and sized by characters
+        data = [
+            filler * n + "😀🎉" + filler * (80 - n) + "🚀" * k
+            for filler in ["a", "é", "中"]
+            for n in range(0, 80, 5)
+            for k in [0, 1, 40]
+        ]
+        result = yurki.regexp.replace(data, r"^", "<", jobs=jobs)
+        assert result == ["<" + s for s in data]
+        assert [len(s) for s in result] == [len(s) + 1 for s in data]
+        assert all(unicode_kind(s) == 4 for s in result)
+        assert all(terminator(s) == bytes(4) for s in result)
+
+    @pytest.mark.parametrize("text", ["ascii text", "latin-1 é", "wide 中文", "astral 😀"])
+    def test_nul_terminated(self, text):
+        for n in [1, 7, 64, 1000]:
+            (result,) = yurki.regexp.replace([text * n], r"^", "<", jobs=1)
+            assert terminator(result) == bytes(len(terminator(result)))
+
+    @pytest.mark.parametrize("jobs", [1, 4])
+    def test_kind_follows_output(self, jobs):
+        # The kind is chosen from the result text, so narrowing it narrows the string
+        cases = [
+            ("emoji 😀 gone 🎉" * 10, r"[^\x00-\x7f]", 1),
+            ("latin é kept 😀" * 10, r"[^\x00-\xff]", 1),
+            ("wide 中 kept 😀" * 10, r"[^\x00-\uffff]", 2),
+            ("wide 中 gone é" * 10, r"[^\x00-\xff]", 1),
+        ]
+        data = [text for text, _, _ in cases]
+        assert [unicode_kind(s) for s in data] == [4, 4, 4, 2]
+        for text, pattern, kind in cases:
+            (result,) = yurki.regexp.replace([text], pattern, "", count=0, jobs=jobs)
+            assert result == re.sub(pattern, "", text)
+            assert unicode_kind(result) == kind
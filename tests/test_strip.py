@@ -0,0 +1,58 @@
+import random
+
+import pytest
+
+import yurki
+
+
+JOBS = [1, 4]
+
+
+def random_rows(n, seed=3):
+    rng = random.Random(seed)
+    rows = []
+    for _ in range(n):
+        inner = "".join(rng.choice("a é中😀 _") for _ in range(rng.randrange(8)))
+        rows.append(" " * rng.randrange(80) + inner + " " * rng.randrange(80))
+    return rows
+
+
+def python_strip(s, chars, left, right):
+    if left and right:
+        return s.strip(chars)
+    if left:
+        return s.lstrip(chars)
+    if right:
+        return s.rstrip(chars)
+    return s
+
+
+class TestStripString:
+    @pytest.mark.parametrize("jobs", JOBS)
+    @pytest.mark.parametrize("chars", [None, " ", "_", "a ", "é", "😀 ", ""])
+    @pytest.mark.parametrize("left,right", [(True, True), (True, False), (False, True)])
+    def test_matches_str_strip(self, jobs, chars, left, right):
+        data = random_rows(1_000)
+        result = yurki.internal.strip_string(data, chars, left=left, right=right, jobs=jobs)
+        assert result == [python_strip(s, chars, left, right) for s in data]
+
+    def test_padding_around_vector_boundaries(self):
+        data = [" " * n + "x" * k + " " * m for n in range(70) for k in [0, 1, 2] for m in [0, 1, 63, 64, 65]]
+        assert yurki.internal.strip_string(data, " ") == [s.strip(" ") for s in data]
+        assert yurki.internal.strip_string(data, " ", left=False) == [s.rstrip(" ") for s in data]
+
+    def test_python_whitespace(self):
+        # str.isspace includes the separators 0x1C-0x1F and U+0085
+        data = ["\x1c\x1f a \x85　", "\t\n\x0b\x0c\r b  "]
+        assert yurki.internal.strip_string(data) == [s.strip() for s in data]
+
+    def test_unchanged_strings_are_inputs(self):
+        data = ["".join(["a", "b"]), "".join([" ", "c"])]
+        result = yurki.internal.strip_string(data)
+        assert result[0] is data[0]
+        assert result == ["ab", "c"]
+
+    def test_slice_and_inplace(self):
+        data = [" a ", " b ", " c "]
+        yurki.internal.strip_string(data, start=1, inplace=True)
+        assert data == [" a ", "b", "c"]
@@ -0,0 +1,60 @@
+import os
+
+import pytest
+
+import yurki
+
+
+JOBS = [1, 4]
+
+# Lone surrogates in 2-byte strings (alone, with latin-1 or CJK text) and in
+# 4-byte ones (with an astral character), short and long enough for SIMD
+SURROGATES = ["\udc80", chr(0xD800), "\udbff", "\udfff"]
+CONTEXTS = ["{}", "abc{}def", "é{}ü", "中{}文", "😀{}😀", "x" * 100 + "{}" + "中" * 100, "😀" * 50 + "{}" + "a" * 50]
+DATA = [context.format(s) for context in CONTEXTS for s in SURROGATES] + [
+    "\udc80\udc81\udc82",
+    "\ud800é\ud800",
+    "a\ud800" * 40,
+    os.fsdecode(b"file\xff\xfe.txt"),
+]
+
+
+def lossy(s):
+    return "".join("�" if 0xD800 <= ord(c) < 0xE000 else c for c in s)
+
+
+def ascii_upper(s):
+    return "".join(c.upper() if c.isascii() else c for c in s)
+
+
+class TestSurrogates:
+    @pytest.mark.parametrize("jobs", JOBS)
+    def test_replaced_in_new_results(self, jobs):
+        result = yurki.internal.ascii_upper_string(DATA, jobs=jobs)
+        assert result == [ascii_upper(lossy(s)) for s in DATA]
+
+    @pytest.mark.parametrize("jobs", JOBS)
+    def test_regex_sees_replacement(self, jobs):
+        assert all(yurki.regexp.is_match(DATA, "�", jobs=jobs))
+        result = yurki.regexp.replace(DATA, "�", "?", count=0, jobs=jobs)
+        assert result == [lossy(s).replace("�", "?") for s in DATA]
+
+    @pytest.mark.parametrize("jobs", JOBS)
+    def test_unchanged_strings_are_inputs(self, jobs):
+        result = yurki.regexp.replace(DATA, r"no match here", "", jobs=jobs)
+        assert all(after is before for before, after in zip(DATA, result))
+
+    @pytest.mark.parametrize("jobs", JOBS)
+    def test_lengths_kept(self, jobs):
+        assert yurki.internal.char_len(DATA, jobs=jobs) == [len(s) for s in DATA]
+
+    def test_surrogate_pair_in_two_byte_string(self):
+        # Read as UTF-16: a high surrogate followed by a low one is one character
+        assert yurki.regexp.find(["a\ud83d\ude00b"], r".\w$") == ["😀b"]
+        assert yurki.regexp.find(["\ude00\ud83dx"], r"..") == ["��"]
+        # A result that is the whole input is the input object itself
+        (whole,) = yurki.regexp.find(["\ude00\ud83d"], r".+")
+        assert whole == "\ude00\ud83d"
+
+    def test_encode_utf8(self):
+        assert yurki.internal.encode_utf8(["a\udc80", "😀\ud800"]) == [b"a\xef\xbf\xbd", "😀�".encode()]
@@ -0,0 +1,75 @@
+import unicodedata
+
+import pytest
+
+import yurki
+
+
+JOBS = [1, 4]
+
+DATA = [
+    "",
+    "Hello, World!",
+    "ALL CAPS and lower 123",
+    "".join(chr(b) for b in range(128)) * 2,
+    "straße",
+    "ﬁne ﬂow",
+    "İstanbul",
+    "ǅemal ǈ ǋ",
+    "ΟΔΥΣΣΕΥΣ",
+    "ΣΑΣ ΣΑΣ. Σ",
+    "Σ'Α ΑΣ' ΑΣ-Β",
+    "ΑΣ́ ΑΣ́Β",
+    "µ ſ ᾳ ῼ",
+    "Ⓐⓑ Ⅸⅻ",
+    "日本語 😀 𐐀𐐨",
+    "Café CRÈME brûlée",
+]
+
+
+class TestSwapcase:
+    @pytest.mark.parametrize("jobs", JOBS)
+    def test_matches_str(self, jobs):
+        data = DATA * 20
+        assert yurki.internal.swapcase_string(data, jobs=jobs) == [s.swapcase() for s in data]
+
+    def test_multi_char_expansions(self):
+        assert yurki.internal.swapcase_string(["ß", "ﬃ", "İ", "ŉ"]) == ["SS", "FFI", "i̇", "ʼN"]
+
+    def test_final_sigma(self):
+        assert yurki.internal.swapcase_string(["ΣΑΣ", "Σ", "ΑΣ Β", "ΑΣΑ"]) == ["σας", "σ", "ας β", "ασα"]
+
+    def test_every_code_point(self):
+        # Skip characters whose case mappings changed after Python's Unicode version
+        chars = [
+            chr(cp)
+            for cp in range(0x110000)
+            if not 0xD800 <= cp < 0xE000 and unicodedata.category(chr(cp)) != "Cn"
+        ]
+        result = yurki.internal.swapcase_string(chars)
+        mismatched = [c for c, r in zip(chars, result) if r != c.swapcase()]
+        assert len(mismatched) < 20, [hex(ord(c)) for c in mismatched]
+
+    def test_ascii_path_matches_unicode_path(self):
+        ascii_text = "".join(chr(b) for b in range(128)) * 3
+        # A non-ASCII character sends the rest of the string through the Unicode path
+        (via_ascii, via_unicode) = yurki.internal.swapcase_string([ascii_text, ascii_text + "é"])
+        assert via_unicode == via_ascii + "É"
+
+    @pytest.mark.parametrize("jobs", JOBS)
+    def test_uncased_strings_are_inputs(self, jobs):
+        data = [f"{i} - {i * 2}" if i % 10 else f"Row {i}" for i in range(1_000)] + ["日本語 123"]
+        result = yurki.internal.swapcase_string(data, jobs=jobs)
+        for i, (before, after) in enumerate(zip(data, result)):
+            if i % 10 and i < 1_000 or i == 1_000:
+                assert after is before
+            else:
+                assert after == before.swapcase()
+
+    def test_inplace(self):
+        data = ["aB", "Cd"]
+        assert yurki.internal.swapcase_string(data, inplace=True) is data
+        assert data == ["Ab", "cD"]
+
+    def test_empty_list(self):
+        assert yurki.internal.swapcase_string([], jobs=4) == []
@@ -0,0 +1,95 @@
+import pytest
+
+import yurki
+
+
+JOBS = [1, 4]
+
+DATA = [
+    "",
+    "hello world",
+    "Hello, World! 123",
+    "the quick brown fox jumps over the lazy dog " * 5,
+    "tabs\tand\nnewlines",
+    "café naïve",
+    "日本語 text",
+    "emoji 😀 here",
+]
+
+TABLES = [
+    str.maketrans("abc", "xyz"),
+    str.maketrans("aeiou", "AEIOU"),
+    str.maketrans("", "", "aeiou"),
+    str.maketrans({"a": "alpha", "b": None, "c": "ç", "é": "e", "😀": ":)", "日": 0x65E5}),
+    str.maketrans(" \t\n", "___"),
+    {ord("l"): ord("L"), "not a code point": "x", -1: "y"},
+    {},
+]
+
+
+def rot13_table():
+    # Letters as str, everything else as its own code point
+    table = list(range(256))
+    for base in (ord("a"), ord("A")):
+        for i in range(26):
+            table[base + i] = chr(base + (i + 13) % 26)
+    return table
+
+
+class TestTranslate:
+    @pytest.mark.parametrize("jobs", JOBS)
+    @pytest.mark.parametrize("table", TABLES)
+    def test_matches_str(self, jobs, table):
+        data = DATA * 20
+        assert yurki.internal.translate(data, table, jobs=jobs) == [s.translate(table) for s in data]
+
+    @pytest.mark.parametrize("jobs", JOBS)
+    def test_latin1_sequence_table(self, jobs):
+        table = rot13_table()
+        data = DATA * 20
+        assert yurki.internal.translate(data, table, jobs=jobs) == [s.translate(table) for s in data]
+
+    def test_ascii_table_every_character(self):
+        table = {b: (b * 7 + 3) % 128 for b in range(128)}
+        data = ["".join(chr(b) for b in range(128)) * 3, "short", "x" * 1000]
+        assert yurki.internal.translate(data, table) == [s.translate(table) for s in data]
+
+    def test_str_and_short_sequence_tables(self):
+        table = "ABCDEFGHIJKLMNOPQRSTUVWXYZ" * 4
+        data = ["\x00\x05\x19z", "abc"]
+        assert yurki.internal.translate(data, table) == [s.translate(table) for s in data]
+        assert yurki.internal.translate(["abc"], ["x", None]) == ["abc".translate(["x", None])]
+
+    def test_deletion(self):
+        table = str.maketrans("", "", "lo")
+        assert yurki.internal.translate(["hello world", "lol"], table) == ["he wrd", ""]
+
+    @pytest.mark.parametrize("jobs", JOBS)
+    def test_untouched_strings_are_inputs(self, jobs):
+        data = [f"row {i}" if i % 10 else f"ROW {i}" for i in range(1_000)]
+        table = str.maketrans("ROW", "row")
+        result = yurki.internal.translate(data, table, jobs=jobs)
+        for i, (before, after) in enumerate(zip(data, result)):
+            if i % 10:
+                assert after is before
+            else:
+                assert after == f"row {i}"
+
+    def test_inplace_and_slice(self):
+        data = ["ab", "ab", "ab"]
+        result = yurki.internal.translate(data, str.maketrans("a", "z"), inplace=True, step=2)
+        assert result is data
+        assert data == ["zb", "ab", "zb"]
+
+    def test_invalid_tables(self):
+        with pytest.raises(TypeError):
+            yurki.internal.translate(["a"], 5)
+        with pytest.raises(TypeError):
+            yurki.internal.translate(["a"], {97: 1.5})
+        with pytest.raises(ValueError):
+            yurki.internal.translate(["a"], {97: 0x110000})
+        with pytest.raises(ValueError):
+            "a".translate({97: 0x110000})
+
+    def test_empty_list(self):
+        assert yurki.internal.translate([], {}, jobs=4) == []
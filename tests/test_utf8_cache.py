@@ -0,0 +1,88 @@
+import ctypes
+import sys
+import tracemalloc
+
+import pytest
+
+import yurki
+
+
+pytestmark = pytest.mark.skipif(not yurki.internal.fast_string_enabled(), reason="yurki.String only")
+
+as_utf8 = ctypes.pythonapi.PyUnicode_AsUTF8AndSize
+as_utf8.restype = ctypes.c_void_p
+as_utf8.argtypes = [ctypes.py_object, ctypes.POINTER(ctypes.c_ssize_t)]
+
+DATA = ["ascii row", "latin é row", "wide 日本語 row", "astral 😀 row", "mixed é日😀 row"]
+
+
+@pytest.fixture
+def utf8_cache():
+    previous = yurki.internal.get_utf8_cache()
+    yield yurki.internal.set_utf8_cache
+    yurki.internal.set_utf8_cache(previous)
+
+
+def utf8_of(s):
+    size = ctypes.c_ssize_t()
+    address = as_utf8(s, ctypes.byref(size))
+    return address, ctypes.string_at(address, size.value)
+
+
+def make_strings(data, jobs=1):
+    # Replacing guarantees new result strings rather than the inputs
+    return yurki.regexp.replace(data, r"row", "line", jobs=jobs)
+
+
+class TestUtf8Cache:
+    def test_default_off(self):
+        assert yurki.internal.get_utf8_cache() is False
+
+    @pytest.mark.parametrize("jobs", [1, 4])
+    def test_attached_at_creation(self, utf8_cache, jobs):
+        utf8_cache(True)
+        result = make_strings(DATA * 100, jobs=jobs)
+        for s in result:
+            assert type(s) is yurki.internal.String
+            address, utf8 = utf8_of(s)
+            assert utf8 == s.encode()
+            if not s.isascii():
+                # Inside the string's own block, not a buffer CPython allocated
+                assert id(s) < address < id(s) + sys.getsizeof(s)
+        assert result == [s.replace("row", "line") for s in DATA * 100]
+
+    def test_size_includes_utf8(self, utf8_cache):
+        utf8_cache(False)
+        (plain,) = make_strings(["wide 日本語 row"])
+        utf8_cache(True)
+        (cached,) = make_strings(["wide 日本語 row"])
+        assert sys.getsizeof(cached) == sys.getsizeof(plain) + len(plain.encode()) + 1
+
+    def test_not_attached_when_off(self, utf8_cache):
+        utf8_cache(False)
+        (s,) = make_strings(["wide 日本語 row"])
+        before = sys.getsizeof(s)
+        address, utf8 = utf8_of(s)
+        assert utf8 == s.encode()
+        # CPython encoded and cached it on request, in a separate buffer
+        assert sys.getsizeof(s) == before + len(utf8) + 1
+
+    @pytest.mark.parametrize("enabled", [False, True])
+    def test_no_leak(self, utf8_cache, enabled):
+        utf8_cache(enabled)
+        data = ["wide 日本語 row " + "x" * 200] * 2_000
+
+        def round_trip():
+            for s in make_strings(data):
+                utf8_of(s)
+
+        round_trip()
+        tracemalloc.start()
+        try:
+            round_trip()
+            baseline = tracemalloc.get_traced_memory()[0]
+            for _ in range(5):
+                round_trip()
+            assert tracemalloc.get_traced_memory()[0] - baseline < 100_000
+        finally:
+            tracemalloc.stop()
@@ -7,6 +7,8 @@ in-place for memory efficiency.
 Modules:
     regexp: Regular expression operations (find, match, capture, split, replace)
     internal: Low-level Rust functions (for advanced users)
+    list_from: Build a yurki.List from any iterable
+    set_fast_string: Turn yurki.String results on or off at runtime
 
 Examples:
     >>> import yurki
@@ -20,5 +22,8 @@ Examples:
 import yurki.regexp as regexp
 from .yurki import internal
 
+list_from = internal.list_from
+set_fast_string = internal.set_fast_string
 
-__all__ = ["regexp", "internal"]
+
+__all__ = ["regexp", "internal", "list_from", "set_fast_string"]
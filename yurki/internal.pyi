@@ -1,6 +1,7 @@
 """Type stubs for yurki.internal module (Rust implementation)."""
 
-from typing import List
+import os
+from typing import Any, Callable, Dict, Iterable, Iterator, List, Optional, Sequence, Tuple, Union
 
 def find_regex_in_string(
     list: List[str],
@@ -8,7 +9,14 @@ def find_regex_in_string(
     case: bool = False,
     jobs: int = 1,
     inplace: bool = False,
-) -> List[str]:
+    progress: Optional[Callable[[int, int], None]] = None,
+    progress_interval: int = 10_000,
+    start: Optional[int] = None,
+    stop: Optional[int] = None,
+    step: Optional[int] = None,
+    no_match: str = "empty",
+    intern_results: bool = False,
+) -> List[Optional[str]]:
     """Find first regex match in each string.
 
     Args:
@@ -17,9 +25,15 @@ def find_regex_in_string(
         case: Case-insensitive matching when True
         jobs: Number of parallel workers
         inplace: Modify original list when True
+        progress: Called as progress(processed, total) on the calling thread
+        progress_interval: Minimum number of items between progress calls
+        start, stop, step: Process only list[start:stop:step]; with inplace only those
+            positions are overwritten
+        no_match: Result for strings without a match, "empty" for "" or "none" for None
+        intern_results: Reuse one string object for equal results; not allowed with inplace
 
     Returns:
-        List of matched strings (empty string if no match)
+        List of matched strings ("" or None if no match, see no_match)
     """
     ...
 
@@ -29,6 +43,11 @@ def is_match_regex_in_string(
     case: bool = False,
     jobs: int = 1,
     inplace: bool = False,
+    progress: Optional[Callable[[int, int], None]] = None,
+    progress_interval: int = 10_000,
+    start: Optional[int] = None,
+    stop: Optional[int] = None,
+    step: Optional[int] = None,
 ) -> List[bool]:
     """Check if each string matches regex pattern.
 
@@ -38,6 +57,70 @@ def is_match_regex_in_string(
         case: Case-insensitive matching when True
         jobs: Number of parallel workers
         inplace: Modify original list when True
+        progress: Called as progress(processed, total) on the calling thread
+        progress_interval: Minimum number of items between progress calls
+        start, stop, step: Process only list[start:stop:step]; with inplace only those
+            positions are overwritten
+
+    Returns:
+        List of booleans indicating matches
+    """
+    ...
+
+def is_match_at_start_regex_in_string(
+    list: List[str],
+    pattern: str,
+    case: bool = False,
+    jobs: int = 1,
+    inplace: bool = False,
+    progress: Optional[Callable[[int, int], None]] = None,
+    progress_interval: int = 10_000,
+    start: Optional[int] = None,
+    stop: Optional[int] = None,
+    step: Optional[int] = None,
+) -> List[bool]:
+    """Check if each string matches regex pattern at its start, like re.match.
+
+    Args:
+        list: List of strings to process
+        pattern: Regular expression pattern
+        case: Case-insensitive matching when True
+        jobs: Number of parallel workers
+        inplace: Modify original list when True
+        progress: Called as progress(processed, total) on the calling thread
+        progress_interval: Minimum number of items between progress calls
+        start, stop, step: Process only list[start:stop:step]; with inplace only those
+            positions are overwritten
+
+    Returns:
+        List of booleans indicating matches
+    """
+    ...
+
+def is_fullmatch_regex_in_string(
+    list: List[str],
+    pattern: str,
+    case: bool = False,
+    jobs: int = 1,
+    inplace: bool = False,
+    progress: Optional[Callable[[int, int], None]] = None,
+    progress_interval: int = 10_000,
+    start: Optional[int] = None,
+    stop: Optional[int] = None,
+    step: Optional[int] = None,
+) -> List[bool]:
+    """Check if each string matches regex pattern in full, like re.fullmatch.
+
+    Args:
+        list: List of strings to process
+        pattern: Regular expression pattern
+        case: Case-insensitive matching when True
+        jobs: Number of parallel workers
+        inplace: Modify original list when True
+        progress: Called as progress(processed, total) on the calling thread
+        progress_interval: Minimum number of items between progress calls
+        start, stop, step: Process only list[start:stop:step]; with inplace only those
+            positions are overwritten
 
     Returns:
         List of booleans indicating matches
@@ -50,6 +133,12 @@ def capture_regex_in_string(
     case: bool = False,
     jobs: int = 1,
     inplace: bool = False,
+    progress: Optional[Callable[[int, int], None]] = None,
+    progress_interval: int = 10_000,
+    start: Optional[int] = None,
+    stop: Optional[int] = None,
+    step: Optional[int] = None,
+    intern_results: bool = False,
 ) -> List[List[str]]:
     """Capture regex groups from each string.
 
@@ -59,18 +148,81 @@ def capture_regex_in_string(
         case: Case-insensitive matching when True
         jobs: Number of parallel workers
         inplace: Modify original list when True
+        progress: Called as progress(processed, total) on the calling thread
+        progress_interval: Minimum number of items between progress calls
+        start, stop, step: Process only list[start:stop:step]; with inplace only those
+            positions are overwritten
+        intern_results: Reuse one string object for equal results; not allowed with inplace
 
     Returns:
         List of lists containing [full_match, group1, group2, ...]
     """
     ...
 
+def capture_columns(
+    list: List[str],
+    pattern: str,
+    case: bool = False,
+    jobs: int = 1,
+) -> Tuple[List[Optional[str]], ...]:
+    """Capture regex groups from each string into one column per group.
+
+    Args:
+        list: List of strings to process
+        pattern: Regular expression pattern with at least one capture group
+        case: Case-insensitive matching when True
+        jobs: Number of parallel workers
+
+    Returns:
+        Tuple with one list per capture group, None where a group did not match
+
+    Raises:
+        ValueError: For an invalid pattern or one without capture groups
+    """
+    ...
+
+def group_by_regex(
+    list: List[str],
+    pattern: str,
+    group: Union[int, str] = 1,
+    case: bool = False,
+    jobs: int = 1,
+) -> Dict[Optional[str], List[str]]:
+    """Bucket strings by the text a capture group extracts from each.
+
+    Keys appear in the order they are first seen, and each bucket keeps its
+    rows in list order. Rows where the pattern or the group does not match
+    go to the None bucket.
+
+    Args:
+        list: List of strings to process
+        pattern: Regular expression pattern
+        group: Capture group number or name, 0 for the whole match
+        case: Case-insensitive matching when True
+        jobs: Number of parallel workers
+
+    Returns:
+        Dict of each key to a yurki.List of the same string objects, a list
+        with set_fast_types(lists=False)
+
+    Raises:
+        ValueError: For an invalid pattern
+        IndexError: If the pattern has no such group
+    """
+    ...
+
 def split_by_regexp_string(
     list: List[str],
     pattern: str,
     case: bool = False,
     jobs: int = 1,
     inplace: bool = False,
+    progress: Optional[Callable[[int, int], None]] = None,
+    progress_interval: int = 10_000,
+    start: Optional[int] = None,
+    stop: Optional[int] = None,
+    step: Optional[int] = None,
+    intern_results: bool = False,
 ) -> List[List[str]]:
     """Split strings by regex delimiter.
 
@@ -80,12 +232,102 @@ def split_by_regexp_string(
         case: Case-insensitive matching when True
         jobs: Number of parallel workers
         inplace: Modify original list when True
+        progress: Called as progress(processed, total) on the calling thread
+        progress_interval: Minimum number of items between progress calls
+        start, stop, step: Process only list[start:stop:step]; with inplace only those
+            positions are overwritten
+        intern_results: Reuse one string object for equal results; not allowed with inplace
 
     Returns:
         List of lists containing split parts
     """
     ...
 
+def split_whitespace_string(
+    list: List[str],
+    unicode: bool = False,
+    jobs: int = 1,
+    inplace: bool = False,
+    progress: Optional[Callable[[int, int], None]] = None,
+    progress_interval: int = 10_000,
+    start: Optional[int] = None,
+    stop: Optional[int] = None,
+    step: Optional[int] = None,
+    intern_results: bool = False,
+) -> List[List[str]]:
+    """Split strings on runs of whitespace, like str.split() with no separator.
+
+    Runs of whitespace separate once and leading or trailing whitespace gives no
+    empty fields. By default only ASCII whitespace separates, including 0x1C-0x1F,
+    found with a SIMD scan. With unicode=True, non-ASCII strings also split on
+    no-break and other Unicode spaces, as CPython does.
+
+    Args:
+        list: List of strings to process
+        unicode: Use CPython's Unicode whitespace for non-ASCII strings
+        jobs: Number of parallel workers
+        inplace: Modify original list when True
+        progress: Called as progress(processed, total) on the calling thread
+        progress_interval: Minimum number of items between progress calls
+        start, stop, step: Process only list[start:stop:step]; with inplace only those
+            positions are overwritten
+        intern_results: Reuse one string object for equal results; not allowed with inplace
+
+    Returns:
+        List of lists containing the fields of each string
+    """
+    ...
+
+def split_columns(
+    list: List[str],
+    pattern: str,
+    n: int,
+    case: bool = False,
+    jobs: int = 1,
+    missing: str = "empty",
+    extra: str = "truncate",
+) -> Tuple[List[Optional[str]], ...]:
+    """Split strings by regex delimiter into n columns.
+
+    Args:
+        list: List of strings to process
+        pattern: Regular expression pattern
+        n: Number of columns, at least 1
+        case: Case-insensitive matching when True
+        jobs: Number of parallel workers
+        missing: Value for absent fields, "empty" for "" or "none" for None
+        extra: Rows with more than n fields, "truncate" to drop the rest or "error" to raise
+
+    Returns:
+        Tuple of n lists, list k holding field k of each string
+
+    Raises:
+        ValueError: For an invalid pattern, or with extra="error", for the first
+            row with more than n fields
+    """
+    ...
+
+def flat_split(
+    list: List[str],
+    pattern: str,
+    case: bool = False,
+    jobs: int = 1,
+    rows: bool = False,
+) -> Union[List[str], Tuple[List[str], List[int]]]:
+    """Split strings by regex delimiter and concatenate all pieces into one list.
+
+    Args:
+        list: List of strings to process
+        pattern: Regular expression pattern
+        case: Case-insensitive matching when True
+        jobs: Number of parallel workers
+        rows: Also return the source row index of every piece when True
+
+    Returns:
+        Flat list of pieces in row order, or a (pieces, rows) tuple with rows=True
+    """
+    ...
+
 def replace_regexp_in_string(
     list: List[str],
     pattern: str,
@@ -94,6 +336,12 @@ def replace_regexp_in_string(
     case: bool = False,
     jobs: int = 1,
     inplace: bool = False,
+    progress: Optional[Callable[[int, int], None]] = None,
+    progress_interval: int = 10_000,
+    start: Optional[int] = None,
+    stop: Optional[int] = None,
+    step: Optional[int] = None,
+    intern_results: bool = False,
 ) -> List[str]:
     """Replace regex matches in strings.
 
@@ -105,8 +353,1141 @@ def replace_regexp_in_string(
         case: Case-insensitive matching when True
         jobs: Number of parallel workers
         inplace: Modify original list when True
+        progress: Called as progress(processed, total) on the calling thread
+        progress_interval: Minimum number of items between progress calls
+        start, stop, step: Process only list[start:stop:step]; with inplace only those
+            positions are overwritten
+        intern_results: Reuse one string object for equal results; not allowed with inplace
 
     Returns:
         List of strings with replacements applied
     """
     ...
+
+def replace_regex_callback(
+    list: List[str],
+    pattern: str,
+    func: Callable[[str], str],
+    count: int = 1,
+    case: bool = False,
+    jobs: int = 1,
+) -> List[str]:
+    """Replace regex matches in strings with what func returns for each match.
+
+    Matches are found by parallel workers, then func is called with the text of
+    each match on the calling thread, in order, holding the GIL: the callback
+    runs serially and usually dominates the run time.
+
+    Args:
+        list: List of strings to process
+        pattern: Regular expression pattern
+        func: Called with the matched text, returns its replacement str
+        count: Maximum number of replacements per string (0 for all)
+        case: Case-insensitive matching when True
+        jobs: Number of parallel workers for matching
+
+    Returns:
+        List of strings with replacements applied; strings without a match are
+        returned as they are
+
+    Raises:
+        TypeError: If func is not callable or returns something other than a str
+    """
+    ...
+
+def pipeline(
+    list: List[str],
+    ops: List[Tuple[Any, ...]],
+    jobs: int = 1,
+    inplace: bool = False,
+    progress: Optional[Callable[[int, int], None]] = None,
+    progress_interval: int = 10_000,
+    start: Optional[int] = None,
+    stop: Optional[int] = None,
+    step: Optional[int] = None,
+    intern_results: bool = False,
+) -> List[Any]:
+    """Apply several operations per string in a single pass.
+
+    Each op is a tuple (name, *args). String ops, which can be chained:
+    ("lower",), ("upper",), ("strip",), ("lstrip",), ("rstrip",),
+    ("replace", old, new, count=0), ("replace_regexp", pattern, replacement, count=1, case=False),
+    ("find", pattern, case=False). Allowed only as the last op:
+    ("is_match", pattern, case=False), ("capture", pattern, case=False), ("split", pattern, case=False).
+
+    Args:
+        list: List of strings to process
+        ops: Operations applied in order
+        jobs: Number of parallel workers
+        inplace: Modify original list when True
+        progress: Called as progress(processed, total) on the calling thread
+        progress_interval: Minimum number of items between progress calls
+        start, stop, step: Process only list[start:stop:step]; with inplace only those
+            positions are overwritten
+        intern_results: Reuse one string object for equal results; not allowed with inplace
+
+    Returns:
+        List of results of the last op
+
+    Raises:
+        ValueError: If an op is unknown, has wrong arguments or an invalid pattern
+    """
+    ...
+
+def equals(
+    list_a: List[str],
+    list_b: List[str],
+    jobs: int = 1,
+    inplace: bool = False,
+    progress: Optional[Callable[[int, int], None]] = None,
+    progress_interval: int = 10_000,
+) -> List[bool]:
+    """Compare two lists of strings element-wise.
+
+    Args:
+        list_a: First list of strings
+        list_b: Second list of strings, same length as list_a
+        jobs: Number of parallel workers
+        inplace: Write results into list_a when True
+        progress: Called as progress(processed, total) on the calling thread
+        progress_interval: Minimum number of items between progress calls
+
+    Returns:
+        List of booleans, True where list_a[i] == list_b[i]
+
+    Raises:
+        ValueError: If the lists have different lengths
+    """
+    ...
+
+def concat_pairwise(
+    list_a: List[str],
+    list_b: List[str],
+    sep: str = "",
+    jobs: int = 1,
+    inplace: bool = False,
+    progress: Optional[Callable[[int, int], None]] = None,
+    progress_interval: int = 10_000,
+) -> List[str]:
+    """Join two lists of strings element-wise.
+
+    Args:
+        list_a: First list of strings
+        list_b: Second list of strings, same length as list_a
+        sep: Separator placed between the two parts
+        jobs: Number of parallel workers
+        inplace: Write results into list_a when True
+        progress: Called as progress(processed, total) on the calling thread
+        progress_interval: Minimum number of items between progress calls
+
+    Returns:
+        List of strings list_a[i] + sep + list_b[i]
+
+    Raises:
+        ValueError: If the lists have different lengths
+    """
+    ...
+
+def ascii_upper_string(
+    list: List[str],
+    jobs: int = 1,
+    inplace: bool = False,
+    progress: Optional[Callable[[int, int], None]] = None,
+    progress_interval: int = 10_000,
+    start: Optional[int] = None,
+    stop: Optional[int] = None,
+    step: Optional[int] = None,
+) -> List[str]:
+    """Uppercase each string, ASCII letters only.
+
+    Faster than str.upper() as it maps only a-z with a SIMD pass; every
+    non-ASCII character is left untouched.
+
+    Args:
+        list: List of strings to process
+        jobs: Number of parallel workers
+        inplace: Modify original list when True
+        progress: Called as progress(processed, total) on the calling thread
+        progress_interval: Minimum number of items between progress calls
+        start, stop, step: Process only list[start:stop:step]; with inplace only those
+            positions are overwritten
+
+    Returns:
+        List of uppercased strings
+    """
+    ...
+
+def ascii_lower_string(
+    list: List[str],
+    jobs: int = 1,
+    inplace: bool = False,
+    progress: Optional[Callable[[int, int], None]] = None,
+    progress_interval: int = 10_000,
+    start: Optional[int] = None,
+    stop: Optional[int] = None,
+    step: Optional[int] = None,
+) -> List[str]:
+    """Lowercase each string, ASCII letters only.
+
+    Faster than str.lower() as it maps only A-Z with a SIMD pass; every
+    non-ASCII character is left untouched.
+
+    Args:
+        list: List of strings to process
+        jobs: Number of parallel workers
+        inplace: Modify original list when True
+        progress: Called as progress(processed, total) on the calling thread
+        progress_interval: Minimum number of items between progress calls
+        start, stop, step: Process only list[start:stop:step]; with inplace only those
+            positions are overwritten
+
+    Returns:
+        List of lowercased strings
+    """
+    ...
+
+def swapcase_string(
+    list: List[str],
+    jobs: int = 1,
+    inplace: bool = False,
+    progress: Optional[Callable[[int, int], None]] = None,
+    progress_interval: int = 10_000,
+    start: Optional[int] = None,
+    stop: Optional[int] = None,
+    step: Optional[int] = None,
+) -> List[str]:
+    """Swap the case of each string, like str.swapcase.
+
+    Unicode-aware, with full case mappings ("ß" becomes "SS") and a final
+    sigma at word ends. ASCII strings take a SIMD path; strings without cased
+    characters are returned as is.
+
+    Args:
+        list: List of strings to process
+        jobs: Number of parallel workers
+        inplace: Modify original list when True
+        progress: Called as progress(processed, total) on the calling thread
+        progress_interval: Minimum number of items between progress calls
+        start, stop, step: Process only list[start:stop:step]; with inplace only those
+            positions are overwritten
+
+    Returns:
+        List of strings with case swapped
+    """
+    ...
+
+def isdigit_string(
+    list: List[str],
+    unicode: bool = False,
+    jobs: int = 1,
+    inplace: bool = False,
+    progress: Optional[Callable[[int, int], None]] = None,
+    progress_interval: int = 10_000,
+    start: Optional[int] = None,
+    stop: Optional[int] = None,
+    step: Optional[int] = None,
+) -> List[bool]:
+    """Test each string for digits only, like str.isdigit.
+
+    By default only the ASCII digits 0-9 count, checked with a SIMD pass.
+    With unicode=True, non-ASCII strings follow CPython: digits of every script,
+    superscripts and circled digits count too. Empty strings give False.
+
+    Args:
+        list: List of strings to process
+        unicode: Use CPython's Unicode definitions for non-ASCII strings
+        jobs: Number of parallel workers
+        inplace: Modify original list when True
+        progress: Called as progress(processed, total) on the calling thread
+        progress_interval: Minimum number of items between progress calls
+        start, stop, step: Process only list[start:stop:step]; with inplace only those
+            positions are overwritten
+
+    Returns:
+        List of booleans, one per string
+    """
+    ...
+
+def isalpha_string(
+    list: List[str],
+    unicode: bool = False,
+    jobs: int = 1,
+    inplace: bool = False,
+    progress: Optional[Callable[[int, int], None]] = None,
+    progress_interval: int = 10_000,
+    start: Optional[int] = None,
+    stop: Optional[int] = None,
+    step: Optional[int] = None,
+) -> List[bool]:
+    """Test each string for letters only, like str.isalpha.
+
+    By default only the ASCII letters a-z and A-Z count, checked with a SIMD
+    pass. With unicode=True, non-ASCII strings follow CPython: letters of every
+    script count too. Empty strings give False.
+
+    Args:
+        list: List of strings to process
+        unicode: Use CPython's Unicode definitions for non-ASCII strings
+        jobs: Number of parallel workers
+        inplace: Modify original list when True
+        progress: Called as progress(processed, total) on the calling thread
+        progress_interval: Minimum number of items between progress calls
+        start, stop, step: Process only list[start:stop:step]; with inplace only those
+            positions are overwritten
+
+    Returns:
+        List of booleans, one per string
+    """
+    ...
+
+def isalnum_string(
+    list: List[str],
+    unicode: bool = False,
+    jobs: int = 1,
+    inplace: bool = False,
+    progress: Optional[Callable[[int, int], None]] = None,
+    progress_interval: int = 10_000,
+    start: Optional[int] = None,
+    stop: Optional[int] = None,
+    step: Optional[int] = None,
+) -> List[bool]:
+    """Test each string for letters and digits only, like str.isalnum.
+
+    By default only ASCII letters and digits count, checked with a SIMD pass.
+    With unicode=True, non-ASCII strings follow CPython: letters and numeric
+    characters of every script count too. Empty strings give False.
+
+    Args:
+        list: List of strings to process
+        unicode: Use CPython's Unicode definitions for non-ASCII strings
+        jobs: Number of parallel workers
+        inplace: Modify original list when True
+        progress: Called as progress(processed, total) on the calling thread
+        progress_interval: Minimum number of items between progress calls
+        start, stop, step: Process only list[start:stop:step]; with inplace only those
+            positions are overwritten
+
+    Returns:
+        List of booleans, one per string
+    """
+    ...
+
+def isspace_string(
+    list: List[str],
+    unicode: bool = False,
+    jobs: int = 1,
+    inplace: bool = False,
+    progress: Optional[Callable[[int, int], None]] = None,
+    progress_interval: int = 10_000,
+    start: Optional[int] = None,
+    stop: Optional[int] = None,
+    step: Optional[int] = None,
+) -> List[bool]:
+    """Test each string for whitespace only, like str.isspace.
+
+    By default only ASCII whitespace counts, including the separators
+    0x1C-0x1F, checked with a SIMD pass. With unicode=True, non-ASCII strings
+    follow CPython: no-break and other Unicode spaces count too. Empty strings
+    give False.
+
+    Args:
+        list: List of strings to process
+        unicode: Use CPython's Unicode definitions for non-ASCII strings
+        jobs: Number of parallel workers
+        inplace: Modify original list when True
+        progress: Called as progress(processed, total) on the calling thread
+        progress_interval: Minimum number of items between progress calls
+        start, stop, step: Process only list[start:stop:step]; with inplace only those
+            positions are overwritten
+
+    Returns:
+        List of booleans, one per string
+    """
+    ...
+
+def expandtabs_string(
+    list: List[str],
+    tabsize: int = 8,
+    jobs: int = 1,
+    inplace: bool = False,
+    progress: Optional[Callable[[int, int], None]] = None,
+    progress_interval: int = 10_000,
+    start: Optional[int] = None,
+    stop: Optional[int] = None,
+    step: Optional[int] = None,
+) -> List[str]:
+    """Replace tabs with spaces up to the next tab stop, like str.expandtabs.
+
+    Columns count characters and restart after each newline or carriage
+    return. Strings without a tab are returned as is.
+
+    Args:
+        list: List of strings to process
+        tabsize: Columns between tab stops; 0 or less removes tabs
+        jobs: Number of parallel workers
+        inplace: Modify original list when True
+        progress: Called as progress(processed, total) on the calling thread
+        progress_interval: Minimum number of items between progress calls
+        start, stop, step: Process only list[start:stop:step]; with inplace only those
+            positions are overwritten
+
+    Returns:
+        List of strings with tabs expanded
+    """
+    ...
+
+def translate(
+    list: List[str],
+    table: Union[Dict[int, Union[int, str, None]], Sequence[Union[int, str, None]]],
+    jobs: int = 1,
+    inplace: bool = False,
+    progress: Optional[Callable[[int, int], None]] = None,
+    progress_interval: int = 10_000,
+    start: Optional[int] = None,
+    stop: Optional[int] = None,
+    step: Optional[int] = None,
+) -> List[str]:
+    """Substitute characters through a table, like str.translate.
+
+    The table is read once: a dict keyed by code point (as made by
+    str.maketrans) or a sequence indexed by it, such as a 256-entry list for
+    Latin-1. Values are a code point, a replacement string, or None to delete
+    the character; characters without an entry are kept. Tables mapping ASCII
+    characters to single ASCII characters are applied to ASCII strings with a
+    SIMD lookup.
+
+    Args:
+        list: List of strings to process
+        table: Translation table
+        jobs: Number of parallel workers
+        inplace: Modify original list when True
+        progress: Called as progress(processed, total) on the calling thread
+        progress_interval: Minimum number of items between progress calls
+        start, stop, step: Process only list[start:stop:step]; with inplace only those
+            positions are overwritten
+
+    Returns:
+        List of translated strings
+
+    Raises:
+        TypeError: If the table is neither a dict nor a sequence, or has a value
+            that is not an int, str or None
+        ValueError: If a value is outside range(0x110000)
+    """
+    ...
+
+def fill_template(
+    list: List[str],
+    mapping: Dict[str, Any],
+    jobs: int = 1,
+) -> List[str]:
+    """Fill {key} placeholders in each string from a dict, like str.format_map.
+
+    Placeholders are found with a single scan, without a regex. {{ and }} stand for
+    literal braces, and the key is the text up to the closing brace; format specs
+    and conversions are not interpreted. Values are converted with str() once.
+    Strings without braces are returned as is.
+
+    Args:
+        list: List of template strings
+        mapping: Values by placeholder name
+        jobs: Number of parallel workers
+
+    Returns:
+        List of filled strings
+
+    Raises:
+        KeyError: If a placeholder names a key missing from the mapping, for the
+            first such string
+        ValueError: If braces are unbalanced, with the message of str.format
+        TypeError: If a mapping key is not a str
+    """
+    ...
+
+def char_len(
+    list: List[str],
+    jobs: int = 1,
+    inplace: bool = False,
+    progress: Optional[Callable[[int, int], None]] = None,
+    progress_interval: int = 10_000,
+    start: Optional[int] = None,
+    stop: Optional[int] = None,
+    step: Optional[int] = None,
+) -> List[int]:
+    """Count the characters (code points) of each string.
+
+    Args:
+        list: List of strings to process
+        jobs: Number of parallel workers
+        inplace: Modify original list when True
+        progress: Called as progress(processed, total) on the calling thread
+        progress_interval: Minimum number of items between progress calls
+        start, stop, step: Process only list[start:stop:step]; with inplace only those
+            positions are overwritten
+
+    Returns:
+        List of lengths, equal to len(s) for each string
+    """
+    ...
+
+def strip_string(
+    list: List[str],
+    chars: Optional[str] = None,
+    left: bool = True,
+    right: bool = True,
+    jobs: int = 1,
+    inplace: bool = False,
+    progress: Optional[Callable[[int, int], None]] = None,
+    progress_interval: int = 10_000,
+    start: Optional[int] = None,
+    stop: Optional[int] = None,
+    step: Optional[int] = None,
+) -> List[str]:
+    """Strip characters from both ends of each string, like str.strip.
+
+    left=False gives str.rstrip and right=False str.lstrip. A single ASCII
+    character in chars, such as the padding of fixed-width fields, is
+    skipped with a vector scan. Unchanged strings are returned as they are.
+
+    Args:
+        list: List of strings to process
+        chars: Characters to strip, whitespace when None
+        left: Strip the start of each string
+        right: Strip the end of each string
+        jobs: Number of parallel workers
+        inplace: Modify original list when True
+        progress: Called as progress(processed, total) on the calling thread
+        progress_interval: Minimum number of items between progress calls
+        start, stop, step: Process only list[start:stop:step]; with inplace only those
+            positions are overwritten
+
+    Returns:
+        List of stripped strings
+    """
+    ...
+
+def startswith_string(
+    list: List[str],
+    prefix: Union[str, Tuple[str, ...]],
+    start: Optional[int] = None,
+    end: Optional[int] = None,
+    jobs: int = 1,
+    inplace: bool = False,
+    progress: Optional[Callable[[int, int], None]] = None,
+    progress_interval: int = 10_000,
+) -> List[bool]:
+    """Test whether each string starts with prefix, like str.startswith(prefix, start, end).
+
+    start and end are character indices bounding the compared region, negative ones
+    counting from the end and out-of-range ones clamped as CPython does.
+
+    Args:
+        list: List of strings to process
+        prefix: A str, or a tuple of str any of which may match
+        start: First character of the region, 0 when None
+        end: Character after the region, the end of the string when None
+        jobs: Number of parallel workers
+        inplace: Modify original list when True
+        progress: Called as progress(processed, total) on the calling thread
+        progress_interval: Minimum number of items between progress calls
+
+    Returns:
+        List of booleans
+
+    Raises:
+        TypeError: If prefix is not a str or a tuple of str
+    """
+    ...
+
+def endswith_string(
+    list: List[str],
+    suffix: Union[str, Tuple[str, ...]],
+    start: Optional[int] = None,
+    end: Optional[int] = None,
+    jobs: int = 1,
+    inplace: bool = False,
+    progress: Optional[Callable[[int, int], None]] = None,
+    progress_interval: int = 10_000,
+) -> List[bool]:
+    """Test whether each string ends with suffix, like str.endswith(suffix, start, end).
+
+    start and end are character indices bounding the compared region, negative ones
+    counting from the end and out-of-range ones clamped as CPython does.
+
+    Args:
+        list: List of strings to process
+        suffix: A str, or a tuple of str any of which may match
+        start: First character of the region, 0 when None
+        end: Character after the region, the end of the string when None
+        jobs: Number of parallel workers
+        inplace: Modify original list when True
+        progress: Called as progress(processed, total) on the calling thread
+        progress_interval: Minimum number of items between progress calls
+
+    Returns:
+        List of booleans
+
+    Raises:
+        TypeError: If suffix is not a str or a tuple of str
+    """
+    ...
+
+def count_char_byte(
+    list: List[str],
+    ch: str,
+    jobs: int = 1,
+    inplace: bool = False,
+    progress: Optional[Callable[[int, int], None]] = None,
+    progress_interval: int = 10_000,
+    start: Optional[int] = None,
+    stop: Optional[int] = None,
+    step: Optional[int] = None,
+) -> List[int]:
+    """Count the occurrences of an ASCII character in each string, like s.count(ch).
+
+    Counts with a SIMD byte compare, e.g. the commas of CSV lines.
+
+    Args:
+        list: List of strings to process
+        ch: A single ASCII character
+        jobs: Number of parallel workers
+        inplace: Modify original list when True
+        progress: Called as progress(processed, total) on the calling thread
+        progress_interval: Minimum number of items between progress calls
+        start, stop, step: Process only list[start:stop:step]; with inplace only those
+            positions are overwritten
+
+    Returns:
+        List of counts
+
+    Raises:
+        ValueError: If ch is not a single ASCII character
+    """
+    ...
+
+def match_indices_string(
+    list: List[str],
+    needle: str,
+    overlapping: bool = False,
+    chars: bool = False,
+    jobs: int = 1,
+    inplace: bool = False,
+    progress: Optional[Callable[[int, int], None]] = None,
+    progress_interval: int = 10_000,
+    start: Optional[int] = None,
+    stop: Optional[int] = None,
+    step: Optional[int] = None,
+) -> List[List[int]]:
+    """Find the offsets of every occurrence of a literal substring.
+
+    Gives the positions repeated str.find calls would, resuming after each match,
+    or one character after its start with overlapping=True. An empty needle
+    matches at every position, from 0 to the end. Single-byte needles are found
+    with a SIMD scan.
+
+    Args:
+        list: List of strings to process
+        needle: Literal substring to search for
+        overlapping: Also report matches that overlap an earlier one
+        chars: Report character offsets, as Python indexes strings, instead of
+            UTF-8 byte offsets
+        jobs: Number of parallel workers
+        inplace: Modify original list when True
+        progress: Called as progress(processed, total) on the calling thread
+        progress_interval: Minimum number of items between progress calls
+        start, stop, step: Process only list[start:stop:step]; with inplace only those
+            positions are overwritten
+
+    Returns:
+        List of offset lists, one per string
+    """
+    ...
+
+def encode_utf8(
+    list: List[str],
+    jobs: int = 1,
+    inplace: bool = False,
+    progress: Optional[Callable[[int, int], None]] = None,
+    progress_interval: int = 10_000,
+    start: Optional[int] = None,
+    stop: Optional[int] = None,
+    step: Optional[int] = None,
+) -> List[bytes]:
+    """Encode each string as UTF-8 bytes, like str.encode().
+
+    Args:
+        list: List of strings to process
+        jobs: Number of parallel workers
+        inplace: Modify original list when True
+        progress: Called as progress(processed, total) on the calling thread
+        progress_interval: Minimum number of items between progress calls
+        start, stop, step: Process only list[start:stop:step]; with inplace only those
+            positions are overwritten
+
+    Returns:
+        List of bytes objects
+    """
+    ...
+
+def ascii_upper_inplace(list: List[str], jobs: int = 1) -> List[str]:
+    """Uppercase ASCII letters of every string in list, in place.
+
+    Strings referenced only by the list are rewritten in their own buffer
+    without allocating. Shared, interned or non-ASCII strings are replaced by
+    new uppercased strings, so other references to them are unaffected.
+
+    Args:
+        list: List of strings to modify
+        jobs: Number of parallel workers
+
+    Returns:
+        The same list
+    """
+    ...
+
+def prehash(list: List[str], jobs: int = 1) -> List[str]:
+    """Compute and cache the hash of every string in list.
+
+    Strings keep their hash once computed, so later dict and set insertions,
+    e.g. grouping by these strings, skip hashing them. Hashes are computed
+    in parallel; str subclasses other than yurki.String are hashed on the
+    calling thread.
+
+    Args:
+        list: List of strings to hash
+        jobs: Number of parallel workers
+
+    Returns:
+        The same list
+
+    Raises:
+        TypeError: If an item is not a str
+    """
+    ...
+
+def caesar_shift(list: List[str], n: int, jobs: int = 1, inplace: bool = False) -> List[str]:
+    """Rotate ASCII letters of every string by n places, keeping case.
+
+    Other characters pass through unchanged. With inplace, strings referenced
+    only by the list are rewritten in their own buffer; shared, interned or
+    non-ASCII strings are replaced, so other references to them are unaffected.
+
+    Args:
+        list: List of strings to process
+        n: Number of places to rotate; taken modulo 26, may be negative
+        jobs: Number of parallel workers
+        inplace: Modify original list when True
+
+    Returns:
+        List of rotated strings (the same list with inplace)
+    """
+    ...
+
+def rot13(list: List[str], jobs: int = 1, inplace: bool = False) -> List[str]:
+    """caesar_shift with n=13; applying it twice restores the input."""
+    ...
+
+def map_iter(
+    iterable: Iterable[str],
+    ops: List[Tuple[Any, ...]],
+    jobs: int = 1,
+    batch_size: int = 10_000,
+    inplace: bool = False,
+) -> Iterator[Any]:
+    """Lazily apply a pipeline to an iterable, processing batch_size items at a time.
+
+    Args:
+        iterable: Iterable of strings to process
+        ops: Operations applied in order, as in pipeline()
+        jobs: Number of parallel workers per batch
+        batch_size: Number of items pulled from the iterable per batch
+
+    Returns:
+        Iterator over results of the last op, in input order
+
+    Raises:
+        ValueError: If batch_size or jobs is zero, inplace is True, or an op is invalid
+        TypeError: If the iterable yields a non-string item
+    """
+    ...
+
+def process_file(
+    path: Union[str, os.PathLike[str]],
+    ops: List[Tuple[Any, ...]],
+    jobs: int = 1,
+    encoding: str = "utf-8",
+    errors: str = "strict",
+    output_path: Optional[Union[str, os.PathLike[str]]] = None,
+) -> Union[List[Any], int]:
+    """Apply a pipeline to every line of a file read and split in Rust.
+
+    Args:
+        path: File to read, split into lines on "\\n" or "\\r\\n"
+        ops: Operations applied in order, as in pipeline()
+        jobs: Number of parallel workers
+        encoding: Only "utf-8" is supported
+        errors: "strict" or "replace" for invalid UTF-8
+        output_path: Write results newline-delimited to this file instead of
+            returning a list. With a final is_match op, matching lines are written
+
+    Returns:
+        List of results of the last op, or the number of lines written when
+        output_path is given
+
+    Raises:
+        ValueError: If an argument is invalid, or output_path is used with list ops
+        UnicodeDecodeError: If the file is not valid UTF-8 and errors is "strict"
+    """
+    ...
+
+def set_arena_config(
+    initial_kb: int,
+    reset_mb: int,
+    free_mb: int,
+    batch_size: int = 100,
+) -> None:
+    """Configure per-worker bump arena thresholds.
+
+    Args:
+        initial_kb: Initial arena capacity in KB
+        reset_mb: Arena size in MB above which the arena is reset
+        free_mb: Arena size in MB above which the arena is dropped and reallocated
+        batch_size: Number of items processed between arena checks
+
+    Raises:
+        ValueError: If free_mb < reset_mb, initial_kb exceeds reset_mb, or a value is zero
+    """
+    ...
+
+def get_arena_config() -> Tuple[int, int, int, int]:
+    """Return current arena config as (initial_kb, reset_mb, free_mb, batch_size)."""
+    ...
+
+def set_parallel_threshold(chars: int) -> None:
+    """Set the input size below which maps run sequentially even with jobs > 1.
+
+    The size is estimated in characters from a sample of the items, plus
+    a fixed cost per item. Below it, starting the workers costs more than
+    they save.
+
+    Args:
+        chars: Threshold in estimated characters; 0 always honors jobs
+    """
+    ...
+
+def get_parallel_threshold() -> int:
+    """Return the current parallel threshold, in estimated characters."""
+    ...
+
+def set_simd_thresholds(
+    bytes: Optional[int] = None,
+    ucs1: Optional[int] = None,
+    ucs2: Optional[int] = None,
+    ucs4: Optional[int] = None,
+) -> None:
+    """Set the string sizes from which the transcoding kernels use SIMD.
+
+    Shorter strings take the scalar routine, which is faster where the vector
+    setup costs more than it saves. The crossover depends on the CPU; results are
+    the same whatever the values.
+
+    Args:
+        bytes: UTF-8 analysis, decoding and validation, in bytes
+        ucs1: Latin-1 str to UTF-8, in characters
+        ucs2: UCS-2 str to UTF-8, in characters
+        ucs4: UCS-4 str to UTF-8, in characters
+
+    None keeps the current value; 0 always uses SIMD.
+    """
+    ...
+
+def get_simd_thresholds() -> Dict[str, int]:
+    """Return the SIMD thresholds, as keyword arguments of set_simd_thresholds."""
+    ...
+
+def autotune_simd_thresholds() -> Dict[str, int]:
+    """Measure the SIMD crossover of each kernel on this CPU and use it.
+
+    Times both paths over synthetic strings of 8 to 256 characters, in a fraction
+    of a second. Runs at import when YURKI_AUTOTUNE=1 is set.
+
+    Returns:
+        The new thresholds, as returned by get_simd_thresholds
+    """
+    ...
+
+def set_utf8_cache(enabled: bool) -> None:
+    """Attach the UTF-8 form to non-ASCII result strings created from now on.
+
+    C code reading a str as UTF-8 (PyUnicode_AsUTF8, used by most serializers)
+    then gets it without encoding the string again. Each such string takes its
+    UTF-8 length in extra memory. Off by default; has no effect on ASCII
+    strings, whose contents already are UTF-8, or with disable-fast-string.
+
+    Args:
+        enabled: Whether to attach the UTF-8 form
+    """
+    ...
+
+def get_utf8_cache() -> bool:
+    """Return whether non-ASCII result strings carry their UTF-8 form."""
+    ...
+
+def list_from(iterable: Iterable[Any]) -> List[Any]:
+    """Return a yurki.List of the items of any iterable, generators included.
+
+    The list holds the same objects, not copies. Also available as
+    yurki.list_from.
+
+    Raises:
+        TypeError: If the argument is not iterable
+    """
+    ...
+
+def chunk_list(list: Sequence[Any], size: int) -> List[List[Any]]:
+    """Split a list into consecutive yurki.Lists of size items.
+
+    The last chunk is shorter if the items don't divide evenly. The chunks
+    hold the same objects, not copies, so no string is converted.
+
+    Args:
+        list: Items to batch
+        size: Number of items per chunk
+
+    Returns:
+        A yurki.List of yurki.List chunks, empty for an empty list; lists
+        with set_fast_types(lists=False)
+
+    Raises:
+        ValueError: If size is not positive
+    """
+    ...
+
+def copy_list(list: Sequence[str], jobs: int = 1) -> List[str]:
+    """Return a shallow copy of a list of strings, built in parallel.
+
+    The copy is a new yurki.List (a list with set_fast_types(lists=False))
+    holding the same str and yurki.String objects with their reference counts
+    raised, so it is safe to keep before an inplace operation rewrites the
+    original. Instances of other str subclasses are copied as new strings. It goes through the same map
+    engine as every other operation, doing no work per string, so timing it
+    measures the cost of list traversal and result building alone.
+
+    Args:
+        list: Strings to copy
+        jobs: Number of parallel jobs
+
+    Returns:
+        A new list of the same strings
+
+    Raises:
+        TypeError: If an item is not a str
+    """
+    ...
+
+def reversed_list(list: Sequence[Any]) -> List[Any]:
+    """Return a new yurki.List of the items in reverse order.
+
+    The list passed in is left as it is, and the new one holds the same
+    objects, not copies.
+
+    Args:
+        list: Items to reverse
+
+    Returns:
+        A yurki.List of the items, last first, a list with
+        set_fast_types(lists=False)
+    """
+    ...
+
+def sort_strings(list: Sequence[str], reverse: bool = False, jobs: int = 1) -> List[str]:
+    """Return a new yurki.List of the strings in sorted order, like sorted().
+
+    Strings compare by code point and equal strings keep their order, also
+    with reverse. Comparison methods of str subclasses are not called.
+
+    Args:
+        list: Strings to sort
+        reverse: Sort in descending order
+        jobs: Number of parallel jobs
+
+    Returns:
+        A yurki.List of the same string objects, sorted, a list with
+        set_fast_types(lists=False)
+
+    Raises:
+        TypeError: If an item is not a str
+    """
+    ...
+
+def simd_implementation() -> str:
+    """Return the instruction set the transcoding kernels were dispatched to.
+
+    One of "avx512" or "avx2" when selected at runtime, otherwise the baseline the
+    module was compiled for ("sse2", "neon", "wasm-simd128", "portable", ...).
+    """
+    ...
+
+def fast_string_enabled() -> bool:
+    """Return whether string results are yurki.String objects.
+
+    False when turned off with set_fast_types or when the module was built with
+    the disable-fast-string feature; results are then plain str, created with
+    the GIL held.
+    """
+    ...
+
+def set_fast_types(strings: bool = True, lists: bool = True) -> None:
+    """Choose the types of the results created from now on.
+
+    With strings or lists off, results are plain str or list instead of
+    yurki.String or yurki.List, for libraries that reject subclasses (exact
+    type checks, custom serializers). Plain results are created with the GIL
+    held, which costs some throughput with jobs > 1. Each call reads the
+    setting once when it starts, so it can be switched around a single call,
+    and a switch during a call (from a progress callback or another thread)
+    takes effect from the next one.
+
+    Args:
+        strings: Whether string results are yurki.String
+        lists: Whether list results are yurki.List
+    """
+    ...
+
+def set_fast_string(enabled: bool) -> None:
+    """Turn yurki.String results on or off at runtime, leaving lists as they are.
+
+    The runtime counterpart of the disable-fast-string build feature, for
+    bisecting whether the custom string type is behind a memory issue or a
+    crash without rebuilding. Values are the same either way. Has no effect
+    on a module built with disable-fast-string.
+
+    Args:
+        enabled: Whether string results are yurki.String
+    """
+    ...
+
+def get_fast_types() -> Tuple[bool, bool]:
+    """Return the current result types as (strings, lists), see set_fast_types."""
+    ...
+
+class CompiledPattern:
+    """Regular expression compiled once and reused across calls."""
+
+    pattern: str
+    case: bool
+    multiline: bool
+    dotall: bool
+
+    def __init__(
+        self,
+        pattern: str,
+        case: bool = False,
+        multiline: bool = False,
+        dotall: bool = False,
+    ) -> None:
+        """Compile pattern.
+
+        Args:
+            pattern: Regular expression pattern
+            case: Case-insensitive matching when True
+            multiline: ^ and $ match at line boundaries when True
+            dotall: . matches newlines when True
+
+        Raises:
+            ValueError: If the pattern is invalid
+        """
+        ...
+
+    def replace(
+        self,
+        list: List[str],
+        replacement: str,
+        count: int = 1,
+        jobs: int = 1,
+        inplace: bool = False,
+        progress: Optional[Callable[[int, int], None]] = None,
+        progress_interval: int = 10_000,
+    ) -> List[str]:
+        """Replace matches in each string (count=0 replaces all)."""
+        ...
+
+    def findall(
+        self,
+        list: List[str],
+        jobs: int = 1,
+        inplace: bool = False,
+        progress: Optional[Callable[[int, int], None]] = None,
+        progress_interval: int = 10_000,
+        overlapping: bool = False,
+    ) -> List[List[str]]:
+        """Find all non-overlapping matches in each string.
+
+        With overlapping=True the search resumes one character after each
+        match start instead of after its end, so matches may overlap. This
+        can cost O(n*m) for n characters and matches of length m.
+        """
+        ...
+
+    def match(
+        self,
+        list: List[str],
+        jobs: int = 1,
+        inplace: bool = False,
+        progress: Optional[Callable[[int, int], None]] = None,
+        progress_interval: int = 10_000,
+    ) -> List[bool]:
+        """Check if each string matches."""
+        ...
+
+class Classifier:
+    """Labels strings by the first matching pattern of a regex set."""
+
+    labels: List[str]
+    default: str
+
+    def __init__(
+        self,
+        patterns: Dict[str, str],
+        default: str = "",
+        priority: Optional[List[str]] = None,
+        case: bool = False,
+    ) -> None:
+        """Compile patterns into a regex set.
+
+        Args:
+            patterns: Mapping of label to regular expression pattern
+            default: Label for strings matching no pattern
+            priority: Labels in the order they are tried (dict order when None)
+            case: Case-insensitive matching when True
+
+        Raises:
+            ValueError: If a pattern is invalid or priority names an unknown label
+        """
+        ...
+
+    def classify(
+        self,
+        list: List[str],
+        multi: bool = False,
+        jobs: int = 1,
+        inplace: bool = False,
+        progress: Optional[Callable[[int, int], None]] = None,
+        progress_interval: int = 10_000,
+    ) -> Union[List[str], List[List[str]]]:
+        """Label each string.
+
+        Args:
+            list: List of strings to process
+            multi: Return all matching labels (in priority order) when True
+            jobs: Number of parallel workers
+            inplace: Modify original list when True
+            progress: Called as progress(processed, total) on the calling thread
+            progress_interval: Minimum number of items between progress calls
+
+        Returns:
+            List of labels, or list of label lists when multi is True
+        """
+        ...
+
+    def __len__(self) -> int: ...
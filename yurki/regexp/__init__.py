@@ -1,4 +1,5 @@
 import os
+from typing import Callable
 
 import yurki
 
@@ -11,8 +12,19 @@ def __auto_select_jobs(data: list[str]) -> int:
 
 
 def find(
-    data: list[str], pattern: str, case: bool = False, jobs: int | None = None, inplace: bool = False
-) -> list[str]:
+    data: list[str],
+    pattern: str,
+    case: bool = False,
+    jobs: int | None = None,
+    inplace: bool = False,
+    progress: Callable[[int, int], None] | None = None,
+    progress_interval: int = 10_000,
+    start: int | None = None,
+    stop: int | None = None,
+    step: int | None = None,
+    no_match: str = "empty",
+    intern_results: bool = False,
+) -> list[str | None]:
     """Find the first regex match in each string.
 
     Args:
@@ -21,26 +33,48 @@ def find(
         case: Whether to enable case-insensitive matching. Defaults to False
         jobs: Number of parallel jobs to use. Auto-selects based on data size if None
         inplace: Whether to modify the original list. Defaults to False
+        progress: Callback invoked as progress(processed, total) while running. Defaults to None
+        progress_interval: Minimum number of items between progress calls. Defaults to 10_000
+        start, stop, step: Process only data[start:stop:step], with Python slice semantics.
+            With inplace, only those positions are overwritten. Defaults to the whole list
+        no_match: What to return for strings with no match: "empty" for an empty string,
+            "none" for None. Defaults to "empty"
+        intern_results: Return the same string object for equal results, saving memory when
+            outputs repeat a lot. Not supported with inplace. Defaults to False
 
     Returns:
         List of strings containing the first match found in each input string.
-        Empty strings are returned for strings with no matches.
+        Empty strings (or None with no_match="none") are returned for strings with no matches.
 
     Examples:
         >>> yurki.regexp.find(['hello world', 'test 123'], r'\\d+')
         ['', '123']
 
+        >>> yurki.regexp.find(['hello world', 'test 123'], r'\\d+', no_match='none')
+        [None, '123']
+
         >>> yurki.regexp.find(['Hello', 'hello'], r'hello', case=True)
         ['Hello', 'hello']
     """
     if jobs is None:
         jobs = __auto_select_jobs(data)
 
-    return yurki.internal.find_regex_in_string(data, pattern, case, jobs, inplace)
+    return yurki.internal.find_regex_in_string(
+        data, pattern, case, jobs, inplace, progress, progress_interval, start, stop, step, no_match, intern_results
+    )
 
 
 def is_match(
-    data: list[str], pattern: str, case: bool = False, jobs: int | None = None, inplace: bool = False
+    data: list[str],
+    pattern: str,
+    case: bool = False,
+    jobs: int | None = None,
+    inplace: bool = False,
+    progress: Callable[[int, int], None] | None = None,
+    progress_interval: int = 10_000,
+    start: int | None = None,
+    stop: int | None = None,
+    step: int | None = None,
 ) -> list[bool]:
     """Check if each string matches the regex pattern.
 
@@ -50,6 +84,10 @@ def is_match(
         case: Whether to enable case-insensitive matching. Defaults to False
         jobs: Number of parallel jobs to use. Auto-selects based on data size if None
         inplace: Whether to modify the original list. Defaults to False
+        progress: Callback invoked as progress(processed, total) while running. Defaults to None
+        progress_interval: Minimum number of items between progress calls. Defaults to 10_000
+        start, stop, step: Process only data[start:stop:step], with Python slice semantics.
+            With inplace, only those positions are overwritten. Defaults to the whole list
 
     Returns:
         List of booleans indicating whether each string matches the pattern.
@@ -64,11 +102,111 @@ def is_match(
     if jobs is None:
         jobs = __auto_select_jobs(data)
 
-    return yurki.internal.is_match_regex_in_string(data, pattern, case, jobs, inplace)
+    return yurki.internal.is_match_regex_in_string(data, pattern, case, jobs, inplace, progress, progress_interval, start, stop, step)
+
+
+def is_match_at_start(
+    data: list[str],
+    pattern: str,
+    case: bool = False,
+    jobs: int | None = None,
+    inplace: bool = False,
+    progress: Callable[[int, int], None] | None = None,
+    progress_interval: int = 10_000,
+    start: int | None = None,
+    stop: int | None = None,
+    step: int | None = None,
+) -> list[bool]:
+    """Check if each string starts with a match of the regex pattern.
+
+    Like `re.match`, while `is_match` searches anywhere like `re.search`.
+
+    Args:
+        data: List of strings to test
+        pattern: Regular expression pattern to match against
+        case: Whether to enable case-insensitive matching. Defaults to False
+        jobs: Number of parallel jobs to use. Auto-selects based on data size if None
+        inplace: Whether to modify the original list. Defaults to False
+        progress: Callback invoked as progress(processed, total) while running. Defaults to None
+        progress_interval: Minimum number of items between progress calls. Defaults to 10_000
+        start, stop, step: Process only data[start:stop:step], with Python slice semantics.
+            With inplace, only those positions are overwritten. Defaults to the whole list
+
+    Returns:
+        List of booleans indicating whether each string matches the pattern.
+
+    Examples:
+        >>> yurki.regexp.is_match_at_start(['test123', '123test'], r'\\d+')
+        [False, True]
+
+        >>> yurki.regexp.is_match_at_start(['ab', 'b'], r'a|b')
+        [True, True]
+    """
+    if jobs is None:
+        jobs = __auto_select_jobs(data)
+
+    return yurki.internal.is_match_at_start_regex_in_string(
+        data, pattern, case, jobs, inplace, progress, progress_interval, start, stop, step
+    )
+
+
+def is_fullmatch(
+    data: list[str],
+    pattern: str,
+    case: bool = False,
+    jobs: int | None = None,
+    inplace: bool = False,
+    progress: Callable[[int, int], None] | None = None,
+    progress_interval: int = 10_000,
+    start: int | None = None,
+    stop: int | None = None,
+    step: int | None = None,
+) -> list[bool]:
+    """Check if each string matches the regex pattern in full.
+
+    Like `re.fullmatch`: the match must span the whole string.
+
+    Args:
+        data: List of strings to test
+        pattern: Regular expression pattern to match against
+        case: Whether to enable case-insensitive matching. Defaults to False
+        jobs: Number of parallel jobs to use. Auto-selects based on data size if None
+        inplace: Whether to modify the original list. Defaults to False
+        progress: Callback invoked as progress(processed, total) while running. Defaults to None
+        progress_interval: Minimum number of items between progress calls. Defaults to 10_000
+        start, stop, step: Process only data[start:stop:step], with Python slice semantics.
+            With inplace, only those positions are overwritten. Defaults to the whole list
+
+    Returns:
+        List of booleans indicating whether each string matches the pattern.
+
+    Examples:
+        >>> yurki.regexp.is_fullmatch(['123', '123test'], r'\\d+')
+        [True, False]
+
+        >>> yurki.regexp.is_fullmatch(['ab'], r'a|ab')
+        [True]
+    """
+    if jobs is None:
+        jobs = __auto_select_jobs(data)
+
+    return yurki.internal.is_fullmatch_regex_in_string(
+        data, pattern, case, jobs, inplace, progress, progress_interval, start, stop, step
+    )
 
 
 def capture(
-    data: list[str], pattern: str, case: bool = False, jobs: int | None = None, inplace: bool = False
+    data: list[str],
+    pattern: str,
+    case: bool = False,
+    jobs: int | None = None,
+    inplace: bool = False,
+    progress: Callable[[int, int], None] | None = None,
+    progress_interval: int = 10_000,
+    start: int | None = None,
+    stop: int | None = None,
+    step: int | None = None,
+    intern_results: bool = False,
 ) -> list[list[str]]:
     """Capture regex groups from each string.
 
@@ -78,6 +216,12 @@ def capture(
         case: Whether to enable case-insensitive matching. Defaults to False
         jobs: Number of parallel jobs to use. Auto-selects based on data size if None
         inplace: Whether to modify the original list. Defaults to False
+        progress: Callback invoked as progress(processed, total) while running. Defaults to None
+        progress_interval: Minimum number of items between progress calls. Defaults to 10_000
+        start, stop, step: Process only data[start:stop:step], with Python slice semantics.
+            With inplace, only those positions are overwritten. Defaults to the whole list
+        intern_results: Return the same string object for equal results, saving memory when
+            outputs repeat a lot. Not supported with inplace. Defaults to False
 
     Returns:
         List of lists containing captured groups for each string.
@@ -101,11 +245,60 @@ def capture(
     if jobs is None:
         jobs = __auto_select_jobs(data)
 
-    return yurki.internal.capture_regex_in_string(data, pattern, case, jobs, inplace)
+    return yurki.internal.capture_regex_in_string(
+        data, pattern, case, jobs, inplace, progress, progress_interval, start, stop, step, intern_results
+    )
+
+
+def capture_columns(
+    data: list[str],
+    pattern: str,
+    case: bool = False,
+    jobs: int | None = None,
+) -> tuple[list[str | None], ...]:
+    """Capture regex groups from each string and return one list per group.
+
+    The columnar form of `capture`: list k holds group k + 1 of the first match
+    in every string, ready to load as a dataframe column.
+
+    Args:
+        data: List of strings to capture from
+        pattern: Regular expression pattern with at least one capture group
+        case: Whether to enable case-insensitive matching. Defaults to False
+        jobs: Number of parallel jobs to use. Auto-selects based on data size if None
+
+    Returns:
+        Tuple with one list per capture group. Groups that did not take part in
+        the match, and every group of a string with no match, are None.
+
+    Raises:
+        ValueError: If the pattern is invalid or has no capture groups.
+
+    Examples:
+        >>> yurki.regexp.capture_columns(['1-2', '30-40', 'none'], r'(\\d+)-(\\d+)')
+        (['1', '30', None], ['2', '40', None])
+
+        >>> yurki.regexp.capture_columns(['ab', 'a'], r'(a)(b)?')
+        (['a', 'a'], ['b', None])
+    """
+    if jobs is None:
+        jobs = __auto_select_jobs(data)
+
+    return yurki.internal.capture_columns(data, pattern, case, jobs)
 
 
 def split(
-    data: list[str], pattern: str, case: bool = False, jobs: int | None = None, inplace: bool = False
+    data: list[str],
+    pattern: str,
+    case: bool = False,
+    jobs: int | None = None,
+    inplace: bool = False,
+    progress: Callable[[int, int], None] | None = None,
+    progress_interval: int = 10_000,
+    start: int | None = None,
+    stop: int | None = None,
+    step: int | None = None,
+    intern_results: bool = False,
 ) -> list[list[str]]:
     """Split each string using a regex pattern as delimiter.
 
@@ -115,6 +308,12 @@ def split(
         case: Whether to enable case-insensitive matching. Defaults to False
         jobs: Number of parallel jobs to use. Auto-selects based on data size if None
         inplace: Whether to modify the original list. Defaults to False
+        progress: Callback invoked as progress(processed, total) while running. Defaults to None
+        progress_interval: Minimum number of items between progress calls. Defaults to 10_000
+        start, stop, step: Process only data[start:stop:step], with Python slice semantics.
+            With inplace, only those positions are overwritten. Defaults to the whole list
+        intern_results: Return the same string object for equal results, saving memory when
+            outputs repeat a lot. Not supported with inplace. Defaults to False
 
     Returns:
         List of lists containing the split parts for each string.
@@ -132,8 +331,79 @@ def split(
     if jobs is None:
         jobs = __auto_select_jobs(data)
 
-    return yurki.internal.split_by_regexp_string(data, pattern, case, jobs, inplace)
+    return yurki.internal.split_by_regexp_string(
+        data, pattern, case, jobs, inplace, progress, progress_interval, start, stop, step, intern_results
+    )
+
+
+
+def split_columns(
+    data: list[str],
+    pattern: str,
+    n: int,
+    case: bool = False,
+    jobs: int | None = None,
+    missing: str = "empty",
+    extra: str = "truncate",
+) -> tuple[list[str | None], ...]:
+    """Split each string into n fields and return one list per field position.
+
+    Args:
+        data: List of strings to split
+        pattern: Regular expression pattern to use as delimiter
+        n: Number of columns to produce
+        case: Whether to enable case-insensitive matching. Defaults to False
+        jobs: Number of parallel jobs to use. Auto-selects based on data size if None
+        missing: Value for fields a row doesn't have: "empty" for an empty string,
+            "none" for None. Defaults to "empty"
+        extra: What to do with rows that have more than n fields: "truncate" drops the
+            extra fields, "error" raises ValueError. Defaults to "truncate"
+
+    Returns:
+        Tuple of n lists; list k holds field k of every string.
+
+    Examples:
+        >>> yurki.regexp.split_columns(['a,1', 'b,2', 'c'], r',', 2)
+        (['a', 'b', 'c'], ['1', '2', ''])
+
+        >>> yurki.regexp.split_columns(['a,1,x', 'b'], r',', 2, missing='none')
+        (['a', 'b'], ['1', None])
+    """
+    if jobs is None:
+        jobs = __auto_select_jobs(data)
+
+    return yurki.internal.split_columns(data, pattern, n, case, jobs, missing, extra)
+
+def flat_split(
+    data: list[str],
+    pattern: str,
+    case: bool = False,
+    jobs: int | None = None,
+    rows: bool = False,
+) -> list[str] | tuple[list[str], list[int]]:
+    """Split each string by regex delimiter and return all pieces as one flat list.
+
+    Args:
+        data: List of strings to split
+        pattern: Regular expression pattern to use as delimiter
+        case: Whether to enable case-insensitive matching. Defaults to False
+        jobs: Number of parallel jobs to use. Auto-selects based on data size if None
+        rows: Whether to also return the source row index of every piece. Defaults to False
+
+    Returns:
+        List of pieces in row order, or a (pieces, rows) tuple if rows is True.
+
+    Examples:
+        >>> yurki.regexp.flat_split(['a b', 'c'], r' ')
+        ['a', 'b', 'c']
+
+        >>> yurki.regexp.flat_split(['a b', 'c'], r' ', rows=True)
+        (['a', 'b', 'c'], [0, 0, 1])
+    """
+    if jobs is None:
+        jobs = __auto_select_jobs(data)
 
+    return yurki.internal.flat_split(data, pattern, case, jobs, rows)
 
 def replace(
     data: list[str],
@@ -143,6 +413,12 @@ def replace(
     case: bool = False,
     jobs: int | None = None,
     inplace: bool = False,
+    progress: Callable[[int, int], None] | None = None,
+    progress_interval: int = 10_000,
+    start: int | None = None,
+    stop: int | None = None,
+    step: int | None = None,
+    intern_results: bool = False,
 ) -> list[str]:
     """Replace regex matches in each string.
 
@@ -157,6 +433,12 @@ def replace(
         case: Whether to enable case-insensitive matching. Defaults to False
         jobs: Number of parallel jobs to use. Auto-selects based on data size if None
         inplace: Whether to modify the original list. Defaults to False
+        progress: Callback invoked as progress(processed, total) while running. Defaults to None
+        progress_interval: Minimum number of items between progress calls. Defaults to 10_000
+        start, stop, step: Process only data[start:stop:step], with Python slice semantics.
+            With inplace, only those positions are overwritten. Defaults to the whole list
+        intern_results: Return the same string object for equal results, saving memory when
+            outputs repeat a lot. Not supported with inplace. Defaults to False
 
     Returns:
         List of strings with replacements applied.
@@ -180,7 +462,116 @@ def replace(
     if jobs is None:
         jobs = __auto_select_jobs(data)
 
-    return yurki.internal.replace_regexp_in_string(data, pattern, replacement, count, case, jobs, inplace)
+    return yurki.internal.replace_regexp_in_string(
+        data,
+        pattern,
+        replacement,
+        count,
+        case,
+        jobs,
+        inplace,
+        progress,
+        progress_interval,
+        start,
+        stop,
+        step,
+        intern_results,
+    )
+
+
+def replace_callback(
+    data: list[str],
+    pattern: str,
+    func: Callable[[str], str],
+    count: int = 1,
+    case: bool = False,
+    jobs: int | None = None,
+) -> list[str]:
+    """Replace regex matches in each string with the result of calling func on them.
+
+    Like re.sub with a function, except that func receives the matched text
+    instead of a match object.
+
+    Args:
+        data: List of strings to perform replacements on
+        pattern: Regular expression pattern to match
+        func: Called with the text of each match; returns the replacement string
+        count: Number of replacements to make per string, 0 for all. Defaults to 1
+        case: Whether to enable case-insensitive matching. Defaults to False
+        jobs: Number of parallel jobs used for matching. Auto-selects based on data size if None
+
+    Returns:
+        List of strings with replacements applied.
+
+    Note:
+        Only the matching runs in parallel. func is Python code, so it is called
+        on the calling thread, one match at a time, and usually dominates the run time;
+        prefer replace() when the replacement is a fixed template.
+
+    Examples:
+        >>> yurki.regexp.replace_callback(['a1b22'], r'\\d+', lambda s: str(int(s) * 2), count=0)
+        ['a2b44']
+    """
+    if jobs is None:
+        jobs = __auto_select_jobs(data)
+
+    return yurki.internal.replace_regex_callback(data, pattern, func, count, case, jobs)
+
+def compile(
+    pattern: str, case: bool = False, multiline: bool = False, dotall: bool = False
+) -> "yurki.internal.CompiledPattern":
+    """Compile a regex pattern once for reuse across many calls.
+
+    Args:
+        pattern: Regular expression pattern to compile
+        case: Whether to enable case-insensitive matching. Defaults to False
+        multiline: Whether ^ and $ match at line boundaries. Defaults to False
+        dotall: Whether . matches newlines. Defaults to False
+
+    Returns:
+        CompiledPattern with replace, findall and match methods.
+
+    Raises:
+        ValueError: If the pattern is not a valid regular expression.
+
+    Examples:
+        >>> pattern = yurki.regexp.compile(r'\\d+')
+        >>> pattern.findall(['a1b22', 'none'])
+        [['1', '22'], []]
+
+        >>> pattern.replace(['a1b22'], 'N', count=0)
+        ['aNbN']
+    """
+    return yurki.internal.CompiledPattern(pattern, case, multiline, dotall)
+
+
+def classifier(
+    patterns: dict[str, str], default: str = "", priority: list[str] | None = None, case: bool = False
+) -> "yurki.internal.Classifier":
+    """Build a classifier that labels strings by the first matching pattern.
+
+    Args:
+        patterns: Mapping of label to regular expression pattern
+        default: Label returned for strings matching no pattern. Defaults to ""
+        priority: Labels in the order they should be tried. Defaults to dict order
+        case: Whether to enable case-insensitive matching. Defaults to False
+
+    Returns:
+        Classifier whose classify method returns one label per string, or
+        all matching labels per string with multi=True.
+
+    Raises:
+        ValueError: If a pattern is invalid or priority names an unknown label.
+
+    Examples:
+        >>> clf = yurki.regexp.classifier({'num': r'\\d', 'word': r'\\w'}, default='other')
+        >>> clf.classify(['a1', 'ab', '--'])
+        ['num', 'word', 'other']
+
+        >>> clf.classify(['a1'], multi=True)
+        [['num', 'word']]
+    """
+    return yurki.internal.Classifier(patterns, default, priority, case)
 
 
-__all__ = ["find", "is_match", "capture", "split", "replace"]
+__all__ = ["find", "is_match", "is_match_at_start", "is_fullmatch", "capture", "split", "replace", "compile", "classifier"]